@@ -21,8 +21,8 @@ class Buffer(object):
 
     def push_game(self, game_result):
         """push game result to the buffer, each element consist of (winner, player, board, position)"""
-        win, path = game_result
-        for (player, board, pos) in path:
+        win, path, _ = game_result
+        for (player, board, pos, *_) in path:
             row, col = pos
             pos = row * self.board_size + col
             self.buffer.append((win, player, board, pos))
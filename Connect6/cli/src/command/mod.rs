@@ -0,0 +1,463 @@
+//! Subcommands of the `connect6` binary.
+//!
+//! - play : human against the built-in policy.
+//! - selfplay : games of the built-in policy against itself, written as records and training shard.
+//! - match : games between two built-in policies with alternating colours.
+//! - replay : step through the saved game record.
+//! - analyze : search the position and print the statistics of the search.
+//! - engine : policy as the engine speaking the text protocol, reference `connect6::engine`.
+//! - serve : host the networked games over TCP, reference `connect6::server`.
+//! - connect : join the networked game as the human, the built-in policy or the spectator.
+//!
+//! Errors are reported as the message, the binary prints it and exits with non-zero code.
+use connect6::agent::{Agent, AsyncAgent, DebugObserver, PlayResult};
+use connect6::arena::{Arena, MatchResult};
+use connect6::engine::Engine;
+use connect6::export::ShardWriter;
+use connect6::game::{Game, Player};
+use connect6::policy::{MultiPolicy, Policy, PolicySpec};
+use connect6::record::{format_pos, format_result, parse_pos, GameRecord};
+use connect6::server::{RemotePolicy, Role, Server};
+use human::HumanPolicy;
+
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Convert the error to the message.
+fn to_msg<E: fmt::Display>(err: E) -> String {
+    err.to_string()
+}
+
+/// Summary of the finished game.
+fn summary(result: &PlayResult) -> String {
+    format!(
+        "{:?} by {:?}, {} moves",
+        result.status,
+        result.reason,
+        result.path.len()
+    )
+}
+
+/// Read the game record from the file.
+pub fn read_record<P: AsRef<Path>>(path: P) -> Result<GameRecord, String> {
+    let path = path.as_ref();
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+    text.parse::<GameRecord>()
+        .map_err(|e| format!("couldn't parse {}: {}", path.display(), e))
+}
+
+/// Write the game record to the file.
+pub fn write_record<P: AsRef<Path>>(path: P, record: &GameRecord) -> Result<(), String> {
+    let path = path.as_ref();
+    fs::write(path, record.to_string())
+        .map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+/// Parse the whitespace separated moves in move-list notation, such as `H8 G7 G9`.
+pub fn parse_moves(text: &str) -> Result<Vec<(usize, usize)>, String> {
+    text.split_whitespace()
+        .map(|x| parse_pos(x).ok_or_else(|| format!("invalid move {}", x)))
+        .collect()
+}
+
+/// Construct the position from the first `ply` moves of the record and the additional moves.
+///
+/// Board size, rule and setup position of the record are used if the record is given.
+pub fn load_position(
+    record: Option<&GameRecord>,
+    ply: Option<usize>,
+    moves: &[(usize, usize)],
+    size: usize,
+) -> Result<Game, String> {
+    let (mut game, recorded) = match record {
+        Some(record) => {
+            let ply = ply.unwrap_or(record.moves.len()).min(record.moves.len());
+            (record.setup_game().map_err(to_msg)?, &record.moves[..ply])
+        }
+        None => (Game::with_size(size), &[][..]),
+    };
+    for pos in recorded.iter().chain(moves.iter()) {
+        game.set(*pos)
+            .map_err(|e| format!("invalid move {}: {}", format_pos(*pos), e))?;
+    }
+    Ok(game)
+}
+
+/// Play the game between the human on stdio and the built-in policy, human takes given colour.
+///
+/// Record of the game is written to `record` if it is given.
+pub fn play(
+    opponent: &PolicySpec,
+    color: Player,
+    size: usize,
+    record: Option<&Path>,
+) -> Result<PlayResult, String> {
+    let stdin = io::stdin();
+    let mut human = HumanPolicy::new(stdin.lock(), io::stdout());
+    let mut policy = opponent.build();
+
+    let (result, black, white) = if color == Player::White {
+        let mut multi_policy = MultiPolicy::new(&mut policy, &mut human);
+        let result = Agent::debug(&mut multi_policy).with_board_size(size).play();
+        (result, opponent.to_string(), "human".to_string())
+    } else {
+        let mut multi_policy = MultiPolicy::new(&mut human, &mut policy);
+        let result = Agent::debug(&mut multi_policy).with_board_size(size).play();
+        (result, "human".to_string(), opponent.to_string())
+    };
+    let result = result.map_err(to_msg)?;
+    println!("{}", summary(&result));
+
+    if let Some(path) = record {
+        let mut game_record = GameRecord::from_play_result(&result);
+        game_record.black = black;
+        game_record.white = white;
+        write_record(path, &game_record)?;
+    }
+    Ok(result)
+}
+
+/// Play `num_games` games of the policy against itself in parallel, return the number of finished games.
+///
+/// Records are written to `out_dir` as `game_{id}.txt`, and positions are written to the training shard.
+pub fn selfplay(
+    spec: &PolicySpec,
+    num_games: usize,
+    num_threads: usize,
+    size: usize,
+    seed: Option<u64>,
+    out_dir: Option<&Path>,
+    shard: Option<&Path>,
+) -> Result<usize, String> {
+    if let Some(dir) = out_dir {
+        fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+    }
+    let mut shard_writer = match shard {
+        Some(path) => Some(
+            ShardWriter::create(path, size)
+                .map_err(|e| format!("couldn't create {}: {}", path.display(), e))?,
+        ),
+        None => None,
+    };
+
+    let mut agent = AsyncAgent::new(|| spec.build())
+        .with_board_size(size)
+        .with_num_threads(num_threads);
+    if let Some(seed) = seed {
+        agent = agent.with_seed(seed);
+    }
+
+    let mut num_finished = 0;
+    let mut stream = agent.stream(num_games as i32);
+    while let Some((id, result)) = stream.next_with_id() {
+        // failed games are reported and skipped
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                eprintln!("{}", err);
+                continue;
+            }
+        };
+        println!("game {}: {}", id, summary(&result));
+
+        if let Some(dir) = out_dir {
+            let mut record = GameRecord::from_play_result(&result);
+            record.black = spec.to_string();
+            record.white = spec.to_string();
+            write_record(dir.join(format!("game_{:05}.txt", id)), &record)?;
+        }
+        if let Some(ref mut writer) = shard_writer {
+            writer.push_result(&result).map_err(to_msg)?;
+        }
+        num_finished += 1;
+    }
+
+    if let Some(writer) = shard_writer {
+        writer.finish().map_err(to_msg)?;
+    }
+    Ok(num_finished)
+}
+
+/// Play the match of `num_games` games between two policies and write the report.
+pub fn play_match<W: Write>(
+    first: &PolicySpec,
+    second: &PolicySpec,
+    num_games: usize,
+    size: usize,
+    seed: Option<u64>,
+    timeout: Option<Duration>,
+    mut writer: W,
+) -> Result<MatchResult, String> {
+    let (first_spec, second_spec) = (first.clone(), second.clone());
+    let mut arena = Arena::new()
+        .with_board_size(size)
+        .with_player(&first.to_string(), move || first_spec.build())
+        .with_player(&second.to_string(), move || second_spec.build());
+    if let Some(seed) = seed {
+        arena = arena.with_seed(seed);
+    }
+    if let Some(timeout) = timeout {
+        arena = arena.with_timeout(timeout);
+    }
+
+    let result = arena.play_match(0, 1, num_games);
+    let stats = &result.stats;
+    let write = |writer: &mut W| -> io::Result<()> {
+        writeln!(writer, "{} vs {}", first, second)?;
+        writeln!(
+            writer,
+            "games {}, failed {}",
+            stats.num_games(),
+            result.num_failed
+        )?;
+        writeln!(
+            writer,
+            "win-draw-loss {}, score {:.3}",
+            stats,
+            stats.score()
+        )?;
+        writeln!(writer, "elo {}", stats.elo())
+    };
+    write(&mut writer).map_err(to_msg)?;
+    Ok(result)
+}
+
+/// Step through the record, print the board on each move.
+///
+/// If `input` is given, read the command for each step, empty line or `n` for the next move,
+/// `b` for the previous move and `q` for quit. Otherwise every move is printed at once.
+pub fn replay<R: BufRead, W: Write>(
+    record: &GameRecord,
+    mut input: Option<R>,
+    mut writer: W,
+) -> Result<(), String> {
+    let mut game = record.setup_game().map_err(to_msg)?;
+    let mut step = |writer: &mut W, input: &mut Option<R>| -> Result<bool, String> {
+        let mut command = String::new();
+        if let Some(ref mut reader) = input {
+            write!(writer, "[n]ext, [b]ack, [q]uit > ").map_err(to_msg)?;
+            writer.flush().map_err(to_msg)?;
+            if reader.read_line(&mut command).map_err(to_msg)? == 0 {
+                return Ok(false);
+            }
+        }
+
+        match command.trim() {
+            "q" | "quit" => return Ok(false),
+            "b" | "back" => match game.undo() {
+                Some(result) => writeln!(
+                    writer,
+                    "take back {:?} {}",
+                    result.player,
+                    format_pos(result.position)
+                ),
+                None => writeln!(writer, "start of the game"),
+            }
+            .map_err(to_msg)?,
+            "" | "n" | "next" => {
+                let pos = match record.moves.get(game.ply()) {
+                    Some(pos) => *pos,
+                    None if input.is_none() => return Ok(false),
+                    None => {
+                        writeln!(writer, "end of the game").map_err(to_msg)?;
+                        return Ok(true);
+                    }
+                };
+                let result = game
+                    .set(pos)
+                    .map_err(|e| format!("invalid move {}: {}", format_pos(pos), e))?;
+                writeln!(
+                    writer,
+                    "{}. {:?} {}",
+                    game.ply(),
+                    result.player,
+                    format_pos(pos)
+                )
+                .map_err(to_msg)?;
+            }
+            _ => {
+                writeln!(writer, "unknown command").map_err(to_msg)?;
+                return Ok(true);
+            }
+        }
+        game.print(writer).map_err(to_msg)?;
+        Ok(true)
+    };
+
+    writeln!(
+        writer,
+        "{} (Black) vs {} (White), size {}, {} moves",
+        record.black,
+        record.white,
+        record.size,
+        record.moves.len()
+    )
+    .map_err(to_msg)?;
+    while step(&mut writer, &mut input)? {}
+    writeln!(writer, "result {:?}", record.result).map_err(to_msg)
+}
+
+/// Search the position with the policy, write the selection and the statistics of the search.
+///
+/// Visit count distribution is written for the `num_top` most visited positions.
+pub fn analyze<W: Write>(
+    game: &Game,
+    spec: &PolicySpec,
+    num_top: usize,
+    mut writer: W,
+) -> Result<(usize, usize), String> {
+    if game.status().is_end() {
+        return Err(format!("game is already over, {:?}", game.status()));
+    }
+    game.print(&mut writer).map_err(to_msg)?;
+    writeln!(
+        writer,
+        "{:?} to move, {} remain",
+        game.get_turn(),
+        game.get_remain()
+    )
+    .map_err(to_msg)?;
+
+    let mut policy = spec.build();
+    let start = Instant::now();
+    let pos = policy
+        .next(game)
+        .ok_or_else(|| format!("{} couldn't select the position", spec))?;
+    let elapsed = start.elapsed();
+
+    let write = |writer: &mut W| -> io::Result<()> {
+        writeln!(
+            writer,
+            "selected {} by {}, {}.{:03}s elapsed",
+            format_pos(pos),
+            spec,
+            elapsed.as_secs(),
+            elapsed.subsec_millis()
+        )?;
+        let info = match policy.search_info() {
+            Some(info) => info,
+            None => return writeln!(writer, "no search statistics"),
+        };
+        writeln!(
+            writer,
+            "value {:+.3}, q-value {:+.3}",
+            info.value, info.q_value
+        )?;
+
+        let size = game.get_size();
+        let mut visits = (0..size)
+            .flat_map(|row| (0..size).map(move |col| (row, col)))
+            .map(|(row, col)| ((row, col), info.visits[row][col]))
+            .filter(|(_, visit)| *visit > 0.)
+            .collect::<Vec<_>>();
+        visits.sort_by(|(_, v1), (_, v2)| v2.partial_cmp(v1).unwrap());
+        for (pos, visit) in visits.into_iter().take(num_top) {
+            writeln!(writer, "{:>4} {:.3}", format_pos(pos), visit)?;
+        }
+        Ok(())
+    };
+    write(&mut writer).map_err(to_msg)?;
+    Ok(pos)
+}
+
+/// Run the policy as the engine, execute the commands of the reader until `quit`.
+pub fn engine<R: BufRead, W: Write>(
+    spec: &PolicySpec,
+    name: &str,
+    size: usize,
+    reader: R,
+    writer: W,
+) -> Result<(), String> {
+    let mut policy = spec.build();
+    let mut engine = Engine::new(&mut policy)
+        .with_name(name)
+        .with_board_size(size);
+    engine.run(reader, writer).map_err(to_msg)
+}
+
+/// Host `num_games` networked games one after another, return the records.
+///
+/// Records are written to `out_dir` as `game_{id}.txt`.
+pub fn serve<W: Write>(
+    server: &Server,
+    num_games: usize,
+    out_dir: Option<&Path>,
+    mut writer: W,
+) -> Result<Vec<GameRecord>, String> {
+    if let Some(dir) = out_dir {
+        fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+    }
+    let addr = server.local_addr().map_err(to_msg)?;
+    writeln!(writer, "listening on {}", addr).map_err(to_msg)?;
+
+    let mut records = Vec::with_capacity(num_games);
+    for id in 0..num_games {
+        let record = server.serve().map_err(to_msg)?;
+        writeln!(
+            writer,
+            "game {}: {} vs {}, {}, {} moves",
+            id,
+            record.black,
+            record.white,
+            format_result(record.result),
+            record.moves.len()
+        )
+        .map_err(to_msg)?;
+
+        if let Some(dir) = out_dir {
+            write_record(dir.join(format!("game_{:05}.txt", id)), &record)?;
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Join the networked game with given role, the human on stdio plays if the policy is not given.
+///
+/// Record of the game is written to `record` if it is given.
+pub fn connect(
+    addr: &str,
+    role: Role,
+    name: &str,
+    spec: Option<&PolicySpec>,
+    record: Option<&Path>,
+) -> Result<GameRecord, String> {
+    let mut observer = DebugObserver::new();
+    let game_record = match spec {
+        Some(spec) => {
+            let mut policy = spec.build();
+            RemotePolicy::new(&mut policy, role)
+                .with_name(name)
+                .with_observer(&mut observer)
+                .play(addr)
+        }
+        None => {
+            let stdin = io::stdin();
+            let mut human = HumanPolicy::new(stdin.lock(), io::stdout());
+            RemotePolicy::new(&mut human, role)
+                .with_name(name)
+                .with_observer(&mut observer)
+                .play(addr)
+        }
+    }
+    .map_err(to_msg)?;
+
+    println!(
+        "{} vs {}, {}, {} moves",
+        game_record.black,
+        game_record.white,
+        format_result(game_record.result),
+        game_record.moves.len()
+    );
+    if let Some(path) = record {
+        write_record(path, &game_record)?;
+    }
+    Ok(game_record)
+}
@@ -0,0 +1,200 @@
+use super::*;
+use connect6::export::ShardReader;
+use connect6::game::GameStatus;
+use connect6::MAX_BOARD_SIZE;
+
+use std::env;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::thread;
+
+/// Empty directory for the test outputs.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("connect6_cli_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_parse_moves() {
+    let moves = parse_moves(" H8 g7\nG9 ").unwrap();
+    assert_eq!(moves, vec![(7, 7), (6, 6), (8, 6)]);
+    assert!(parse_moves("H8 8H").is_err());
+    assert_eq!(parse_moves("").unwrap(), vec![]);
+}
+
+#[test]
+fn test_load_position() {
+    let mut record = GameRecord::new(9);
+    record.moves = vec![(4, 4), (3, 3), (5, 5)];
+
+    let game = load_position(Some(&record), Some(1), &[(0, 0)], 15).unwrap();
+    assert_eq!(game.get_size(), 9);
+    assert_eq!(game.ply(), 2);
+    assert_eq!(game.get_board()[0][0], Player::White);
+
+    let game = load_position(Some(&record), Some(10), &[], 15).unwrap();
+    assert_eq!(game.ply(), 3);
+
+    let game = load_position(None, None, &[(7, 7)], 15).unwrap();
+    assert_eq!(game.get_size(), 15);
+    assert_eq!(game.ply(), 1);
+
+    assert!(load_position(Some(&record), None, &[(4, 4)], 15).is_err());
+
+    // moves are played from the setup position of the record
+    let handicap = Game::with_handicap(9, Player::Black, &[(0, 8)]).unwrap();
+    record.setup = GameRecord::from_game(&handicap).setup;
+    let game = load_position(Some(&record), Some(1), &[], 15).unwrap();
+    assert_eq!(game.get_board()[0][8], Player::Black);
+    assert_eq!(game.get_board()[4][4], Player::White);
+    assert_eq!(game.ply(), 1);
+
+    // size of the record is not trusted
+    for size in [0, MAX_BOARD_SIZE + 1].iter() {
+        record.size = *size;
+        assert!(load_position(Some(&record), None, &[], 15).is_err());
+    }
+}
+
+#[test]
+fn test_selfplay() {
+    let dir = test_dir("selfplay");
+    let shard = dir.join("selfplay.shard");
+    let spec = "random".parse::<PolicySpec>().unwrap();
+
+    let num = selfplay(&spec, 3, 2, 7, Some(42), Some(&dir), Some(&shard)).unwrap();
+    assert_eq!(num, 3);
+
+    let mut num_moves = 0;
+    for id in 0..3 {
+        let record = read_record(dir.join(format!("game_{:05}.txt", id))).unwrap();
+        assert_eq!(record.size, 7);
+        assert_eq!(record.black, "random");
+        assert!(record.result != GameStatus::Ongoing);
+        num_moves += record.moves.len();
+    }
+
+    let reader = ShardReader::open(&shard).unwrap();
+    assert_eq!(reader.board_size(), 7);
+    assert_eq!(reader.num_samples(), num_moves);
+}
+
+#[test]
+fn test_play_match() {
+    let first = "random:seed=1".parse::<PolicySpec>().unwrap();
+    let second = "default:num_iter=5".parse::<PolicySpec>().unwrap();
+
+    let mut output = Vec::new();
+    let result = play_match(&first, &second, 2, 7, None, None, &mut output).unwrap();
+    assert_eq!(result.stats.num_games() + result.num_failed, 2);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("random:seed=1 vs default:num_iter=5\n"));
+    assert!(output.contains("elo"));
+}
+
+#[test]
+fn test_replay() {
+    let mut record = GameRecord::new(7);
+    record.moves = vec![(3, 3), (2, 2), (4, 4)];
+
+    let mut output = Vec::new();
+    replay::<Cursor<&[u8]>, _>(&record, None, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("1. Black D4"));
+    assert!(output.contains("2. White C3"));
+    assert!(output.contains("3. White E5"));
+    assert!(output.ends_with("result Ongoing\n"));
+
+    // next, back, next, next, quit
+    let input = Cursor::new("\nb\nn\nn\nq\nn\n".as_bytes());
+    let mut output = Vec::new();
+    replay(&record, Some(input), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("take back Black D4"));
+    assert_eq!(output.matches("1. Black D4").count(), 2);
+    assert!(output.contains("2. White C3"));
+    assert!(!output.contains("E5"));
+
+    record.size = MAX_BOARD_SIZE + 1;
+    assert!(replay::<Cursor<&[u8]>, _>(&record, None, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_analyze() {
+    let game = load_position(None, None, &[(3, 3)], 7).unwrap();
+    let spec = "alphazero:num_simulation=20,seed=3"
+        .parse::<PolicySpec>()
+        .unwrap();
+
+    let mut output = Vec::new();
+    let pos = analyze(&game, &spec, 3, &mut output).unwrap();
+    assert_eq!(game.get_board()[pos.0][pos.1], Player::None);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("White to move, 2 remain"));
+    assert!(output.contains(&format!("selected {}", format_pos(pos))));
+    assert!(output.contains("q-value"));
+
+    let spec = "random".parse::<PolicySpec>().unwrap();
+    let mut output = Vec::new();
+    analyze(&game, &spec, 3, &mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("no search statistics"));
+}
+
+#[test]
+fn test_engine() {
+    let spec = "light:seed=5".parse::<PolicySpec>().unwrap();
+    let input = "1 name\n2 boardsize 7\n3 play b D4\n4 genmove w\n5 final_score\n6 quit\n";
+
+    let mut output = Vec::new();
+    engine(&spec, "zero", 15, input.as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let responses = output.split("\n\n").collect::<Vec<_>>();
+    assert_eq!(responses[0], "=1 zero");
+    assert_eq!(responses[1], "=2");
+    assert_eq!(responses[2], "=3");
+
+    let moves = parse_moves(&responses[3]["=4 ".len()..]).unwrap();
+    assert_eq!(moves.len(), 2);
+    assert!(moves.iter().all(|&(row, col)| row < 7 && col < 7));
+    assert_eq!(responses[4], "=5 *");
+    assert_eq!(responses[5], "=6");
+}
+
+#[test]
+fn test_serve_connect() {
+    let dir = test_dir("serve");
+    let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(7);
+    let addr = server.local_addr().unwrap().to_string();
+
+    let players = [(Role::Black, "alice"), (Role::White, "bob")]
+        .iter()
+        .map(|&(role, name)| {
+            let addr = addr.clone();
+            let path = dir.join(format!("{}.txt", name));
+            thread::spawn(move || {
+                let spec = "random:seed=3".parse::<PolicySpec>().unwrap();
+                connect(&addr, role, name, Some(&spec), Some(&path)).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = Vec::new();
+    let records = serve(&server, 1, Some(&dir), &mut output).unwrap();
+    assert_eq!(records.len(), 1);
+    for player in players {
+        assert_eq!(player.join().unwrap(), records[0]);
+    }
+    assert_eq!(read_record(dir.join("game_00000.txt")).unwrap(), records[0]);
+    assert_eq!(read_record(dir.join("alice.txt")).unwrap(), records[0]);
+
+    let output = String::from_utf8(output).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], format!("listening on {}", addr));
+    assert!(lines[1].starts_with("game 0: alice vs bob, "));
+}
@@ -0,0 +1,78 @@
+//! Policy for the human player reading the selection line by line.
+//!
+//! Position is accepted in both notations, move-list notation of the record such as `H8`,
+//! and the labels of the printed board, lowercase row and uppercase column such as `hH`.
+//! Typing `quit` or `resign`, or closing the input gives up the game.
+use connect6::game::{Game, Player};
+use connect6::policy::Policy;
+use connect6::record::parse_pos;
+
+use std::io::{BufRead, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// Parse the position from the input, move-list notation or the labels of the printed board.
+pub fn parse_input(input: &str) -> Option<(usize, usize)> {
+    let input = input.trim();
+    if input.chars().any(|x| x.is_ascii_digit()) {
+        return parse_pos(input);
+    }
+    let mut chars = input.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(row), Some(col), None) if row.is_ascii_lowercase() && col.is_ascii_uppercase() => {
+            Some((row as usize - 'a' as usize, col as usize - 'A' as usize))
+        }
+        _ => None,
+    }
+}
+
+/// Policy for the human player, prompt to the writer and read the selection from the reader.
+pub struct HumanPolicy<R: BufRead, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> HumanPolicy<R, W> {
+    /// Construct a new `HumanPolicy`.
+    pub fn new(reader: R, writer: W) -> HumanPolicy<R, W> {
+        HumanPolicy { reader, writer }
+    }
+}
+
+impl<R: BufRead, W: Write> Policy for HumanPolicy<R, W> {
+    /// Read the selection until the empty position is given, `None` if the player gave up.
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        let size = game.get_size();
+        loop {
+            write!(
+                self.writer,
+                "{:?} ({} remain) > ",
+                game.get_turn(),
+                game.get_remain()
+            )
+            .ok()?;
+            self.writer.flush().ok()?;
+
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+            let line = line.trim();
+            if line == "quit" || line == "resign" {
+                return None;
+            }
+
+            let msg = match parse_input(line) {
+                Some((row, col)) if row < size && col < size => {
+                    if game.get_board()[row][col] == Player::None {
+                        return Some((row, col));
+                    }
+                    "already set position, retry"
+                }
+                _ => "invalid input, retry",
+            };
+            writeln!(self.writer, "{}", msg).ok()?;
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use super::*;
+use std::io::Cursor;
+
+#[test]
+fn test_parse_input() {
+    assert_eq!(parse_input("H8"), Some((7, 7)));
+    assert_eq!(parse_input(" c10 "), Some((9, 2)));
+    assert_eq!(parse_input("hH"), Some((7, 7)));
+    assert_eq!(parse_input("aC"), Some((0, 2)));
+
+    assert_eq!(parse_input("Hh"), None);
+    assert_eq!(parse_input("hHh"), None);
+    assert_eq!(parse_input(""), None);
+}
+
+#[test]
+fn test_next() {
+    let mut game = Game::with_size(7);
+    game.set((0, 0)).unwrap();
+
+    // invalid, out of the board, already set and valid
+    let input = Cursor::new("xyz\nH9\nA1\nbC\n");
+    let mut output = Vec::new();
+    {
+        let mut policy = HumanPolicy::new(input, &mut output);
+        assert_eq!(policy.next(&game), Some((1, 2)));
+    }
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.matches("invalid input, retry").count(), 2);
+    assert_eq!(output.matches("already set position, retry").count(), 1);
+    assert!(output.starts_with("White (2 remain) > "));
+}
+
+#[test]
+fn test_give_up() {
+    let game = Game::with_size(7);
+
+    let mut policy = HumanPolicy::new(Cursor::new("resign\n"), Vec::new());
+    assert_eq!(policy.next(&game), None);
+
+    let mut policy = HumanPolicy::new(Cursor::new(""), Vec::new());
+    assert_eq!(policy.next(&game), None);
+}
@@ -0,0 +1,442 @@
+//! Command-line interface of connect6.
+//!
+//! Built-in policies are selected by name with the parameters, `name[:key=value,...]`.
+//!
+//! ```text
+//! connect6 play --opponent alphazero:num_simulation=100 --color white
+//! connect6 selfplay --policy light --games 100 --threads 4 --out records --shard train.shard
+//! connect6 match random default:num_iter=100 --games 20
+//! connect6 replay records/game_00000.txt
+//! connect6 analyze --record records/game_00000.txt --ply 10 --policy alphazero
+//! connect6 engine --policy alphazero:num_simulation=400 --size 19
+//! connect6 serve --addr 0.0.0.0:6666 --games 10 --out records
+//! connect6 connect 127.0.0.1:6666 --role white --policy light
+//! ```
+#[macro_use]
+extern crate clap;
+extern crate connect6;
+
+mod command;
+mod human;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use connect6::game::Player;
+use connect6::policy::PolicySpec;
+use connect6::server::{Role, Server};
+use connect6::{BOARD_SIZE, MAX_BOARD_SIZE};
+
+use std::io;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+/// Validate the policy specification.
+fn is_spec(text: String) -> Result<(), String> {
+    text.parse::<PolicySpec>().map(|_| ())
+}
+
+/// Validate the positive number.
+fn is_positive(text: String) -> Result<(), String> {
+    match text.parse::<usize>() {
+        Ok(num) if num > 0 => Ok(()),
+        _ => Err(format!("expected positive number, found {}", text)),
+    }
+}
+
+/// Validate the board size, columns are written as alphabets.
+fn is_board_size(text: String) -> Result<(), String> {
+    match text.parse::<usize>() {
+        Ok(size) if size > 0 && size <= MAX_BOARD_SIZE => Ok(()),
+        _ => Err(format!(
+            "expected board size in [1, {}], found {}",
+            MAX_BOARD_SIZE, text
+        )),
+    }
+}
+
+/// Argument of the board size.
+fn size_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("size")
+        .long("size")
+        .takes_value(true)
+        .validator(is_board_size)
+        .help("length of one side of the board")
+}
+
+/// Argument of the seed.
+fn seed_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("seed")
+        .long("seed")
+        .takes_value(true)
+        .validator(|x| x.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+        .help("seed of the games, reproduce the games with the same seed")
+}
+
+/// Argument of the policy specification.
+fn spec_arg<'a, 'b>(name: &'a str, default: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .takes_value(true)
+        .default_value(default)
+        .validator(is_spec)
+        .help("policy as name[:key=value,...]")
+}
+
+fn app<'a, 'b>() -> App<'a, 'b> {
+    App::new("connect6")
+        .about("Play, self-play and analyze Connect6 with the built-in policies")
+        .after_help("POLICIES:\n    random, default (mcts), alphazero, light\n    e.g. alphazero:num_simulation=100,c_puct=1.5,seed=42")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("play")
+                .about("Play against the built-in policy")
+                .arg(spec_arg("opponent", "default"))
+                .arg(
+                    Arg::with_name("color")
+                        .long("color")
+                        .takes_value(true)
+                        .possible_values(&["black", "white"])
+                        .default_value("black")
+                        .help("colour of the human player"),
+                )
+                .arg(size_arg())
+                .arg(
+                    Arg::with_name("record")
+                        .long("record")
+                        .takes_value(true)
+                        .help("write the record of the game"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("selfplay")
+                .about("Play the games of the policy against itself")
+                .arg(spec_arg("policy", "light"))
+                .arg(
+                    Arg::with_name("games")
+                        .long("games")
+                        .takes_value(true)
+                        .default_value("1")
+                        .validator(is_positive)
+                        .help("number of the games"),
+                )
+                .arg(
+                    Arg::with_name("threads")
+                        .long("threads")
+                        .takes_value(true)
+                        .default_value("4")
+                        .validator(is_positive)
+                        .help("number of the games played in parallel"),
+                )
+                .arg(size_arg())
+                .arg(seed_arg())
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .takes_value(true)
+                        .help("directory of the records"),
+                )
+                .arg(
+                    Arg::with_name("shard")
+                        .long("shard")
+                        .takes_value(true)
+                        .help("write the positions to the training shard"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("match")
+                .alias("arena")
+                .about("Play the match between two policies")
+                .arg(
+                    Arg::with_name("first")
+                        .required(true)
+                        .validator(is_spec)
+                        .help("policy of the first player"),
+                )
+                .arg(
+                    Arg::with_name("second")
+                        .required(true)
+                        .validator(is_spec)
+                        .help("policy of the second player"),
+                )
+                .arg(
+                    Arg::with_name("games")
+                        .long("games")
+                        .takes_value(true)
+                        .default_value("10")
+                        .validator(is_positive)
+                        .help("number of the games, colours alternate game by game"),
+                )
+                .arg(size_arg())
+                .arg(seed_arg())
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .takes_value(true)
+                        .validator(is_positive)
+                        .help("time limit of each game in seconds"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("replay")
+                .about("Step through the saved game")
+                .arg(
+                    Arg::with_name("record")
+                        .required(true)
+                        .help("record of the game"),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .help("print every move at once"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("analyze")
+                .about("Search the position and print the statistics")
+                .arg(spec_arg("policy", "alphazero:num_simulation=200"))
+                .arg(
+                    Arg::with_name("record")
+                        .long("record")
+                        .takes_value(true)
+                        .help("start from the record of the game"),
+                )
+                .arg(
+                    Arg::with_name("ply")
+                        .long("ply")
+                        .takes_value(true)
+                        .requires("record")
+                        .validator(|x| x.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                        .help("number of the moves of the record to play"),
+                )
+                .arg(
+                    Arg::with_name("moves")
+                        .long("moves")
+                        .takes_value(true)
+                        .help("additional moves, such as \"H8 G7 G9\""),
+                )
+                .arg(size_arg())
+                .arg(
+                    Arg::with_name("top")
+                        .long("top")
+                        .takes_value(true)
+                        .default_value("10")
+                        .validator(is_positive)
+                        .help("number of the most visited positions to print"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("engine")
+                .about("Run the policy as the engine speaking the text protocol on stdin and stdout")
+                .arg(spec_arg("policy", "alphazero:num_simulation=200"))
+                .arg(size_arg())
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .takes_value(true)
+                        .help("name of the engine, default the policy"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("serve")
+                .about("Host the networked games over TCP")
+                .arg(
+                    Arg::with_name("addr")
+                        .long("addr")
+                        .takes_value(true)
+                        .default_value("127.0.0.1:6666")
+                        .help("address to listen on"),
+                )
+                .arg(size_arg())
+                .arg(
+                    Arg::with_name("games")
+                        .long("games")
+                        .takes_value(true)
+                        .default_value("1")
+                        .validator(is_positive)
+                        .help("number of the games to host"),
+                )
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .takes_value(true)
+                        .help("directory to write the records of the games"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("connect")
+                .about("Join the networked game as the human, the built-in policy or the spectator")
+                .arg(
+                    Arg::with_name("addr")
+                        .required(true)
+                        .help("address of the server"),
+                )
+                .arg(
+                    Arg::with_name("role")
+                        .long("role")
+                        .takes_value(true)
+                        .possible_values(&["black", "white", "spectator"])
+                        .default_value("black")
+                        .help("role in the game"),
+                )
+                .arg(
+                    Arg::with_name("policy")
+                        .long("policy")
+                        .takes_value(true)
+                        .validator(is_spec)
+                        .help("policy as name[:key=value,...], human on stdin if not given"),
+                )
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .takes_value(true)
+                        .help("name of the player, default the policy or human"),
+                )
+                .arg(
+                    Arg::with_name("record")
+                        .long("record")
+                        .takes_value(true)
+                        .help("write the record of the game"),
+                ),
+        )
+}
+
+/// Parse the validated policy specification.
+fn spec_of(matches: &ArgMatches, name: &str) -> PolicySpec {
+    matches.value_of(name).unwrap().parse().unwrap()
+}
+
+/// Board size of the subcommand, default `BOARD_SIZE`.
+fn size_of(matches: &ArgMatches) -> usize {
+    value_t!(matches, "size", usize).unwrap_or(BOARD_SIZE)
+}
+
+/// Seed of the subcommand if it is given.
+fn seed_of(matches: &ArgMatches) -> Option<u64> {
+    value_t!(matches, "seed", u64).ok()
+}
+
+fn run(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        ("play", Some(matches)) => {
+            let color = match matches.value_of("color") {
+                Some("white") => Player::White,
+                _ => Player::Black,
+            };
+            let record = matches.value_of("record").map(Path::new);
+            command::play(
+                &spec_of(matches, "opponent"),
+                color,
+                size_of(matches),
+                record,
+            )
+            .map(|_| ())
+        }
+        ("selfplay", Some(matches)) => {
+            let num_games = value_t!(matches, "games", usize).unwrap();
+            let num_finished = command::selfplay(
+                &spec_of(matches, "policy"),
+                num_games,
+                value_t!(matches, "threads", usize).unwrap(),
+                size_of(matches),
+                seed_of(matches),
+                matches.value_of("out").map(Path::new),
+                matches.value_of("shard").map(Path::new),
+            )?;
+            println!("{} of {} games finished", num_finished, num_games);
+            Ok(())
+        }
+        ("match", Some(matches)) => {
+            let timeout = value_t!(matches, "timeout", u64)
+                .ok()
+                .map(Duration::from_secs);
+            command::play_match(
+                &spec_of(matches, "first"),
+                &spec_of(matches, "second"),
+                value_t!(matches, "games", usize).unwrap(),
+                size_of(matches),
+                seed_of(matches),
+                timeout,
+                io::stdout(),
+            )
+            .map(|_| ())
+        }
+        ("replay", Some(matches)) => {
+            let record = command::read_record(matches.value_of("record").unwrap())?;
+            if matches.is_present("all") {
+                command::replay::<io::StdinLock, _>(&record, None, io::stdout())
+            } else {
+                let stdin = io::stdin();
+                command::replay(&record, Some(stdin.lock()), io::stdout())
+            }
+        }
+        ("analyze", Some(matches)) => {
+            let record = match matches.value_of("record") {
+                Some(path) => Some(command::read_record(path)?),
+                None => None,
+            };
+            let moves = command::parse_moves(matches.value_of("moves").unwrap_or(""))?;
+            let game = command::load_position(
+                record.as_ref(),
+                value_t!(matches, "ply", usize).ok(),
+                &moves,
+                size_of(matches),
+            )?;
+            command::analyze(
+                &game,
+                &spec_of(matches, "policy"),
+                value_t!(matches, "top", usize).unwrap(),
+                io::stdout(),
+            )
+            .map(|_| ())
+        }
+        ("engine", Some(matches)) => {
+            let spec = spec_of(matches, "policy");
+            let name = matches
+                .value_of("name")
+                .map_or_else(|| spec.to_string(), |x| x.to_string());
+            let stdin = io::stdin();
+            command::engine(&spec, &name, size_of(matches), stdin.lock(), io::stdout())
+        }
+        ("serve", Some(matches)) => {
+            let addr = matches.value_of("addr").unwrap();
+            let server = Server::bind(addr)
+                .map_err(|e| format!("couldn't bind {}: {}", addr, e))?
+                .with_board_size(size_of(matches));
+            command::serve(
+                &server,
+                value_t!(matches, "games", usize).unwrap(),
+                matches.value_of("out").map(Path::new),
+                io::stdout(),
+            )
+            .map(|_| ())
+        }
+        ("connect", Some(matches)) => {
+            let role = matches.value_of("role").unwrap().parse::<Role>().unwrap();
+            let spec = matches
+                .value_of("policy")
+                .map(|x| x.parse::<PolicySpec>().unwrap());
+            let name = match (matches.value_of("name"), spec.as_ref()) {
+                (Some(name), _) => name.to_string(),
+                (None, Some(spec)) => spec.to_string(),
+                (None, None) => "human".to_string(),
+            };
+            command::connect(
+                matches.value_of("addr").unwrap(),
+                role,
+                &name,
+                spec.as_ref(),
+                matches.value_of("record").map(Path::new),
+            )
+            .map(|_| ())
+        }
+        _ => unreachable!("subcommand is required"),
+    }
+}
+
+fn main() {
+    let matches = app().get_matches();
+    if let Err(msg) = run(&matches) {
+        eprintln!("error: {}", msg);
+        process::exit(1);
+    }
+}
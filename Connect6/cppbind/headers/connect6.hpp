@@ -1,8 +1,10 @@
 #ifndef CONNECT6_H
 #define CONNECT6_H
 
+#include <cstdint>
 #include <cstring>
 #include <memory>
+#include <stdexcept>
 #include <string>
 #include <tuple>
 #include <vector>
@@ -11,8 +13,8 @@ namespace Connect6_RustFFI {
     constexpr size_t BOARD_SIZE = 15;
     constexpr size_t BOARD_CAPACITY = BOARD_SIZE * BOARD_SIZE;
 
-    using Callback = void(*)(int player, float* values, float* policies, int len);
-    using PolicyCallback = void(*)(float* boards, int* position);
+    using Callback = void(*)(int player, float* values, float* policies, int len, int board_size);
+    using PolicyCallback = void(*)(float* board, int board_size, int* position);
 
     template <typename T>
     using AllocatorType = T*(*)(int size);
@@ -25,13 +27,19 @@ namespace Connect6_RustFFI {
     extern "C" {
         struct Path {
             int turn;
-            int board[BOARD_SIZE][BOARD_SIZE];
+            int* board;
+            int board_size;
             int row;
             int col;
+            float* visits;
+            float value;
+            float q_value;
+            float elapsed;
         };
 
         struct PlayResult {
             int winner;
+            int status;
             Path* paths;
             int len;
         };
@@ -43,36 +51,76 @@ namespace Connect6_RustFFI {
 
         Vec cpp_play(PolicyCallback callback,
                      AllocatorType<Path> alloc_path,
+                     AllocatorType<int> alloc_board,
+                     AllocatorType<float> alloc_visits,
                      AllocatorType<PlayResult> alloc_result,
                      bool debug,
-                     int num_game_thread);
+                     int num_game_thread,
+                     int board_size,
+                     int* num_failed);
 
         Vec cpp_self_play(Callback callback,
                           AllocatorType<Path> alloc_path,
+                          AllocatorType<int> alloc_board,
+                          AllocatorType<float> alloc_visits,
                           AllocatorType<PlayResult> alloc_result,
                           int num_simulation,
                           float epsilon,
                           double dirichlet_alpha,
                           float c_puct,
                           bool debug,
-                          int num_game_thread);
+                          int num_game_thread,
+                          int board_size,
+                          int* num_failed);
 
         PlayResult cpp_play_with(Callback callback,
                                  AllocatorType<Path> alloc_path,
+                                 AllocatorType<int> alloc_board,
+                                 AllocatorType<float> alloc_visits,
                                  int num_simulation,
                                  float epsilon,
                                  double dirichlet_alpha,
-                                 float c_puct);
+                                 float c_puct,
+                                 int board_size);
+
+        struct ReplayBuffer;
+
+        ReplayBuffer* cpp_replay_new(int capacity, int max_age);
+        void cpp_replay_free(ReplayBuffer* buffer);
+        void cpp_replay_push(ReplayBuffer* buffer, const PlayResult* result);
+        int cpp_replay_len(const ReplayBuffer* buffer);
+        int cpp_replay_num_games(const ReplayBuffer* buffer);
+        void cpp_replay_seed(ReplayBuffer* buffer, uint64_t seed);
+        void cpp_replay_clear(ReplayBuffer* buffer);
+        int cpp_replay_sample(ReplayBuffer* buffer,
+                              int num_sample,
+                              bool augment,
+                              int board_size,
+                              float* values,
+                              int* turns,
+                              int* boards,
+                              float* policies,
+                              int* positions);
+        bool cpp_replay_snapshot(const ReplayBuffer* buffer, const char* path);
+        ReplayBuffer* cpp_replay_restore(const char* path);
+
+        int cpp_export_npz(const PlayResult* results, int len, int board_size, const char* path, bool compress);
+        int cpp_export_shard(const PlayResult* results,
+                             int len,
+                             int board_size,
+                             const char* path,
+                             int chunk_size,
+                             bool compress);
     }
 
     namespace Test_FFI {
         extern "C" {
             Path test_new_raw_path();
-            Path test_with_raw_path();
-            Path test_echo_raw_path(int turn, int* board, int row, int col);
+            Path test_with_raw_path(AllocatorType<int> allocator, AllocatorType<float> visits_allocator);
+            Path test_echo_raw_path(int turn, int* board, int board_size, int row, int col, AllocatorType<int> allocator, AllocatorType<float> visits_allocator);
 
-            PlayResult test_with_raw_play_result(AllocatorType<Path> allocator);
-            PlayResult test_echo_raw_play_result(int winner, Path* path, int len, AllocatorType<Path> allocator);
+            PlayResult test_with_raw_play_result(AllocatorType<Path> allocator, AllocatorType<int> board_allocator, AllocatorType<float> visits_allocator);
+            PlayResult test_echo_raw_play_result(int winner, int status, Path* path, int len, AllocatorType<Path> allocator, AllocatorType<int> board_allocator, AllocatorType<float> visits_allocator);
 
             struct VecInt {
                 int* vec;
@@ -87,8 +135,8 @@ namespace Connect6_RustFFI {
                 int len;
             };
 
-            VecFloat test_echo_cppeval(int turn, int* boards, int len, Callback callback, AllocatorType<float> allocator);
-            VecInt test_cpp_policy(float* board, PolicyCallback callback, AllocatorType<int> allocator);
+            VecFloat test_echo_cppeval(int turn, int* boards, int len, int board_size, Callback callback, AllocatorType<float> allocator);
+            VecInt test_cpp_policy(float* board, int board_size, PolicyCallback callback, AllocatorType<int> allocator);
         }
     }
 }
@@ -106,6 +154,24 @@ namespace Connect6 {
         White = 1,
     };
 
+    enum class GameStatus : int {
+        Ongoing = 0,
+        Win = 1,
+        Draw = 2,
+    };
+
+    std::string to_string(GameStatus status) {
+        switch (status) {
+        case GameStatus::Ongoing:
+            return { "Ongoing" };
+        case GameStatus::Win:
+            return { "Win" };
+        case GameStatus::Draw:
+            return { "Draw" };
+        }
+        return { "" };
+    }
+
     std::string to_string(Player player) {
         switch (player) {
         case Player::Black:
@@ -120,30 +186,41 @@ namespace Connect6 {
 
     class Path {
     public:
-        Path() : turn(Player::None), position(std::make_tuple(0, 0)), board(nullptr) {
+        Path() : turn(Player::None), position(std::make_tuple(0, 0)), board_size(0), board(nullptr),
+                 visits(nullptr), value(0), q_value(0), elapsed(0) {
             // Do Nothing
         }
 
         Path(Player turn, 
              const std::tuple<size_t, size_t>& position, 
-             int board_[BOARD_SIZE][BOARD_SIZE]) :
-            turn(turn), position(position), 
-            board(std::make_unique<int[]>(BOARD_CAPACITY))
+             const int* board_,
+             size_t board_size = BOARD_SIZE) :
+            turn(turn), position(position), board_size(board_size),
+            board(std::make_unique<int[]>(board_size * board_size)),
+            visits(nullptr), value(0), q_value(0), elapsed(0)
         {
-            std::memcpy(board.get(), board_, BOARD_CAPACITY);
+            std::memcpy(board.get(), board_, sizeof(int) * board_size * board_size);
         }
 
         Path(const Connect6_RustFFI::Path& path) :
             turn(static_cast<Player>(path.turn)), 
             position(std::make_tuple(path.row, path.col)),
-            board(std::make_unique<int[]>(BOARD_CAPACITY))
+            board_size(path.board_size),
+            board(path.board),
+            visits(path.visits),
+            value(path.value),
+            q_value(path.q_value),
+            elapsed(path.elapsed)
         {
-            std::memcpy(board.get(), path.board, BOARD_CAPACITY);
+            // Take ownership of the board and visits allocated by alloc_board and alloc_visits
         }
 
         Path(const Path&) = delete;
         Path(Path&& other) : 
-            turn(other.turn), position(other.position), board(std::move(other.board))
+            turn(other.turn), position(other.position),
+            board_size(other.board_size), board(std::move(other.board)),
+            visits(std::move(other.visits)), value(other.value),
+            q_value(other.q_value), elapsed(other.elapsed)
         {
             // Do Nothing
         }
@@ -152,7 +229,12 @@ namespace Connect6 {
         Path& operator=(Path&& other) {
             turn = other.turn;
             position = other.position;
+            board_size = other.board_size;
             board = std::move(other.board);
+            visits = std::move(other.visits);
+            value = other.value;
+            q_value = other.q_value;
+            elapsed = other.elapsed;
             return *this;
         }
 
@@ -164,6 +246,10 @@ namespace Connect6 {
             return position;
         };
 
+        size_t GetBoardSize() const {
+            return board_size;
+        }
+
         int* GetBoard() {
             return board.get();
         }
@@ -173,33 +259,63 @@ namespace Connect6 {
         }
 
         int* operator[](size_t idx) {
-            return &board[idx * BOARD_SIZE];
+            return &board[idx * board_size];
         }
 
         const int* operator[](size_t idx) const {
-            return &board[idx * BOARD_SIZE];
+            return &board[idx * board_size];
+        }
+
+        bool HasSearch() const {
+            return visits != nullptr;
+        }
+
+        float* GetVisits() {
+            return visits.get();
+        }
+
+        const float* GetVisits() const {
+            return visits.get();
+        }
+
+        float GetValue() const {
+            return value;
+        }
+
+        float GetQValue() const {
+            return q_value;
+        }
+
+        float GetElapsed() const {
+            return elapsed;
         }
 
     private:
         Player turn;
         std::tuple<size_t, size_t> position;
+        size_t board_size;
         std::unique_ptr<int[]> board;
+        std::unique_ptr<float[]> visits;
+        float value;
+        float q_value;
+        float elapsed;
     };
 
     class GameResult {
     public:
-        GameResult() : winner(Player::None), size(0), paths(nullptr) {
+        GameResult() : winner(Player::None), status(GameStatus::Ongoing), size(0), paths(nullptr) {
             // Do Nothing
         }
 
-        GameResult(Player winner, size_t size, std::unique_ptr<Path[]>&& paths) :
-            winner(winner), size(size), paths(std::move(paths))
+        GameResult(Player winner, GameStatus status, size_t size, std::unique_ptr<Path[]>&& paths) :
+            winner(winner), status(status), size(size), paths(std::move(paths))
         {
             // Do Nothing
         }
 
         GameResult(const Connect6_RustFFI::PlayResult& run_result) :
             winner(static_cast<Player>(run_result.winner)),
+            status(static_cast<GameStatus>(run_result.status)),
             size(run_result.len),
             paths(std::make_unique<Path[]>(size))
         {
@@ -212,6 +328,10 @@ namespace Connect6 {
             return winner;
         }
 
+        GameStatus GetStatus() const {
+            return status;
+        }
+
         size_t GetSize() const {
             return size;
         }
@@ -248,7 +368,7 @@ namespace Connect6 {
             return paths.get() + size;
         }
 
-        static std::vector<GameResult> from_vec(Connect6_RustFFI::Vec& result) {
+        static std::vector<GameResult> from_vec(Connect6_RustFFI::Vec& result, int num_failed = 0) {
             size_t len = result.len;
 
             std::vector<GameResult> game_result;
@@ -259,16 +379,167 @@ namespace Connect6 {
                 delete[] result.vec[i].paths;
             }
             delete[] result.vec;
+
+            // failed games by panics or timeouts are not included in the result
+            if (num_failed > 0) {
+                throw std::runtime_error(std::to_string(num_failed) + " games failed");
+            }
             return game_result;
         }
 
     private:
         Player winner;
+        GameStatus status;
 
         size_t size;
         std::unique_ptr<Path[]> paths;
     };
 
+    class RawResultView {
+    public:
+        RawResultView(const GameResult& result) {
+            namespace FFI = Connect6_RustFFI;
+            paths.reserve(result.GetSize());
+            for (const Path& path : result) {
+                FFI::Path raw;
+                raw.turn = static_cast<int>(path.GetTurn());
+                raw.board = const_cast<int*>(path.GetBoard());
+                raw.board_size = path.GetBoardSize();
+                raw.row = std::get<0>(path.GetPos());
+                raw.col = std::get<1>(path.GetPos());
+                raw.visits = const_cast<float*>(path.GetVisits());
+                raw.value = path.GetValue();
+                raw.q_value = path.GetQValue();
+                raw.elapsed = path.GetElapsed();
+                paths.push_back(raw);
+            }
+
+            this->result.winner = static_cast<int>(result.GetWinner());
+            this->result.status = static_cast<int>(result.GetStatus());
+            this->result.paths = paths.data();
+            this->result.len = paths.size();
+        }
+
+        RawResultView(const RawResultView&) = delete;
+        RawResultView(RawResultView&& other) = default;
+
+        const Connect6_RustFFI::PlayResult& Get() const {
+            return result;
+        }
+
+    private:
+        // Non-owning views of the paths, ffi copies the data
+        std::vector<Connect6_RustFFI::Path> paths;
+        Connect6_RustFFI::PlayResult result;
+    };
+
+    struct Batch {
+        size_t len = 0;
+        std::vector<float> values;
+        std::vector<int> turns;
+        std::vector<int> boards;
+        std::vector<float> policies;
+        std::vector<std::tuple<size_t, size_t>> positions;
+    };
+
+    class ReplayBuffer {
+    public:
+        ReplayBuffer(size_t capacity, int max_age = -1) :
+            buffer(Connect6_RustFFI::cpp_replay_new(capacity, max_age))
+        {
+            // Do Nothing
+        }
+
+        ReplayBuffer(const ReplayBuffer&) = delete;
+        ReplayBuffer(ReplayBuffer&& other) : buffer(other.buffer) {
+            other.buffer = nullptr;
+        }
+
+        ~ReplayBuffer() {
+            Connect6_RustFFI::cpp_replay_free(buffer);
+        }
+
+        ReplayBuffer& operator=(const ReplayBuffer&) = delete;
+        ReplayBuffer& operator=(ReplayBuffer&& other) {
+            std::swap(buffer, other.buffer);
+            return *this;
+        }
+
+        explicit operator bool() const {
+            return buffer != nullptr;
+        }
+
+        void Push(const GameResult& result) {
+            RawResultView view(result);
+            Connect6_RustFFI::cpp_replay_push(buffer, &view.Get());
+        }
+
+        size_t GetSize() const {
+            return Connect6_RustFFI::cpp_replay_len(buffer);
+        }
+
+        size_t GetNumGames() const {
+            return Connect6_RustFFI::cpp_replay_num_games(buffer);
+        }
+
+        void Seed(uint64_t seed) {
+            Connect6_RustFFI::cpp_replay_seed(buffer, seed);
+        }
+
+        void Clear() {
+            Connect6_RustFFI::cpp_replay_clear(buffer);
+        }
+
+        Batch Sample(size_t num_sample, bool augment = true, size_t board_size = BOARD_SIZE) {
+            size_t capacity = board_size * board_size;
+
+            Batch batch;
+            batch.values.resize(num_sample);
+            batch.turns.resize(num_sample);
+            batch.boards.resize(num_sample * capacity);
+            batch.policies.resize(num_sample * capacity);
+            std::vector<int> positions(num_sample * 2);
+
+            int len = Connect6_RustFFI::cpp_replay_sample(
+                buffer,
+                num_sample,
+                augment,
+                board_size,
+                batch.values.data(),
+                batch.turns.data(),
+                batch.boards.data(),
+                batch.policies.data(),
+                positions.data());
+
+            // Empty batch if the board size is out of range or the board size of the positions differs
+            batch.len = len < 0 ? 0 : len;
+            batch.values.resize(batch.len);
+            batch.turns.resize(batch.len);
+            batch.boards.resize(batch.len * capacity);
+            batch.policies.resize(batch.len * capacity);
+            for (size_t i = 0; i < batch.len; ++i) {
+                batch.positions.emplace_back(positions[i * 2], positions[i * 2 + 1]);
+            }
+            return batch;
+        }
+
+        bool Snapshot(const std::string& path) const {
+            return Connect6_RustFFI::cpp_replay_snapshot(buffer, path.c_str());
+        }
+
+        static ReplayBuffer Restore(const std::string& path) {
+            // Null buffer if failed, check with operator bool
+            return ReplayBuffer(Connect6_RustFFI::cpp_replay_restore(path.c_str()));
+        }
+
+    private:
+        ReplayBuffer(Connect6_RustFFI::ReplayBuffer* buffer) : buffer(buffer) {
+            // Take ownership of the buffer
+        }
+
+        Connect6_RustFFI::ReplayBuffer* buffer;
+    };
+
     struct Param {
         int num_simulation = 800;
         float epsilon = 0.25;
@@ -276,6 +547,7 @@ namespace Connect6 {
         float c_puct = 1;
         bool debug = false;
         int num_game_thread = 11;
+        int board_size = BOARD_SIZE;
 
         Param&& NumSimulation(int num_simulation) && {
             this->num_simulation = num_simulation;
@@ -306,37 +578,52 @@ namespace Connect6 {
             this->num_game_thread = num_game_thread;
             return std::move(*this);
         }
+
+        Param&& BoardSize(int board_size) && {
+            this->board_size = board_size;
+            return std::move(*this);
+        }
     };
 
-    std::vector<GameResult> play(PolicyCallback callback, bool debug, int num_game_thread)
+    std::vector<GameResult> play(PolicyCallback callback, bool debug, int num_game_thread, int board_size = BOARD_SIZE)
     {
         namespace FFI = Connect6_RustFFI;
+        int num_failed = 0;
         FFI::Vec result = FFI::cpp_play(
             callback,
             &FFI::allocator<FFI::Path>,
+            &FFI::allocator<int>,
+            &FFI::allocator<float>,
             &FFI::allocator<FFI::PlayResult>,
             debug,
-            num_game_thread
+            num_game_thread,
+            board_size,
+            &num_failed
         );
 
-        return GameResult::from_vec(result);
+        return GameResult::from_vec(result, num_failed);
     }
 
     std::vector<GameResult> self_play(Callback callback, const Param& param)
     {
         namespace FFI = Connect6_RustFFI;
+        int num_failed = 0;
         FFI::Vec result = FFI::cpp_self_play(
                 callback,
                 &FFI::allocator<FFI::Path>,
+                &FFI::allocator<int>,
+                &FFI::allocator<float>,
                 &FFI::allocator<FFI::PlayResult>,
                 param.num_simulation,
                 param.epsilon,
                 param.dirichlet_alpha,
                 param.c_puct,
                 param.debug,
-                param.num_game_thread);
+                param.num_game_thread,
+                param.board_size,
+                &num_failed);
 
-        return GameResult::from_vec(result);
+        return GameResult::from_vec(result, num_failed);
     }
 
     GameResult play_with(Callback callback, const Param& param)
@@ -345,13 +632,51 @@ namespace Connect6 {
         FFI::PlayResult result = FFI::cpp_play_with(
             callback,
             &FFI::allocator<FFI::Path>,
+            &FFI::allocator<int>,
+            &FFI::allocator<float>,
             param.num_simulation,
             param.epsilon,
             param.dirichlet_alpha,
-            param.c_puct);
+            param.c_puct,
+            param.board_size);
         
         return GameResult(result);
     }
+
+    std::vector<Connect6_RustFFI::PlayResult> to_raw_results(const std::vector<GameResult>& results,
+                                                             std::vector<RawResultView>& views)
+    {
+        std::vector<Connect6_RustFFI::PlayResult> raw;
+        views.reserve(results.size());
+        raw.reserve(results.size());
+        for (const GameResult& result : results) {
+            views.emplace_back(result);
+            raw.push_back(views.back().Get());
+        }
+        return raw;
+    }
+
+    int export_npz(const std::vector<GameResult>& results,
+                   const std::string& path,
+                   size_t board_size = BOARD_SIZE,
+                   bool compress = true)
+    {
+        std::vector<RawResultView> views;
+        auto raw = to_raw_results(results, views);
+        return Connect6_RustFFI::cpp_export_npz(raw.data(), raw.size(), board_size, path.c_str(), compress);
+    }
+
+    int export_shard(const std::vector<GameResult>& results,
+                     const std::string& path,
+                     size_t board_size = BOARD_SIZE,
+                     size_t chunk_size = 1024,
+                     bool compress = true)
+    {
+        std::vector<RawResultView> views;
+        auto raw = to_raw_results(results, views);
+        return Connect6_RustFFI::cpp_export_shard(
+            raw.data(), raw.size(), board_size, path.c_str(), chunk_size, compress);
+    }
 }
 
 #endif
\ No newline at end of file
@@ -1,8 +1,10 @@
 #ifndef CONNECT6_DEC_H
 #define CONNECT6_DEC_H
 
+#include <cstdint>
 #include <cstring>
 #include <memory>
+#include <stdexcept>
 #include <string>
 #include <tuple>
 #include <vector>
@@ -11,8 +13,8 @@ namespace Connect6_RustFFI {
     constexpr size_t BOARD_SIZE = 15;
     constexpr size_t BOARD_CAPACITY = BOARD_SIZE * BOARD_SIZE;
 
-    using Callback = void(*)(int player, float* values, float* policies, int len);
-    using PolicyCallback = void(*)(float* boards, int* position);
+    using Callback = void(*)(int player, float* values, float* policies, int len, int board_size);
+    using PolicyCallback = void(*)(float* board, int board_size, int* position);
 
     template <typename T>
     using AllocatorType = T*(*)(int size);
@@ -25,13 +27,19 @@ namespace Connect6_RustFFI {
     extern "C" {
         struct Path {
             int turn;
-            int board[BOARD_SIZE][BOARD_SIZE];
+            int* board;
+            int board_size;
             int row;
             int col;
+            float* visits;
+            float value;
+            float q_value;
+            float elapsed;
         };
 
         struct PlayResult {
             int winner;
+            int status;
             Path* paths;
             int len;
         };
@@ -43,36 +51,76 @@ namespace Connect6_RustFFI {
 
         Vec cpp_play(PolicyCallback callback,
                      AllocatorType<Path> alloc_path,
+                     AllocatorType<int> alloc_board,
+                     AllocatorType<float> alloc_visits,
                      AllocatorType<PlayResult> alloc_result,
                      bool debug,
-                     int num_game_thread);
+                     int num_game_thread,
+                     int board_size,
+                     int* num_failed);
 
         Vec cpp_self_play(Callback callback,
                           AllocatorType<Path> alloc_path,
+                          AllocatorType<int> alloc_board,
+                          AllocatorType<float> alloc_visits,
                           AllocatorType<PlayResult> alloc_result,
                           int num_simulation,
                           float epsilon,
                           double dirichlet_alpha,
                           float c_puct,
                           bool debug,
-                          int num_game_thread);
+                          int num_game_thread,
+                          int board_size,
+                          int* num_failed);
 
         PlayResult cpp_play_with(Callback callback,
                                  AllocatorType<Path> alloc_path,
+                                 AllocatorType<int> alloc_board,
+                                 AllocatorType<float> alloc_visits,
                                  int num_simulation,
                                  float epsilon,
                                  double dirichlet_alpha,
-                                 float c_puct);
+                                 float c_puct,
+                                 int board_size);
+
+        struct ReplayBuffer;
+
+        ReplayBuffer* cpp_replay_new(int capacity, int max_age);
+        void cpp_replay_free(ReplayBuffer* buffer);
+        void cpp_replay_push(ReplayBuffer* buffer, const PlayResult* result);
+        int cpp_replay_len(const ReplayBuffer* buffer);
+        int cpp_replay_num_games(const ReplayBuffer* buffer);
+        void cpp_replay_seed(ReplayBuffer* buffer, uint64_t seed);
+        void cpp_replay_clear(ReplayBuffer* buffer);
+        int cpp_replay_sample(ReplayBuffer* buffer,
+                              int num_sample,
+                              bool augment,
+                              int board_size,
+                              float* values,
+                              int* turns,
+                              int* boards,
+                              float* policies,
+                              int* positions);
+        bool cpp_replay_snapshot(const ReplayBuffer* buffer, const char* path);
+        ReplayBuffer* cpp_replay_restore(const char* path);
+
+        int cpp_export_npz(const PlayResult* results, int len, int board_size, const char* path, bool compress);
+        int cpp_export_shard(const PlayResult* results,
+                             int len,
+                             int board_size,
+                             const char* path,
+                             int chunk_size,
+                             bool compress);
     }
 
     namespace Test_FFI {
         extern "C" {
             Path test_new_raw_path();
-            Path test_with_raw_path();
-            Path test_echo_raw_path(int turn, int* board, int row, int col);
+            Path test_with_raw_path(AllocatorType<int> allocator, AllocatorType<float> visits_allocator);
+            Path test_echo_raw_path(int turn, int* board, int board_size, int row, int col, AllocatorType<int> allocator, AllocatorType<float> visits_allocator);
 
-            PlayResult test_with_raw_play_result(AllocatorType<Path> allocator);
-            PlayResult test_echo_raw_play_result(int winner, Path* path, int len, AllocatorType<Path> allocator);
+            PlayResult test_with_raw_play_result(AllocatorType<Path> allocator, AllocatorType<int> board_allocator, AllocatorType<float> visits_allocator);
+            PlayResult test_echo_raw_play_result(int winner, int status, Path* path, int len, AllocatorType<Path> allocator, AllocatorType<int> board_allocator, AllocatorType<float> visits_allocator);
 
             struct VecInt {
                 int* vec;
@@ -87,8 +135,8 @@ namespace Connect6_RustFFI {
                 int len;
             };
 
-            VecFloat test_echo_cppeval(int turn, int* boards, int len, Callback callback, AllocatorType<float> allocator);
-            VecInt test_cpp_policy(float* board, PolicyCallback callback, AllocatorType<int> allocator);
+            VecFloat test_echo_cppeval(int turn, int* boards, int len, int board_size, Callback callback, AllocatorType<float> allocator);
+            VecInt test_cpp_policy(float* board, int board_size, PolicyCallback callback, AllocatorType<int> allocator);
         }
     }
 }
@@ -106,6 +154,14 @@ namespace Connect6 {
         White = 1,
     };
 
+    enum class GameStatus : int {
+        Ongoing = 0,
+        Win = 1,
+        Draw = 2,
+    };
+
+    std::string to_string(GameStatus status);
+
     std::string to_string(Player player);
 
     class Path {
@@ -114,7 +170,8 @@ namespace Connect6 {
 
         Path(Player turn, 
              const std::tuple<size_t, size_t>& position, 
-             int board_[BOARD_SIZE][BOARD_SIZE]);
+             const int* board_,
+             size_t board_size = BOARD_SIZE);
 
         Path(const Connect6_RustFFI::Path& path);
 
@@ -128,26 +185,46 @@ namespace Connect6 {
 
         const std::tuple<size_t, size_t>& GetPos() const;
 
+        size_t GetBoardSize() const;
+
         int* GetBoard();
         const int* GetBoard() const;
 
         int* operator[](size_t idx);
         const int* operator[](size_t idx) const;
 
+        bool HasSearch() const;
+
+        float* GetVisits();
+        const float* GetVisits() const;
+
+        float GetValue() const;
+
+        float GetQValue() const;
+
+        float GetElapsed() const;
+
     private:
         Player turn;
         std::tuple<size_t, size_t> position;
+        size_t board_size;
         std::unique_ptr<int[]> board;
+        std::unique_ptr<float[]> visits;
+        float value;
+        float q_value;
+        float elapsed;
     };
 
     class GameResult {
     public:
         GameResult();
-        GameResult(Player winner, size_t size, std::unique_ptr<Path[]>&& paths);
+        GameResult(Player winner, GameStatus status, size_t size, std::unique_ptr<Path[]>&& paths);
         GameResult(const Connect6_RustFFI::PlayResult& run_result);
 
         Player GetWinner() const;
 
+        GameStatus GetStatus() const;
+
         size_t GetSize() const;
 
         Path& operator[](size_t idx);
@@ -161,15 +238,75 @@ namespace Connect6 {
         const Path* end() const;
         const Path* cend() const;
 
-        static std::vector<GameResult> from_vec(Connect6_RustFFI::Vec& result);
+        static std::vector<GameResult> from_vec(Connect6_RustFFI::Vec& result, int num_failed = 0);
 
     private:
         Player winner;
+        GameStatus status;
 
         size_t size;
         std::unique_ptr<Path[]> paths;
     };
 
+    class RawResultView {
+    public:
+        RawResultView(const GameResult& result);
+
+        RawResultView(const RawResultView&) = delete;
+        RawResultView(RawResultView&& other) = default;
+
+        const Connect6_RustFFI::PlayResult& Get() const;
+
+    private:
+        std::vector<Connect6_RustFFI::Path> paths;
+        Connect6_RustFFI::PlayResult result;
+    };
+
+    struct Batch {
+        size_t len = 0;
+        std::vector<float> values;
+        std::vector<int> turns;
+        std::vector<int> boards;
+        std::vector<float> policies;
+        std::vector<std::tuple<size_t, size_t>> positions;
+    };
+
+    class ReplayBuffer {
+    public:
+        ReplayBuffer(size_t capacity, int max_age = -1);
+
+        ReplayBuffer(const ReplayBuffer&) = delete;
+        ReplayBuffer(ReplayBuffer&& other);
+
+        ~ReplayBuffer();
+
+        ReplayBuffer& operator=(const ReplayBuffer&) = delete;
+        ReplayBuffer& operator=(ReplayBuffer&& other);
+
+        explicit operator bool() const;
+
+        void Push(const GameResult& result);
+
+        size_t GetSize() const;
+
+        size_t GetNumGames() const;
+
+        void Seed(uint64_t seed);
+
+        void Clear();
+
+        Batch Sample(size_t num_sample, bool augment = true, size_t board_size = BOARD_SIZE);
+
+        bool Snapshot(const std::string& path) const;
+
+        static ReplayBuffer Restore(const std::string& path);
+
+    private:
+        ReplayBuffer(Connect6_RustFFI::ReplayBuffer* buffer);
+
+        Connect6_RustFFI::ReplayBuffer* buffer;
+    };
+
     struct Param {
         int num_simulation = 800;
         float epsilon = 0.25;
@@ -177,6 +314,7 @@ namespace Connect6 {
         float c_puct = 1;
         bool debug = false;
         int num_game_thread = 11;
+        int board_size = BOARD_SIZE;
 
         Param&& NumSimulation(int num_simulation) &&;
         Param&& Epsilon(float epsilon) &&;
@@ -184,13 +322,25 @@ namespace Connect6 {
         Param&& CPuct(float c_puct) &&;
         Param&& Debug(bool debug) &&;
         Param&& NumGameThread(int num_game_thread) &&;
+        Param&& BoardSize(int board_size) &&;
     };
 
-    std::vector<GameResult> play(PolicyCallback callback, bool debug, int num_game_thread);
+    std::vector<GameResult> play(PolicyCallback callback, bool debug, int num_game_thread, int board_size = BOARD_SIZE);
 
     std::vector<GameResult> self_play(Callback callback, const Param& param);
 
     GameResult play_with(Callback callback, const Param& param);
+
+    int export_npz(const std::vector<GameResult>& results,
+                   const std::string& path,
+                   size_t board_size = BOARD_SIZE,
+                   bool compress = true);
+
+    int export_shard(const std::vector<GameResult>& results,
+                     const std::string& path,
+                     size_t board_size = BOARD_SIZE,
+                     size_t chunk_size = 1024,
+                     bool compress = true);
 }
 
 #endif
\ No newline at end of file
@@ -0,0 +1,103 @@
+//! C ffi of connect6::export.
+//!
+//! Play results are passed as the array of `RawPlayResult` and copied,
+//! so the caller keeps the ownership of the results.
+use connect6::agent::PlayResult;
+use connect6::export::{ShardWriter, TrainingBatch};
+use cppbind::{path_from_raw, CInt, RawPlayResult};
+
+use std::os::raw::c_char;
+
+#[cfg(test)]
+mod tests;
+
+/// Copy the results, `None` if the board size of any position differs from `board_size`.
+fn results_from_raw(
+    results: *const RawPlayResult,
+    len: CInt,
+    board_size: usize,
+) -> Option<Vec<PlayResult>> {
+    let raw = if len > 0 {
+        unsafe { ::std::slice::from_raw_parts(results, len as usize) }
+    } else {
+        &[]
+    };
+    let results = raw
+        .iter()
+        .map(|x| unsafe { x.to_result() })
+        .collect::<Vec<_>>();
+
+    let valid = results
+        .iter()
+        .flat_map(|x| x.path.iter())
+        .all(|x| x.board.size() == board_size);
+    if valid {
+        Some(results)
+    } else {
+        None
+    }
+}
+
+/// Export the play results to the npz file of `features`, `policies` and `values`.
+///
+/// Return the number of the exported positions, or -1 if failed.
+#[no_mangle]
+pub extern "C" fn cpp_export_npz(
+    results: *const RawPlayResult,
+    len: CInt,
+    board_size: CInt,
+    path: *const c_char,
+    compress: bool,
+) -> CInt {
+    let results = match results_from_raw(results, len, board_size as usize) {
+        Some(results) => results,
+        None => return -1,
+    };
+    let path = match path_from_raw(path) {
+        Some(path) => path,
+        None => return -1,
+    };
+
+    let batch = TrainingBatch::from_results(board_size as usize, &results);
+    match batch.save_npz(path, compress) {
+        Ok(_) => batch.len() as CInt,
+        Err(_) => -1,
+    }
+}
+
+/// Export the play results to the shard file with `chunk_size` samples per chunk.
+///
+/// Return the number of the exported positions, or -1 if failed.
+#[no_mangle]
+pub extern "C" fn cpp_export_shard(
+    results: *const RawPlayResult,
+    len: CInt,
+    board_size: CInt,
+    path: *const c_char,
+    chunk_size: CInt,
+    compress: bool,
+) -> CInt {
+    let results = match results_from_raw(results, len, board_size as usize) {
+        Some(results) => results,
+        None => return -1,
+    };
+    let path = match path_from_raw(path) {
+        Some(path) => path,
+        None => return -1,
+    };
+
+    let write = || {
+        let mut writer = ShardWriter::create(path, board_size as usize)?
+            .with_chunk_size(chunk_size.max(1) as usize)
+            .with_compression(compress);
+        for result in results.iter() {
+            writer.push_result(result)?;
+        }
+        let num_samples = writer.num_samples();
+        writer.finish().map(|_| num_samples)
+    };
+    match write() {
+        Ok(num_samples) => num_samples as CInt,
+        Err(_) => -1,
+    }
+}
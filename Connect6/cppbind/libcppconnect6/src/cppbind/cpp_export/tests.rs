@@ -0,0 +1,67 @@
+use super::*;
+use cppbind::{Allocator, CInt};
+
+use connect6::export::ShardReader;
+use connect6::{agent::Agent, policy::RandomPolicy};
+use std::ffi::CString;
+use std::mem;
+
+extern "C" fn test_allocator<T: Default + Clone>(size: CInt) -> *mut T {
+    let mut vec = vec![T::default(); size as usize];
+    let ptr = vec.as_mut_ptr();
+    mem::forget(vec);
+    return ptr;
+}
+
+fn raw_results(board_size: usize) -> (Vec<RawPlayResult>, usize) {
+    let alloc = Allocator::new(test_allocator);
+    let alloc_board = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+
+    let mut policy = RandomPolicy::new();
+    let mut num_samples = 0;
+    let mut raw = Vec::new();
+    for _ in 0..2 {
+        let result = Agent::new(&mut policy)
+            .with_board_size(board_size)
+            .play()
+            .unwrap();
+        num_samples += result.path.len();
+        raw.push(RawPlayResult::with_result(
+            &result,
+            &alloc,
+            &alloc_board,
+            &alloc_visits,
+        ));
+    }
+    (raw, num_samples)
+}
+
+#[test]
+fn test_export_npz() {
+    let (raw, num_samples) = raw_results(5);
+    let path = ::std::env::temp_dir().join("connect6_cpp_export_test.npz");
+    let cpath = CString::new(path.to_str().unwrap()).unwrap();
+
+    let len = cpp_export_npz(raw.as_ptr(), 2, 5, cpath.as_ptr(), true);
+    assert_eq!(len, num_samples as CInt);
+    assert!(path.exists());
+    ::std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(cpp_export_npz(raw.as_ptr(), 2, 7, cpath.as_ptr(), true), -1);
+}
+
+#[test]
+fn test_export_shard() {
+    let (raw, num_samples) = raw_results(5);
+    let path = ::std::env::temp_dir().join("connect6_cpp_export_test.bin");
+    let cpath = CString::new(path.to_str().unwrap()).unwrap();
+
+    let len = cpp_export_shard(raw.as_ptr(), 2, 5, cpath.as_ptr(), 8, false);
+    assert_eq!(len, num_samples as CInt);
+
+    let reader = ShardReader::open(&path).unwrap();
+    assert_eq!(reader.num_samples(), num_samples);
+    assert_eq!(reader.chunk_len(0), 8);
+    ::std::fs::remove_file(&path).unwrap();
+}
@@ -1,11 +1,11 @@
-use connect6::{game, policy, Board, BOARD_SIZE};
+use connect6::{game, policy, Board};
 use cppbind::{board_to_float, CFloat, CInt};
 
 #[cfg(test)]
 mod tests;
 
-/// void(float* boards, int* position_result)
-pub type PolicyCallback = extern "C" fn(*const [[CFloat; BOARD_SIZE]; BOARD_SIZE], *mut [CInt; 2]);
+/// void(float* board, int board_size, int* position_result)
+pub type PolicyCallback = extern "C" fn(*const CFloat, CInt, *mut [CInt; 2]);
 
 /// C++ FFI policy bindings.
 pub struct CppPolicy {
@@ -24,7 +24,8 @@ impl CppPolicy {
         let board_f = board_to_float(board);
 
         (self.callback)(
-            &board_f as *const [[CFloat; BOARD_SIZE]; BOARD_SIZE],
+            board_f.as_slice().as_ptr(),
+            board.size() as CInt,
             &mut res as *mut [CInt; 2],
         );
 
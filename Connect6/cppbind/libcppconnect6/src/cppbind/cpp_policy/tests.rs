@@ -1,28 +1,25 @@
 use super::*;
 
-use connect6::game::Player;
+use connect6::{game::Player, BOARD_SIZE};
 use rand;
+use std::slice;
 
-extern "C" fn test_callback(
-    board_ptr: *const [[CFloat; BOARD_SIZE]; BOARD_SIZE],
-    res_ptr: *mut [CInt; 2],
-) {
-    let board = unsafe { board_ptr.as_ref() }.unwrap();
+extern "C" fn test_callback(board_ptr: *const CFloat, board_size: CInt, res_ptr: *mut [CInt; 2]) {
+    let size = board_size as usize;
+    let board = unsafe { slice::from_raw_parts(board_ptr, size * size) };
     let res = unsafe { res_ptr.as_mut() }.unwrap();
 
     let mut sum = 0.;
-    for row in board {
-        for cell in row {
-            sum += *cell;
-        }
+    for cell in board {
+        sum += *cell;
     }
 
-    res[0] = ((sum as usize) % BOARD_SIZE) as CInt;
+    res[0] = ((sum.abs() as usize) % size) as CInt;
     res[1] = 0;
 }
 
 fn create_random_board() -> Board {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             board[i][j] = Player::from(rand::random::<i32>() % 3 - 1);
@@ -50,7 +47,7 @@ fn test_cpp_policy_callback() {
 
     let cpp_policy = CppPolicy::new(test_callback);
     if let Some((row, col)) = cpp_policy.callback(&board) {
-        assert_eq!(row, (sum as usize) % BOARD_SIZE);
+        assert_eq!(row, (sum.abs() as usize) % BOARD_SIZE);
         assert_eq!(col, 0);
     } else {
         assert!(false);
@@ -0,0 +1,139 @@
+//! C ffi of connect6::replay::ReplayBuffer.
+//!
+//! Buffer is passed to C++ as an opaque pointer, created by `cpp_replay_new` or `cpp_replay_restore`
+//! and must be released by `cpp_replay_free`.
+//! Reference [connect6.hpp](https://github.com/revsic/AlphaZero-Connect6/blob/master/Connect6/cppbind/headers/connect6.hpp)
+//! for the C++ RAII wrapper `Connect6::ReplayBuffer`.
+use connect6::replay::ReplayBuffer;
+use connect6::MAX_BOARD_SIZE;
+use cppbind::{path_from_raw, CFloat, CInt, RawPlayResult};
+
+use std::os::raw::c_char;
+use std::ptr;
+
+#[cfg(test)]
+mod tests;
+
+/// Create a new replay buffer keeping at most `capacity` positions of the latest `max_age` games.
+///
+/// Negative `max_age` means no limit of the games.
+#[no_mangle]
+pub extern "C" fn cpp_replay_new(capacity: CInt, max_age: CInt) -> *mut ReplayBuffer {
+    let mut buffer = ReplayBuffer::new(capacity.max(0) as usize);
+    if max_age >= 0 {
+        buffer = buffer.with_max_age(max_age as u64);
+    }
+    Box::into_raw(Box::new(buffer))
+}
+
+/// Release the replay buffer, null is ignored.
+#[no_mangle]
+pub extern "C" fn cpp_replay_free(buffer: *mut ReplayBuffer) {
+    if !buffer.is_null() {
+        drop(unsafe { Box::from_raw(buffer) });
+    }
+}
+
+/// Push the play result, the result is copied and still owned by the caller.
+#[no_mangle]
+pub extern "C" fn cpp_replay_push(buffer: *mut ReplayBuffer, result: *const RawPlayResult) {
+    let buffer = unsafe { &mut *buffer };
+    let result = unsafe { (*result).to_result() };
+    buffer.push(&result);
+}
+
+/// Number of the stored positions.
+#[no_mangle]
+pub extern "C" fn cpp_replay_len(buffer: *const ReplayBuffer) -> CInt {
+    unsafe { (*buffer).len() as CInt }
+}
+
+/// Number of the games pushed since the construction.
+#[no_mangle]
+pub extern "C" fn cpp_replay_num_games(buffer: *const ReplayBuffer) -> CInt {
+    unsafe { (*buffer).num_games() as CInt }
+}
+
+/// Seed the random number generator of the sampling.
+#[no_mangle]
+pub extern "C" fn cpp_replay_seed(buffer: *mut ReplayBuffer, seed: u64) {
+    unsafe { (*buffer).seed(seed) }
+}
+
+/// Remove all positions.
+#[no_mangle]
+pub extern "C" fn cpp_replay_clear(buffer: *mut ReplayBuffer) {
+    unsafe { (*buffer).clear() }
+}
+
+/// Sample `num_sample` positions without replacement into the caller arrays, return the number of the samples.
+///
+/// With `capacity = board_size * board_size`, `values` and `turns` have `num_sample` elements,
+/// `boards` and `policies` have `num_sample * capacity` and `positions` has `num_sample * 2`, (row, col) pairs.
+/// Return -1 without writing if `board_size` isn't in range [1, MAX_BOARD_SIZE],
+/// or the board size of any sample differs from `board_size`.
+#[no_mangle]
+pub extern "C" fn cpp_replay_sample(
+    buffer: *mut ReplayBuffer,
+    num_sample: CInt,
+    augment: bool,
+    board_size: CInt,
+    values: *mut CFloat,
+    turns: *mut CInt,
+    boards: *mut CInt,
+    policies: *mut CFloat,
+    positions: *mut CInt,
+) -> CInt {
+    if board_size <= 0 || board_size as usize > MAX_BOARD_SIZE {
+        return -1;
+    }
+    let buffer = unsafe { &mut *buffer };
+    let samples = buffer.sample(num_sample.max(0) as usize, augment);
+
+    let size = board_size as usize;
+    if samples.iter().any(|x| x.board.size() != size) {
+        return -1;
+    }
+
+    let len = samples.len();
+    let capacity = size * size;
+    let values = unsafe { ::std::slice::from_raw_parts_mut(values, len) };
+    let turns = unsafe { ::std::slice::from_raw_parts_mut(turns, len) };
+    let boards = unsafe { ::std::slice::from_raw_parts_mut(boards, len * capacity) };
+    let policies = unsafe { ::std::slice::from_raw_parts_mut(policies, len * capacity) };
+    let positions = unsafe { ::std::slice::from_raw_parts_mut(positions, len * 2) };
+
+    for (i, sample) in samples.iter().enumerate() {
+        values[i] = sample.value;
+        turns[i] = sample.turn as CInt;
+
+        let cells = sample.board.as_slice().iter().map(|x| *x as CInt);
+        for (p, cell) in boards[i * capacity..(i + 1) * capacity]
+            .iter_mut()
+            .zip(cells)
+        {
+            *p = cell;
+        }
+        policies[i * capacity..(i + 1) * capacity].copy_from_slice(sample.policy.as_slice());
+
+        positions[i * 2] = sample.pos.0 as CInt;
+        positions[i * 2 + 1] = sample.pos.1 as CInt;
+    }
+    len as CInt
+}
+
+/// Save the snapshot of the buffer to the file, return false if failed.
+#[no_mangle]
+pub extern "C" fn cpp_replay_snapshot(buffer: *const ReplayBuffer, path: *const c_char) -> bool {
+    let buffer = unsafe { &*buffer };
+    matches!(path_from_raw(path).map(|x| buffer.snapshot(x)), Some(Ok(_)))
+}
+
+/// Restore the buffer from the snapshot file, return null if failed.
+#[no_mangle]
+pub extern "C" fn cpp_replay_restore(path: *const c_char) -> *mut ReplayBuffer {
+    match path_from_raw(path).map(ReplayBuffer::restore) {
+        Some(Ok(buffer)) => Box::into_raw(Box::new(buffer)),
+        _ => ptr::null_mut(),
+    }
+}
@@ -0,0 +1,111 @@
+use super::*;
+use cppbind::{Allocator, CInt};
+
+use connect6::{agent::Agent, policy::RandomPolicy};
+use std::ffi::CString;
+use std::mem;
+
+extern "C" fn test_allocator<T: Default + Clone>(size: CInt) -> *mut T {
+    let mut vec = vec![T::default(); size as usize];
+    let ptr = vec.as_mut_ptr();
+    mem::forget(vec);
+    return ptr;
+}
+
+fn raw_result(board_size: usize) -> RawPlayResult {
+    let mut policy = RandomPolicy::new();
+    let result = Agent::new(&mut policy)
+        .with_board_size(board_size)
+        .play()
+        .unwrap();
+
+    let alloc = Allocator::new(test_allocator);
+    let alloc_board = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+    RawPlayResult::with_result(&result, &alloc, &alloc_board, &alloc_visits)
+}
+
+#[test]
+fn test_replay_sample() {
+    let buffer = cpp_replay_new(1000, -1);
+    let raw = raw_result(5);
+    cpp_replay_push(buffer, &raw);
+
+    assert_eq!(cpp_replay_len(buffer), raw.len);
+    assert_eq!(cpp_replay_num_games(buffer), 1);
+
+    let num_sample = 4;
+    let mut values = vec![0.; num_sample];
+    let mut turns = vec![0; num_sample];
+    let mut boards = vec![0; num_sample * 25];
+    let mut policies = vec![0.; num_sample * 25];
+    let mut positions = vec![0; num_sample * 2];
+
+    let len = cpp_replay_sample(
+        buffer,
+        num_sample as CInt,
+        true,
+        5,
+        values.as_mut_ptr(),
+        turns.as_mut_ptr(),
+        boards.as_mut_ptr(),
+        policies.as_mut_ptr(),
+        positions.as_mut_ptr(),
+    );
+    assert_eq!(len, num_sample as CInt);
+    for i in 0..num_sample {
+        let idx = positions[i * 2] as usize * 5 + positions[i * 2 + 1] as usize;
+        assert_eq!(boards[i * 25 + idx], 0);
+        assert_eq!(policies[i * 25 + idx], 1.);
+        assert!(turns[i] == -1 || turns[i] == 1);
+    }
+
+    let len = cpp_replay_sample(
+        buffer,
+        num_sample as CInt,
+        false,
+        7,
+        values.as_mut_ptr(),
+        turns.as_mut_ptr(),
+        boards.as_mut_ptr(),
+        policies.as_mut_ptr(),
+        positions.as_mut_ptr(),
+    );
+    assert_eq!(len, -1);
+
+    for board_size in [0, -5, 27].iter() {
+        let len = cpp_replay_sample(
+            buffer,
+            num_sample as CInt,
+            false,
+            *board_size,
+            values.as_mut_ptr(),
+            turns.as_mut_ptr(),
+            boards.as_mut_ptr(),
+            policies.as_mut_ptr(),
+            positions.as_mut_ptr(),
+        );
+        assert_eq!(len, -1);
+    }
+    cpp_replay_free(buffer);
+}
+
+#[test]
+fn test_replay_snapshot() {
+    let buffer = cpp_replay_new(1000, 10);
+    cpp_replay_push(buffer, &raw_result(5));
+
+    let path = ::std::env::temp_dir().join("connect6_cpp_replay_test.bin");
+    let cpath = CString::new(path.to_str().unwrap()).unwrap();
+    assert!(cpp_replay_snapshot(buffer, cpath.as_ptr()));
+
+    let restored = cpp_replay_restore(cpath.as_ptr());
+    assert!(!restored.is_null());
+    assert_eq!(cpp_replay_len(restored), cpp_replay_len(buffer));
+
+    cpp_replay_free(restored);
+    cpp_replay_free(buffer);
+    ::std::fs::remove_file(&path).unwrap();
+
+    assert!(cpp_replay_restore(cpath.as_ptr()).is_null());
+}
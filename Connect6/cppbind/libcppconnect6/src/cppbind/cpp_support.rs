@@ -1,4 +1,8 @@
-use connect6::{Board, BOARD_SIZE};
+use connect6::game::{GameStatus, Player};
+use connect6::{Board, GenericBoard};
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
 
 /// std::os::raw::c_int
 pub type CInt = ::std::os::raw::c_int;
@@ -6,13 +10,34 @@ pub type CInt = ::std::os::raw::c_int;
 /// std::os::raw::c_float
 pub type CFloat = ::std::os::raw::c_float;
 
+/// Convert GameStatus to CInt, { 0: Ongoing, 1: Win, 2: Draw }
+pub fn status_to_int(status: &GameStatus) -> CInt {
+    match status {
+        &GameStatus::Ongoing => 0,
+        &GameStatus::Win(_) => 1,
+        &GameStatus::Draw => 2,
+    }
+}
+
+/// Convert CInt status code and winner to GameStatus, reverse of `status_to_int`
+pub fn status_from_int(status: CInt, winner: Player) -> GameStatus {
+    match status {
+        1 => GameStatus::Win(winner),
+        2 => GameStatus::Draw,
+        _ => GameStatus::Ongoing,
+    }
+}
+
 /// Convert Player:Board to CFloat:Board
-pub fn board_to_float(board: &Board) -> [[CFloat; BOARD_SIZE]; BOARD_SIZE] {
-    let mut converted = [[0.; BOARD_SIZE]; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            converted[i][j] = board[i][j] as i32 as CFloat;
-        }
+pub fn board_to_float(board: &Board) -> GenericBoard<CFloat> {
+    board.map(|x| *x as i32 as CFloat)
+}
+
+/// Convert null-terminated C string to the file path, `None` if null or not utf-8
+pub fn path_from_raw(path: *const c_char) -> Option<String> {
+    if path.is_null() {
+        return None;
     }
-    converted
+    let path = unsafe { CStr::from_ptr(path) };
+    path.to_str().ok().map(|x| x.to_string())
 }
@@ -1,15 +1,16 @@
-use connect6::{game::Player, policy, Board, BOARD_SIZE};
+use connect6::{game::Player, policy, Board, GenericBoard};
 use cppbind::{board_to_float, CFloat, CInt};
 
 #[cfg(test)]
 mod tests;
 
-/// void(int player, float* values, float* board, int length)
+/// void(int player, float* values, float* board, int length, int board_size)
 pub type Callback = extern "C" fn(
-    CInt,                                    // player
-    *mut CFloat,                             // out: value
-    *mut [[CFloat; BOARD_SIZE]; BOARD_SIZE], // in: board, out: policy
-    CInt,                                    // num boards
+    CInt,        // player
+    *mut CFloat, // out: value
+    *mut CFloat, // in: board, out: policy
+    CInt,        // num boards
+    CInt,        // board size
 );
 
 /// AlphaZero value, policy approximator with c ffi callback
@@ -28,28 +29,34 @@ impl CppEval {
         &self,
         turn: Player,
         board: &Vec<Board>,
-    ) -> Option<(Vec<f32>, Vec<[[f32; BOARD_SIZE]; BOARD_SIZE]>)> {
+    ) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
         let len = board.len();
+        let size = board.first().map(|x| x.size()).unwrap_or(0);
         let player = turn as CInt;
         let mut values = vec![0.; len];
-        let mut policies = board.iter().map(board_to_float).collect::<Vec<_>>();
+        let mut policies = board
+            .iter()
+            .flat_map(|x| board_to_float(x).as_slice().to_vec())
+            .collect::<Vec<_>>();
 
         (self.callback)(
             player,
             values.as_mut_ptr(),
             policies.as_mut_ptr(),
             len as CInt,
+            size as CInt,
         );
+
+        let policies = policies
+            .chunks((size * size).max(1))
+            .filter_map(|x| GenericBoard::from_vec(size, x.to_vec()))
+            .collect();
         Some((values, policies))
     }
 }
 
 impl policy::Evaluator for CppEval {
-    fn eval(
-        &self,
-        turn: Player,
-        board: &Vec<Board>,
-    ) -> Option<(Vec<f32>, Vec<[[f32; BOARD_SIZE]; BOARD_SIZE]>)> {
+    fn eval(&self, turn: Player, board: &Vec<Board>) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
         self.callback(turn, board)
     }
 }
@@ -1,34 +1,33 @@
 use super::*;
 
+use connect6::BOARD_SIZE;
 use rand;
 use std::slice;
 
 extern "C" fn test_callback(
     player: CInt,
     values: *mut CFloat,
-    policies: *mut [[CFloat; BOARD_SIZE]; BOARD_SIZE],
+    policies: *mut CFloat,
     len: CInt,
+    board_size: CInt,
 ) {
     let len = len as usize;
+    let capacity = (board_size * board_size) as usize;
     let value_ref = unsafe { slice::from_raw_parts_mut(values, len) };
-    let policy_ref = unsafe { slice::from_raw_parts_mut(policies, len) };
+    let policy_ref = unsafe { slice::from_raw_parts_mut(policies, len * capacity) };
 
     for i in 0..len {
         value_ref[i] = i as f32;
-
-        let ind = &mut policy_ref[i];
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                ind[i][j] *= 2.;
-            }
-        }
+    }
+    for cell in policy_ref.iter_mut() {
+        *cell *= 2.;
     }
     value_ref[0] = player as f32;
 }
 
 #[test]
 fn test_convert_to_c_float() {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
 
     board[0][0] = Player::Black;
     board[0][BOARD_SIZE - 1] = Player::White;
@@ -49,10 +48,10 @@ fn test_cppeval_new() {
     assert!(true);
 }
 
-fn create_random_board() -> Board {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
+fn create_random_board(size: usize) -> Board {
+    let mut board = Board::new(size);
+    for i in 0..size {
+        for j in 0..size {
             board[i][j] = Player::from(rand::random::<i32>() % 3 - 1);
         }
     }
@@ -65,7 +64,9 @@ fn test_cppeval_callback() {
 
     let player = Player::Black;
     let len = rand::random::<usize>() % 10 + 10;
-    let boards = (0..len).map(|_| create_random_board()).collect::<Vec<_>>();
+    let boards = (0..len)
+        .map(|_| create_random_board(BOARD_SIZE))
+        .collect::<Vec<_>>();
 
     let result = eval.callback(player, &boards);
     assert!(result.is_some());
@@ -77,16 +78,22 @@ fn test_cppeval_callback() {
 
     assert_eq!(value, target_value);
 
-    let double = |x: &[[Player; BOARD_SIZE]; BOARD_SIZE]| {
-        let mut board = [[0.; BOARD_SIZE]; BOARD_SIZE];
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                board[i][j] = 2. * x[i][j] as i32 as f32
-            }
-        }
-        board
-    };
+    let double = |x: &Board| x.map(|x| 2. * *x as i32 as f32);
 
     let target_policy = boards.iter().map(double).collect::<Vec<_>>();
     assert_eq!(policy, target_policy);
 }
+
+#[test]
+fn test_sized_cppeval_callback() {
+    let eval = CppEval::new(test_callback);
+    let boards = vec![create_random_board(9), create_random_board(9)];
+
+    let result = eval.callback(Player::White, &boards);
+    assert!(result.is_some());
+
+    let (value, policy) = result.unwrap();
+    assert_eq!(value.len(), 2);
+    assert_eq!(policy.len(), 2);
+    assert!(policy.iter().all(|x| x.size() == 9));
+}
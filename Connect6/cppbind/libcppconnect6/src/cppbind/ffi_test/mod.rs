@@ -5,9 +5,37 @@
 //! [connect6.hpp](https://github.com/revsic/AlphaZero-Connect6/blob/master/Connect6/cppbind/connect6.hpp)
 //! Reference [test_cppbind](https://github.com/revsic/AlphaZero-Connect6/blob/master/Connect6/cppbind/test_cppbind/main.cpp).
 //!
-use connect6::{agent, game::Player, policy::Evaluator, BOARD_CAPACITY, BOARD_SIZE};
+use connect6::{
+    agent,
+    game::{GameStatus, Player},
+    policy::{Evaluator, SearchInfo},
+    Board, GenericBoard, BOARD_SIZE,
+};
 use cppbind::*;
 
+use std::time::Duration;
+
+/// Convert row-major ordered int array to `Board`
+fn board_from_raw(board_ptr: *const CInt, board_size: usize) -> Board {
+    let board_slice = unsafe { ::std::slice::from_raw_parts(board_ptr, board_size * board_size) };
+    let cells = board_slice.iter().map(|x| Player::from(*x)).collect();
+    Board::from_vec(board_size, cells).unwrap()
+}
+
+/// Convert search data of `RawPath`, `None` if `visits` is null
+fn search_from_raw(path: &RawPath) -> Option<SearchInfo> {
+    if path.visits.is_null() {
+        return None;
+    }
+    let size = path.board_size as usize;
+    let visits = unsafe { ::std::slice::from_raw_parts(path.visits, size * size) };
+    Some(SearchInfo {
+        visits: GenericBoard::from_vec(size, visits.to_vec()).unwrap(),
+        value: path.value,
+        q_value: path.q_value,
+    })
+}
+
 /// Return `RawPath::new()`;
 #[no_mangle]
 pub extern "C" fn test_new_raw_path() -> RawPath {
@@ -15,9 +43,15 @@ pub extern "C" fn test_new_raw_path() -> RawPath {
 }
 
 /// Generate sample `agent::Path` and return `RawPath::with_path`.
+///
+/// Path has the search data, visit distribution concentrated on the position,
+/// value 0.5, q-value -0.25 and 1.5 seconds elapsed.
 #[no_mangle]
-pub extern "C" fn test_with_raw_path() -> RawPath {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+pub extern "C" fn test_with_raw_path(
+    allocator: AllocatorType<CInt>,
+    visits_allocator: AllocatorType<CFloat>,
+) -> RawPath {
+    let mut board = Board::new(BOARD_SIZE);
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             let id = ((i * BOARD_SIZE + j) % 3) as i32 - 1;
@@ -25,12 +59,24 @@ pub extern "C" fn test_with_raw_path() -> RawPath {
         }
     }
 
+    let pos = (0, BOARD_SIZE % 5 + 1);
+    let mut visits = GenericBoard::new(BOARD_SIZE);
+    visits[pos.0][pos.1] = 1.;
+
     let path = agent::Path {
         turn: Player::White,
         board,
-        pos: (0, BOARD_SIZE % 5 + 1),
+        pos,
+        search: Some(SearchInfo {
+            visits,
+            value: 0.5,
+            q_value: -0.25,
+        }),
+        elapsed: Duration::from_millis(1500),
     };
-    RawPath::with_path(&path)
+    let alloc = Allocator::new(allocator);
+    let alloc_visits = Allocator::new(visits_allocator);
+    RawPath::with_path(&path, &alloc, &alloc_visits)
 }
 
 /// Get path info from C++ and return repackaged one.
@@ -38,32 +84,36 @@ pub extern "C" fn test_with_raw_path() -> RawPath {
 pub extern "C" fn test_echo_raw_path(
     turn: CInt,
     board_ptr: *mut CInt,
+    board_size: CInt,
     row: CInt,
     col: CInt,
+    allocator: AllocatorType<CInt>,
+    visits_allocator: AllocatorType<CFloat>,
 ) -> RawPath {
-    let board_slice = unsafe { ::std::slice::from_raw_parts(board_ptr, BOARD_CAPACITY) };
-    let mut board = [[0; BOARD_SIZE]; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            board[i][j] = board_slice[i * BOARD_SIZE + j];
-        }
-    }
-    RawPath {
-        turn,
-        board,
-        row,
-        col,
-    }
+    let path = agent::Path {
+        turn: Player::from(turn),
+        board: board_from_raw(board_ptr, board_size as usize),
+        pos: (row as usize, col as usize),
+        search: None,
+        elapsed: Duration::default(),
+    };
+    let alloc = Allocator::new(allocator);
+    let alloc_visits = Allocator::new(visits_allocator);
+    RawPath::with_path(&path, &alloc, &alloc_visits)
 }
 
 /// Generate sample `agent::PlayResult` and return `RawPlayResult::with_result`.
 #[no_mangle]
-pub extern "C" fn test_with_raw_play_result(allocator: AllocatorType<RawPath>) -> RawPlayResult {
+pub extern "C" fn test_with_raw_play_result(
+    allocator: AllocatorType<RawPath>,
+    board_allocator: AllocatorType<CInt>,
+    visits_allocator: AllocatorType<CFloat>,
+) -> RawPlayResult {
     let mut vec = Vec::new();
     let mut player = Player::Black;
 
     for i in 0..10 {
-        let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+        let mut board = Board::new(BOARD_SIZE);
         for j in 0..i + 1 {
             board[j][j] = Player::from(((i + j) % 3) as i32 - 1);
         }
@@ -71,6 +121,8 @@ pub extern "C" fn test_with_raw_play_result(allocator: AllocatorType<RawPath>) -
             turn: player,
             board,
             pos: (i, i + 1),
+            search: None,
+            elapsed: Duration::default(),
         });
 
         player.mut_switch();
@@ -78,46 +130,56 @@ pub extern "C" fn test_with_raw_play_result(allocator: AllocatorType<RawPath>) -
 
     let result = agent::PlayResult {
         winner: Player::Black,
+        status: GameStatus::Win(Player::Black),
+        reason: agent::EndReason::Line,
+        would_resign: Player::None,
         path: vec,
     };
 
     let alloc = Allocator::new(allocator);
-    RawPlayResult::with_result(&result, &alloc)
+    let alloc_board = Allocator::new(board_allocator);
+    let alloc_visits = Allocator::new(visits_allocator);
+    RawPlayResult::with_result(&result, &alloc, &alloc_board, &alloc_visits)
 }
 
 /// Get play result from C++ and return repackaged one.
 #[no_mangle]
 pub extern "C" fn test_echo_raw_play_result(
     winner: CInt,
+    status: CInt,
     path: *mut RawPath,
     len: CInt,
     allocator: AllocatorType<RawPath>,
+    board_allocator: AllocatorType<CInt>,
+    visits_allocator: AllocatorType<CFloat>,
 ) -> RawPlayResult {
     let path_s = unsafe { ::std::slice::from_raw_parts(path, len as usize) };
 
     let mut vec = Vec::new();
     for i in 0..len as usize {
-        let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-        for j in 0..BOARD_SIZE {
-            for k in 0..BOARD_SIZE {
-                board[j][k] = Player::from(path_s[i].board[j][k]);
-            }
-        }
-
         vec.push(agent::Path {
             turn: Player::from(path_s[i].turn),
-            board,
+            board: board_from_raw(path_s[i].board, path_s[i].board_size as usize),
             pos: (path_s[i].row as usize, path_s[i].col as usize),
+            search: search_from_raw(&path_s[i]),
+            elapsed: Duration::default(),
         });
     }
 
+    let winner = Player::from(winner);
+    let status = status_from_int(status, winner);
     let result = agent::PlayResult {
-        winner: Player::from(winner),
+        winner,
+        status,
+        reason: agent::EndReason::from_status(status),
+        would_resign: Player::None,
         path: vec,
     };
 
     let alloc = Allocator::new(allocator);
-    RawPlayResult::with_result(&result, &alloc)
+    let alloc_board = Allocator::new(board_allocator);
+    let alloc_visits = Allocator::new(visits_allocator);
+    RawPlayResult::with_result(&result, &alloc, &alloc_board, &alloc_visits)
 }
 
 /// Generate sample `Vec<i32>` and return `RawVec::with_vec`.
@@ -150,25 +212,19 @@ pub extern "C" fn test_echo_cppeval(
     turn: CInt,
     boards: *const CInt,
     len: CInt,
+    board_size: CInt,
     callback: Callback,
     allocator: AllocatorType<CFloat>,
 ) -> RawVec<CFloat> {
     let turn = Player::from(turn);
     let len = len as usize;
+    let size = board_size as usize;
+    let capacity = size * size;
 
-    let boards = unsafe { ::std::slice::from_raw_parts(boards, len * BOARD_CAPACITY) };
-
-    let mut cnt = 0;
     let mut vec = Vec::new();
-    for _ in 0..len {
-        let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-        for r in 0..BOARD_SIZE {
-            for c in 0..BOARD_SIZE {
-                board[r][c] = Player::from(boards[cnt]);
-                cnt += 1;
-            }
-        }
-        vec.push(board);
+    for i in 0..len {
+        let ptr = unsafe { boards.offset((i * capacity) as isize) };
+        vec.push(board_from_raw(ptr, size));
     }
 
     let cppeval = CppEval::new(callback);
@@ -186,11 +242,7 @@ pub extern "C" fn test_echo_cppeval(
     }
 
     for policy in policies {
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                ret.push(policy[i][j]);
-            }
-        }
+        ret.extend_from_slice(policy.as_slice());
     }
 
     let alloc = Allocator::new(allocator);
@@ -199,18 +251,18 @@ pub extern "C" fn test_echo_cppeval(
 
 #[no_mangle]
 pub extern "C" fn test_cpp_policy(
-    board_ptr: *const [[CFloat; BOARD_SIZE]; BOARD_SIZE],
+    board_ptr: *const CFloat,
+    board_size: CInt,
     callback: PolicyCallback,
     allocator: AllocatorType<CInt>,
 ) -> RawVec<CInt> {
-    let board_ref = unsafe { board_ptr.as_ref() }.unwrap();
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            board[i][j] = Player::from(board_ref[i][j] as i32);
-        }
-    }
+    let size = board_size as usize;
+    let board_slice = unsafe { ::std::slice::from_raw_parts(board_ptr, size * size) };
+    let cells = board_slice
+        .iter()
+        .map(|x| Player::from(*x as i32))
+        .collect();
+    let board = Board::from_vec(size, cells).unwrap();
 
     let cpp_policy = CppPolicy::new(callback);
     let res = if let Some((row, col)) = cpp_policy.callback(&board) {
@@ -2,14 +2,18 @@
 //!
 //! It provides `extern "C"` based bindings for some utilities to implement policy and `AlphaZero` evaluator.
 //!
+pub use self::cpp_export::*;
 pub use self::cpp_policy::*;
+pub use self::cpp_replay::*;
 pub use self::cpp_support::*;
 pub use self::cppeval::*;
 pub use self::rawobj::*;
 
 pub mod ffi_test;
 
+mod cpp_export;
 mod cpp_policy;
+mod cpp_replay;
 mod cpp_support;
 mod cppeval;
 mod rawobj;
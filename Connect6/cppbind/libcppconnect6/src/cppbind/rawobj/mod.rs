@@ -1,5 +1,9 @@
-use connect6::{agent, BOARD_SIZE};
-use cppbind::CInt;
+use connect6::game::Player;
+use connect6::policy::SearchInfo;
+use connect6::{agent, Board, GenericBoard};
+use cppbind::{status_from_int, status_to_int, CFloat, CInt};
+
+use std::time::Duration;
 
 #[cfg(test)]
 mod tests;
@@ -26,19 +30,32 @@ impl<T> Allocator<T> {
 }
 
 /// Path object for c ffi
+///
+/// `board` is row-major ordered array of `board_size * board_size` cells.
+/// `visits` is row-major ordered visit count distribution with the same size,
+/// null if the policy didn't attach the search data, then `value` and `q_value` are zero.
+/// `elapsed` is the time spent to select the position in seconds.
 #[repr(C)]
 #[derive(Clone)]
 pub struct RawPath {
     pub turn: CInt,
-    pub board: [[CInt; BOARD_SIZE]; BOARD_SIZE],
+    pub board: *mut CInt,
+    pub board_size: CInt,
     pub row: CInt,
     pub col: CInt,
+    pub visits: *mut CFloat,
+    pub value: CFloat,
+    pub q_value: CFloat,
+    pub elapsed: CFloat,
 }
 
 /// PlayResult object for c ffi
+///
+/// `status` is a code of `GameStatus`, { 0: Ongoing, 1: Win, 2: Draw }.
 #[repr(C)]
 pub struct RawPlayResult {
     pub winner: CInt,
+    pub status: CInt,
     pub path: *mut RawPath,
     pub len: CInt,
 }
@@ -55,27 +72,81 @@ impl RawPath {
     pub fn new() -> RawPath {
         RawPath {
             turn: 0,
-            board: [[0; BOARD_SIZE]; BOARD_SIZE],
+            board: ::std::ptr::null_mut(),
+            board_size: 0,
             row: 0,
             col: 0,
+            visits: ::std::ptr::null_mut(),
+            value: 0.,
+            q_value: 0.,
+            elapsed: 0.,
         }
     }
 
-    /// Create RawPath from Path
-    pub fn with_path(path: &agent::Path) -> RawPath {
-        let mut board = [[0; BOARD_SIZE]; BOARD_SIZE];
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                board[i][j] = path.board[i][j] as CInt;
-            }
+    /// Create RawPath from Path with given allocators for board and visits (for C++ new operation)
+    pub fn with_path(
+        path: &agent::Path,
+        alloc: &Allocator<CInt>,
+        alloc_visits: &Allocator<CFloat>,
+    ) -> RawPath {
+        let cells = path.board.as_slice();
+
+        let board = alloc.get(cells.len());
+        for (p, cell) in board.iter_mut().zip(cells.iter()) {
+            *p = *cell as CInt;
+        }
+
+        let mut raw_path = RawPath::new();
+        if let Some(search) = &path.search {
+            let probs = search.visits.as_slice();
+            let visits = alloc_visits.get(probs.len());
+            visits.copy_from_slice(probs);
+
+            raw_path.visits = visits.as_mut_ptr();
+            raw_path.value = search.value;
+            raw_path.q_value = search.q_value;
         }
 
         let (row, col) = path.pos;
+        let elapsed = path.elapsed;
         RawPath {
             turn: path.turn as CInt,
-            board,
+            board: board.as_mut_ptr(),
+            board_size: path.board.size() as CInt,
             row: row as CInt,
             col: col as CInt,
+            elapsed: elapsed.as_secs() as CFloat + elapsed.subsec_nanos() as CFloat * 1e-9,
+            ..raw_path
+        }
+    }
+
+    /// Convert RawPath to Path, copying the board and visits
+    ///
+    /// # Safety
+    /// `board` and non-null `visits` must point `board_size * board_size` valid elements.
+    pub unsafe fn to_path(&self) -> agent::Path {
+        let size = self.board_size as usize;
+        let cells = ::std::slice::from_raw_parts(self.board, size * size);
+        let cells = cells.iter().map(|x| Player::from(*x)).collect();
+
+        let search = if self.visits.is_null() {
+            None
+        } else {
+            let visits = ::std::slice::from_raw_parts(self.visits, size * size);
+            Some(SearchInfo {
+                visits: GenericBoard::from_vec(size, visits.to_vec()).unwrap(),
+                value: self.value,
+                q_value: self.q_value,
+            })
+        };
+
+        let elapsed = self.elapsed.max(0.);
+        agent::Path {
+            turn: Player::from(self.turn),
+            board: Board::from_vec(size, cells).unwrap(),
+            pos: (self.row as usize, self.col as usize),
+            search,
+            elapsed: Duration::new(elapsed as u64, (elapsed.fract() * 1e9) as u32),
         }
     }
 }
@@ -88,23 +159,58 @@ impl Default for RawPath {
 }
 
 impl RawPlayResult {
-    /// Create RawPlayResult from PlayResult with given allocator (for C++ new operation)
-    pub fn with_result(result: &agent::PlayResult, alloc: &Allocator<RawPath>) -> RawPlayResult {
+    /// Create RawPlayResult from PlayResult with given allocators (for C++ new operation)
+    pub fn with_result(
+        result: &agent::PlayResult,
+        alloc: &Allocator<RawPath>,
+        alloc_board: &Allocator<CInt>,
+        alloc_visits: &Allocator<CFloat>,
+    ) -> RawPlayResult {
         let path = &result.path;
         let len = path.len();
 
         let ptr = alloc.get(len);
-        let itr = path.iter().map(|x| RawPath::with_path(x));
+        let itr = path
+            .iter()
+            .map(|x| RawPath::with_path(x, alloc_board, alloc_visits));
         for (p, i) in ptr.iter_mut().zip(itr) {
             *p = i;
         }
 
         RawPlayResult {
             winner: result.winner as CInt,
+            status: status_to_int(&result.status),
             path: ptr.as_mut_ptr(),
             len: len as CInt,
         }
     }
+
+    /// Convert RawPlayResult to PlayResult, copying the paths
+    ///
+    /// Reason of the game end is recovered from the status, without adjudication.
+    ///
+    /// # Safety
+    /// `path` must point `len` valid `RawPath`s, reference `RawPath::to_path`.
+    pub unsafe fn to_result(&self) -> agent::PlayResult {
+        let path = if self.len > 0 {
+            ::std::slice::from_raw_parts(self.path, self.len as usize)
+                .iter()
+                .map(|x| x.to_path())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let winner = Player::from(self.winner);
+        let status = status_from_int(self.status, winner);
+        agent::PlayResult {
+            winner,
+            status,
+            reason: agent::EndReason::from_status(status),
+            would_resign: Player::None,
+            path,
+        }
+    }
 }
 
 impl<T> RawVec<T> {
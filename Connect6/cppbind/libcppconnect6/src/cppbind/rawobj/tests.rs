@@ -1,9 +1,10 @@
 use super::*;
 
-use connect6::{agent, game::Player, policy, Board};
+use connect6::{agent, game::Player, policy, Board, GenericBoard, BOARD_SIZE};
 use rand;
 
 use std::mem;
+use std::time::Duration;
 
 extern "C" fn test_allocator<T: Default + Clone>(size: CInt) -> *mut T {
     let mut vec = vec![T::default(); size as usize];
@@ -12,14 +13,11 @@ extern "C" fn test_allocator<T: Default + Clone>(size: CInt) -> *mut T {
     return ptr;
 }
 
-fn convert_board_from(board: &[[CInt; BOARD_SIZE]; BOARD_SIZE]) -> Board {
-    let mut player_board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            player_board[i][j] = Player::from(board[i][j]);
-        }
-    }
-    player_board
+fn convert_board_from(raw_path: &RawPath) -> Board {
+    let size = raw_path.board_size as usize;
+    let cells = unsafe { Vec::from_raw_parts(raw_path.board, size * size, size * size) };
+    let cells = cells.into_iter().map(Player::from).collect();
+    Board::from_vec(size, cells).unwrap()
 }
 
 #[test]
@@ -28,7 +26,7 @@ fn test_raw_path() {
 
     let turn = rand_player();
 
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             board[i][j] = rand_player();
@@ -37,13 +35,51 @@ fn test_raw_path() {
 
     let pos = (rand::random(), rand::random());
 
-    let path = agent::Path { turn, board, pos };
-    let raw_path = RawPath::with_path(&path);
+    let path = agent::Path {
+        turn,
+        board: board.clone(),
+        pos,
+        search: None,
+        elapsed: Duration::from_millis(250),
+    };
+    let alloc = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+    let raw_path = RawPath::with_path(&path, &alloc, &alloc_visits);
 
     assert_eq!(raw_path.turn, turn as CInt);
-    assert_eq!(convert_board_from(&raw_path.board), board);
+    assert_eq!(raw_path.board_size, BOARD_SIZE as CInt);
+    assert_eq!(convert_board_from(&raw_path), board);
     assert_eq!(raw_path.row, pos.0 as CInt);
     assert_eq!(raw_path.col, pos.1 as CInt);
+    assert!(raw_path.visits.is_null());
+    assert_eq!(raw_path.elapsed, 0.25);
+}
+
+#[test]
+fn test_raw_path_search() {
+    let mut visits = GenericBoard::new(9);
+    visits[2][3] = 0.75;
+    visits[4][5] = 0.25;
+
+    let path = agent::Path {
+        turn: Player::Black,
+        board: Board::new(9),
+        pos: (2, 3),
+        search: Some(policy::SearchInfo {
+            visits: visits.clone(),
+            value: 0.5,
+            q_value: -0.5,
+        }),
+        elapsed: Duration::default(),
+    };
+    let alloc = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+    let raw_path = RawPath::with_path(&path, &alloc, &alloc_visits);
+
+    let raw_visits = unsafe { Vec::from_raw_parts(raw_path.visits, 81, 81) };
+    assert_eq!(raw_visits.as_slice(), visits.as_slice());
+    assert_eq!(raw_path.value, 0.5);
+    assert_eq!(raw_path.q_value, -0.5);
 }
 
 #[test]
@@ -54,9 +90,12 @@ fn test_raw_play_result() {
 
     let result = result.unwrap();
     let alloc = Allocator::new(test_allocator);
-    let raw_result = RawPlayResult::with_result(&result, &alloc);
+    let alloc_board = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+    let raw_result = RawPlayResult::with_result(&result, &alloc, &alloc_board, &alloc_visits);
 
     assert_eq!(raw_result.winner, result.winner as CInt);
+    assert_eq!(raw_result.status, status_to_int(&result.status));
     assert_eq!(raw_result.len, result.path.len() as CInt);
 
     let len = raw_result.len as usize;
@@ -70,7 +109,68 @@ fn test_raw_play_result() {
         assert_eq!(raw_path.turn, path.turn as CInt);
         assert_eq!(raw_path.row, path.pos.0 as CInt);
         assert_eq!(raw_path.col, path.pos.1 as CInt);
-        assert_eq!(convert_board_from(&raw_path.board), path.board);
+        assert_eq!(convert_board_from(raw_path), path.board);
+    }
+}
+
+#[test]
+fn test_to_result() {
+    let param = policy::HyperParameter::light_weight();
+    let mut policy = policy::AlphaZero::with_param(Box::new(policy::RandomEvaluator::new()), param);
+    let result = agent::Agent::new(&mut policy).with_board_size(5).play();
+    assert!(result.is_ok());
+
+    let result = result.unwrap();
+    let alloc = Allocator::new(test_allocator);
+    let alloc_board = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+    let raw_result = RawPlayResult::with_result(&result, &alloc, &alloc_board, &alloc_visits);
+
+    let recovered = unsafe { raw_result.to_result() };
+    assert_eq!(recovered.winner, result.winner);
+    assert_eq!(recovered.status, result.status);
+    assert_eq!(recovered.path.len(), result.path.len());
+    for (recovered, path) in recovered.path.iter().zip(result.path.iter()) {
+        assert_eq!(recovered.turn, path.turn);
+        assert_eq!(recovered.board, path.board);
+        assert_eq!(recovered.pos, path.pos);
+        assert_eq!(recovered.search, path.search);
+    }
+}
+
+#[test]
+fn test_sized_raw_path() {
+    let mut board = Board::new(9);
+    board[8][8] = Player::White;
+
+    let path = agent::Path {
+        turn: Player::Black,
+        board: board.clone(),
+        pos: (8, 8),
+        search: None,
+        elapsed: Duration::default(),
+    };
+    let alloc = Allocator::new(test_allocator);
+    let alloc_visits = Allocator::new(test_allocator);
+    let raw_path = RawPath::with_path(&path, &alloc, &alloc_visits);
+
+    assert_eq!(raw_path.board_size, 9);
+    assert_eq!(convert_board_from(&raw_path), board);
+}
+
+#[test]
+fn test_status_code() {
+    use connect6::game::GameStatus;
+    use cppbind::status_from_int;
+
+    let status = [
+        GameStatus::Ongoing,
+        GameStatus::Win(Player::White),
+        GameStatus::Draw,
+    ];
+    for (code, status) in status.iter().enumerate() {
+        assert_eq!(status_to_int(status), code as CInt);
+        assert_eq!(status_from_int(code as CInt, status.winner()), *status);
     }
 }
 
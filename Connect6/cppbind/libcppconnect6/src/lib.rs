@@ -6,63 +6,109 @@ extern crate rand;
 pub mod cppbind;
 pub use cppbind::ffi_test::*;
 
+use connect6::agent::PlayResult;
+use std::error::Error;
+
+/// Convert the successful results, and write the number of the failed games if `num_failed` is not null.
+fn collect_results<T, F>(
+    results: Vec<Result<PlayResult, Box<Error + Send>>>,
+    num_failed: *mut cppbind::CInt,
+    convert: F,
+) -> Vec<T>
+where
+    F: Fn(&PlayResult) -> T,
+{
+    let failed = results.iter().filter(|x| x.is_err()).count();
+    if !num_failed.is_null() {
+        unsafe {
+            *num_failed = failed as cppbind::CInt;
+        }
+    }
+    results
+        .iter()
+        .filter_map(|x| x.as_ref().ok())
+        .map(convert)
+        .collect()
+}
+
 /// Return Connect6 playing results with given cpp callback.
 ///
 /// # Arguments
 ///
-/// * `callback` - callback for cpp_policy, void(float* boards, int* result).
+/// * `callback` - callback for cpp_policy, void(float* boards, int board_size, int* result).
 /// * `cpp_alloc_path` - cppbind::RawPath allocator for obtaining memory from cpp ffi.
+/// * `cpp_alloc_board` - board allocator of cppbind::RawPath for obtaining memory from cpp ffi.
+/// * `cpp_alloc_visits` - visits allocator of cppbind::RawPath for obtaining memory from cpp ffi.
 /// * `cpp_alloc_result` - cppbind::RawPlayResult allocator for obtaining memory from cpp ffi.
 /// * `debug` - bool, enable debug mode. if enable, selection and board status will be printed
 /// * `num_game_thread` - i32, number of threads asynchronously self-playing connect6
+/// * `board_size` - i32, length of one side of the board
+/// * `num_failed` - int*, nullable, number of the games failed by panics or timeouts is written,
+///   results of the failed games are not included.
 ///
 #[no_mangle]
 pub extern "C" fn cpp_play(
     callback: cppbind::PolicyCallback,
     cpp_alloc_path: cppbind::AllocatorType<cppbind::RawPath>,
+    cpp_alloc_board: cppbind::AllocatorType<cppbind::CInt>,
+    cpp_alloc_visits: cppbind::AllocatorType<cppbind::CFloat>,
     cpp_alloc_result: cppbind::AllocatorType<cppbind::RawPlayResult>,
     debug: bool,
     num_game_thread: i32,
+    board_size: i32,
+    num_failed: *mut cppbind::CInt,
 ) -> cppbind::RawVec<cppbind::RawPlayResult> {
     use connect6::agent;
 
     let alloc_path = cppbind::Allocator::new(cpp_alloc_path);
+    let alloc_board = cppbind::Allocator::new(cpp_alloc_board);
+    let alloc_visits = cppbind::Allocator::new(cpp_alloc_visits);
     let alloc_result = cppbind::Allocator::new(cpp_alloc_result);
+    let board_size = board_size as usize;
 
-    let raw_result = if num_game_thread == 1 {
+    let results: Vec<Result<PlayResult, Box<Error + Send>>> = if num_game_thread == 1 {
         let mut cpp_policy = cppbind::CppPolicy::new(callback);
         let mut agent = if debug {
             agent::Agent::debug(&mut cpp_policy)
         } else {
             agent::Agent::new(&mut cpp_policy)
-        };
+        }
+        .with_board_size(board_size);
 
-        let result = agent.play().unwrap();
-        vec![cppbind::RawPlayResult::with_result(&result, &alloc_path)]
+        vec![agent.play()]
     } else {
         let policy_gen = || cppbind::CppPolicy::new(callback);
         let agent = if debug {
             agent::AsyncAgent::debug(policy_gen)
         } else {
             agent::AsyncAgent::new(policy_gen)
-        };
+        }
+        .with_board_size(board_size);
 
         agent
             .run(num_game_thread)
-            .iter()
-            .map(|x| cppbind::RawPlayResult::with_result(x, &alloc_path))
-            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|x| x.map_err(|e| Box::new(e) as Box<Error + Send>))
+            .collect()
     };
 
+    let raw_result = collect_results(results, num_failed, |x| {
+        cppbind::RawPlayResult::with_result(x, &alloc_path, &alloc_board, &alloc_visits)
+    });
     cppbind::RawVec::with_vec(raw_result, &alloc_result)
 }
 
 /// Return Connect6 self-playing results with given cpp callback and hyperparameters
 ///
+/// With multiple games, boards of the games are evaluated together in a single callback,
+/// so `length` of the callback could be a multiple of 8.
+///
 /// # Arguments
 ///
-/// * `callback` - callback for cppbind, void(int player, float* values, float* boards, int length).
+/// * `callback` - callback for cppbind, void(int player, float* values, float* boards, int length, int board_size).
 /// * `cpp_alloc_path` - cppbind::RawPath allocator for obtaining memory from cpp ffi.
+/// * `cpp_alloc_board` - board allocator of cppbind::RawPath for obtaining memory from cpp ffi.
+/// * `cpp_alloc_visits` - visits allocator of cppbind::RawPath for obtaining memory from cpp ffi.
 /// * `cpp_alloc_result` - cppbind::RawPlayResult allocator for obtaining memory from cpp ffi.
 /// * `num_simulation` - i32, number of simulations for each turn.
 /// * `epsilon` - f32, ratio for applying exploit, exploration. lower epsilon, more exploit
@@ -70,11 +116,16 @@ pub extern "C" fn cpp_play(
 /// * `c_puct` - f32, ratio of q-value and puct, hyperparameter of AlphaZero MCTS
 /// * `debug` - bool, enable debug mode. if enable, selection and board status will be printed
 /// * `num_game_thread` - i32, number of threads asynchronously self-playing connect6
+/// * `board_size` - i32, length of one side of the board
+/// * `num_failed` - int*, nullable, number of the games failed by panics or timeouts is written,
+///   results of the failed games are not included.
 ///
 #[no_mangle]
 pub extern "C" fn cpp_self_play(
     callback: cppbind::Callback,
     cpp_alloc_path: cppbind::AllocatorType<cppbind::RawPath>,
+    cpp_alloc_board: cppbind::AllocatorType<cppbind::CInt>,
+    cpp_alloc_visits: cppbind::AllocatorType<cppbind::CFloat>,
     cpp_alloc_result: cppbind::AllocatorType<cppbind::RawPlayResult>,
     num_simulation: i32,
     epsilon: f32,
@@ -82,6 +133,8 @@ pub extern "C" fn cpp_self_play(
     c_puct: f32,
     debug: bool,
     num_game_thread: i32,
+    board_size: i32,
+    num_failed: *mut cppbind::CInt,
 ) -> cppbind::RawVec<cppbind::RawPlayResult> {
     use connect6::{agent, policy};
 
@@ -93,35 +146,44 @@ pub extern "C" fn cpp_self_play(
     };
 
     let alloc_path = cppbind::Allocator::new(cpp_alloc_path);
+    let alloc_board = cppbind::Allocator::new(cpp_alloc_board);
+    let alloc_visits = cppbind::Allocator::new(cpp_alloc_visits);
     let alloc_result = cppbind::Allocator::new(cpp_alloc_result);
+    let board_size = board_size as usize;
 
-    let raw_result = if num_game_thread == 1 {
+    let results: Vec<Result<PlayResult, Box<Error + Send>>> = if num_game_thread == 1 {
         let cppeval = Box::new(cppbind::CppEval::new(callback));
         let mut alphazero = policy::AlphaZero::with_param(cppeval, param);
         let mut agent = if debug {
             agent::Agent::debug(&mut alphazero)
         } else {
             agent::Agent::new(&mut alphazero)
-        };
+        }
+        .with_board_size(board_size);
 
-        let result = agent.play().unwrap();
-        vec![cppbind::RawPlayResult::with_result(&result, &alloc_path)]
+        vec![agent.play()]
     } else {
-        let policy_gen =
-            || policy::AlphaZero::with_param(Box::new(cppbind::CppEval::new(callback)), param);
+        // evaluations of the games are batched into a single callback
+        let batch = policy::BatchEvaluator::new(Box::new(cppbind::CppEval::new(callback)));
+        let policy_gen = || policy::AlphaZero::with_param(Box::new(batch.handle()), param);
         let async_agent = if debug {
             agent::AsyncAgent::debug(policy_gen)
         } else {
             agent::AsyncAgent::new(policy_gen)
-        };
+        }
+        .with_board_size(board_size)
+        .with_num_threads(num_game_thread as usize);
 
         async_agent
             .run(num_game_thread)
-            .iter()
-            .map(|x| cppbind::RawPlayResult::with_result(x, &alloc_path))
-            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|x| x.map_err(|e| Box::new(e) as Box<Error + Send>))
+            .collect()
     };
 
+    let raw_result = collect_results(results, num_failed, |x| {
+        cppbind::RawPlayResult::with_result(x, &alloc_path, &alloc_board, &alloc_visits)
+    });
     cppbind::RawVec::with_vec(raw_result, &alloc_result)
 }
 
@@ -129,21 +191,27 @@ pub extern "C" fn cpp_self_play(
 ///
 /// # Arguments
 ///
-/// * `callback` - callback for cppbind, void(int player, float* values, float* boards, int length).
+/// * `callback` - callback for cppbind, void(int player, float* values, float* boards, int length, int board_size).
 /// * `cpp_alloc_path` - cppbind::RawPath allocator for obtaining memory from cpp ffi.
+/// * `cpp_alloc_board` - board allocator of cppbind::RawPath for obtaining memory from cpp ffi.
+/// * `cpp_alloc_visits` - visits allocator of cppbind::RawPath for obtaining memory from cpp ffi.
 /// * `num_simulation` - i32, number of simulations for each turn.
 /// * `epsilon` - f32, ratio for applying exploit, exploration. lower epsilon, more exploit.
 /// * `dirichlet_alpha` - f64, hyperparameter for dirichlet distribution.
 /// * `c_puct` - f32, ratio of q-value and puct, hyperparameter of AlphaZero MCTS.
+/// * `board_size` - i32, length of one side of the board
 ///
 #[no_mangle]
 pub extern "C" fn cpp_play_with(
     callback: cppbind::Callback,
     cpp_alloc_path: cppbind::AllocatorType<cppbind::RawPath>,
+    cpp_alloc_board: cppbind::AllocatorType<cppbind::CInt>,
+    cpp_alloc_visits: cppbind::AllocatorType<cppbind::CFloat>,
     num_simulation: i32,
     epsilon: f32,
     dirichlet_alpha: f64,
     c_puct: f32,
+    board_size: i32,
 ) -> cppbind::RawPlayResult {
     use connect6::{agent, policy};
 
@@ -162,8 +230,12 @@ pub extern "C" fn cpp_play_with(
     let mut io_policy = policy::IoPolicy::new(&mut stdin, &mut stdout);
 
     let mut multi_policy = policy::MultiPolicy::new(&mut cpp_policy, &mut io_policy);
-    let result = agent::Agent::debug(&mut multi_policy).play();
+    let result = agent::Agent::debug(&mut multi_policy)
+        .with_board_size(board_size as usize)
+        .play();
 
     let alloc_path = cppbind::Allocator::new(cpp_alloc_path);
-    cppbind::RawPlayResult::with_result(&result.unwrap(), &alloc_path)
+    let alloc_board = cppbind::Allocator::new(cpp_alloc_board);
+    let alloc_visits = cppbind::Allocator::new(cpp_alloc_visits);
+    cppbind::RawPlayResult::with_result(&result.unwrap(), &alloc_path, &alloc_board, &alloc_visits)
 }
@@ -5,9 +5,10 @@
 int row, col;
 float global_board[Connect6::BOARD_SIZE][Connect6::BOARD_SIZE];
 
-void cpppolicy_callback(float* board, int* position) {
+void cpppolicy_callback(float* board, int board_size, int* position) {
     using Connect6::BOARD_SIZE;
 
+    REQUIRE(board_size == BOARD_SIZE);
     for (size_t i = 0; i < BOARD_SIZE; ++i) {
         for (size_t j = 0; j < BOARD_SIZE; ++j) {
             REQUIRE(global_board[i][j] == board[i * BOARD_SIZE + j]);
@@ -39,7 +40,7 @@ TEST_CASE("Evaluate CppPolicy", "[CppPolicy]") {
     }
 
     float* board_ptr = reinterpret_cast<float*>(global_board);
-    Test_FFI::VecInt vec = Test_FFI::test_cpp_policy(board_ptr, cpppolicy_callback, &allocator<int>);
+    Test_FFI::VecInt vec = Test_FFI::test_cpp_policy(board_ptr, BOARD_SIZE, cpppolicy_callback, &allocator<int>);
     REQUIRE(vec.len == 2);
     REQUIRE(vec.vec[0] == row);
     REQUIRE(vec.vec[1] == col);
@@ -2,19 +2,19 @@
 #include "catch2/catch.hpp"
 #include <random>
 
-void cppeval_callback(int player, float* values, float* policies, int len_) {
-    using Connect6::BOARD_SIZE;
-    using Connect6::BOARD_CAPACITY;
-
+void cppeval_callback(int player, float* values, float* policies, int len_, int board_size_) {
     size_t len = len_;    
+    size_t board_size = board_size_;
+    size_t board_capacity = board_size * board_size;
+
     for (size_t i = 0; i < len; ++i) {
         values[i] = i + player;
     }
 
     for (size_t i = 0; i < len; ++i) {
-        for (size_t j = 0; j < BOARD_SIZE; ++j) {
-            for (size_t k = 0; k < BOARD_SIZE; ++k) {
-                policies[i * BOARD_CAPACITY + j * BOARD_SIZE + k] *= 2;
+        for (size_t j = 0; j < board_size; ++j) {
+            for (size_t k = 0; k < board_size; ++k) {
+                policies[i * board_capacity + j * board_size + k] *= 2;
             }
         }
     }
@@ -42,7 +42,7 @@ TEST_CASE("Echo CppEval", "[CppEval]") {
         }
     }
 
-    Test_FFI::VecFloat res = Test_FFI::test_echo_cppeval(turn, boards, len, cppeval_callback, &allocator<float>);
+    Test_FFI::VecFloat res = Test_FFI::test_echo_cppeval(turn, boards, len, BOARD_SIZE, cppeval_callback, &allocator<float>);
     REQUIRE(res.len == len + len * BOARD_CAPACITY);
 
     for (size_t i = 0; i < len; ++i) {
@@ -60,4 +60,5 @@ TEST_CASE("Echo CppEval", "[CppEval]") {
         }
     }
     delete[] res.vec;
+    delete[] boards;
 }
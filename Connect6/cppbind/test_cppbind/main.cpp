@@ -2,10 +2,14 @@
 
 #include "connect6.hpp"
 #include "catch2/catch.hpp"
+#include <cmath>
+#include <cstdio>
 #include <random>
 
-void main_callback(int player, float* values, float* policies, int len_) {
+void main_callback(int player, float* values, float* policies, int len_, int board_size_) {
     size_t len = len_;
+    size_t board_size = board_size_;
+    size_t board_capacity = board_size * board_size;
     
     std::random_device rd;
     std::default_random_engine gen(rd());
@@ -16,26 +20,20 @@ void main_callback(int player, float* values, float* policies, int len_) {
         values[i] = dist(gen);
     }
 
-    using Connect6::BOARD_SIZE;
-    using Connect6::BOARD_CAPACITY;
-
     for (size_t i = 0; i < len; ++i) {
-        for (size_t j = 0; j < BOARD_SIZE; ++j) {
-            for (size_t k = 0; k < BOARD_SIZE; ++k) {
-                policies[i * BOARD_CAPACITY + j * BOARD_SIZE + k] = dist(gen);
+        for (size_t j = 0; j < board_size; ++j) {
+            for (size_t k = 0; k < board_size; ++k) {
+                policies[i * board_capacity + j * board_size + k] = dist(gen);
             }
         }
     }
 }
 
-void main_policy(float* boards, int* position) {
-    using Connect6::BOARD_SIZE;
-    using Connect6::BOARD_CAPACITY;
-
-    for (size_t i = 0; i < BOARD_CAPACITY; ++i) {
+void main_policy(float* boards, int board_size, int* position) {
+    for (int i = 0; i < board_size * board_size; ++i) {
         if (boards[i] == 0) {
-            int row = i / BOARD_SIZE;
-            int col = i % BOARD_SIZE;
+            int row = i / board_size;
+            int col = i % board_size;
 
             position[0] = row;
             position[1] = col;
@@ -54,4 +52,89 @@ TEST_CASE("Check Connect6::self_play", "[Connect6]") {
 TEST_CASE("Check Connect6::play", "[Connect6]") {
     auto result = Connect6::play(main_policy, false, 2);
     REQUIRE(result.size() == 2);
+
+    for (auto& game : result) {
+        if (game.GetWinner() != Connect6::Player::None) {
+            REQUIRE(game.GetStatus() == Connect6::GameStatus::Win);
+        } else {
+            REQUIRE(game.GetStatus() != Connect6::GameStatus::Win);
+        }
+    }
+}
+
+TEST_CASE("Check Connect6::self_play with board size", "[Connect6]") {
+    auto param = Connect6::Param()
+        .NumSimulation(2)
+        .NumGameThread(1)
+        .BoardSize(9);
+    auto result = Connect6::self_play(main_callback, param);
+    REQUIRE(result.size() == 1);
+    for (auto& path : result[0]) {
+        REQUIRE(path.GetBoardSize() == 9);
+        REQUIRE(path.HasSearch());
+
+        float sum = 0;
+        for (size_t i = 0; i < 81; ++i) {
+            sum += path.GetVisits()[i];
+        }
+        REQUIRE(std::abs(sum - 1) < 1e-4);
+    }
+}
+
+TEST_CASE("Check Connect6::play without search", "[Connect6]") {
+    auto result = Connect6::play(main_policy, false, 1);
+    REQUIRE(result.size() == 1);
+    for (auto& path : result[0]) {
+        REQUIRE(!path.HasSearch());
+        REQUIRE(path.GetElapsed() >= 0);
+    }
+}
+
+TEST_CASE("Check Connect6::ReplayBuffer", "[Connect6]") {
+    auto param = Connect6::Param()
+        .NumSimulation(2)
+        .NumGameThread(1)
+        .BoardSize(9);
+    auto result = Connect6::self_play(main_callback, param);
+
+    Connect6::ReplayBuffer buffer(1000);
+    buffer.Push(result[0]);
+    REQUIRE(buffer.GetSize() == result[0].GetSize());
+    REQUIRE(buffer.GetNumGames() == 1);
+
+    auto batch = buffer.Sample(8, true, 9);
+    REQUIRE(batch.len == 8);
+    for (size_t i = 0; i < batch.len; ++i) {
+        size_t row, col;
+        std::tie(row, col) = batch.positions[i];
+        REQUIRE(batch.boards[i * 81 + row * 9 + col] == 0);
+        REQUIRE(std::abs(batch.values[i]) <= 1);
+    }
+    REQUIRE(buffer.Sample(8, true, 15).len == 0);
+
+    REQUIRE(buffer.Snapshot("replay_buffer.bin"));
+    auto restored = Connect6::ReplayBuffer::Restore("replay_buffer.bin");
+    REQUIRE(restored);
+    REQUIRE(restored.GetSize() == buffer.GetSize());
+    std::remove("replay_buffer.bin");
+}
+
+TEST_CASE("Check Connect6::export_npz and export_shard", "[Connect6]") {
+    auto param = Connect6::Param()
+        .NumSimulation(2)
+        .NumGameThread(2)
+        .BoardSize(9);
+    auto result = Connect6::self_play(main_callback, param);
+
+    int num_positions = 0;
+    for (auto& game : result) {
+        num_positions += game.GetSize();
+    }
+
+    REQUIRE(Connect6::export_npz(result, "export.npz", 9) == num_positions);
+    REQUIRE(Connect6::export_shard(result, "export.bin", 9, 16, true) == num_positions);
+    REQUIRE(Connect6::export_npz(result, "export.npz", 15) == -1);
+
+    std::remove("export.npz");
+    std::remove("export.bin");
 }
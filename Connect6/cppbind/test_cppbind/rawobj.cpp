@@ -9,27 +9,36 @@ TEST_CASE("RawPath::new", "[RawPath]") {
     REQUIRE(path.turn == 0);
     REQUIRE(path.row == 0);
     REQUIRE(path.col == 0);
-
-    for (size_t i = 0; i < BOARD_SIZE; ++i) {
-        for (size_t j = 0; j < BOARD_SIZE; ++j) {
-            REQUIRE(path.board[i][j] == 0);
-        }
-    }
+    REQUIRE(path.board == nullptr);
+    REQUIRE(path.board_size == 0);
+    REQUIRE(path.visits == nullptr);
 }
 
 TEST_CASE("RawPath::with_path", "[RawPath]") {
     using namespace Connect6_RustFFI;
 
-    Path path = Test_FFI::test_with_raw_path();
+    Path path = Test_FFI::test_with_raw_path(&allocator<int>, &allocator<float>);
     REQUIRE(path.turn == static_cast<int>(Connect6::Player::White));
+    REQUIRE(path.board_size == BOARD_SIZE);
     REQUIRE(path.row == 0);
     REQUIRE(path.col == BOARD_SIZE % 5 + 1);
 
     for (size_t i = 0; i < BOARD_SIZE; ++i) {
         for (size_t j = 0; j < BOARD_SIZE; ++j) {
-            REQUIRE(path.board[i][j] == static_cast<int>(i * BOARD_SIZE + j) % 3 - 1);
+            REQUIRE(path.board[i * BOARD_SIZE + j] == static_cast<int>(i * BOARD_SIZE + j) % 3 - 1);
         }
     }
+
+    REQUIRE(path.visits != nullptr);
+    for (size_t i = 0; i < BOARD_CAPACITY; ++i) {
+        REQUIRE(path.visits[i] == (i == path.col ? 1 : 0));
+    }
+    REQUIRE(path.value == 0.5);
+    REQUIRE(path.q_value == -0.25);
+    REQUIRE(path.elapsed == 1.5);
+
+    delete[] path.board;
+    delete[] path.visits;
 }
 
 TEST_CASE("Echo RawPath", "[RawPath]") {
@@ -51,26 +60,30 @@ TEST_CASE("Echo RawPath", "[RawPath]") {
     int row = rand_position();
     int col = rand_position();
 
-    Path path = Test_FFI::test_echo_raw_path(turn, board[0], row, col);
+    Path path = Test_FFI::test_echo_raw_path(turn, board[0], BOARD_SIZE, row, col, &allocator<int>, &allocator<float>);
 
     REQUIRE(turn == path.turn);
     REQUIRE(row == path.row);
     REQUIRE(col == path.col);
+    REQUIRE(path.board_size == BOARD_SIZE);
 
     for (size_t i = 0; i < BOARD_SIZE; ++i) {
         for (size_t j = 0; j < BOARD_SIZE; ++j) {
-            REQUIRE(board[i][j] == path.board[i][j]);
+            REQUIRE(board[i][j] == path.board[i * BOARD_SIZE + j]);
         }
     }
+    REQUIRE(path.visits == nullptr);
+    delete[] path.board;
 }
 
 TEST_CASE("RawPlayResult::with_result", "[RawPlayResult]") {
     using namespace Connect6_RustFFI;
 
-    PlayResult res = Test_FFI::test_with_raw_play_result(&allocator<Path>);
+    PlayResult res = Test_FFI::test_with_raw_play_result(&allocator<Path>, &allocator<int>, &allocator<float>);
 
     REQUIRE(res.len == 10);
     REQUIRE(res.winner == static_cast<int>(Connect6::Player::Black));
+    REQUIRE(res.status == static_cast<int>(Connect6::GameStatus::Win));
 
     for (size_t i = 0; i < 10; ++i) {
         REQUIRE(res.paths[i].turn == (i % 2 == 0 ? -1 : 1));
@@ -78,8 +91,9 @@ TEST_CASE("RawPlayResult::with_result", "[RawPlayResult]") {
         REQUIRE(res.paths[i].col == i + 1);
 
         for (size_t j = 0; j < i + 1; ++j) {
-            REQUIRE(res.paths[i].board[j][j] == static_cast<int>(i + j) % 3 - 1);
+            REQUIRE(res.paths[i].board[j * BOARD_SIZE + j] == static_cast<int>(i + j) % 3 - 1);
         }
+        delete[] res.paths[i].board;
     }
     delete[] res.paths;
 }
@@ -94,6 +108,7 @@ TEST_CASE("Echo RawPlayResult", "[RawPlayResult]") {
     auto rand_position = [&]{ return gen() % BOARD_SIZE; };
 
     int winner = rand_player();
+    int status = gen() % 3;
     int len = gen() % 150 + 100;
 
     Path* paths = new Path[len];
@@ -101,23 +116,25 @@ TEST_CASE("Echo RawPlayResult", "[RawPlayResult]") {
         paths[i].turn = rand_player();
         paths[i].row = rand_position();
         paths[i].col = rand_position();
+        paths[i].board_size = BOARD_SIZE;
+        paths[i].board = new int[BOARD_CAPACITY];
+        paths[i].visits = nullptr;
 
-        for (size_t r = 0; r < BOARD_SIZE; ++r) {
-            for (size_t c = 0; c < BOARD_SIZE; ++c) {
-                paths[i].board[r][c] = 0;
-            }
+        for (size_t j = 0; j < BOARD_CAPACITY; ++j) {
+            paths[i].board[j] = 0;
         }
 
         int iter_len = gen() % BOARD_CAPACITY;
         for (size_t j = 0; j < iter_len; ++j) {
             size_t row = rand_position();
             size_t col = rand_position();
-            paths[i].board[row][col] = rand_player();
+            paths[i].board[row * BOARD_SIZE + col] = rand_player();
         }
     }
 
-    PlayResult res = Test_FFI::test_echo_raw_play_result(winner, paths, len, &allocator<Path>);
+    PlayResult res = Test_FFI::test_echo_raw_play_result(winner, status, paths, len, &allocator<Path>, &allocator<int>, &allocator<float>);
     REQUIRE(res.winner == winner);
+    REQUIRE(res.status == status);
     REQUIRE(res.len == len);
 
     for (size_t i = 0; i < len; ++i) {
@@ -125,13 +142,16 @@ TEST_CASE("Echo RawPlayResult", "[RawPlayResult]") {
         REQUIRE(res.paths[i].row == paths[i].row);
         REQUIRE(res.paths[i].col == paths[i].col);
 
-        for (size_t r = 0; r < BOARD_SIZE; ++r) {
-            for (size_t c = 0; c < BOARD_SIZE; ++c) {
-                REQUIRE(res.paths[i].board[r][c] == paths[i].board[r][c]);
-            }
+        REQUIRE(res.paths[i].board_size == BOARD_SIZE);
+        for (size_t j = 0; j < BOARD_CAPACITY; ++j) {
+            REQUIRE(res.paths[i].board[j] == paths[i].board[j]);
         }
+        REQUIRE(res.paths[i].visits == nullptr);
+        delete[] res.paths[i].board;
+        delete[] paths[i].board;
     }
     delete[] res.paths;
+    delete[] paths;
 }
 
 TEST_CASE("RawVec::with_vec", "[RawVec]") {
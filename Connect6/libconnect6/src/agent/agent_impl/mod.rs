@@ -18,28 +18,122 @@
 //! let result = Agent::debug(&mut multi_policy).play();
 //! # assert!(result.is_ok());
 //! ```
-use game::{Game, Player};
-use policy::Policy;
+use agent::{DebugObserver, Observer};
+use game::{Game, GameStatus, Player, Rule};
+use policy::{derive_seed, entropy_rng, seeded_rng, Policy, SearchInfo};
 use Board;
 
+use rand::rngs::StdRng;
+use rand::Rng;
 use std::error::Error;
-use std::io;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[cfg(test)]
 mod tests;
 
 /// Unit of playing history, turn, board and selected position.
+///
+/// `search` is the search data attached by the policy, reference [Policy::search_info](../policy/trait.Policy.html#method.search_info),
+/// and `elapsed` is the time spent by the policy to select the position.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Path {
     pub turn: Player,
     pub board: Board,
     pub pos: (usize, usize),
+    pub search: Option<SearchInfo>,
+    pub elapsed: Duration,
+}
+
+/// Reason of the game end.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EndReason {
+    /// Winner made the line.
+    Line,
+    /// Board is full without winner.
+    FullBoard,
+    /// Player resigned, opponent wins.
+    Resign,
+    /// Game reached the maximum ply, adjudicated as draw.
+    MaxPly,
+    /// Policy couldn't select the next position.
+    NoMove,
+}
+
+impl EndReason {
+    /// Reason of the game end with given status, without adjudication.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::EndReason, game::{GameStatus, Player}};
+    /// assert_eq!(EndReason::from_status(GameStatus::Win(Player::Black)), EndReason::Line);
+    /// assert_eq!(EndReason::from_status(GameStatus::Ongoing), EndReason::NoMove);
+    /// ```
+    pub fn from_status(status: GameStatus) -> EndReason {
+        match status {
+            GameStatus::Win(_) => EndReason::Line,
+            GameStatus::Draw => EndReason::FullBoard,
+            GameStatus::Ongoing => EndReason::NoMove,
+        }
+    }
 }
 
-/// Result of playing game, consists of winner and path (history of game).
+/// Adjudication rules of `Agent`, resignation and maximum ply.
+///
+/// Player resigns if the [value](../policy/trait.Policy.html#method.value) of its selection
+/// is lower than `resign_threshold`. Resignation is disabled on the `resign_disabled_ratio` of the games,
+/// then `PlayResult::would_resign` can be compared with the winner to measure the false-positive rate.
+///
+/// Game is adjudicated as draw if the number of stones on the board reaches `max_ply`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::agent::Adjudication;
+/// let adjudication = Adjudication::new().with_resign(-0.9, 0.1).with_max_ply(100);
+/// assert_eq!(adjudication.resign_threshold, Some(-0.9));
+/// assert_eq!(adjudication.max_ply, Some(100));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Adjudication {
+    pub resign_threshold: Option<f32>,
+    pub resign_disabled_ratio: f32,
+    pub max_ply: Option<usize>,
+}
+
+impl Adjudication {
+    /// Construct a new `Adjudication` without any rules.
+    pub fn new() -> Adjudication {
+        Adjudication::default()
+    }
+
+    /// Resign under the `threshold`, except the `disabled_ratio` of the games.
+    pub fn with_resign(mut self, threshold: f32, disabled_ratio: f32) -> Adjudication {
+        self.resign_threshold = Some(threshold);
+        self.resign_disabled_ratio = disabled_ratio;
+        self
+    }
+
+    /// Adjudicate the game as draw at `max_ply` stones.
+    pub fn with_max_ply(mut self, max_ply: usize) -> Adjudication {
+        self.max_ply = Some(max_ply);
+        self
+    }
+}
+
+/// Result of playing game, consists of winner, status, reason of the end and path (history of game).
+///
+/// `status` is `GameStatus::Ongoing` if the policy gave up before the game end.
+/// `would_resign` is the player who first reached the resignation threshold, whether resignation is disabled or not.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayResult {
     pub winner: Player,
+    pub status: GameStatus,
+    pub reason: EndReason,
+    pub would_resign: Player,
     pub path: Vec<Path>,
 }
 
@@ -64,7 +158,10 @@ pub struct PlayResult {
 pub struct Agent<'a> {
     game: Game,
     debug: bool,
+    adjudication: Adjudication,
     policy: &'a mut Policy,
+    observers: Vec<&'a mut Observer>,
+    rng: StdRng,
 }
 
 impl<'a> Agent<'a> {
@@ -81,7 +178,10 @@ impl<'a> Agent<'a> {
         Agent {
             game: Game::new(),
             debug: false,
+            adjudication: Adjudication::new(),
             policy,
+            observers: Vec::new(),
+            rng: entropy_rng(),
         }
     }
 
@@ -96,12 +196,99 @@ impl<'a> Agent<'a> {
     /// ```
     pub fn debug(policy: &'a mut Policy) -> Agent<'a> {
         Agent {
-            game: Game::new(),
             debug: true,
-            policy,
+            ..Agent::new(policy)
         }
     }
 
+    /// Set the length of one side of the board, default `BOARD_SIZE`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, policy::RandomPolicy};
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).with_board_size(9).play();
+    /// assert_eq!(result.unwrap().path[0].board.size(), 9);
+    /// ```
+    pub fn with_board_size(mut self, size: usize) -> Agent<'a> {
+        self.game = Game::with_rule(size, self.game.get_rule());
+        self
+    }
+
+    /// Set the rule of the game, default Connect6.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, game::Rule, policy::RandomPolicy};
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).with_rule(Rule::gomoku()).play().unwrap();
+    /// assert_eq!(result.path[1].turn, result.path[0].turn.switch());
+    /// ```
+    pub fn with_rule(mut self, rule: Rule) -> Agent<'a> {
+        self.game = Game::with_rule(self.game.get_size(), rule);
+        self
+    }
+
+    /// Set the adjudication rules, default none.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::{Adjudication, Agent, EndReason}, policy::RandomPolicy};
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy)
+    ///     .with_adjudication(Adjudication::new().with_max_ply(10))
+    ///     .play()
+    ///     .unwrap();
+    /// assert!(result.path.len() <= 10);
+    /// # assert!(result.reason == EndReason::MaxPly || result.path.len() < 10);
+    /// ```
+    pub fn with_adjudication(mut self, adjudication: Adjudication) -> Agent<'a> {
+        self.adjudication = adjudication;
+        self
+    }
+
+    /// Seed the policy and the agent, the same seed replays the same game with deterministic evaluator.
+    ///
+    /// The policy is reseeded with the seed derived from given, reference [Policy::seed](../policy/trait.Policy.html#method.seed).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::{Agent, PlayResult}, policy::RandomPolicy};
+    /// let mut policy = RandomPolicy::new();
+    /// let result1 = Agent::new(&mut policy).with_seed(42).play().unwrap();
+    /// let result2 = Agent::new(&mut policy).with_seed(42).play().unwrap();
+    ///
+    /// let positions = |result: &PlayResult| result.path.iter().map(|x| x.pos).collect::<Vec<_>>();
+    /// assert_eq!(positions(&result1), positions(&result2));
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Agent<'a> {
+        self.policy.seed(derive_seed(seed, 0));
+        self.rng = seeded_rng(derive_seed(seed, 1));
+        self
+    }
+
+    /// Attach the observer, it is notified in the order of attachment.
+    ///
+    /// In debug mode, `DebugObserver` is notified first.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::{Agent, LogObserver}, policy::RandomPolicy};
+    /// let mut policy = RandomPolicy::new();
+    /// let mut observer = LogObserver::new();
+    /// let result = Agent::new(&mut policy).with_observer(&mut observer).play();
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn with_observer(mut self, observer: &'a mut Observer) -> Agent<'a> {
+        self.observers.push(observer);
+        self
+    }
+
     /// Self-play the game with given policy.
     ///
     /// # Examples
@@ -119,14 +306,36 @@ impl<'a> Agent<'a> {
     /// # Errors
     /// if selected position raise Err at [Game::play](../game/struct.Game.html#method.play).
     pub fn play(&mut self) -> Result<PlayResult, Box<Error + Send>> {
-        let mut winner = Player::None;
+        let mut status = GameStatus::Ongoing;
+        let mut reason = EndReason::NoMove;
+        let mut would_resign = Player::None;
         let mut path = Vec::new();
         let game = &mut self.game;
 
+        let adjudication = self.adjudication;
+        let resign_enabled = self.rng.gen::<f32>() >= adjudication.resign_disabled_ratio;
+
+        let mut debug_observer = DebugObserver::new();
+        let mut observers: Vec<&mut Observer> = Vec::new();
+        if self.debug {
+            observers.push(&mut debug_observer);
+        }
+        for observer in self.observers.iter_mut() {
+            observers.push(&mut **observer);
+        }
+
+        for observer in observers.iter_mut() {
+            observer.on_game_start(game);
+        }
         loop {
-            if self.debug {
-                game.print(&mut io::stdout()).unwrap();
+            if let Some(max_ply) = adjudication.max_ply {
+                if game.num_stones() >= max_ply {
+                    status = GameStatus::Draw;
+                    reason = EndReason::MaxPly;
+                    break;
+                }
             }
+
             let before = Instant::now();
             let pos = self.policy.next(&game);
             let duration = before.elapsed();
@@ -136,44 +345,78 @@ impl<'a> Agent<'a> {
                 break;
             }
             let pos = pos.unwrap();
+
+            if let (Some(threshold), Some(value)) =
+                (adjudication.resign_threshold, self.policy.value())
+            {
+                if value < threshold && would_resign == Player::None {
+                    would_resign = game.get_turn();
+                    if resign_enabled {
+                        status = GameStatus::Win(would_resign.switch());
+                        reason = EndReason::Resign;
+                        break;
+                    }
+                }
+            }
             path.push(Path {
                 turn: game.get_turn(),
-                board: *game.get_board(),
+                board: game.get_board().clone(),
                 pos,
+                search: self.policy.search_info(),
+                elapsed: duration,
             });
 
-            match game.set(pos) {
-                Ok(result) => {
-                    if self.debug {
-                        // log the selection info
-                        let (row, col) = result.position;
-                        let row = (row as u8 + 0x61) as char;
-                        let col = (col as u8 + 0x41) as char;
-                        println!(
-                            "{:?} ({}, {}), remain {}, {}.{} elapsed",
-                            result.player,
-                            row,
-                            col,
-                            result.num_remain,
-                            duration.as_secs(),
-                            duration.subsec_millis()
-                        );
-                    }
-                }
-                Err(err) => return Err(err),
-            };
+            let result = game.set(pos)?;
+            for observer in observers.iter_mut() {
+                observer.on_move(game, &result, duration);
+            }
 
-            // if game end, method return the winner, or None.
-            let is_end = game.is_game_end();
-            if is_end != Player::None {
-                winner = is_end;
+            // if game end, method return the winner, or draw.
+            if let Some(line) = result.line {
+                status = GameStatus::Win(line.player);
+                reason = EndReason::Line;
+                break;
+            }
+            if game.is_full() {
+                status = GameStatus::Draw;
+                reason = EndReason::FullBoard;
                 break;
             }
         }
 
-        if self.debug {
-            game.print(&mut io::stdout()).unwrap();
+        let result = PlayResult {
+            winner: status.winner(),
+            status,
+            reason,
+            would_resign,
+            path,
+        };
+        for observer in observers.iter_mut() {
+            observer.on_game_end(game, &result);
         }
-        Ok(PlayResult { winner, path })
+        Ok(result)
+    }
+
+    /// Play the game from given position, instead of the empty board.
+    ///
+    /// Board size and rule of given game are used, and the path starts from given position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, game::{Game, Player}, policy::RandomPolicy};
+    /// let opening = Game::from_opening(15, &[(7, 7), (6, 6), (8, 8)]).unwrap();
+    ///
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).play_from(opening).unwrap();
+    /// assert_eq!(result.path[0].turn, Player::Black);
+    /// assert_eq!(result.path[0].board[6][6], Player::White);
+    /// ```
+    ///
+    /// # Errors
+    /// if selected position raise Err at [Game::play](../game/struct.Game.html#method.play).
+    pub fn play_from(&mut self, game: Game) -> Result<PlayResult, Box<Error + Send>> {
+        self.game = game;
+        self.play()
     }
 }
@@ -1,10 +1,51 @@
 use super::*;
+use game::Rule;
 use policy::DefaultPolicy;
-use BOARD_SIZE;
+use {Board, BOARD_SIZE};
 
 use std::sync::mpsc;
 use std::thread;
 
+/// Policy select the first empty cell until the given number of stones are placed.
+struct FirstEmptyPolicy {
+    limit: usize,
+}
+
+impl Policy for FirstEmptyPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        if game.ply() >= self.limit {
+            return None;
+        }
+        let size = game.get_size();
+        let board = game.get_board();
+        (0..size * size)
+            .map(|i| (i / size, i % size))
+            .find(|&(row, col)| board[row][col] == Player::None)
+    }
+}
+
+/// `FirstEmptyPolicy` estimating the loss after the given number of stones.
+struct LosingPolicy {
+    policy: FirstEmptyPolicy,
+    losing_from: usize,
+    value: f32,
+}
+
+impl Policy for LosingPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        self.value = if game.ply() >= self.losing_from {
+            -1.
+        } else {
+            0.
+        };
+        self.policy.next(game)
+    }
+
+    fn value(&self) -> Option<f32> {
+        Some(self.value)
+    }
+}
+
 struct TestPolicy {
     receiver: mpsc::Receiver<(usize, usize)>,
 }
@@ -86,23 +127,19 @@ fn test_play() {
 
     let run_result = result.unwrap().map_err(|_| assert!(false)).unwrap();
     assert_eq!(run_result.winner, Player::White);
+    assert_eq!(run_result.status, GameStatus::Win(Player::White));
     assert_eq!(run_result.path.len(), 11);
 
     let mut turn = Player::Black;
     let mut num_remain = 1;
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
 
     let mut paths = run_result.path.iter();
     let path = paths.next();
     assert!(path.is_some());
-    assert_eq!(
-        *path.unwrap(),
-        Path {
-            turn,
-            board,
-            pos: (0, 0)
-        }
-    );
+    let path = path.unwrap();
+    assert_eq!((path.turn, &path.board, path.pos), (turn, &board, (0, 0)));
+    assert_eq!(path.search, None);
 
     let mut prev = (0, 0);
     let mut test = |pos: (usize, usize)| {
@@ -119,7 +156,7 @@ fn test_play() {
             num_remain = 2;
             turn.mut_switch();
         }
-        assert_eq!(*path, Path { turn, board, pos });
+        assert_eq!((path.turn, &path.board, path.pos), (turn, &board, pos));
     };
 
     // expect history equal to record
@@ -128,3 +165,221 @@ fn test_play() {
         test(*turn2);
     }
 }
+
+#[test]
+fn test_with_board_size() {
+    let (sender, receiver) = mpsc::channel();
+    let created = thread::spawn(move || {
+        let mut policy = TestPolicy { receiver };
+        Agent::new(&mut policy).with_board_size(9).play()
+    });
+    sender.send((8, 8)).unwrap();
+    sender.send((9, 0)).unwrap();
+
+    let result = created.join();
+    assert!(result.is_ok());
+
+    match result.unwrap() {
+        Ok(_) => assert!(false),
+        Err(err) => assert_eq!(err.description(), "invalid position"),
+    }
+}
+
+#[test]
+fn test_play_draw() {
+    let mut policy = FirstEmptyPolicy { limit: 100 };
+    let result = Agent::new(&mut policy).with_board_size(5).play().unwrap();
+
+    assert_eq!(result.status, GameStatus::Draw);
+    assert_eq!(result.reason, EndReason::FullBoard);
+    assert_eq!(result.winner, Player::None);
+    assert_eq!(result.path.len(), 25);
+}
+
+#[test]
+fn test_play_aborted() {
+    let mut policy = FirstEmptyPolicy { limit: 3 };
+    let result = Agent::new(&mut policy).play().unwrap();
+
+    assert_eq!(result.status, GameStatus::Ongoing);
+    assert_eq!(result.reason, EndReason::NoMove);
+    assert_eq!(result.winner, Player::None);
+    assert_eq!(result.path.len(), 3);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_play_result() {
+    use bincode;
+    use serde_json;
+
+    let mut policy = FirstEmptyPolicy { limit: 5 };
+    let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+
+    let json = serde_json::to_string(&result).unwrap();
+    let parsed = serde_json::from_str::<PlayResult>(&json).unwrap();
+    assert_eq!(parsed.winner, result.winner);
+    assert_eq!(parsed.status, result.status);
+    assert_eq!(parsed.path, result.path);
+
+    let encoded = bincode::serialize(&result).unwrap();
+    let parsed = bincode::deserialize::<PlayResult>(&encoded).unwrap();
+    assert_eq!(parsed.path, result.path);
+}
+
+#[test]
+fn test_with_rule() {
+    let mut policy = FirstEmptyPolicy { limit: 100 };
+    let result = Agent::new(&mut policy)
+        .with_board_size(7)
+        .with_rule(Rule::new(4, 1, 1))
+        .play()
+        .unwrap();
+
+    // stones alternate as checkerboard, white first connects the left-down diagonal
+    assert_eq!(result.status, GameStatus::Win(Player::White));
+    assert_eq!(result.path.len(), 22);
+    assert_eq!(result.path[0].board.size(), 7);
+    for (i, path) in result.path.iter().enumerate() {
+        let expected = if i % 2 == 0 {
+            Player::Black
+        } else {
+            Player::White
+        };
+        assert_eq!(path.turn, expected);
+    }
+}
+
+#[test]
+fn test_play_from() {
+    let opening = Game::from_opening(7, &[(3, 3), (0, 0), (0, 1)]).unwrap();
+
+    let mut policy = FirstEmptyPolicy { limit: 5 };
+    let result = Agent::new(&mut policy).play_from(opening).unwrap();
+
+    // path starts from the opening, with its board size
+    assert_eq!(result.status, GameStatus::Ongoing);
+    assert_eq!(result.path.len(), 2);
+    assert_eq!(result.path[0].turn, Player::Black);
+    assert_eq!(result.path[0].board.size(), 7);
+    assert_eq!(result.path[0].board[3][3], Player::Black);
+    assert_eq!(result.path[0].pos, (0, 2));
+}
+
+#[test]
+fn test_resign() {
+    let mut policy = LosingPolicy {
+        policy: FirstEmptyPolicy { limit: 100 },
+        losing_from: 3,
+        value: 0.,
+    };
+    let result = Agent::new(&mut policy)
+        .with_board_size(7)
+        .with_adjudication(Adjudication::new().with_resign(-0.9, 0.))
+        .play()
+        .unwrap();
+
+    // black resigns at the fourth stone
+    assert_eq!(result.status, GameStatus::Win(Player::White));
+    assert_eq!(result.reason, EndReason::Resign);
+    assert_eq!(result.would_resign, Player::Black);
+    assert_eq!(result.path.len(), 3);
+}
+
+#[test]
+fn test_resign_disabled() {
+    let mut policy = LosingPolicy {
+        policy: FirstEmptyPolicy { limit: 100 },
+        losing_from: 3,
+        value: 0.,
+    };
+    let result = Agent::new(&mut policy)
+        .with_board_size(7)
+        .with_adjudication(Adjudication::new().with_resign(-0.9, 1.))
+        .play()
+        .unwrap();
+
+    // game continues, recording the resignation which would have occured
+    assert_eq!(result.reason, EndReason::Line);
+    assert_eq!(result.would_resign, Player::Black);
+    assert!(result.path.len() > 3);
+}
+
+#[test]
+fn test_max_ply() {
+    let mut policy = FirstEmptyPolicy { limit: 100 };
+    let result = Agent::new(&mut policy)
+        .with_board_size(7)
+        .with_adjudication(Adjudication::new().with_max_ply(4))
+        .play()
+        .unwrap();
+
+    assert_eq!(result.status, GameStatus::Draw);
+    assert_eq!(result.reason, EndReason::MaxPly);
+    assert_eq!(result.would_resign, Player::None);
+    assert_eq!(result.path.len(), 4);
+
+    // ply counts the stones of the opening
+    let opening = Game::from_opening(7, &[(3, 3), (4, 4), (5, 5)]).unwrap();
+    let result = Agent::new(&mut policy)
+        .with_adjudication(Adjudication::new().with_max_ply(4))
+        .play_from(opening)
+        .unwrap();
+    assert_eq!(result.reason, EndReason::MaxPly);
+    assert_eq!(result.path.len(), 1);
+
+    // and the stones of the position without history
+    let handicap = Game::with_handicap(7, Player::Black, &[(3, 3), (5, 5)]).unwrap();
+    let result = Agent::new(&mut policy)
+        .with_adjudication(Adjudication::new().with_max_ply(4))
+        .play_from(handicap)
+        .unwrap();
+    assert_eq!(result.reason, EndReason::MaxPly);
+    assert_eq!(result.path.len(), 2);
+}
+
+#[test]
+fn test_search_info() {
+    use policy::{AlphaZero, HyperParameter, RandomEvaluator};
+
+    let param = HyperParameter::light_weight();
+    let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
+    let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+
+    for path in result.path.iter() {
+        let search = path.search.as_ref().unwrap();
+        let total = search.visits.as_slice().iter().sum::<f32>();
+        assert!((total - 1.).abs() < 1e-4);
+        assert!(search.visits[path.pos.0][path.pos.1] > 0.);
+    }
+
+    // policy without search
+    let mut policy = FirstEmptyPolicy { limit: 3 };
+    let result = Agent::new(&mut policy).play().unwrap();
+    assert!(result.path.iter().all(|x| x.search.is_none()));
+}
+
+#[test]
+fn test_seed() {
+    use policy::{AlphaZero, HyperParameter, RandomEvaluator};
+
+    let play = |seed: u64| {
+        let param = HyperParameter::light_weight();
+        let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
+        let result = Agent::new(&mut policy)
+            .with_board_size(7)
+            .with_seed(seed)
+            .play()
+            .unwrap();
+        result
+            .path
+            .into_iter()
+            .map(|x| (x.pos, x.search.unwrap()))
+            .collect::<Vec<_>>()
+    };
+
+    // replaying the seed gives the identical game and search
+    let path = play(1);
+    assert_eq!(path, play(1));
+    assert_ne!(path, play(2));
+}
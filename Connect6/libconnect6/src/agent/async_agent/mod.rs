@@ -2,6 +2,8 @@
 //!
 //! `AsyncAgent` play multiple games on tokio thread-pool.
 //! It pass the policy generator and return the vector of game result.
+//! `GameStream` yields the results as the games finish, and keeps the games in flight continuously.
+//! Each game is isolated, error, panic or timeout of a game is reported as `RunError` of the game.
 //!
 //! # Examples
 //! ```rust
@@ -11,16 +13,26 @@
 //! let async_agent = AsyncAgent::debug(policy_gen);
 //!
 //! let result = async_agent.run(4);
-//! println!("ratio: {}", result.iter().map(|x| x.winner as i32).sum::<i32>() as f32 / 4.);
+//! let winners = result.iter().filter_map(|x| x.as_ref().ok()).map(|x| x.winner as i32);
+//! println!("ratio: {}", winners.sum::<i32>() as f32 / 4.);
 //! # assert_eq!(result.len(), 4);
 //! ```
-use agent::{Agent, PlayResult};
-use policy::Policy;
+use agent::{Adjudication, Agent, DebugObserver, Observer, PlayResult};
+use game::{Game, Rule};
+use policy::{derive_seed, Policy};
+use BOARD_SIZE;
 
 use futures::future;
-use std::sync::mpsc;
-use std::time::Instant;
-use tokio::executor::thread_pool::ThreadPool;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::executor::thread_pool::{Builder, ThreadPool};
 
 #[cfg(test)]
 mod tests;
@@ -38,12 +50,22 @@ mod tests;
 /// let async_agent = AsyncAgent::debug(policy_gen);
 ///
 /// let result = async_agent.run(4);
-/// println!("ratio: {}", result.iter().map(|x| x.winner as i32).sum::<i32>());
+/// let winners = result.iter().filter_map(|x| x.as_ref().ok()).map(|x| x.winner as i32);
+/// println!("ratio: {}", winners.sum::<i32>());
 /// # assert_eq!(result.len(), 4);
 /// ```
 pub struct AsyncAgent<P: 'static + Policy + Send, F: Fn() -> P> {
     policy_gen: F,
     debug: bool,
+    board_size: usize,
+    rule: Rule,
+    openings: Vec<Game>,
+    adjudication: Adjudication,
+    observers: Vec<Arc<Mutex<Observer + Send>>>,
+    timeout: Option<Duration>,
+    seed: Option<u64>,
+    num_threads: Option<usize>,
+    max_in_flight: Option<usize>,
 }
 
 impl<P: 'static + Policy + Send, F: Fn() -> P> AsyncAgent<P, F> {
@@ -62,6 +84,15 @@ impl<P: 'static + Policy + Send, F: Fn() -> P> AsyncAgent<P, F> {
         AsyncAgent {
             policy_gen,
             debug: false,
+            board_size: BOARD_SIZE,
+            rule: Rule::default(),
+            openings: Vec::new(),
+            adjudication: Adjudication::new(),
+            observers: Vec::new(),
+            timeout: None,
+            seed: None,
+            num_threads: None,
+            max_in_flight: None,
         }
     }
 
@@ -78,11 +109,204 @@ impl<P: 'static + Policy + Send, F: Fn() -> P> AsyncAgent<P, F> {
         AsyncAgent {
             policy_gen,
             debug: true,
+            board_size: BOARD_SIZE,
+            rule: Rule::default(),
+            openings: Vec::new(),
+            adjudication: Adjudication::new(),
+            observers: Vec::new(),
+            timeout: None,
+            seed: None,
+            num_threads: None,
+            max_in_flight: None,
         }
     }
 
+    /// Set the length of one side of the board, default `BOARD_SIZE`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_board_size(9);
+    ///
+    /// let result = async_agent.run(2);
+    /// assert_eq!(result[0].as_ref().unwrap().path[0].board.size(), 9);
+    /// ```
+    pub fn with_board_size(mut self, size: usize) -> AsyncAgent<P, F> {
+        self.board_size = size;
+        self
+    }
+
+    /// Set the rule of the games, default Connect6.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, game::Rule, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_rule(Rule::gomoku());
+    ///
+    /// let result = async_agent.run(2);
+    /// let path = &result[0].as_ref().unwrap().path;
+    /// assert_eq!(path[1].turn, path[0].turn.switch());
+    /// ```
+    pub fn with_rule(mut self, rule: Rule) -> AsyncAgent<P, F> {
+        self.rule = rule;
+        self
+    }
+
+    /// Set the starting positions, each game plays from the opening of index `id % len`.
+    ///
+    /// If openings are given, board size and rule of the openings are used.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, game::Game, policy::RandomPolicy};
+    /// let openings = vec![
+    ///     Game::from_opening(9, &[(4, 4)]).unwrap(),
+    ///     Game::from_opening(9, &[(0, 0)]).unwrap(),
+    /// ];
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_openings(openings);
+    ///
+    /// let result = async_agent.run(2);
+    /// assert_eq!(result[0].as_ref().unwrap().path[0].board.size(), 9);
+    /// ```
+    pub fn with_openings(mut self, openings: Vec<Game>) -> AsyncAgent<P, F> {
+        self.openings = openings;
+        self
+    }
+
+    /// Set the adjudication rules of each game, default none.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::{Adjudication, AsyncAgent}, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let adjudication = Adjudication::new().with_max_ply(10);
+    /// let async_agent = AsyncAgent::new(gen).with_adjudication(adjudication);
+    ///
+    /// let result = async_agent.run(2);
+    /// assert!(result[0].as_ref().unwrap().path.len() <= 10);
+    /// ```
+    pub fn with_adjudication(mut self, adjudication: Adjudication) -> AsyncAgent<P, F> {
+        self.adjudication = adjudication;
+        self
+    }
+
+    /// Set the number of the worker threads playing the games, default the number of cpus.
+    ///
+    /// Games blocked on the shared resources, such as `BatchEvaluator`, need more threads than cpus to run together.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_num_threads(4);
+    ///
+    /// let result = async_agent.run(4);
+    /// assert!(result.iter().all(|x| x.is_ok()));
+    /// ```
+    pub fn with_num_threads(mut self, num_threads: usize) -> AsyncAgent<P, F> {
+        self.num_threads = Some(num_threads.max(1));
+        self
+    }
+
+    /// Set the maximum number of the games in flight of `stream`, default all games are started at once.
+    ///
+    /// The next game is started as a game ends, so that the stream stopped early doesn't leave the remaining games.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_board_size(7).with_max_in_flight(2);
+    ///
+    /// let stream = async_agent.stream(4);
+    /// assert_eq!(stream.num_in_flight(), 2);
+    /// assert_eq!(stream.count(), 4);
+    /// ```
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> AsyncAgent<P, F> {
+        self.max_in_flight = Some(max_in_flight.max(1));
+        self
+    }
+
+    /// Set the seed of the games, default none, seeded from the entropy.
+    ///
+    /// Each game is seeded with the seed derived from given seed and the game id,
+    /// so the game of the same id is replayed regardless of the scheduling of the threads.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_seed(42);
+    ///
+    /// let result1 = async_agent.run(2);
+    /// let result2 = async_agent.run(2);
+    /// for (game1, game2) in result1.iter().zip(result2.iter()) {
+    ///     let (game1, game2) = (game1.as_ref().unwrap(), game2.as_ref().unwrap());
+    ///     assert_eq!(game1.path[0].pos, game2.path[0].pos);
+    /// }
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> AsyncAgent<P, F> {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Attach the observer shared by all games, it is locked on each callback.
+    ///
+    /// Pass `Arc<Mutex<O>>` to inspect the observer after the games.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::{AsyncAgent, LogObserver}, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_observer(LogObserver::new());
+    ///
+    /// let result = async_agent.run(2);
+    /// # assert_eq!(result.len(), 2);
+    /// ```
+    pub fn with_observer<O: 'static + Observer + Send>(mut self, observer: O) -> AsyncAgent<P, F> {
+        self.observers.push(Arc::new(Mutex::new(observer)));
+        self
+    }
+
+    /// Set the time limit of each game, default unlimited.
+    ///
+    /// The game exceeding the limit is reported as `RunErrorKind::Timeout` and `run` does not wait for it.
+    /// Its worker thread is not interrupted, so that the games which could not start
+    /// since every worker is occupied by the timed out games are also reported as timeout.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// # use std::time::Duration;
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_timeout(Duration::from_secs(60));
+    ///
+    /// let result = async_agent.run(2);
+    /// assert!(result.iter().all(|x| x.is_ok()));
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> AsyncAgent<P, F> {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Self-play the given number of games asynchronously on thread pool.
     ///
+    /// It returns the result of each game ordered by the game id, from 0 to `num - 1`.
+    /// Games failed by the error of the policy, panic or timeout are reported as `RunError`,
+    /// and do not affect the other games.
+    ///
     /// # Examples
     /// ```rust
     /// # extern crate connect6;
@@ -91,48 +315,386 @@ impl<P: 'static + Policy + Send, F: Fn() -> P> AsyncAgent<P, F> {
     /// let async_agent = AsyncAgent::new(gen);
     ///
     /// let result = async_agent.run(4);
-    /// println!("result: {}", result.iter().map(|x| x.winner as i32).sum::<i32>());
+    /// let winners = result.iter().filter_map(|x| x.as_ref().ok()).map(|x| x.winner as i32);
+    /// println!("result: {}", winners.sum::<i32>());
     /// # assert_eq!(result.len(), 4);
     /// ```
+    pub fn run(&self, num: i32) -> Vec<Result<PlayResult, RunError>> {
+        let mut stream = self.stream(num);
+        let mut results = Vec::new();
+        while let Some(result) = stream.next_with_id() {
+            results.push(result);
+        }
+        results.sort_by_key(|&(id, _)| id);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Self-play the given number of games and yield the results in the order of completion.
+    ///
+    /// Games in flight are bounded by [with_max_in_flight](#method.with_max_in_flight).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_board_size(9);
+    ///
+    /// for result in async_agent.stream(4) {
+    ///     println!("winner: {:?}", result.unwrap().winner);
+    /// }
+    /// ```
+    pub fn stream<'a>(&'a self, num: i32) -> GameStream<'a, P, F> {
+        let num = num.max(0) as usize;
+        let num_in_flight = self.max_in_flight.map_or(num, |max| max.min(num));
+        GameStream::new(self, num_in_flight, Some(num))
+    }
+
+    /// Self-play continuously, keeping the given number of games in flight until it is stopped.
     ///
-    /// # Panics
-    /// If some games return the Err from [Agent::play](./struct.Agent.html#method.play).
-    pub fn run(&self, num: i32) -> Vec<PlayResult> {
-        let thread_pool = ThreadPool::new();
-        let (sender, receiver) = mpsc::channel();
-        for id in 0..num {
-            let debug = self.debug;
-            let policy = (self.policy_gen)();
-            let sender = sender.clone();
-            thread_pool.spawn(future::lazy(move || {
-                let mut policy = policy;
+    /// After [GameStream::stop](./struct.GameStream.html#method.stop),
+    /// the games in flight are yielded and no more games are started.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+    /// let gen = || RandomPolicy::new();
+    /// let async_agent = AsyncAgent::new(gen).with_board_size(9);
+    ///
+    /// let mut stream = async_agent.continuous(2);
+    /// let mut num_games = 0;
+    /// while let Some(result) = stream.next() {
+    ///     assert!(result.is_ok());
+    ///     num_games += 1;
+    ///     if num_games == 5 {
+    ///         stream.stop();
+    ///     }
+    /// }
+    /// assert!(num_games >= 5);
+    /// ```
+    pub fn continuous<'a>(&'a self, num_in_flight: usize) -> GameStream<'a, P, F> {
+        GameStream::new(self, num_in_flight, None)
+    }
 
-                let now = Instant::now();
-                let res = Agent::new(&mut policy).play();
-                let elapsed = now.elapsed();
+    /// Construct the thread pool playing the games.
+    fn thread_pool(&self) -> ThreadPool {
+        match self.num_threads {
+            Some(num_threads) => Builder::new().pool_size(num_threads).build(),
+            None => ThreadPool::new(),
+        }
+    }
 
-                if let Ok(result) = res {
-                    sender.send(result).unwrap();
+    /// Spawn the game of given id on the thread pool, it sends the start and the end of the game.
+    fn spawn(&self, thread_pool: &ThreadPool, id: i32, sender: mpsc::Sender<Message>) {
+        let debug = self.debug;
+        let board_size = self.board_size;
+        let rule = self.rule;
+        let opening = if self.openings.is_empty() {
+            None
+        } else {
+            Some(self.openings[id as usize % self.openings.len()].clone())
+        };
+        let adjudication = self.adjudication;
+        let observers = self.observers.clone();
+        let seed = self.seed.map(|seed| derive_seed(seed, id as u64));
+        let policy = (self.policy_gen)();
+        thread_pool.spawn(future::lazy(move || {
+            // receiver could be dropped if the stream was finished by timeout
+            let _ = sender.send(Message::Start(id));
+            let play = panic::AssertUnwindSafe(move || {
+                let mut policy = policy;
+                let mut observers = observers;
+                let mut summary = DebugObserver::summary(id);
+
+                let mut agent = Agent::new(&mut policy)
+                    .with_board_size(board_size)
+                    .with_rule(rule)
+                    .with_adjudication(adjudication);
+                if let Some(seed) = seed {
+                    agent = agent.with_seed(seed);
                 }
                 if debug {
-                    println!(
-                        "run: {}, elapsed {}.{}s",
-                        id,
-                        elapsed.as_secs(),
-                        elapsed.subsec_millis()
-                    );
+                    agent = agent.with_observer(&mut summary);
+                }
+                for observer in observers.iter_mut() {
+                    agent = agent.with_observer(observer);
                 }
-                Ok(())
-            }));
+                match opening {
+                    Some(game) => agent.play_from(game),
+                    None => agent.play(),
+                }
+            });
+
+            let result = match panic::catch_unwind(play) {
+                Ok(Ok(result)) => Ok(result),
+                Ok(Err(err)) => Err(RunErrorKind::Play(err)),
+                Err(payload) => Err(RunErrorKind::Panic(panic_message(payload))),
+            };
+            let _ = sender.send(Message::End(id, result));
+            Ok(())
+        }));
+    }
+}
+
+/// Handle to stop the continuous `GameStream` from the other thread.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::AsyncAgent, policy::RandomPolicy};
+/// let gen = || RandomPolicy::new();
+/// let async_agent = AsyncAgent::new(gen).with_board_size(9);
+///
+/// let mut stream = async_agent.continuous(2);
+/// let handle = stream.stop_handle();
+/// std::thread::spawn(move || handle.stop());
+///
+/// let results = stream.collect::<Vec<_>>();
+/// assert!(results.len() >= 2);
+/// ```
+#[derive(Clone)]
+pub struct StopHandle {
+    stopped: Arc<AtomicBool>,
+}
+
+impl StopHandle {
+    /// Stop starting new games.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Return true if the stream is stopped.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+}
+
+/// Iterator over the results of the games, in the order of completion.
+///
+/// It is constructed by [AsyncAgent::stream](./struct.AsyncAgent.html#method.stream)
+/// or [AsyncAgent::continuous](./struct.AsyncAgent.html#method.continuous).
+/// Dropping the stream cancels the games waiting for the worker, and does not wait for the running games.
+pub struct GameStream<'a, P: 'static + Policy + Send, F: 'a + Fn() -> P> {
+    agent: &'a AsyncAgent<P, F>,
+    thread_pool: Option<ThreadPool>,
+    sender: mpsc::Sender<Message>,
+    receiver: mpsc::Receiver<Message>,
+    next_id: i32,
+    total: Option<usize>,
+    // start time of the games in flight, None if it is waiting for the worker
+    in_flight: HashMap<i32, Option<Instant>>,
+    timed_out: VecDeque<i32>,
+    last_progress: Instant,
+    stop: StopHandle,
+}
+
+impl<'a, P: 'static + Policy + Send, F: Fn() -> P> GameStream<'a, P, F> {
+    /// Construct a `GameStream` keeping the given number of games in flight, up to the total.
+    fn new(
+        agent: &'a AsyncAgent<P, F>,
+        num_in_flight: usize,
+        total: Option<usize>,
+    ) -> GameStream<'a, P, F> {
+        let (sender, receiver) = mpsc::channel();
+        let mut stream = GameStream {
+            agent,
+            thread_pool: Some(agent.thread_pool()),
+            sender,
+            receiver,
+            next_id: 0,
+            total,
+            in_flight: HashMap::new(),
+            timed_out: VecDeque::new(),
+            last_progress: Instant::now(),
+            stop: StopHandle {
+                stopped: Arc::new(AtomicBool::new(false)),
+            },
+        };
+        for _ in 0..num_in_flight {
+            stream.start_next();
         }
+        stream
+    }
 
-        let mut results = Vec::new();
-        for _ in 0..num {
-            // able to panic, when agent return Err instead PlayResult
-            let res = receiver.recv().unwrap();
-            results.push(res);
+    /// Stop starting new games, the games in flight are still yielded.
+    pub fn stop(&mut self) {
+        self.stop.stop();
+    }
+
+    /// Return the handle to stop the stream from the other thread.
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop.clone()
+    }
+
+    /// Return the number of the games in flight.
+    pub fn num_in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Start the next game, if the stream is not stopped and not reached to the total.
+    fn start_next(&mut self) {
+        if self.stop.is_stopped() {
+            return;
+        }
+        if let Some(total) = self.total {
+            if self.next_id as usize >= total {
+                return;
+            }
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        if let Some(ref thread_pool) = self.thread_pool {
+            self.agent.spawn(thread_pool, id, self.sender.clone());
+            self.in_flight.insert(id, None);
+        }
+    }
+
+    /// Return the next result with the id of the game.
+    pub fn next_with_id(&mut self) -> Option<(i32, Result<PlayResult, RunError>)> {
+        loop {
+            if let Some(id) = self.timed_out.pop_front() {
+                self.start_next();
+                let kind = RunErrorKind::Timeout;
+                return Some((id, Err(RunError { id, kind })));
+            }
+            if self.in_flight.is_empty() {
+                // do not wait for the timed out games
+                if let Some(thread_pool) = self.thread_pool.take() {
+                    thread_pool.shutdown();
+                }
+                return None;
+            }
+
+            let message = match self.agent.timeout {
+                None => self
+                    .receiver
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+                Some(timeout) => {
+                    // earliest deadline of the running games, or of the waiting games
+                    let last_progress = self.last_progress;
+                    let deadline = self
+                        .in_flight
+                        .values()
+                        .map(|start| start.unwrap_or(last_progress) + timeout)
+                        .min()
+                        .unwrap();
+                    let now = Instant::now();
+                    let wait = if deadline > now {
+                        deadline - now
+                    } else {
+                        Duration::from_secs(0)
+                    };
+                    self.receiver.recv_timeout(wait)
+                }
+            };
+
+            match message {
+                Ok(Message::Start(id)) => {
+                    self.last_progress = Instant::now();
+                    if let Some(start) = self.in_flight.get_mut(&id) {
+                        *start = Some(self.last_progress);
+                    }
+                }
+                Ok(Message::End(id, result)) => {
+                    self.last_progress = Instant::now();
+                    // result of the timed out game is discarded
+                    if self.in_flight.remove(&id).is_some() {
+                        self.start_next();
+                        return Some((id, result.map_err(|kind| RunError { id, kind })));
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let timeout = self.agent.timeout.unwrap();
+                    let (now, last_progress) = (Instant::now(), self.last_progress);
+                    let mut expired = self
+                        .in_flight
+                        .iter()
+                        .filter(|&(_, start)| start.unwrap_or(last_progress) + timeout <= now)
+                        .map(|(id, _)| *id)
+                        .collect::<Vec<_>>();
+                    expired.sort();
+                    for id in expired {
+                        self.in_flight.remove(&id);
+                        self.timed_out.push_back(id);
+                    }
+                }
+                // unreachable, stream holds the sender
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+}
+
+impl<'a, P: 'static + Policy + Send, F: Fn() -> P> Iterator for GameStream<'a, P, F> {
+    type Item = Result<PlayResult, RunError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_id().map(|(_, result)| result)
+    }
+}
+
+impl<'a, P: 'static + Policy + Send, F: Fn() -> P> Drop for GameStream<'a, P, F> {
+    fn drop(&mut self) {
+        // cancel the waiting games without blocking on the running games
+        if let Some(thread_pool) = self.thread_pool.take() {
+            thread_pool.shutdown_now();
+        }
+    }
+}
+
+/// Message from the game to `GameStream`.
+enum Message {
+    Start(i32),
+    End(i32, Result<PlayResult, RunErrorKind>),
+}
+
+/// Extract the message from the payload of the panic.
+fn panic_message(payload: Box<Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Reason of the failed game.
+#[derive(Debug)]
+pub enum RunErrorKind {
+    /// `Agent::play` returned the error.
+    Play(Box<Error + Send>),
+    /// Policy or observer panicked, with the panic message.
+    Panic(String),
+    /// Game exceeded the time limit.
+    Timeout,
+}
+
+/// Error of the game played by `AsyncAgent`, with the id of the game.
+#[derive(Debug)]
+pub struct RunError {
+    pub id: i32,
+    pub kind: RunErrorKind,
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            RunErrorKind::Play(ref err) => write!(f, "game {} failed: {}", self.id, err),
+            RunErrorKind::Panic(ref msg) => write!(f, "game {} panicked: {}", self.id, msg),
+            RunErrorKind::Timeout => write!(f, "game {} timed out", self.id),
+        }
+    }
+}
+
+impl Error for RunError {
+    fn description(&self) -> &str {
+        match self.kind {
+            RunErrorKind::Play(_) => "game failed",
+            RunErrorKind::Panic(_) => "game panicked",
+            RunErrorKind::Timeout => "game timed out",
         }
-        thread_pool.shutdown();
-        results
     }
 }
@@ -1,9 +1,34 @@
 use super::*;
-use game::Player;
+use game::{GameStatus, Player};
 use policy::{AlphaZero, HyperParameter, RandomEvaluator, RandomPolicy};
+use BOARD_SIZE;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 use std::time::Instant;
 
+/// Policy failing in several ways.
+enum FaultyPolicy {
+    Random(RandomPolicy),
+    Invalid,
+    Panic,
+    Sleep(Duration),
+}
+
+impl Policy for FaultyPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        match self {
+            FaultyPolicy::Random(policy) => policy.next(game),
+            FaultyPolicy::Invalid => Some((BOARD_SIZE, BOARD_SIZE)),
+            FaultyPolicy::Panic => panic!("faulty policy"),
+            FaultyPolicy::Sleep(duration) => {
+                thread::sleep(*duration);
+                None
+            }
+        }
+    }
+}
+
 #[test]
 fn test_run() {
     let policy_gen = || RandomPolicy::new();
@@ -16,9 +41,11 @@ fn test_run() {
 
     assert_eq!(result.len(), 4);
     for run_result in result {
+        let run_result = run_result.unwrap();
         if let Some(last) = run_result.path.last() {
             if run_result.winner != Player::None {
                 assert_eq!(last.turn, run_result.winner);
+                assert_eq!(run_result.status, GameStatus::Win(run_result.winner));
             }
         }
     }
@@ -28,7 +55,7 @@ fn test_run() {
 fn test_alphazero_run() {
     let param = HyperParameter::light_weight();
 
-    let policy_gen = || AlphaZero::with_param(Box::new(RandomEvaluator {}), param);
+    let policy_gen = || AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
     let async_agent = AsyncAgent::debug(policy_gen);
 
     let now = Instant::now();
@@ -38,10 +65,218 @@ fn test_alphazero_run() {
 
     assert_eq!(result.len(), 4);
     for run_result in result {
+        let run_result = run_result.unwrap();
         if let Some(last) = run_result.path.last() {
             if run_result.winner != Player::None {
                 assert_eq!(last.turn, run_result.winner);
+                assert_eq!(run_result.status, GameStatus::Win(run_result.winner));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_with_openings() {
+    let openings = vec![
+        Game::from_opening(9, &[(4, 4)]).unwrap(),
+        Game::with_handicap(9, Player::Black, &[(0, 0), (8, 8)]).unwrap(),
+    ];
+    let policy_gen = || RandomPolicy::new();
+    let async_agent = AsyncAgent::new(policy_gen).with_openings(openings);
+
+    let result = async_agent.run(4);
+    assert_eq!(result.len(), 4);
+
+    // each game starts from one of the openings
+    let mut num_handicap = 0;
+    for run_result in result {
+        let first = &run_result.unwrap().path[0];
+        assert_eq!(first.board.size(), 9);
+        assert_eq!(first.turn, Player::White);
+        if first.board[8][8] == Player::Black {
+            num_handicap += 1;
+        } else {
+            assert_eq!(first.board[4][4], Player::Black);
+        }
+    }
+    assert_eq!(num_handicap, 2);
+}
+
+#[test]
+fn test_run_errors() {
+    let counter = AtomicUsize::new(0);
+    let policy_gen = || match counter.fetch_add(1, Ordering::SeqCst) % 3 {
+        0 => FaultyPolicy::Random(RandomPolicy::new()),
+        1 => FaultyPolicy::Invalid,
+        _ => FaultyPolicy::Panic,
+    };
+    let async_agent = AsyncAgent::new(policy_gen).with_board_size(7);
+
+    let result = async_agent.run(6);
+    assert_eq!(result.len(), 6);
+    for (id, run_result) in result.iter().enumerate() {
+        match (id % 3, run_result) {
+            (0, Ok(result)) => assert!(!result.path.is_empty()),
+            (1, Err(err)) => {
+                assert_eq!(err.id, id as i32);
+                assert_eq!(err.description(), "game failed");
+                match err.kind {
+                    RunErrorKind::Play(ref err) => {
+                        assert_eq!(err.description(), "invalid position")
+                    }
+                    _ => assert!(false),
+                }
             }
+            (2, Err(err)) => {
+                assert_eq!(err.id, id as i32);
+                match err.kind {
+                    RunErrorKind::Panic(ref msg) => assert_eq!(msg, "faulty policy"),
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+}
+
+#[test]
+fn test_run_timeout() {
+    // sleeping game is the last one, not to occupy the workers of the other games
+    let counter = AtomicUsize::new(0);
+    let policy_gen = || match counter.fetch_add(1, Ordering::SeqCst) {
+        2 => FaultyPolicy::Sleep(Duration::from_secs(5)),
+        _ => FaultyPolicy::Random(RandomPolicy::new()),
+    };
+    let async_agent = AsyncAgent::new(policy_gen)
+        .with_board_size(7)
+        .with_timeout(Duration::from_millis(500));
+
+    let now = Instant::now();
+    let result = async_agent.run(3);
+    assert!(now.elapsed() < Duration::from_secs(4));
+
+    assert!(result[0].is_ok());
+    assert!(result[1].is_ok());
+    match result[2] {
+        Err(ref err) => {
+            assert_eq!(err.id, 2);
+            assert_eq!(err.description(), "game timed out");
         }
+        Ok(_) => assert!(false),
     }
 }
+
+#[test]
+fn test_stream() {
+    let policy_gen = || RandomPolicy::new();
+    let async_agent = AsyncAgent::new(policy_gen).with_board_size(7);
+
+    let mut stream = async_agent.stream(5);
+    assert_eq!(stream.num_in_flight(), 5);
+
+    let mut ids = Vec::new();
+    while let Some((id, result)) = stream.next_with_id() {
+        assert!(result.is_ok());
+        ids.push(id);
+    }
+    ids.sort();
+    assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+    assert_eq!(stream.num_in_flight(), 0);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_stream_max_in_flight() {
+    let counter = AtomicUsize::new(0);
+    let policy_gen = || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        RandomPolicy::new()
+    };
+    let async_agent = AsyncAgent::new(policy_gen)
+        .with_board_size(7)
+        .with_max_in_flight(2);
+
+    let mut stream = async_agent.stream(5);
+    assert_eq!(stream.num_in_flight(), 2);
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    // finished game is replaced by the next one up to the total
+    assert!(stream.next().unwrap().is_ok());
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+    assert_eq!(stream.count(), 4);
+    assert_eq!(counter.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn test_continuous() {
+    let policy_gen = || RandomPolicy::new();
+    let async_agent = AsyncAgent::new(policy_gen).with_board_size(7);
+
+    let mut stream = async_agent.continuous(3);
+    let mut num_games = 0;
+    while let Some(result) = stream.next() {
+        assert!(result.is_ok());
+        num_games += 1;
+        if num_games <= 10 {
+            // finished game is replaced by the new one
+            assert_eq!(stream.num_in_flight(), 3);
+        }
+        if num_games == 10 {
+            stream.stop();
+        }
+    }
+    // three games in flight at the stop are also yielded
+    assert_eq!(num_games, 13);
+}
+
+#[test]
+fn test_continuous_stop_handle() {
+    let policy_gen = || RandomPolicy::new();
+    let async_agent = AsyncAgent::new(policy_gen).with_board_size(7);
+
+    let mut stream = async_agent.continuous(2);
+    let handle = stream.stop_handle();
+    assert!(!handle.is_stopped());
+
+    let first = stream.next();
+    assert!(first.is_some());
+    handle.stop();
+    assert!(stream.count() <= 2);
+}
+
+#[test]
+fn test_stream_drop() {
+    let counter = AtomicUsize::new(0);
+    let policy_gen = || match counter.fetch_add(1, Ordering::SeqCst) {
+        0 => FaultyPolicy::Random(RandomPolicy::new()),
+        _ => FaultyPolicy::Sleep(Duration::from_secs(5)),
+    };
+    let async_agent = AsyncAgent::new(policy_gen).with_board_size(7);
+
+    // dropping the stream does not wait for the sleeping games
+    let now = Instant::now();
+    {
+        let mut stream = async_agent.stream(3);
+        assert!(stream.next().unwrap().is_ok());
+    }
+    assert!(now.elapsed() < Duration::from_secs(4));
+}
+
+#[test]
+fn test_seed() {
+    let param = HyperParameter::light_weight();
+    let policy_gen = || AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
+    let async_agent = AsyncAgent::new(policy_gen).with_board_size(7).with_seed(7);
+
+    let positions = |result: Vec<Result<PlayResult, RunError>>| {
+        result
+            .into_iter()
+            .map(|x| x.unwrap().path.iter().map(|p| p.pos).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+    // games are replayed by id, and each game has its own seed
+    let games = positions(async_agent.run(3));
+    assert_eq!(games, positions(async_agent.run(3)));
+    assert_ne!(games[0], games[1]);
+    assert_ne!(games[1], games[2]);
+}
@@ -7,6 +7,8 @@
 //! `AsyncAgent` is multi-thread based agent, playing multiple games asynchronously.
 //! It pass the policy generator and return the vector of `PlayResult`.
 //!
+//! Both agents notify the moves and the end of the game to the attached `Observer`s.
+//!
 //! # Examples
 //! Play single game with single policy.
 //! ```rust
@@ -27,12 +29,15 @@
 //! let async_agent = AsyncAgent::debug(policy_gen);
 //!
 //! let result = async_agent.run(4);
-//! println!("ratio: {}", result.iter().map(|x| x.winner as i32).sum::<i32>());
+//! let winners = result.iter().filter_map(|x| x.as_ref().ok()).map(|x| x.winner as i32);
+//! println!("ratio: {}", winners.sum::<i32>());
 //! assert_eq!(result.len(), 4);
 //! ```
 //!
 pub use self::agent_impl::*;
 pub use self::async_agent::*;
+pub use self::observer::{DebugObserver, LogObserver, Observer};
 
 mod agent_impl;
 mod async_agent;
+mod observer;
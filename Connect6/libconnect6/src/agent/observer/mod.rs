@@ -0,0 +1,224 @@
+//! Event hooks of the playing agent.
+//!
+//! `Observer` is notified at the start of the game, on each move and at the end of the game.
+//! Agents accept several observers, so that metrics, live viewers and record writers
+//! can be attached without modifying the agent.
+//!
+//! `DebugObserver` prints the board and timings to stdout, it is used by the debug mode agents.
+//! `LogObserver` writes the same events to the `log` crate facade.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{agent::{Agent, Observer}, game::{Game, SetResult}, policy::RandomPolicy};
+//! # use std::time::Duration;
+//! struct MoveCounter {
+//!     num_moves: usize,
+//! }
+//!
+//! impl Observer for MoveCounter {
+//!     fn on_move(&mut self, _: &Game, _: &SetResult, _: Duration) {
+//!         self.num_moves += 1;
+//!     }
+//! }
+//!
+//! let mut counter = MoveCounter { num_moves: 0 };
+//! let mut policy = RandomPolicy::new();
+//! let result = Agent::new(&mut policy).with_observer(&mut counter).play().unwrap();
+//! assert_eq!(counter.num_moves, result.path.len());
+//! ```
+use agent::PlayResult;
+use game::{Game, SetResult};
+use record::format_pos;
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Event hooks of the playing agent, every callback does nothing by default.
+pub trait Observer {
+    /// Called before the first move with the starting position.
+    fn on_game_start(&mut self, _game: &Game) {}
+
+    /// Called after each move with the game state, the result of the move
+    /// and the elapsed time of the policy to select it.
+    fn on_move(&mut self, _game: &Game, _result: &SetResult, _elapsed: Duration) {}
+
+    /// Called after the game end, or the policy gave up.
+    fn on_game_end(&mut self, _game: &Game, _result: &PlayResult) {}
+}
+
+/// Shared observer, for the games played on multiple threads.
+///
+/// It locks the observer on each callback, the observer poisoned by the panicked game is still notified.
+impl<T: Observer + ?Sized> Observer for Arc<Mutex<T>> {
+    fn on_game_start(&mut self, game: &Game) {
+        self.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .on_game_start(game)
+    }
+
+    fn on_move(&mut self, game: &Game, result: &SetResult, elapsed: Duration) {
+        self.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .on_move(game, result, elapsed)
+    }
+
+    fn on_game_end(&mut self, game: &Game, result: &PlayResult) {
+        self.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .on_game_end(game, result)
+    }
+}
+
+/// Observer printing the board and timings to stdout.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::{Agent, DebugObserver}, policy::RandomPolicy};
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy)
+///     .with_board_size(7)
+///     .with_observer(&mut DebugObserver::new())
+///     .play();
+/// assert!(result.is_ok());
+/// ```
+pub struct DebugObserver {
+    id: Option<i32>,
+    start: Instant,
+}
+
+impl DebugObserver {
+    /// Construct a new `DebugObserver` printing the board on each move.
+    pub fn new() -> DebugObserver {
+        DebugObserver {
+            id: None,
+            start: Instant::now(),
+        }
+    }
+
+    /// Construct a `DebugObserver` printing only the summary of the game with given id.
+    pub fn summary(id: i32) -> DebugObserver {
+        DebugObserver {
+            id: Some(id),
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for DebugObserver {
+    fn default() -> DebugObserver {
+        DebugObserver::new()
+    }
+}
+
+impl Observer for DebugObserver {
+    fn on_game_start(&mut self, game: &Game) {
+        self.start = Instant::now();
+        if self.id.is_none() {
+            game.print(&mut io::stdout()).unwrap();
+        }
+    }
+
+    fn on_move(&mut self, game: &Game, result: &SetResult, elapsed: Duration) {
+        if self.id.is_some() {
+            return;
+        }
+        // log the selection info
+        println!(
+            "{:?} {}, remain {}, {}.{} elapsed",
+            result.player,
+            format_pos(result.position),
+            result.num_remain,
+            elapsed.as_secs(),
+            elapsed.subsec_millis()
+        );
+        game.print(&mut io::stdout()).unwrap();
+    }
+
+    fn on_game_end(&mut self, _game: &Game, result: &PlayResult) {
+        if let Some(id) = self.id {
+            let elapsed = self.start.elapsed();
+            println!(
+                "run: {}, {:?} by {:?}, elapsed {}.{}s",
+                id,
+                result.status,
+                result.reason,
+                elapsed.as_secs(),
+                elapsed.subsec_millis()
+            );
+        }
+    }
+}
+
+/// Observer writing the events to the `log` crate facade.
+///
+/// Game start and end are written in `Info` level, and each moves are written in `Debug` level.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::{Agent, LogObserver}, policy::RandomPolicy};
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy)
+///     .with_observer(&mut LogObserver::new())
+///     .play();
+/// assert!(result.is_ok());
+/// ```
+pub struct LogObserver {
+    start: Instant,
+}
+
+impl LogObserver {
+    /// Construct a new `LogObserver`.
+    pub fn new() -> LogObserver {
+        LogObserver {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for LogObserver {
+    fn default() -> LogObserver {
+        LogObserver::new()
+    }
+}
+
+impl Observer for LogObserver {
+    fn on_game_start(&mut self, game: &Game) {
+        self.start = Instant::now();
+        info!(
+            "game start, size {}, {:?} to play {} stones",
+            game.get_size(),
+            game.get_turn(),
+            game.get_remain()
+        );
+    }
+
+    fn on_move(&mut self, _game: &Game, result: &SetResult, elapsed: Duration) {
+        debug!(
+            "{:?} {}, remain {}, {}.{}s elapsed",
+            result.player,
+            format_pos(result.position),
+            result.num_remain,
+            elapsed.as_secs(),
+            elapsed.subsec_millis()
+        );
+    }
+
+    fn on_game_end(&mut self, _game: &Game, result: &PlayResult) {
+        let elapsed = self.start.elapsed();
+        info!(
+            "game end, {:?} by {:?} after {} moves, {}.{}s elapsed",
+            result.status,
+            result.reason,
+            result.path.len(),
+            elapsed.as_secs(),
+            elapsed.subsec_millis()
+        );
+    }
+}
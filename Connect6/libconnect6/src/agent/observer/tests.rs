@@ -0,0 +1,100 @@
+use super::*;
+use agent::{Agent, AsyncAgent};
+use game::{GameStatus, Player};
+use policy::{Policy, RandomPolicy};
+
+/// Policy select the first empty cell until the given number of stones are placed.
+struct FirstEmptyPolicy {
+    limit: usize,
+}
+
+impl Policy for FirstEmptyPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        if game.ply() >= self.limit {
+            return None;
+        }
+        let size = game.get_size();
+        let board = game.get_board();
+        (0..size * size)
+            .map(|i| (i / size, i % size))
+            .find(|&(row, col)| board[row][col] == Player::None)
+    }
+}
+
+/// Observer recording the sequence of the events.
+#[derive(Default)]
+struct EventRecorder {
+    events: Vec<String>,
+}
+
+impl Observer for EventRecorder {
+    fn on_game_start(&mut self, game: &Game) {
+        self.events.push(format!("start {}", game.ply()));
+    }
+
+    fn on_move(&mut self, game: &Game, result: &SetResult, _: Duration) {
+        let (row, col) = result.position;
+        assert_eq!(game.get_board()[row][col], result.player);
+        self.events.push(format!("move {} {}", row, col));
+    }
+
+    fn on_game_end(&mut self, _: &Game, result: &PlayResult) {
+        self.events.push(format!("end {:?}", result.status));
+    }
+}
+
+#[test]
+fn test_observer_events() {
+    let mut recorder = EventRecorder::default();
+    let mut policy = FirstEmptyPolicy { limit: 3 };
+    let result = Agent::new(&mut policy)
+        .with_observer(&mut recorder)
+        .play()
+        .unwrap();
+
+    assert_eq!(result.status, GameStatus::Ongoing);
+    assert_eq!(
+        recorder.events,
+        vec!["start 0", "move 0 0", "move 0 1", "move 0 2", "end Ongoing"]
+    );
+}
+
+#[test]
+fn test_multiple_observers() {
+    let mut first = EventRecorder::default();
+    let mut second = EventRecorder::default();
+    let mut policy = FirstEmptyPolicy { limit: 100 };
+    let result = Agent::new(&mut policy)
+        .with_board_size(5)
+        .with_observer(&mut first)
+        .with_observer(&mut DebugObserver::new())
+        .with_observer(&mut LogObserver::new())
+        .with_observer(&mut second)
+        .play()
+        .unwrap();
+
+    assert_eq!(result.status, GameStatus::Draw);
+    assert_eq!(first.events.len(), 25 + 2);
+    assert_eq!(first.events, second.events);
+    assert_eq!(first.events.last().unwrap(), "end Draw");
+}
+
+#[test]
+fn test_shared_observer() {
+    let recorder = Arc::new(Mutex::new(EventRecorder::default()));
+    let policy_gen = || RandomPolicy::new();
+    let async_agent = AsyncAgent::debug(policy_gen)
+        .with_board_size(7)
+        .with_observer(recorder.clone());
+
+    let result = async_agent.run(3);
+    let num_moves = result
+        .iter()
+        .map(|x| x.as_ref().unwrap().path.len())
+        .sum::<usize>();
+
+    let events = &recorder.lock().unwrap().events;
+    assert_eq!(events.len(), num_moves + 3 * 2);
+    assert_eq!(events.iter().filter(|x| x.starts_with("start")).count(), 3);
+    assert_eq!(events.iter().filter(|x| x.starts_with("end")).count(), 3);
+}
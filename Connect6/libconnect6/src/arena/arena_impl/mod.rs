@@ -0,0 +1,389 @@
+//! Implementation of `Arena`.
+//!
+//! Each match is played by `AsyncAgent` with the policy of both players,
+//! first player takes black on the even games and white on the odd games.
+use agent::AsyncAgent;
+use arena::{Sprt, SprtResult, Stats};
+use game::{Game, GameStatus, Player, Rule};
+use policy::{derive_seed, Policy};
+use BOARD_SIZE;
+
+use std::cell::Cell;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of the games in flight of the match without the number of threads,
+/// so that the match stopped early doesn't leave many games running.
+const MAX_IN_FLIGHT: usize = 16;
+
+/// Boxed policy generator of the player.
+type PolicyGen = Box<Fn() -> Box<Policy + Send>>;
+
+/// Policy of a game in the match, dispatching the turn to the policy of each player.
+struct MatchPolicy {
+    black: Box<Policy + Send>,
+    white: Box<Policy + Send>,
+}
+
+impl Policy for MatchPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        match game.get_turn() {
+            Player::Black => self.black.next(game),
+            Player::White => self.white.next(game),
+            Player::None => None,
+        }
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.black.seed(derive_seed(seed, 0));
+        self.white.seed(derive_seed(seed, 1));
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.black.set_deadline(deadline);
+        self.white.set_deadline(deadline);
+    }
+}
+
+/// Result of the match, from the perspective of the first player.
+///
+/// Games failed by errors, panics or timeouts and the games aborted without the result,
+/// such as the policy without the move, are counted in `num_failed` instead of `stats`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchResult {
+    pub stats: Stats,
+    pub num_failed: usize,
+    pub sprt: Option<SprtResult>,
+}
+
+/// Result of the gating, whether the candidate beats the current best.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GateResult {
+    pub result: MatchResult,
+    pub passed: bool,
+}
+
+/// Result of the round-robin tournament.
+///
+/// `stats[i][j]` is the statistics of player `i` against player `j`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tournament {
+    pub names: Vec<String>,
+    pub stats: Vec<Vec<Stats>>,
+    pub num_failed: usize,
+}
+
+impl Tournament {
+    /// Total statistics of the player against the others.
+    pub fn total(&self, player: usize) -> Stats {
+        self.stats[player]
+            .iter()
+            .fold(Stats::new(), |acc, stats| Stats {
+                wins: acc.wins + stats.wins,
+                draws: acc.draws + stats.draws,
+                losses: acc.losses + stats.losses,
+            })
+    }
+}
+
+impl fmt::Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.names.iter().map(|x| x.len()).max().unwrap_or(0);
+        write!(f, "{:1$}", "", width)?;
+        for name in self.names.iter() {
+            write!(f, " {:>12}", name)?;
+        }
+        writeln!(f, " {:>12} elo", "total")?;
+
+        for (i, name) in self.names.iter().enumerate() {
+            write!(f, "{:1$}", name, width)?;
+            for (j, stats) in self.stats[i].iter().enumerate() {
+                let cell = if i == j {
+                    "-".to_string()
+                } else {
+                    stats.to_string()
+                };
+                write!(f, " {:>12}", cell)?;
+            }
+            let total = self.total(i);
+            writeln!(f, " {:>12} {}", total.to_string(), total.elo())?;
+        }
+        Ok(())
+    }
+}
+
+/// Arena for playing the matches between the policies.
+///
+/// Players are registered with the name and the policy generator, and referred by the registered order.
+/// Games of a match are played in parallel by `AsyncAgent`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{arena::Arena, policy::{DefaultPolicy, RandomPolicy}};
+/// let arena = Arena::new()
+///     .with_board_size(7)
+///     .with_player("random", || RandomPolicy::new())
+///     .with_player("mcts", || DefaultPolicy::with_num_iter(10));
+///
+/// let tournament = arena.round_robin(2);
+/// println!("{}", tournament);
+/// # assert_eq!(tournament.total(0).num_games() + tournament.num_failed, 2);
+/// ```
+pub struct Arena {
+    names: Vec<String>,
+    players: Vec<PolicyGen>,
+    board_size: usize,
+    rule: Rule,
+    timeout: Option<Duration>,
+    seed: Option<u64>,
+    num_threads: Option<usize>,
+}
+
+impl Arena {
+    /// Construct a new empty `Arena`.
+    pub fn new() -> Arena {
+        Arena {
+            names: Vec::new(),
+            players: Vec::new(),
+            board_size: BOARD_SIZE,
+            rule: Rule::default(),
+            timeout: None,
+            seed: None,
+            num_threads: None,
+        }
+    }
+
+    /// Register the player with the name and the policy generator.
+    pub fn with_player<P, F>(mut self, name: &str, policy_gen: F) -> Arena
+    where
+        P: 'static + Policy + Send,
+        F: 'static + Fn() -> P,
+    {
+        self.names.push(name.to_string());
+        self.players.push(Box::new(move || {
+            Box::new(policy_gen()) as Box<Policy + Send>
+        }));
+        self
+    }
+
+    /// Set the board size of the games.
+    pub fn with_board_size(mut self, size: usize) -> Arena {
+        self.board_size = size;
+        self
+    }
+
+    /// Set the rule of the games.
+    pub fn with_rule(mut self, rule: Rule) -> Arena {
+        self.rule = rule;
+        self
+    }
+
+    /// Set the time limit of each game, timed out games are counted as failed.
+    pub fn with_timeout(mut self, timeout: Duration) -> Arena {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the seed of the matches, the game of the same id is replayed with deterministic policies.
+    pub fn with_seed(mut self, seed: u64) -> Arena {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the number of the threads playing the games of the match in parallel.
+    ///
+    /// Games are started as the previous games end, at most the number of threads
+    /// or 16 games if it isn't set, so that the early stopped match doesn't leave the remaining games.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Arena {
+        self.num_threads = Some(num_threads.max(1));
+        self
+    }
+
+    /// Names of the registered players.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Play the match of `num_games` games between the given players.
+    ///
+    /// # Panics
+    /// If the index of the player is out of range.
+    pub fn play_match(&self, first: usize, second: usize, num_games: usize) -> MatchResult {
+        self.run_match(first, second, num_games, |_, _| false)
+    }
+
+    /// Play the match at most `max_games` games, stop early if `sprt` accepts one of the hypotheses.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{arena::{Arena, Sprt}, policy::RandomPolicy};
+    /// let arena = Arena::new()
+    ///     .with_board_size(7)
+    ///     .with_player("random1", || RandomPolicy::new())
+    ///     .with_player("random2", || RandomPolicy::new());
+    ///
+    /// let result = arena.play_sprt(0, 1, 6, &Sprt::new(0., 50., 0.05, 0.05));
+    /// assert!(result.sprt.is_some());
+    /// assert!(result.stats.num_games() + result.num_failed <= 6);
+    /// ```
+    pub fn play_sprt(
+        &self,
+        first: usize,
+        second: usize,
+        max_games: usize,
+        sprt: &Sprt,
+    ) -> MatchResult {
+        let mut result = self.run_match(first, second, max_games, |stats, _| {
+            sprt.test(stats) != SprtResult::Continue
+        });
+        result.sprt = Some(sprt.test(&result.stats));
+        result
+    }
+
+    /// Gate the candidate, whether its score against the current best reaches the `threshold`.
+    ///
+    /// Match stops early if the result is decided regardless of the remaining games.
+    /// AlphaZero replaces the best network if the candidate wins by margin of 55%.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{arena::Arena, policy::{DefaultPolicy, RandomPolicy}};
+    /// let arena = Arena::new()
+    ///     .with_board_size(7)
+    ///     .with_player("best", || RandomPolicy::new())
+    ///     .with_player("candidate", || DefaultPolicy::with_num_iter(10));
+    ///
+    /// let gate = arena.gate(1, 0, 4, 0.55);
+    /// println!("passed: {}, score: {}", gate.passed, gate.result.stats.score());
+    /// ```
+    pub fn gate(
+        &self,
+        candidate: usize,
+        best: usize,
+        num_games: usize,
+        threshold: f64,
+    ) -> GateResult {
+        let result = self.run_match(candidate, best, num_games, |stats, remain| {
+            let points = stats.wins as f64 + 0.5 * stats.draws as f64;
+            let remain = remain as f64;
+            let total = (stats.num_games() as f64 + remain).max(1.);
+            points / total >= threshold || (points + remain) / total < threshold
+        });
+        let passed = result.stats.num_games() > 0 && result.stats.score() >= threshold;
+        GateResult { result, passed }
+    }
+
+    /// Play the round-robin tournament, `num_games` games for each pair of the players.
+    pub fn round_robin(&self, num_games: usize) -> Tournament {
+        let num_players = self.players.len();
+        let mut stats = vec![vec![Stats::new(); num_players]; num_players];
+        let mut num_failed = 0;
+        let pairs = (0..num_players).flat_map(|i| (i + 1..num_players).map(move |j| (i, j)));
+        for (i, j) in pairs {
+            let result = self.play_match(i, j, num_games);
+            stats[i][j] = result.stats;
+            stats[j][i] = result.stats.reverse();
+            num_failed += result.num_failed;
+        }
+        Tournament {
+            names: self.names.clone(),
+            stats,
+            num_failed,
+        }
+    }
+
+    /// Play the match, stop if `should_stop` returns true for the statistics and the number of remaining games.
+    fn run_match<S>(
+        &self,
+        first: usize,
+        second: usize,
+        num_games: usize,
+        should_stop: S,
+    ) -> MatchResult
+    where
+        S: Fn(&Stats, usize) -> bool,
+    {
+        let first_gen = &self.players[first];
+        let second_gen = &self.players[second];
+
+        // policy generator is called in the order of the game id
+        let counter = Cell::new(0);
+        let policy_gen = || {
+            let id = counter.get();
+            counter.set(id + 1);
+            if id % 2 == 0 {
+                MatchPolicy {
+                    black: first_gen(),
+                    white: second_gen(),
+                }
+            } else {
+                MatchPolicy {
+                    black: second_gen(),
+                    white: first_gen(),
+                }
+            }
+        };
+
+        let mut agent = AsyncAgent::new(policy_gen)
+            .with_board_size(self.board_size)
+            .with_rule(self.rule)
+            .with_max_in_flight(self.num_threads.unwrap_or(MAX_IN_FLIGHT));
+        if let Some(num_threads) = self.num_threads {
+            agent = agent.with_num_threads(num_threads);
+        }
+        if let Some(timeout) = self.timeout {
+            agent = agent.with_timeout(timeout);
+        }
+        if let Some(seed) = self.seed {
+            agent = agent.with_seed(seed);
+        }
+
+        let mut stats = Stats::new();
+        let mut num_failed = 0;
+        let mut stream = agent.stream(num_games as i32);
+        while let Some((id, result)) = stream.next_with_id() {
+            match result {
+                Ok(ref result) if result.status == GameStatus::Ongoing => {
+                    num_failed += 1;
+                    warn!("game {} aborted by {:?}", id, result.reason);
+                }
+                Ok(result) => {
+                    let player = if id % 2 == 0 {
+                        Player::Black
+                    } else {
+                        Player::White
+                    };
+                    stats.record(result.winner, player);
+                }
+                Err(err) => {
+                    num_failed += 1;
+                    warn!("{}", err);
+                }
+            }
+            let remain = num_games - stats.num_games() - num_failed;
+            if should_stop(&stats, remain) {
+                // games waiting for the worker are cancelled on drop
+                stream.stop();
+                break;
+            }
+        }
+
+        MatchResult {
+            stats,
+            num_failed,
+            sprt: None,
+        }
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Arena {
+        Arena::new()
+    }
+}
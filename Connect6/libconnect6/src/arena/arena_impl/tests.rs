@@ -0,0 +1,166 @@
+use super::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Policy filling the first row, wins against `WeakPolicy` on both colours.
+struct StrongPolicy;
+
+impl Policy for StrongPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        let board = game.get_board();
+        (0..board.size())
+            .map(|col| (0, col))
+            .find(|&(row, col)| board[row][col] == Player::None)
+    }
+}
+
+/// Policy placing the stones sparsely, not making a line in a few turns.
+struct WeakPolicy;
+
+impl Policy for WeakPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        let board = game.get_board();
+        let size = board.size();
+        (0..size)
+            .flat_map(|col| (1..size / 2).map(move |row| (row * 2, col)))
+            .find(|&(row, col)| board[row][col] == Player::None)
+    }
+}
+
+/// Policy panicking on the white turn.
+struct BlackOnlyPolicy;
+
+impl Policy for BlackOnlyPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        if game.get_turn() == Player::White {
+            panic!("black only");
+        }
+        StrongPolicy.next(game)
+    }
+}
+
+/// Policy without the move, the game is aborted.
+struct PassPolicy;
+
+impl Policy for PassPolicy {
+    fn next(&mut self, _game: &Game) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+fn arena() -> Arena {
+    Arena::new()
+        .with_board_size(9)
+        .with_player("strong", || StrongPolicy)
+        .with_player("weak", || WeakPolicy)
+}
+
+#[test]
+fn test_play_match() {
+    let arena = arena();
+    assert_eq!(arena.names(), ["strong".to_string(), "weak".to_string()]);
+
+    let result = arena.play_match(0, 1, 4);
+    assert_eq!(result.stats.to_string(), "4-0-0");
+    assert_eq!(result.num_failed, 0);
+    assert_eq!(result.sprt, None);
+
+    let result = arena.play_match(1, 0, 4);
+    assert_eq!(result.stats.to_string(), "0-0-4");
+}
+
+#[test]
+fn test_colour_alternation() {
+    let arena = arena().with_player("black-only", || BlackOnlyPolicy);
+
+    // first player takes white on the odd games
+    let result = arena.play_match(2, 1, 6);
+    assert_eq!(result.stats.to_string(), "3-0-0");
+    assert_eq!(result.num_failed, 3);
+}
+
+#[test]
+fn test_aborted() {
+    let arena = arena().with_player("pass", || PassPolicy);
+
+    // aborted games aren't counted as draws
+    let result = arena.play_match(2, 1, 4);
+    assert_eq!(result.stats.num_games(), 0);
+    assert_eq!(result.num_failed, 4);
+
+    let gate = arena.gate(2, 1, 4, 0.55);
+    assert!(!gate.passed);
+}
+
+#[test]
+fn test_play_sprt() {
+    let arena = arena();
+    let sprt = Sprt::new(0., 50., 0.05, 0.05);
+
+    let result = arena.play_sprt(0, 1, 100, &sprt);
+    assert_eq!(result.sprt, Some(SprtResult::AcceptH1));
+    assert!(result.stats.num_games() < 100);
+    assert_eq!(result.stats.losses, 0);
+
+    let result = arena.play_sprt(1, 0, 100, &sprt);
+    assert_eq!(result.sprt, Some(SprtResult::AcceptH0));
+    assert!(result.stats.num_games() < 100);
+}
+
+#[test]
+fn test_gate() {
+    let arena = arena();
+
+    // decided after 6 wins of 10 games
+    let gate = arena.gate(0, 1, 10, 0.55);
+    assert!(gate.passed);
+    assert_eq!(gate.result.stats.num_games(), 6);
+
+    // decided after 5 losses of 10 games
+    let gate = arena.gate(1, 0, 10, 0.55);
+    assert!(!gate.passed);
+    assert_eq!(gate.result.stats.num_games(), 5);
+}
+
+#[test]
+fn test_early_stop() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let handle = counter.clone();
+    let arena = Arena::new()
+        .with_board_size(9)
+        .with_num_threads(1)
+        .with_player("strong", move || {
+            handle.fetch_add(1, Ordering::SeqCst);
+            StrongPolicy
+        })
+        .with_player("weak", || WeakPolicy);
+
+    // decided by the first game, the remaining games aren't started
+    let gate = arena.gate(0, 1, 100, 0.);
+    assert!(gate.passed);
+    assert_eq!(gate.result.stats.num_games(), 1);
+    assert!(counter.load(Ordering::SeqCst) <= 2);
+}
+
+#[test]
+fn test_round_robin() {
+    let arena = arena().with_player("strong2", || StrongPolicy);
+
+    let tournament = arena.round_robin(2);
+    // strong and strong2 run out of the moves on the first row, the games are aborted
+    assert_eq!(tournament.num_failed, 2);
+    assert_eq!(tournament.stats[0][2].num_games(), 0);
+    assert_eq!(tournament.stats[0][1].to_string(), "2-0-0");
+    assert_eq!(tournament.stats[2][1].to_string(), "2-0-0");
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(tournament.stats[i][j], tournament.stats[j][i].reverse());
+        }
+    }
+    assert_eq!(tournament.total(1).to_string(), "0-0-4");
+
+    let table = tournament.to_string();
+    assert_eq!(table.lines().count(), 4);
+    assert!(table.contains("strong2"));
+}
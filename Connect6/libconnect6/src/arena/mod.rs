@@ -0,0 +1,27 @@
+//! Arena for comparing the policies with the matches.
+//!
+//! `Arena` plays the matches between the registered policy generators on tokio thread-pool,
+//! alternating the colour of the players game by game.
+//! Match is reported as win/draw/loss `Stats` and Elo estimate with the confidence interval.
+//!
+//! It also supports the early stop by `Sprt`, round-robin `Tournament`
+//! and the gating step of AlphaZero training, whether the candidate beats the current best.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{arena::Arena, policy::RandomPolicy};
+//! let arena = Arena::new()
+//!     .with_board_size(7)
+//!     .with_player("random1", || RandomPolicy::new())
+//!     .with_player("random2", || RandomPolicy::new());
+//!
+//! let result = arena.play_match(0, 1, 4);
+//! assert_eq!(result.stats.num_games() + result.num_failed, 4);
+//! println!("{} elo: {}", result.stats, result.stats.elo());
+//! ```
+pub use self::arena_impl::*;
+pub use self::rating::*;
+
+mod arena_impl;
+mod rating;
@@ -0,0 +1,256 @@
+//! Match statistics, Elo estimation and sequential probability ratio test.
+//!
+//! `Stats` counts the wins, draws and losses of a player against the opponent.
+//! The score is converted to the Elo difference with the logistic model,
+//! and its confidence interval is estimated by the normal approximation of the score.
+//!
+//! `Sprt` decides whether the Elo difference is `elo0` (H0) or `elo1` (H1)
+//! with the given error rates as early as possible.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{arena::{Sprt, SprtResult, Stats}, game::Player};
+//! let mut stats = Stats::new();
+//! for _ in 0..30 {
+//!     stats.record(Player::Black, Player::Black);
+//! }
+//! stats.record(Player::White, Player::Black);
+//!
+//! let elo = stats.elo();
+//! assert!(elo.estimate > 0.);
+//! assert!(elo.lower < elo.estimate && elo.estimate < elo.upper);
+//!
+//! let sprt = Sprt::new(0., 50., 0.05, 0.05);
+//! assert_eq!(sprt.test(&stats), SprtResult::AcceptH1);
+//! ```
+use game::Player;
+
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Standard normal quantile of 97.5%, for the 95% confidence interval.
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// Expected score of the player with given Elo difference.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::arena::expected_score;
+/// assert_eq!(expected_score(0.), 0.5);
+/// assert!((expected_score(400.) - 10. / 11.).abs() < 1e-9);
+/// ```
+pub fn expected_score(elo: f64) -> f64 {
+    1. / (1. + 10f64.powf(-elo / 400.))
+}
+
+/// Elo difference of the player with given expected score, infinite if score is 0 or 1.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::arena::{elo_from_score, expected_score};
+/// assert_eq!(elo_from_score(0.5), 0.);
+/// assert!((elo_from_score(expected_score(120.)) - 120.).abs() < 1e-9);
+/// assert_eq!(elo_from_score(1.), f64::INFINITY);
+/// ```
+pub fn elo_from_score(score: f64) -> f64 {
+    if score <= 0. {
+        f64::NEG_INFINITY
+    } else if score >= 1. {
+        f64::INFINITY
+    } else {
+        -400. * (1. / score - 1.).log10()
+    }
+}
+
+/// Elo difference estimate with its 95% confidence interval.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Elo {
+    pub estimate: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl fmt::Display for Elo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:+.1} [{:+.1}, {:+.1}]",
+            self.estimate, self.lower, self.upper
+        )
+    }
+}
+
+/// Number of wins, draws and losses of a player.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stats {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Stats {
+    /// Construct an empty `Stats`.
+    pub fn new() -> Stats {
+        Stats::default()
+    }
+
+    /// Record the game of given winner, from the perspective of given player.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{arena::Stats, game::Player};
+    /// let mut stats = Stats::new();
+    /// stats.record(Player::White, Player::White);
+    /// stats.record(Player::None, Player::White);
+    /// stats.record(Player::Black, Player::White);
+    /// assert_eq!((stats.wins, stats.draws, stats.losses), (1, 1, 1));
+    /// ```
+    pub fn record(&mut self, winner: Player, player: Player) {
+        if winner == Player::None {
+            self.draws += 1;
+        } else if winner == player {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+
+    /// Statistics from the perspective of the opponent.
+    pub fn reverse(&self) -> Stats {
+        Stats {
+            wins: self.losses,
+            draws: self.draws,
+            losses: self.wins,
+        }
+    }
+
+    /// Number of the games.
+    pub fn num_games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Average score, win as 1, draw as 0.5 and loss as 0, 0.5 if there is no game.
+    pub fn score(&self) -> f64 {
+        let num_games = self.num_games();
+        if num_games == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / num_games as f64
+    }
+
+    /// Variance of the score of a game.
+    pub fn variance(&self) -> f64 {
+        let num_games = self.num_games();
+        if num_games == 0 {
+            return 0.;
+        }
+        let score = self.score();
+        let sum = self.wins as f64 * (1. - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2);
+        sum / num_games as f64
+    }
+
+    /// Estimate the Elo difference with the 95% confidence interval.
+    pub fn elo(&self) -> Elo {
+        let score = self.score();
+        let margin = Z_95 * (self.variance() / self.num_games().max(1) as f64).sqrt();
+        Elo {
+            estimate: elo_from_score(score),
+            lower: elo_from_score(score - margin),
+            upper: elo_from_score(score + margin),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.draws, self.losses)
+    }
+}
+
+/// Decision of the sequential probability ratio test.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SprtResult {
+    AcceptH0,
+    AcceptH1,
+    Continue,
+}
+
+/// Sequential probability ratio test of the Elo difference, H0: `elo0`, H1: `elo1`.
+///
+/// It uses the normal approximation of the log-likelihood ratio of the score.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Sprt {
+    /// Construct a new `Sprt` with the hypotheses and the probabilities of type I and II error.
+    pub fn new(elo0: f64, elo1: f64, alpha: f64, beta: f64) -> Sprt {
+        Sprt {
+            elo0,
+            elo1,
+            alpha,
+            beta,
+        }
+    }
+
+    /// Lower and upper bound of the log-likelihood ratio.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::arena::Sprt;
+    /// let (lower, upper) = Sprt::new(0., 10., 0.05, 0.05).bounds();
+    /// assert!((upper - (0.95f64 / 0.05).ln()).abs() < 1e-9);
+    /// assert_eq!(lower, -upper);
+    /// ```
+    pub fn bounds(&self) -> (f64, f64) {
+        let lower = (self.beta / (1. - self.alpha)).ln();
+        let upper = ((1. - self.beta) / self.alpha).ln();
+        (lower, upper)
+    }
+
+    /// Log-likelihood ratio of H1 to H0 with given statistics.
+    ///
+    /// If all games have the same result, variance of the win-loss games
+    /// with the midpoint score of the hypotheses is used instead.
+    pub fn llr(&self, stats: &Stats) -> f64 {
+        if stats.num_games() == 0 {
+            return 0.;
+        }
+        let score0 = expected_score(self.elo0);
+        let score1 = expected_score(self.elo1);
+        let mut variance = stats.variance();
+        if variance <= 0. {
+            let mid = (score0 + score1) / 2.;
+            variance = mid * (1. - mid);
+        }
+        let num_games = stats.num_games() as f64;
+        num_games * (score1 - score0) * (2. * stats.score() - score0 - score1) / (2. * variance)
+    }
+
+    /// Test the statistics, continue if more games are required.
+    pub fn test(&self, stats: &Stats) -> SprtResult {
+        let (lower, upper) = self.bounds();
+        let llr = self.llr(stats);
+        if llr >= upper {
+            SprtResult::AcceptH1
+        } else if llr <= lower {
+            SprtResult::AcceptH0
+        } else {
+            SprtResult::Continue
+        }
+    }
+}
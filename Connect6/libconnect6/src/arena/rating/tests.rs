@@ -0,0 +1,56 @@
+use super::*;
+
+fn stats(wins: usize, draws: usize, losses: usize) -> Stats {
+    Stats {
+        wins,
+        draws,
+        losses,
+    }
+}
+
+#[test]
+fn test_score() {
+    assert_eq!(Stats::new().score(), 0.5);
+    assert_eq!(stats(3, 2, 1).score(), 4. / 6.);
+    assert_eq!(stats(3, 2, 1).reverse(), stats(1, 2, 3));
+    assert_eq!(stats(3, 2, 1).num_games(), 6);
+    assert_eq!(stats(3, 2, 1).to_string(), "3-2-1");
+}
+
+#[test]
+fn test_variance() {
+    assert_eq!(Stats::new().variance(), 0.);
+    assert_eq!(stats(0, 4, 0).variance(), 0.);
+    assert_eq!(stats(2, 0, 2).variance(), 0.25);
+}
+
+#[test]
+fn test_elo() {
+    let elo = stats(10, 0, 10).elo();
+    assert_eq!(elo.estimate, 0.);
+    assert!((elo.lower + elo.upper).abs() < 1e-9);
+
+    // more games, narrower interval
+    let few = stats(6, 2, 2).elo();
+    let many = stats(60, 20, 20).elo();
+    assert!((few.estimate - many.estimate).abs() < 1e-9);
+    assert!(many.upper - many.lower < few.upper - few.lower);
+
+    let elo = stats(5, 0, 0).elo();
+    assert_eq!(elo.estimate, f64::INFINITY);
+}
+
+#[test]
+fn test_sprt() {
+    let sprt = Sprt::new(0., 35., 0.05, 0.05);
+    assert_eq!(sprt.test(&Stats::new()), SprtResult::Continue);
+    assert_eq!(sprt.test(&stats(6, 0, 4)), SprtResult::Continue);
+    assert_eq!(sprt.test(&stats(300, 100, 100)), SprtResult::AcceptH1);
+    assert_eq!(sprt.test(&stats(100, 100, 300)), SprtResult::AcceptH0);
+    assert_eq!(sprt.test(&stats(200, 100, 200)), SprtResult::AcceptH0);
+
+    // degenerate variance of the same results
+    assert_eq!(sprt.test(&stats(5, 0, 0)), SprtResult::Continue);
+    assert_eq!(sprt.test(&stats(50, 0, 0)), SprtResult::AcceptH1);
+    assert_eq!(sprt.test(&stats(0, 0, 50)), SprtResult::AcceptH0);
+}
@@ -0,0 +1,454 @@
+//! Packed board representation for search-heavy code paths.
+//!
+//! `BitBoard` stores the stones as two bitsets, one for each player.
+//! Each row has one more padding bit than the length of one side,
+//! so that shifting the bitsets never connects the stones of different rows.
+//! It makes k-in-a-row detection as a few shift and and operations.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{game::Player, BitBoard};
+//! let mut board = BitBoard::new(15);
+//! for i in 0..6 {
+//!     board.set(3, i, Player::White);
+//! }
+//! assert_eq!(board.count(Player::White), 6);
+//! assert_eq!(board.search(), Player::White);
+//! ```
+use game::{Player, Rule};
+use Board;
+
+use std::borrow::Cow;
+
+#[cfg(test)]
+mod tests;
+
+/// Packed board with two bitsets for black and white stones.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    size: usize,
+    black: Vec<u64>,
+    white: Vec<u64>,
+}
+
+/// Bits of the word.
+const WORD: usize = 64;
+
+/// Word of the bitset shifted to the lower index, bit j of the word is bit i * WORD + j + n of given bitset.
+#[inline]
+fn shifted(bits: &[u64], i: usize, n: usize) -> u64 {
+    let (word, bit) = (n / WORD, n % WORD);
+    let get = |i: usize| bits.get(i).cloned().unwrap_or(0);
+    let low = get(i + word);
+    if bit == 0 {
+        low
+    } else {
+        (low >> bit) | (get(i + word + 1) << (WORD - bit))
+    }
+}
+
+/// Word of the bitset shifted to the higher index, bit j of the word is bit i * WORD + j - n of given bitset.
+#[inline]
+fn shifted_back(bits: &[u64], i: usize, n: usize) -> u64 {
+    let (word, bit) = (n / WORD, n % WORD);
+    let get = |offset: usize| i.checked_sub(offset).map_or(0, |j| bits[j]);
+    let high = get(word);
+    if bit == 0 {
+        high
+    } else {
+        (high << bit) | (get(word + 1) >> (WORD - bit))
+    }
+}
+
+/// Bitwise and with the bitset shifted to the lower index in place.
+///
+/// Shifted word only reads the words of the same or higher index, so the words are updated in ascending order.
+fn and_shifted(run: &mut [u64], n: usize) {
+    for i in 0..run.len() {
+        let word = shifted(run, i, n);
+        run[i] &= word;
+    }
+}
+
+/// Return true if the bitset has k continuous bits with given step,
+/// exactly k bits if overline is not allowed.
+///
+/// `run` is the scratch buffer reused over the calls, so that the search doesn't allocate for each shift.
+fn has_run(bits: &[u64], step: usize, rule: &Rule, run: &mut Vec<u64>) -> bool {
+    let k = rule.win_length;
+    // bit i of run is set if `len` continuous bits start from bit i, doubling the length
+    run.clear();
+    run.extend_from_slice(bits);
+    let mut len = 1;
+    while len * 2 <= k {
+        and_shifted(run, len * step);
+        len *= 2;
+    }
+    // two overlapped runs make k continuous bits
+    if len < k {
+        and_shifted(run, (k - len) * step);
+    }
+    if !rule.overline {
+        // exclude the runs continued from the previous bit or to the next bit
+        for (i, word) in run.iter_mut().enumerate() {
+            *word &= !shifted_back(bits, i, step) & !shifted(bits, i, k * step);
+        }
+    }
+    run.iter().any(|x| *x != 0)
+}
+
+impl BitBoard {
+    /// Construct an empty `BitBoard` with given length of one side.
+    ///
+    /// # Panics
+    /// If given size is zero.
+    pub fn new(size: usize) -> BitBoard {
+        assert!(size > 0, "empty board");
+        // rounded up number of the words
+        let num_words = (size * (size + 1) - 1) / WORD + 1;
+        BitBoard {
+            size,
+            black: vec![0; num_words],
+            white: vec![0; num_words],
+        }
+    }
+
+    /// Construct a `BitBoard` from the array form board.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, BitBoard, Board};
+    /// let mut board = Board::new(15);
+    /// board[1][2] = Player::Black;
+    ///
+    /// let bits = BitBoard::from_board(&board);
+    /// assert_eq!(bits.get(1, 2), Player::Black);
+    /// assert_eq!(bits.to_board(), board);
+    /// ```
+    pub fn from_board(board: &Board) -> BitBoard {
+        let size = board.size();
+        let mut bits = BitBoard::new(size);
+        for row in 0..size {
+            for col in 0..size {
+                bits.set(row, col, board[row][col]);
+            }
+        }
+        bits
+    }
+
+    /// Convert to the array form board.
+    pub fn to_board(&self) -> Board {
+        let mut board = Board::new(self.size);
+        for row in 0..self.size {
+            for col in 0..self.size {
+                board[row][col] = self.get(row, col);
+            }
+        }
+        board
+    }
+
+    /// Length of one side.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of cells, square of size.
+    pub fn capacity(&self) -> usize {
+        self.size * self.size
+    }
+
+    /// Index of the bit with the padding of each rows.
+    #[inline]
+    fn index(&self, row: usize, col: usize) -> (usize, u64) {
+        let idx = row * (self.size + 1) + col;
+        (idx / WORD, 1 << (idx % WORD))
+    }
+
+    /// Position of the bit index, None if it is padding.
+    #[inline]
+    fn position(&self, idx: usize) -> Option<(usize, usize)> {
+        let (row, col) = (idx / (self.size + 1), idx % (self.size + 1));
+        if col < self.size {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Get the stone of given position.
+    ///
+    /// # Panics
+    /// If given position is out of board.
+    pub fn get(&self, row: usize, col: usize) -> Player {
+        assert!(row < self.size && col < self.size, "out of board");
+        let (word, mask) = self.index(row, col);
+        if self.black[word] & mask != 0 {
+            Player::Black
+        } else if self.white[word] & mask != 0 {
+            Player::White
+        } else {
+            Player::None
+        }
+    }
+
+    /// Set the stone of given player, `Player::None` clears the position.
+    ///
+    /// # Panics
+    /// If given position is out of board.
+    pub fn set(&mut self, row: usize, col: usize, player: Player) {
+        assert!(row < self.size && col < self.size, "out of board");
+        let (word, mask) = self.index(row, col);
+        self.black[word] &= !mask;
+        self.white[word] &= !mask;
+        match player {
+            Player::Black => self.black[word] |= mask,
+            Player::White => self.white[word] |= mask,
+            Player::None => (),
+        }
+    }
+
+    /// Clear the given position.
+    pub fn clear(&mut self, row: usize, col: usize) {
+        self.set(row, col, Player::None);
+    }
+
+    /// Bitset of given player, empty cells for `Player::None`.
+    fn bits(&self, player: Player) -> Cow<'_, [u64]> {
+        match player {
+            Player::Black => Cow::Borrowed(&self.black),
+            Player::White => Cow::Borrowed(&self.white),
+            Player::None => {
+                let mut empty = vec![0; self.black.len()];
+                for row in 0..self.size {
+                    for col in 0..self.size {
+                        let (word, mask) = self.index(row, col);
+                        empty[word] |= mask;
+                    }
+                }
+                for (e, (b, w)) in empty
+                    .iter_mut()
+                    .zip(self.black.iter().zip(self.white.iter()))
+                {
+                    *e &= !(b | w);
+                }
+                Cow::Owned(empty)
+            }
+        }
+    }
+
+    /// Number of the cells of given player, `Player::None` for empty cells.
+    pub fn count(&self, player: Player) -> usize {
+        match player {
+            Player::None => self.capacity() - self.num_stones(),
+            _ => self
+                .bits(player)
+                .iter()
+                .map(|x| x.count_ones() as usize)
+                .sum(),
+        }
+    }
+
+    /// Number of the stones on the board.
+    pub fn num_stones(&self) -> usize {
+        self.black
+            .iter()
+            .zip(self.white.iter())
+            .map(|(b, w)| (b | w).count_ones() as usize)
+            .sum()
+    }
+
+    /// Positions of the cells of given player, `Player::None` for empty cells, in row-major order.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, BitBoard};
+    /// let mut board = BitBoard::new(3);
+    /// board.set(2, 1, Player::Black);
+    /// board.set(0, 2, Player::Black);
+    /// assert_eq!(board.positions(Player::Black), vec![(0, 2), (2, 1)]);
+    /// ```
+    pub fn positions(&self, player: Player) -> Vec<(usize, usize)> {
+        let mut positions = Vec::new();
+        for (i, word) in self.bits(player).iter().enumerate() {
+            let mut word = *word;
+            while word != 0 {
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                if let Some(pos) = self.position(i * WORD + bit) {
+                    positions.push(pos);
+                }
+            }
+        }
+        positions
+    }
+
+    /// Return the first different position between two boards.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, BitBoard};
+    /// let board = BitBoard::new(15);
+    /// let mut next = board.clone();
+    /// next.set(3, 4, Player::Black);
+    /// assert_eq!(board.diff(&next), Some((3, 4)));
+    /// assert_eq!(board.diff(&board), None);
+    /// ```
+    pub fn diff(&self, other: &BitBoard) -> Option<(usize, usize)> {
+        let words = self.black.iter().zip(self.white.iter());
+        let others = other.black.iter().zip(other.white.iter());
+        for (i, ((b1, w1), (b2, w2))) in words.zip(others).enumerate() {
+            let diff = (b1 ^ b2) | (w1 ^ w2);
+            if diff != 0 {
+                return self.position(i * WORD + diff.trailing_zeros() as usize);
+            }
+        }
+        None
+    }
+
+    /// Find the winner with shift-based six-in-a-row detection, Player::None if no one wins.
+    pub fn search(&self) -> Player {
+        self.search_with(&Rule::default())
+    }
+
+    /// Find the winner under the given rule, Player::None if no one wins.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::{Player, Rule}, BitBoard};
+    /// let mut board = BitBoard::new(15);
+    /// for i in 0..5 {
+    ///     board.set(i, 2, Player::Black);
+    /// }
+    /// assert_eq!(board.search(), Player::None);
+    /// assert_eq!(board.search_with(&Rule::gomoku()), Player::Black);
+    /// ```
+    pub fn search_with(&self, rule: &Rule) -> Player {
+        // right, down, right-down, left-down
+        let stride = self.size + 1;
+        let steps = [1, stride, stride + 1, stride - 1];
+        let mut run = Vec::with_capacity(self.black.len());
+        if steps
+            .iter()
+            .any(|s| has_run(&self.black, *s, rule, &mut run))
+        {
+            Player::Black
+        } else if steps
+            .iter()
+            .any(|s| has_run(&self.white, *s, rule, &mut run))
+        {
+            Player::White
+        } else {
+            Player::None
+        }
+    }
+
+    /// Find the winner with the four lines through given position.
+    ///
+    /// It assumes that there was no winner before the stone of given position was placed.
+    pub fn search_from(&self, row: usize, col: usize) -> Player {
+        self.search_from_with(row, col, &Rule::default())
+    }
+
+    /// Find the winner under the given rule with the four lines through given position.
+    pub fn search_from_with(&self, row: usize, col: usize, rule: &Rule) -> Player {
+        if row >= self.size || col >= self.size {
+            return Player::None;
+        }
+        let player = self.get(row, col);
+        if player == Player::None {
+            return Player::None;
+        }
+
+        let size = self.size as isize;
+        // number of the continuous stones from given position to the direction
+        let count = |dr: isize, dc: isize| {
+            let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+            let mut num = 0;
+            while r >= 0 && c >= 0 && r < size && c < size {
+                if self.get(r as usize, c as usize) != player {
+                    break;
+                }
+                num += 1;
+                r += dr;
+                c += dc;
+            }
+            num
+        };
+
+        let paths = [(0, 1), (1, 0), (1, 1), (1, -1)];
+        for (dr, dc) in paths.iter() {
+            if rule.is_win(1 + count(*dr, *dc) + count(-dr, -dc)) {
+                return player;
+            }
+        }
+        Player::None
+    }
+
+    /// Transform the positions with given mapping.
+    fn transform<F>(&self, map: F) -> BitBoard
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let mut board = BitBoard::new(self.size);
+        for player in [Player::Black, Player::White].iter() {
+            for (row, col) in self.positions(*player) {
+                let (row, col) = map(row, col);
+                board.set(row, col, *player);
+            }
+        }
+        board
+    }
+
+    /// Rotate the board 90 degrees counterclockwise.
+    pub fn rotate_left(&self) -> BitBoard {
+        let size = self.size;
+        self.transform(|row, col| (size - col - 1, row))
+    }
+
+    /// Rotate the board 90 degrees clockwise.
+    pub fn rotate_right(&self) -> BitBoard {
+        let size = self.size;
+        self.transform(|row, col| (col, size - row - 1))
+    }
+
+    /// Flip the board over the vertical axis.
+    pub fn flip_vertical(&self) -> BitBoard {
+        let size = self.size;
+        self.transform(|row, col| (row, size - col - 1))
+    }
+
+    /// Flip the board over the horizontal axis.
+    pub fn flip_horizontal(&self) -> BitBoard {
+        let size = self.size;
+        self.transform(|row, col| (size - row - 1, col))
+    }
+
+    /// Eight symmetries of the board, rotated left and its vertical flip for four times.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, BitBoard};
+    /// let mut board = BitBoard::new(15);
+    /// board.set(0, 0, Player::Black);
+    ///
+    /// let symmetries = board.symmetries();
+    /// assert_eq!(symmetries.len(), 8);
+    /// assert_eq!(symmetries[6], board);
+    /// ```
+    pub fn symmetries(&self) -> Vec<BitBoard> {
+        let mut vec = Vec::with_capacity(8);
+        let mut board = self.clone();
+        for _ in 0..4 {
+            board = board.rotate_left();
+            vec.push(board.clone());
+            vec.push(board.flip_vertical());
+        }
+        vec
+    }
+}
@@ -0,0 +1,219 @@
+use super::*;
+use game::{search, search_with};
+use rand;
+use BOARD_SIZE;
+
+fn rand_board(size: usize) -> Board {
+    let mut board = Board::new(size);
+    for row in 0..size {
+        for col in 0..size {
+            board[row][col] = match rand::random::<u8>() % 3 {
+                0 => Player::Black,
+                1 => Player::White,
+                _ => Player::None,
+            };
+        }
+    }
+    board
+}
+
+#[test]
+fn test_set_and_clear() {
+    let mut board = BitBoard::new(BOARD_SIZE);
+    assert_eq!(board.num_stones(), 0);
+
+    board.set(0, 0, Player::Black);
+    board.set(BOARD_SIZE - 1, BOARD_SIZE - 1, Player::White);
+    assert_eq!(board.get(0, 0), Player::Black);
+    assert_eq!(board.get(BOARD_SIZE - 1, BOARD_SIZE - 1), Player::White);
+    assert_eq!(board.count(Player::Black), 1);
+    assert_eq!(board.count(Player::White), 1);
+    assert_eq!(board.count(Player::None), BOARD_SIZE * BOARD_SIZE - 2);
+
+    board.set(0, 0, Player::White);
+    assert_eq!(board.count(Player::Black), 0);
+    assert_eq!(board.count(Player::White), 2);
+
+    board.clear(0, 0);
+    assert_eq!(board.get(0, 0), Player::None);
+    assert_eq!(board.num_stones(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_set_out_of_board() {
+    let mut board = BitBoard::new(5);
+    board.set(0, 5, Player::Black);
+}
+
+#[test]
+fn test_board_conversion() {
+    for size in [1, 6, 8, 15, 19].iter() {
+        let board = rand_board(*size);
+        let bits = BitBoard::from_board(&board);
+        assert_eq!(bits.to_board(), board);
+
+        let count = |player| board.as_slice().iter().filter(|x| **x == player).count();
+        assert_eq!(bits.count(Player::Black), count(Player::Black));
+        assert_eq!(bits.count(Player::White), count(Player::White));
+        assert_eq!(bits.count(Player::None), count(Player::None));
+        assert_eq!(bits.positions(Player::None).len(), count(Player::None));
+    }
+}
+
+#[test]
+fn test_diff() {
+    let board = BitBoard::from_board(&rand_board(BOARD_SIZE));
+    assert_eq!(board.diff(&board), None);
+
+    let mut other = board.clone();
+    let player = match board.get(7, 9) {
+        Player::None => Player::Black,
+        _ => Player::None,
+    };
+    other.set(7, 9, player);
+    assert_eq!(board.diff(&other), Some((7, 9)));
+}
+
+#[test]
+fn test_search() {
+    for size in [6, 9, 15, 19].iter() {
+        let size = *size;
+        for (dr, dc, start) in [
+            (0, 1, (0, 0)),
+            (1, 0, (0, size - 1)),
+            (1, 1, (0, 0)),
+            (1, -1, (0, size - 1)),
+        ]
+        .iter()
+        {
+            let mut board = BitBoard::new(size);
+            let pos = |i: isize| {
+                let row = start.0 as isize + dr * i;
+                let col = start.1 as isize + dc * i;
+                (row as usize, col as usize)
+            };
+            for i in 0..5 {
+                let (row, col) = pos(i);
+                board.set(row, col, Player::Black);
+            }
+            assert_eq!(board.search(), Player::None);
+
+            let (row, col) = pos(5);
+            board.set(row, col, Player::Black);
+            assert_eq!(board.search(), Player::Black);
+            assert_eq!(board.search_from(row, col), Player::Black);
+        }
+    }
+}
+
+#[test]
+fn test_search_row_boundary() {
+    // stones at the end of the row and the start of the next row are not connected
+    let mut board = BitBoard::new(BOARD_SIZE);
+    for i in 0..3 {
+        board.set(0, BOARD_SIZE - 1 - i, Player::White);
+        board.set(1, i, Player::White);
+    }
+    assert_eq!(board.search(), Player::None);
+    assert_eq!(board.search_from(1, 0), Player::None);
+}
+
+#[test]
+fn test_search_random() {
+    for _ in 0..100 {
+        let board = rand_board(BOARD_SIZE);
+        let bits = BitBoard::from_board(&board);
+
+        let expected = search(&board);
+        let searched = bits.search();
+        if expected == Player::None {
+            assert_eq!(searched, Player::None);
+        } else {
+            // `search` of array form returns the first found winner
+            assert_ne!(searched, Player::None);
+        }
+    }
+}
+
+#[test]
+fn test_search_with_rules() {
+    let rules = [
+        Rule::gomoku(),
+        Rule::gomoku().with_overline(false),
+        Rule::connect6().with_overline(false),
+        Rule::new(4, 1, 1).with_overline(false),
+    ];
+    for rule in rules.iter() {
+        for _ in 0..50 {
+            let board = rand_board(9);
+            let bits = BitBoard::from_board(&board);
+
+            let expected = search_with(&board, rule);
+            let searched = bits.search_with(rule);
+            assert_eq!(expected == Player::None, searched == Player::None);
+        }
+    }
+}
+
+#[test]
+fn test_search_with_exact() {
+    let rule = Rule::connect6().with_overline(false);
+    for (dr, dc, start) in [
+        (0, 1, (2, 0)),
+        (1, 0, (0, 3)),
+        (1, 1, (0, 0)),
+        (1, -1, (0, 8)),
+    ]
+    .iter()
+    {
+        let mut board = BitBoard::new(BOARD_SIZE);
+        let pos = |i: isize| {
+            let row = start.0 as isize + dr * i;
+            let col = start.1 as isize + dc * i;
+            (row as usize, col as usize)
+        };
+        for i in 0..6 {
+            let (row, col) = pos(i);
+            board.set(row, col, Player::White);
+        }
+        assert_eq!(board.search_with(&rule), Player::White);
+
+        let (row, col) = pos(6);
+        board.set(row, col, Player::White);
+        assert_eq!(board.search_with(&rule), Player::None);
+        assert_eq!(board.search_from_with(row, col, &rule), Player::None);
+        assert_eq!(board.search(), Player::White);
+    }
+}
+
+#[test]
+fn test_symmetries() {
+    let board = rand_board(BOARD_SIZE);
+    let bits = BitBoard::from_board(&board);
+
+    assert_eq!(bits.rotate_left().rotate_right(), bits);
+    assert_eq!(bits.flip_vertical().flip_vertical(), bits);
+    assert_eq!(bits.flip_horizontal().flip_horizontal(), bits);
+    assert_eq!(
+        bits.rotate_left().rotate_left(),
+        bits.rotate_right().rotate_right()
+    );
+    assert_eq!(
+        bits.rotate_left().rotate_left(),
+        bits.flip_vertical().flip_horizontal()
+    );
+
+    let mut rotated = board.clone();
+    for row in 0..BOARD_SIZE {
+        for col in 0..BOARD_SIZE {
+            rotated[BOARD_SIZE - col - 1][row] = board[row][col];
+        }
+    }
+    assert_eq!(bits.rotate_left().to_board(), rotated);
+
+    let symmetries = bits.symmetries();
+    assert_eq!(symmetries.len(), 8);
+    assert_eq!(symmetries[6], bits);
+    assert_eq!(symmetries[1], symmetries[0].flip_vertical());
+}
@@ -0,0 +1,200 @@
+//! Square board with runtime configurable size.
+//!
+//! `GenericBoard` stores the cells in row-major order and indexes as `board[row][col]`.
+//! It is used as the game board (`Board`, alias of `GenericBoard<Player>`)
+//! and also as the policy probabilities of `AlphaZero` (`GenericBoard<f32>`).
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{game::Player, Board};
+//! let mut board = Board::new(19);
+//! board[3][4] = Player::Black;
+//!
+//! assert_eq!(board.size(), 19);
+//! assert_eq!(board.capacity(), 19 * 19);
+//! assert_eq!(board[3][4], Player::Black);
+//! ```
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+#[cfg(feature = "serde")]
+use MAX_BOARD_SIZE;
+
+mod bitboard;
+pub use self::bitboard::BitBoard;
+
+#[cfg(test)]
+mod tests;
+
+/// Square board with runtime configurable size, indexed as `board[row][col]`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "RawBoard<T>")
+)]
+pub struct GenericBoard<T> {
+    size: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone + Default> GenericBoard<T> {
+    /// Construct a new `GenericBoard` filled with default value.
+    ///
+    /// # Panics
+    /// If given size is zero.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::GenericBoard;
+    /// let board = GenericBoard::<f32>::new(9);
+    /// assert!(board.iter().all(|row| row.iter().all(|x| *x == 0.)));
+    /// ```
+    pub fn new(size: usize) -> GenericBoard<T> {
+        Self::filled(size, T::default())
+    }
+}
+
+impl<T: Clone> GenericBoard<T> {
+    /// Construct a `GenericBoard` filled with given value.
+    ///
+    /// # Panics
+    /// If given size is zero.
+    pub fn filled(size: usize, value: T) -> GenericBoard<T> {
+        assert!(size > 0, "empty board");
+        GenericBoard {
+            size,
+            cells: vec![value; size * size],
+        }
+    }
+
+    /// Construct a `GenericBoard` from row-major ordered cells, `None` for the empty board.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::GenericBoard;
+    /// let board = GenericBoard::from_vec(2, vec![1, 2, 3, 4]).unwrap();
+    /// assert_eq!(board[1][0], 3);
+    /// assert!(GenericBoard::from_vec(2, vec![1, 2, 3]).is_none());
+    /// assert!(GenericBoard::<i32>::from_vec(0, vec![]).is_none());
+    /// ```
+    pub fn from_vec(size: usize, cells: Vec<T>) -> Option<GenericBoard<T>> {
+        if size == 0 || cells.len() != size * size {
+            return None;
+        }
+        Some(GenericBoard { size, cells })
+    }
+
+    /// Convert each cells with given function.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, Board};
+    /// let mut board = Board::new(5);
+    /// board[0][0] = Player::White;
+    ///
+    /// let converted = board.map(|x| *x as i32 as f32);
+    /// assert_eq!(converted[0][0], 1.);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> GenericBoard<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        GenericBoard {
+            size: self.size,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> GenericBoard<T> {
+    /// Length of one side.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of cells, square of size.
+    pub fn capacity(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Iterate over the rows.
+    pub fn iter(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.size)
+    }
+
+    /// Iterate over the rows mutably.
+    pub fn iter_mut(&mut self) -> slice::ChunksMut<'_, T> {
+        self.cells.chunks_mut(self.size)
+    }
+
+    /// Return row-major ordered cells.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Return row-major ordered cells mutably.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+}
+
+impl<T> Index<usize> for GenericBoard<T> {
+    type Output = [T];
+
+    /// Return the row of given index.
+    fn index(&self, row: usize) -> &[T] {
+        let start = row * self.size;
+        &self.cells[start..start + self.size]
+    }
+}
+
+impl<T> IndexMut<usize> for GenericBoard<T> {
+    /// Return the row of given index mutably.
+    fn index_mut(&mut self, row: usize) -> &mut [T] {
+        let start = row * self.size;
+        &mut self.cells[start..start + self.size]
+    }
+}
+
+/// Unchecked `GenericBoard` for validating the number of cells on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawBoard<T> {
+    size: usize,
+    cells: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawBoard<T>> for GenericBoard<T> {
+    type Error = String;
+
+    fn try_from(raw: RawBoard<T>) -> Result<GenericBoard<T>, String> {
+        if raw.size == 0 {
+            return Err("empty board".to_string());
+        }
+        if raw.size > MAX_BOARD_SIZE {
+            return Err(format!(
+                "board size {} larger than {}",
+                raw.size, MAX_BOARD_SIZE
+            ));
+        }
+        if raw.size.checked_mul(raw.size) != Some(raw.cells.len()) {
+            return Err(format!(
+                "{} cells for the board size {}",
+                raw.cells.len(),
+                raw.size
+            ));
+        }
+        Ok(GenericBoard {
+            size: raw.size,
+            cells: raw.cells,
+        })
+    }
+}
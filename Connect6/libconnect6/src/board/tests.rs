@@ -0,0 +1,88 @@
+use super::*;
+use game::Player;
+
+#[test]
+fn test_new() {
+    let board = GenericBoard::<Player>::new(9);
+    assert_eq!(board.size(), 9);
+    assert_eq!(board.capacity(), 81);
+    assert!(board.as_slice().iter().all(|x| *x == Player::None));
+}
+
+#[test]
+fn test_index() {
+    let mut board = GenericBoard::filled(7, 0);
+    board[2][3] = 1;
+    board[6][6] = 2;
+
+    assert_eq!(board[2][3], 1);
+    assert_eq!(board.as_slice()[2 * 7 + 3], 1);
+    assert_eq!(board.as_slice()[7 * 7 - 1], 2);
+    assert_eq!(board.iter().count(), 7);
+    assert_eq!(board.iter().nth(2).unwrap()[3], 1);
+}
+
+#[test]
+#[should_panic]
+fn test_index_out_of_board() {
+    let board = GenericBoard::filled(5, 0);
+    let _ = board[5][0];
+}
+
+#[test]
+fn test_from_vec() {
+    let board = GenericBoard::from_vec(3, (0..9).collect());
+    assert!(board.is_some());
+
+    let board = board.unwrap();
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(board[i][j], i * 3 + j);
+        }
+    }
+    assert!(GenericBoard::from_vec(3, vec![0; 8]).is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_empty_board() {
+    let _ = GenericBoard::<Player>::new(0);
+}
+
+#[test]
+fn test_map() {
+    let mut board = GenericBoard::<Player>::new(4);
+    board[1][2] = Player::Black;
+
+    let converted = board.map(|x| *x as i32);
+    assert_eq!(converted.size(), 4);
+    assert_eq!(converted[1][2], -1);
+    assert_eq!(converted[0][0], 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde_json;
+
+    let mut board = GenericBoard::filled(2, 0);
+    board[1][0] = 3;
+    let json = serde_json::to_string(&board).unwrap();
+    assert_eq!(json, r#"{"size":2,"cells":[0,0,3,0]}"#);
+    assert_eq!(
+        serde_json::from_str::<GenericBoard<i32>>(&json).unwrap(),
+        board
+    );
+
+    let invalid = r#"{"size":2,"cells":[0,0,3]}"#;
+    assert!(serde_json::from_str::<GenericBoard<i32>>(invalid).is_err());
+
+    let empty = r#"{"size":0,"cells":[]}"#;
+    assert!(serde_json::from_str::<GenericBoard<i32>>(empty).is_err());
+
+    let large = r#"{"size":27,"cells":[]}"#;
+    assert!(serde_json::from_str::<GenericBoard<i32>>(large).is_err());
+
+    let overflow = r#"{"size":18446744073709551615,"cells":[]}"#;
+    assert!(serde_json::from_str::<GenericBoard<i32>>(overflow).is_err());
+}
@@ -0,0 +1,483 @@
+//! Engine text protocol over the line based reader and writer.
+//!
+//! `Engine` wraps any policy as the engine, so that it can be driven by GUIs,
+//! tournament managers and scripts over stdin and stdout.
+//! Protocol follows the style of the Go Text Protocol, each command is a line with an optional numeric id,
+//! and the response is `=` on success or `?` on failure, followed by the id, the result and an empty line.
+//!
+//! ```text
+//! 1 boardsize 15
+//! =1
+//!
+//! 2 play black H8
+//! =2
+//!
+//! 3 genmove white
+//! =3 G7 G9
+//!
+//! ```
+//!
+//! Positions are written in move-list notation of the record, column letter and one-based row number.
+//! Every turn has two stones except the first one, `play` accepts the stones of the same colour at once
+//! and `genmove` generates all remaining stones of the turn.
+//!
+//! - protocol_version, name, version, known_command `command`, list_commands
+//! - boardsize `size` : change the board size and clear the board.
+//! - clear_board : start the new game.
+//! - play `colour` `positions..` : play the stones of the colour.
+//! - genmove `colour` : generate the stones of the colour, `resign` if the policy gave up.
+//! - undo : take back the last stone.
+//! - time_settings `main_time` `byo_yomi_time` `byo_yomi_stones` : time settings in seconds.
+//! - time_left `colour` `time` `stones` : remaining time of the player, `stones` is 0 in the main time.
+//! - showboard : board diagram.
+//! - final_score : result notation, `B+`, `W+`, `Draw` or `*` for the ongoing game.
+//! - quit : stop the engine.
+//!
+//! With the time settings, each stone of `genmove` has the time budget from the clock,
+//! the byo-yomi time over its stones or the main time over `MAIN_TIME_STONES`,
+//! and the search of the policy is stopped at the deadline by `Policy::set_deadline`.
+//! Policy without search ignores the deadline, and the search runs at least one simulation.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{engine::Engine, policy::RandomPolicy};
+//! let mut policy = RandomPolicy::with_seed(42);
+//! let mut engine = Engine::new(&mut policy);
+//!
+//! let input = "1 boardsize 7\n2 play black D4\n3 genmove white\nquit\nshowboard\n";
+//! let mut output = Vec::new();
+//! engine.run(input.as_bytes(), &mut output).unwrap();
+//!
+//! let output = String::from_utf8(output).unwrap();
+//! assert!(output.starts_with("=1\n\n=2\n\n=3 "));
+//! assert!(output.ends_with("=\n\n"));
+//! assert_eq!(engine.game().ply(), 3);
+//! ```
+use game::{Game, Player, Rule};
+use policy::Policy;
+use record::{format_pos, format_result, parse_pos};
+use MAX_BOARD_SIZE;
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Commands known to the engine.
+pub const COMMANDS: &[&str] = &[
+    "protocol_version",
+    "name",
+    "version",
+    "known_command",
+    "list_commands",
+    "boardsize",
+    "clear_board",
+    "play",
+    "genmove",
+    "undo",
+    "time_settings",
+    "time_left",
+    "showboard",
+    "final_score",
+    "quit",
+];
+
+/// Expected number of the stones played in the main time, main time is divided by it for the budget of a stone.
+pub const MAIN_TIME_STONES: u32 = 30;
+
+/// Response of the command, written as `=id result` on success or `?id message` on failure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub id: Option<u32>,
+    pub result: Result<String, String>,
+}
+
+impl fmt::Display for Response {
+    /// Write the response followed by an empty line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (mark, msg) = match self.result {
+            Ok(ref msg) => ('=', msg),
+            Err(ref msg) => ('?', msg),
+        };
+        write!(f, "{}", mark)?;
+        if let Some(id) = self.id {
+            write!(f, "{}", id)?;
+        }
+        if !msg.is_empty() {
+            write!(f, " {}", msg)?;
+        }
+        write!(f, "\n\n")
+    }
+}
+
+/// Time settings of the game, main time and the byo-yomi period for the number of stones.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TimeSettings {
+    pub main_time: Duration,
+    pub byo_yomi_time: Duration,
+    pub byo_yomi_stones: u32,
+}
+
+/// Remaining time of the player, `stones` is the number of stones to play in the byo-yomi period.
+///
+/// `stones` is 0 in the main time.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Clock {
+    pub time: Duration,
+    pub stones: u32,
+}
+
+/// Index of the clock of the player.
+fn clock_index(player: Player) -> usize {
+    match player {
+        Player::White => 1,
+        _ => 0,
+    }
+}
+
+/// Parse the colour, `black`, `b`, `white` or `w` case insensitively.
+fn parse_color(token: Option<&&str>) -> Result<Player, String> {
+    let token = token.ok_or_else(|| "missing colour".to_string())?;
+    match token.to_lowercase().as_str() {
+        "b" | "black" => Ok(Player::Black),
+        "w" | "white" => Ok(Player::White),
+        _ => Err(format!("invalid colour {}", token)),
+    }
+}
+
+/// Parse the argument of given index.
+fn parse_arg<T: FromStr>(args: &[&str], index: usize) -> Result<T, String> {
+    let token = args
+        .get(index)
+        .ok_or_else(|| "missing argument".to_string())?;
+    token
+        .parse()
+        .map_err(|_| format!("invalid argument {}", token))
+}
+
+/// Policy wrapped as the engine, speaking the line based text protocol.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{engine::Engine, game::Player, policy::RandomPolicy};
+/// let mut policy = RandomPolicy::new();
+/// let mut engine = Engine::new(&mut policy).with_board_size(9);
+///
+/// let response = engine.execute("play b E5").unwrap();
+/// assert_eq!(response.to_string(), "=\n\n");
+///
+/// let response = engine.execute("7 genmove white").unwrap();
+/// assert_eq!(response.id, Some(7));
+/// assert_eq!(response.result.unwrap().split_whitespace().count(), 2);
+/// assert_eq!(engine.game().get_turn(), Player::Black);
+/// ```
+pub struct Engine<'a> {
+    policy: &'a mut Policy,
+    game: Game,
+    name: String,
+    time: Option<TimeSettings>,
+    clocks: [Clock; 2],
+    quit: bool,
+}
+
+impl<'a> Engine<'a> {
+    /// Construct a new `Engine` with given policy, the empty board of `BOARD_SIZE`.
+    pub fn new(policy: &'a mut Policy) -> Engine<'a> {
+        Engine {
+            policy,
+            game: Game::new(),
+            name: "connect6".to_string(),
+            time: None,
+            clocks: [Clock::default(); 2],
+            quit: false,
+        }
+    }
+
+    /// Set the name of the engine, response of `name`.
+    pub fn with_name(mut self, name: &str) -> Engine<'a> {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the board size of the games.
+    ///
+    /// # Panics
+    /// If `size` is 0 or larger than `MAX_BOARD_SIZE`, as `boardsize` rejects.
+    pub fn with_board_size(mut self, size: usize) -> Engine<'a> {
+        assert!(
+            size > 0 && size <= MAX_BOARD_SIZE,
+            "unacceptable size {}",
+            size
+        );
+        self.game = Game::with_rule(size, self.game.get_rule());
+        self
+    }
+
+    /// Set the rule of the games.
+    pub fn with_rule(mut self, rule: Rule) -> Engine<'a> {
+        self.game = Game::with_rule(self.game.get_size(), rule);
+        self
+    }
+
+    /// Current game of the engine.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Time settings given by `time_settings`.
+    pub fn time_settings(&self) -> Option<TimeSettings> {
+        self.time
+    }
+
+    /// Remaining time of the player, `None` if there is no time settings.
+    ///
+    /// Clock is updated by `time_left`, and the elapsed time of `genmove` is taken from it.
+    pub fn time_left(&self, player: Player) -> Option<Clock> {
+        self.time.map(|_| self.clocks[clock_index(player)])
+    }
+
+    /// Return true if `quit` is executed.
+    pub fn is_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Execute the command line, `None` for the empty line and the comment starts with `#`.
+    pub fn execute(&mut self, line: &str) -> Option<Response> {
+        let line = line.split('#').next().unwrap_or("");
+        let mut tokens = line.split_whitespace().collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return None;
+        }
+        let id = tokens[0].parse::<u32>().ok();
+        if id.is_some() {
+            tokens.remove(0);
+        }
+
+        let result = match tokens.split_first() {
+            Some((command, args)) => self.dispatch(command, args),
+            None => Err("missing command".to_string()),
+        };
+        Some(Response { id, result })
+    }
+
+    /// Execute the commands read from the reader until `quit` or the end of the input,
+    /// write the responses to the writer.
+    pub fn run<R: BufRead, W: Write>(&mut self, reader: R, mut writer: W) -> io::Result<()> {
+        for line in reader.lines() {
+            if let Some(response) = self.execute(&line?) {
+                write!(writer, "{}", response)?;
+                writer.flush()?;
+            }
+            if self.quit {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute the command with the arguments.
+    fn dispatch(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "protocol_version" => Ok("2".to_string()),
+            "name" => Ok(self.name.clone()),
+            "version" => Ok(env!("CARGO_PKG_VERSION").to_string()),
+            "known_command" => {
+                let known = match args.first() {
+                    Some(command) => COMMANDS.contains(command),
+                    None => false,
+                };
+                Ok(known.to_string())
+            }
+            "list_commands" => Ok(COMMANDS.join("\n")),
+            "boardsize" => self.boardsize(args),
+            "clear_board" => {
+                self.game = Game::with_rule(self.game.get_size(), self.game.get_rule());
+                Ok(String::new())
+            }
+            "play" => self.play(args),
+            "genmove" => self.genmove(args),
+            "undo" => self
+                .game
+                .undo()
+                .map(|_| String::new())
+                .ok_or_else(|| "cannot undo".to_string()),
+            "time_settings" => self.time_settings_from(args),
+            "time_left" => self.time_left_from(args),
+            "showboard" => {
+                let mut buffer = Vec::new();
+                self.game.print(&mut buffer).map_err(|e| e.to_string())?;
+                let board = String::from_utf8_lossy(&buffer);
+                Ok(format!("\n{}", board.trim_end()))
+            }
+            "final_score" => Ok(format_result(self.game.status()).to_string()),
+            "quit" => {
+                self.quit = true;
+                Ok(String::new())
+            }
+            _ => Err(format!("unknown command {}", command)),
+        }
+    }
+
+    /// Change the board size and clear the board, columns are written as alphabets.
+    fn boardsize(&mut self, args: &[&str]) -> Result<String, String> {
+        let size = parse_arg::<usize>(args, 0)?;
+        if size == 0 || size > MAX_BOARD_SIZE {
+            return Err("unacceptable size".to_string());
+        }
+        self.game = Game::with_rule(size, self.game.get_rule());
+        Ok(String::new())
+    }
+
+    /// Play the stones of the colour, none of them is played if any of them is invalid.
+    fn play(&mut self, args: &[&str]) -> Result<String, String> {
+        let player = parse_color(args.first())?;
+        if args.len() < 2 {
+            return Err("missing position".to_string());
+        }
+
+        let mut num_set = 0;
+        let mut result = Ok(String::new());
+        for token in args[1..].iter() {
+            if self.game.status().is_end() {
+                result = Err("game is over".to_string());
+            } else if self.game.get_turn() != player {
+                result = Err(format!("not the turn of {:?}", player));
+            } else if let Some(pos) = parse_pos(token) {
+                match self.game.set(pos) {
+                    Ok(_) => num_set += 1,
+                    Err(err) => result = Err(format!("illegal move {}: {}", token, err)),
+                }
+            } else {
+                result = Err(format!("invalid position {}", token));
+            }
+            if result.is_err() {
+                break;
+            }
+        }
+        // rollback the stones of the failed command
+        if result.is_err() {
+            for _ in 0..num_set {
+                self.game.undo();
+            }
+        }
+        result
+    }
+
+    /// Generate the remaining stones of the turn.
+    fn genmove(&mut self, args: &[&str]) -> Result<String, String> {
+        let player = parse_color(args.first())?;
+        if self.game.status().is_end() {
+            return Err("game is over".to_string());
+        }
+        if self.game.get_turn() != player {
+            return Err(format!("not the turn of {:?}", player));
+        }
+
+        let mut stones = Vec::new();
+        while self.game.get_turn() == player && !self.game.status().is_end() {
+            let start = Instant::now();
+            let budget = self.budget(player);
+            if let Some(budget) = budget {
+                self.policy.set_deadline(Some(start + budget));
+            }
+            let pos = self.policy.next(&self.game);
+            if budget.is_some() {
+                self.policy.set_deadline(None);
+            }
+            self.spend(player, start.elapsed());
+
+            let pos = match pos {
+                Some(pos) => pos,
+                None if stones.is_empty() => return Ok("resign".to_string()),
+                None => break,
+            };
+            if let Err(err) = self.game.set(pos) {
+                for _ in 0..stones.len() {
+                    self.game.undo();
+                }
+                return Err(format!("policy made illegal move: {}", err));
+            }
+            stones.push(format_pos(pos));
+        }
+        Ok(stones.join(" "))
+    }
+
+    /// Set the time settings, clocks of both players are reset.
+    fn time_settings_from(&mut self, args: &[&str]) -> Result<String, String> {
+        let settings = TimeSettings {
+            main_time: Duration::from_secs(parse_arg(args, 0)?),
+            byo_yomi_time: Duration::from_secs(parse_arg(args, 1)?),
+            byo_yomi_stones: parse_arg(args, 2)?,
+        };
+        let clock = if settings.main_time == Duration::default() {
+            Clock {
+                time: settings.byo_yomi_time,
+                stones: settings.byo_yomi_stones,
+            }
+        } else {
+            Clock {
+                time: settings.main_time,
+                stones: 0,
+            }
+        };
+        self.time = Some(settings);
+        self.clocks = [clock; 2];
+        Ok(String::new())
+    }
+
+    /// Set the remaining time of the player.
+    fn time_left_from(&mut self, args: &[&str]) -> Result<String, String> {
+        let player = parse_color(args.first())?;
+        let clock = Clock {
+            time: Duration::from_secs(parse_arg(&args[1..], 0)?),
+            stones: parse_arg(&args[1..], 1)?,
+        };
+        self.clocks[clock_index(player)] = clock;
+        Ok(String::new())
+    }
+
+    /// Time budget of a stone of the player, `None` if there is no time settings.
+    fn budget(&self, player: Player) -> Option<Duration> {
+        self.time?;
+        let clock = self.clocks[clock_index(player)];
+        if clock.stones == 0 {
+            Some(clock.time / MAIN_TIME_STONES)
+        } else {
+            Some(clock.time / clock.stones)
+        }
+    }
+
+    /// Take the elapsed time of a stone from the clock of the player.
+    fn spend(&mut self, player: Player, elapsed: Duration) {
+        let settings = match self.time {
+            Some(settings) => settings,
+            None => return,
+        };
+        let clock = &mut self.clocks[clock_index(player)];
+        let overtime = elapsed.checked_sub(clock.time);
+        clock.time = clock.time.checked_sub(elapsed).unwrap_or_default();
+
+        if clock.stones == 0 {
+            // main time is over, enter the byo-yomi period
+            if let Some(overtime) = overtime {
+                clock.time = settings
+                    .byo_yomi_time
+                    .checked_sub(overtime)
+                    .unwrap_or_default();
+                clock.stones = settings.byo_yomi_stones;
+            }
+        } else {
+            clock.stones -= 1;
+            // next byo-yomi period
+            if clock.stones == 0 {
+                clock.time = settings.byo_yomi_time;
+                clock.stones = settings.byo_yomi_stones;
+            }
+        }
+    }
+}
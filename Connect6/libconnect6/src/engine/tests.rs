@@ -0,0 +1,329 @@
+use super::*;
+use game::GameStatus;
+use policy::RandomPolicy;
+use BOARD_SIZE;
+
+/// Policy filling the first row.
+struct RowPolicy;
+
+impl Policy for RowPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        let board = game.get_board();
+        (0..board.size())
+            .map(|col| (0, col))
+            .find(|&(row, col)| board[row][col] == Player::None)
+    }
+}
+
+/// Policy giving up every turn.
+struct GiveUpPolicy;
+
+impl Policy for GiveUpPolicy {
+    fn next(&mut self, _: &Game) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+/// Policy selecting the occupied position.
+struct IllegalPolicy;
+
+impl Policy for IllegalPolicy {
+    fn next(&mut self, _: &Game) -> Option<(usize, usize)> {
+        Some((7, 7))
+    }
+}
+
+/// Policy recording the deadlines of the selections.
+struct DeadlinePolicy {
+    deadline: Option<Instant>,
+    budgets: Vec<Option<Duration>>,
+}
+
+impl Policy for DeadlinePolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        let now = Instant::now();
+        let budget = self.deadline.map(|x| x.saturating_duration_since(now));
+        self.budgets.push(budget);
+        RowPolicy.next(game)
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+}
+
+fn result(engine: &mut Engine, line: &str) -> Result<String, String> {
+    engine.execute(line).unwrap().result
+}
+
+#[test]
+fn test_response() {
+    let response = Response {
+        id: Some(3),
+        result: Ok("H8".to_string()),
+    };
+    assert_eq!(response.to_string(), "=3 H8\n\n");
+
+    let response = Response {
+        id: None,
+        result: Err("unknown command".to_string()),
+    };
+    assert_eq!(response.to_string(), "? unknown command\n\n");
+
+    let response = Response {
+        id: None,
+        result: Ok(String::new()),
+    };
+    assert_eq!(response.to_string(), "=\n\n");
+}
+
+#[test]
+fn test_execute() {
+    let mut policy = RandomPolicy::new();
+    let mut engine = Engine::new(&mut policy).with_name("random");
+
+    assert_eq!(engine.execute(""), None);
+    assert_eq!(engine.execute("  # comment"), None);
+
+    let response = engine.execute("12 name # comment").unwrap();
+    assert_eq!(response.id, Some(12));
+    assert_eq!(response.result, Ok("random".to_string()));
+
+    assert_eq!(result(&mut engine, "protocol_version"), Ok("2".to_string()));
+    assert_eq!(
+        result(&mut engine, "known_command genmove"),
+        Ok("true".to_string())
+    );
+    assert_eq!(
+        result(&mut engine, "known_command fly"),
+        Ok("false".to_string())
+    );
+    assert_eq!(
+        result(&mut engine, "list_commands")
+            .unwrap()
+            .lines()
+            .count(),
+        COMMANDS.len()
+    );
+    assert!(result(&mut engine, "fly").is_err());
+    assert!(result(&mut engine, "5").is_err());
+}
+
+#[test]
+fn test_boardsize() {
+    let mut policy = RandomPolicy::new();
+    let mut engine = Engine::new(&mut policy);
+    assert_eq!(engine.game().get_size(), BOARD_SIZE);
+
+    assert!(result(&mut engine, "play black H8").is_ok());
+    assert!(result(&mut engine, "boardsize 9").is_ok());
+    assert_eq!(engine.game().get_size(), 9);
+    assert_eq!(engine.game().ply(), 0);
+
+    assert!(result(&mut engine, "boardsize 0").is_err());
+    assert!(result(&mut engine, "boardsize 27").is_err());
+    assert!(result(&mut engine, "boardsize nine").is_err());
+    assert_eq!(engine.game().get_size(), 9);
+
+    assert!(result(&mut engine, "play black E5").is_ok());
+    assert!(result(&mut engine, "clear_board").is_ok());
+    assert_eq!(engine.game().ply(), 0);
+    assert_eq!(engine.game().get_size(), 9);
+}
+
+#[test]
+#[should_panic]
+fn test_with_board_size() {
+    let mut policy = RandomPolicy::new();
+    Engine::new(&mut policy).with_board_size(27);
+}
+
+#[test]
+fn test_play() {
+    let mut policy = RandomPolicy::new();
+    let mut engine = Engine::new(&mut policy);
+
+    assert!(result(&mut engine, "play white H8").is_err());
+    assert!(result(&mut engine, "play black").is_err());
+    assert!(result(&mut engine, "play red H8").is_err());
+    assert!(result(&mut engine, "play b H8").is_ok());
+    assert!(result(&mut engine, "play W G7 g9").is_ok());
+    assert_eq!(engine.game().ply(), 3);
+    assert_eq!(engine.game().get_board()[8][6], Player::White);
+
+    // none of the stones is played if any of them is invalid
+    assert!(result(&mut engine, "play black A1 H8").is_err());
+    assert!(result(&mut engine, "play black A1 8H").is_err());
+    assert!(result(&mut engine, "play black A1 B1 C1").is_err());
+    assert!(result(&mut engine, "play black A1 Z1").is_err());
+    assert_eq!(engine.game().ply(), 3);
+    assert_eq!(engine.game().get_board()[0][0], Player::None);
+
+    assert!(result(&mut engine, "undo").is_ok());
+    assert_eq!(engine.game().ply(), 2);
+    assert_eq!(engine.game().get_turn(), Player::White);
+    assert!(result(&mut engine, "undo").is_ok());
+    assert!(result(&mut engine, "undo").is_ok());
+    assert!(result(&mut engine, "undo").is_err());
+}
+
+#[test]
+fn test_genmove() {
+    let mut policy = RowPolicy;
+    let mut engine = Engine::new(&mut policy).with_board_size(7);
+
+    assert!(result(&mut engine, "genmove white").is_err());
+    assert_eq!(result(&mut engine, "genmove black"), Ok("A1".to_string()));
+    assert_eq!(
+        result(&mut engine, "genmove white"),
+        Ok("B1 C1".to_string())
+    );
+    assert_eq!(engine.game().get_turn(), Player::Black);
+
+    assert!(result(&mut engine, "clear_board").is_ok());
+    for line in [
+        "play black D4",
+        "play white A1 B1",
+        "play black D5 D6",
+        "play white C1 D1",
+        "play black E5 E6",
+        "play white E1 G7",
+        "play black F5 F6",
+    ]
+    .iter()
+    {
+        assert!(result(&mut engine, line).is_ok());
+    }
+    // winning stone ends the turn
+    assert_eq!(result(&mut engine, "genmove white"), Ok("F1".to_string()));
+    assert_eq!(engine.game().status(), GameStatus::Win(Player::White));
+    assert_eq!(result(&mut engine, "final_score"), Ok("W+".to_string()));
+    assert!(result(&mut engine, "genmove black").is_err());
+    assert!(result(&mut engine, "play black E2").is_err());
+}
+
+#[test]
+fn test_genmove_give_up() {
+    let mut policy = GiveUpPolicy;
+    let mut engine = Engine::new(&mut policy);
+    assert_eq!(
+        result(&mut engine, "genmove black"),
+        Ok("resign".to_string())
+    );
+    assert_eq!(engine.game().ply(), 0);
+
+    let mut policy = IllegalPolicy;
+    let mut engine = Engine::new(&mut policy);
+    assert_eq!(result(&mut engine, "genmove black"), Ok("H8".to_string()));
+    assert!(result(&mut engine, "genmove white").is_err());
+    assert_eq!(engine.game().ply(), 1);
+}
+
+#[test]
+fn test_time() {
+    let mut policy = RandomPolicy::new();
+    let mut engine = Engine::new(&mut policy);
+    assert_eq!(engine.time_left(Player::Black), None);
+
+    assert!(result(&mut engine, "time_settings 300 30 5").is_ok());
+    let settings = engine.time_settings().unwrap();
+    assert_eq!(settings.main_time, Duration::from_secs(300));
+    assert_eq!(settings.byo_yomi_time, Duration::from_secs(30));
+    assert_eq!(settings.byo_yomi_stones, 5);
+
+    let clock = engine.time_left(Player::White).unwrap();
+    assert_eq!(clock.time, Duration::from_secs(300));
+    assert_eq!(clock.stones, 0);
+
+    assert!(result(&mut engine, "time_left white 20 3").is_ok());
+    let clock = engine.time_left(Player::White).unwrap();
+    assert_eq!(clock.time, Duration::from_secs(20));
+    assert_eq!(clock.stones, 3);
+
+    assert!(result(&mut engine, "time_left white 20").is_err());
+    assert!(result(&mut engine, "time_settings 300 30").is_err());
+
+    // byo-yomi only
+    assert!(result(&mut engine, "time_settings 0 10 2").is_ok());
+    assert!(result(&mut engine, "genmove black").is_ok());
+    let clock = engine.time_left(Player::Black).unwrap();
+    assert_eq!(clock.stones, 1);
+    assert!(clock.time <= Duration::from_secs(10));
+}
+
+#[test]
+fn test_spend() {
+    let mut policy = RandomPolicy::new();
+    let mut engine = Engine::new(&mut policy);
+    result(&mut engine, "time_settings 10 5 2").unwrap();
+
+    engine.spend(Player::Black, Duration::from_secs(4));
+    let clock = engine.time_left(Player::Black).unwrap();
+    assert_eq!(clock.time, Duration::from_secs(6));
+    assert_eq!(clock.stones, 0);
+
+    // main time is over, enter the byo-yomi period
+    engine.spend(Player::Black, Duration::from_secs(7));
+    let clock = engine.time_left(Player::Black).unwrap();
+    assert_eq!(clock.time, Duration::from_secs(4));
+    assert_eq!(clock.stones, 2);
+
+    engine.spend(Player::Black, Duration::from_secs(1));
+    engine.spend(Player::Black, Duration::from_secs(1));
+    let clock = engine.time_left(Player::Black).unwrap();
+    assert_eq!(clock.time, Duration::from_secs(5));
+    assert_eq!(clock.stones, 2);
+
+    let clock = engine.time_left(Player::White).unwrap();
+    assert_eq!(clock.time, Duration::from_secs(10));
+}
+
+#[test]
+fn test_deadline() {
+    let mut policy = DeadlinePolicy {
+        deadline: None,
+        budgets: Vec::new(),
+    };
+    {
+        let mut engine = Engine::new(&mut policy);
+        result(&mut engine, "genmove black").unwrap();
+        result(&mut engine, "time_settings 300 30 5").unwrap();
+        result(&mut engine, "genmove white").unwrap();
+        result(&mut engine, "time_left black 20 4").unwrap();
+        result(&mut engine, "genmove black").unwrap();
+    }
+    assert_eq!(policy.deadline, None);
+
+    let budgets = policy.budgets;
+    assert_eq!(budgets.len(), 5);
+    assert_eq!(budgets[0], None);
+    // main time over the expected stones
+    let main = Duration::from_secs(300) / MAIN_TIME_STONES;
+    assert!(budgets[1].unwrap() <= main);
+    assert!(budgets[1].unwrap() > main / 2);
+    // byo-yomi time over its stones
+    let byo_yomi = Duration::from_secs(5);
+    assert!(budgets[3].unwrap() <= byo_yomi);
+    assert!(budgets[3].unwrap() > byo_yomi / 2);
+}
+
+#[test]
+fn test_run() {
+    let mut policy = RowPolicy;
+    let mut engine = Engine::new(&mut policy).with_board_size(7);
+
+    let input = "1 genmove b\n\n2 play w A2 B2\n3 showboard\n4 quit\n5 genmove b\n";
+    let mut output = Vec::new();
+    engine.run(input.as_bytes(), &mut output).unwrap();
+    assert!(engine.is_quit());
+    assert_eq!(engine.game().ply(), 3);
+
+    let output = String::from_utf8(output).unwrap();
+    let responses = output.split("\n\n").collect::<Vec<_>>();
+    assert_eq!(responses[0], "=1 A1");
+    assert_eq!(responses[1], "=2");
+    assert!(responses[2].starts_with("=3 \n0 A B C D E F G\na X _ _"));
+    assert!(responses[2].contains("\nb O O _"));
+    assert_eq!(responses[3], "=4");
+    assert_eq!(responses[4], "");
+}
@@ -0,0 +1,27 @@
+use super::*;
+
+#[test]
+fn test_round_trip() {
+    let mut bytes = Vec::new();
+    write_u16(&mut bytes, 0x0102).unwrap();
+    write_u32(&mut bytes, 0x0304_0506).unwrap();
+    write_u64(&mut bytes, 0x0708_090a_0b0c_0d0e).unwrap();
+    write_f32(&mut bytes, -1.5).unwrap();
+    assert_eq!(&bytes[..6], &[2, 1, 6, 5, 4, 3]);
+
+    let mut reader = bytes.as_slice();
+    assert_eq!(read_u16(&mut reader).unwrap(), 0x0102);
+    assert_eq!(read_u32(&mut reader).unwrap(), 0x0304_0506);
+    assert_eq!(read_u64(&mut reader).unwrap(), 0x0708_090a_0b0c_0d0e);
+    assert_eq!(read_f32(&mut reader).unwrap(), -1.5);
+
+    let err = read_u32(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_invalid_data() {
+    let err = invalid_data("shard", "missing index");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "invalid shard: missing index");
+}
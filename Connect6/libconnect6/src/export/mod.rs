@@ -0,0 +1,40 @@
+//! Export of the self-play data for training in any framework.
+//!
+//! Positions of `PlayResult`s are converted to the feature tensors, policy and value targets as `TrainingBatch`,
+//! and written as NumPy `.npy` files, `.npz` archive or the chunked binary shard with the index.
+//!
+//! Features are `NUM_PLANES` planes from the perspective of the player on turn, reference [features](fn.features.html).
+//! Policy target is the visit distribution of the search or the selected position,
+//! and value target is the result of the game, { 1: win, 0: draw, -1: loss }, same as `replay::Sample`.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{agent::Agent, export::{ShardReader, ShardWriter, TrainingBatch}, policy::RandomPolicy};
+//! let mut policy = RandomPolicy::new();
+//! let results = vec![Agent::new(&mut policy).with_board_size(7).play().unwrap()];
+//!
+//! // numpy.load(path) gives `features`, `policies` and `values`
+//! let batch = TrainingBatch::from_results(7, &results);
+//! let npz = batch.write_npz(Vec::new(), true).unwrap();
+//!
+//! // chunked shard, could be read chunk by chunk
+//! let mut writer = ShardWriter::new(Vec::new(), 7).with_chunk_size(16).with_compression(true);
+//! for result in results.iter() {
+//!     writer.push_result(result).unwrap();
+//! }
+//! let shard = writer.finish().unwrap();
+//!
+//! let mut reader = ShardReader::new(std::io::Cursor::new(shard)).unwrap();
+//! assert_eq!(reader.num_samples(), batch.len());
+//! let chunk = reader.read_batch(0).unwrap();
+//! assert_eq!(chunk.len(), 16.min(batch.len()));
+//! ```
+pub use self::npy::*;
+pub use self::shard::*;
+pub use self::tensor::*;
+
+pub(crate) mod io;
+mod npy;
+mod shard;
+mod tensor;
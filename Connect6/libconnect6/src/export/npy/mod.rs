@@ -0,0 +1,326 @@
+//! NumPy `.npy` arrays and `.npz` archives.
+//!
+//! Arrays are written in npy format version 1.0, C order and little-endian,
+//! and npz archive is a zip of npy files, stored or deflated.
+//! Zip64 isn't supported, so each archive is limited to 4GB.
+use export::io::{invalid_data, read_u16, read_u32};
+
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+
+use std::io::{self, Read, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// Magic string of npy format.
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Element type of the npy array.
+pub trait NpyElement: Copy + Default {
+    /// numpy dtype descriptor
+    const DESCR: &'static str;
+
+    /// Append the little-endian bytes of the element.
+    fn extend_le(&self, bytes: &mut Vec<u8>);
+
+    /// Read the element from the little-endian bytes.
+    fn read_le<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_npy_element {
+    ($t:ty, $descr:expr, $size:expr) => {
+        impl NpyElement for $t {
+            const DESCR: &'static str = $descr;
+
+            fn extend_le(&self, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le<R: Read>(reader: &mut R) -> io::Result<Self> {
+                let mut bytes = [0; $size];
+                reader.read_exact(&mut bytes)?;
+                Ok(<$t>::from_le_bytes(bytes))
+            }
+        }
+    };
+}
+
+impl_npy_element!(f32, "<f4", 4);
+impl_npy_element!(i32, "<i4", 4);
+impl_npy_element!(i8, "|i1", 1);
+impl_npy_element!(u8, "|u1", 1);
+
+fn invalid_input(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+/// Name of the format in the error messages.
+const FORMAT: &str = "npy";
+
+/// Serialize the array in npy format.
+fn npy_bytes<T: NpyElement>(shape: &[usize], data: &[T]) -> io::Result<Vec<u8>> {
+    if shape.iter().product::<usize>() != data.len() {
+        return Err(invalid_input("shape doesn't match the length of the data"));
+    }
+
+    let shape = match shape.len() {
+        1 => format!("({},)", shape[0]),
+        _ => {
+            let dims = shape.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+            format!("({})", dims.join(", "))
+        }
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        T::DESCR,
+        shape
+    );
+    // magic, version and header length, total length of the header is aligned to 64 bytes
+    let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+    let padding = (64 - unpadded % 64) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(unpadded + padding + data.len() * 4);
+    bytes.extend_from_slice(NPY_MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for x in data {
+        x.extend_le(&mut bytes);
+    }
+    Ok(bytes)
+}
+
+/// Write the array of the shape in npy format.
+///
+/// # Errors
+/// - if writer raises the error.
+/// - if the product of `shape` doesn't match the length of `data`, as `io::ErrorKind::InvalidInput`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::export::{read_npy, write_npy};
+/// let mut bytes = Vec::new();
+/// write_npy(&mut bytes, &[2, 3], &[0i32, 1, 2, 3, 4, 5]).unwrap();
+///
+/// let (shape, data) = read_npy::<i32, _>(&mut bytes.as_slice()).unwrap();
+/// assert_eq!(shape, vec![2, 3]);
+/// assert_eq!(data, vec![0, 1, 2, 3, 4, 5]);
+/// ```
+pub fn write_npy<W: Write, T: NpyElement>(
+    writer: &mut W,
+    shape: &[usize],
+    data: &[T],
+) -> io::Result<()> {
+    writer.write_all(&npy_bytes(shape, data)?)
+}
+
+/// Read the array in npy format, return its shape and the row-major ordered data.
+///
+/// # Errors
+/// - if reader raises the error.
+/// - if the data isn't C ordered npy array of the element type, as `io::ErrorKind::InvalidData`.
+pub fn read_npy<T: NpyElement, R: Read>(reader: &mut R) -> io::Result<(Vec<usize>, Vec<T>)> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[..6] != NPY_MAGIC {
+        return Err(invalid_data(FORMAT, "invalid magic string"));
+    }
+    let header_len = match magic[6] {
+        1 => read_u16(reader)? as usize,
+        2 | 3 => read_u32(reader)? as usize,
+        _ => return Err(invalid_data(FORMAT, "unsupported version")),
+    };
+
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header =
+        String::from_utf8(header).map_err(|_| invalid_data(FORMAT, "header isn't utf-8"))?;
+
+    if !header.contains(&format!("'descr': '{}'", T::DESCR)) {
+        return Err(invalid_data(FORMAT, "unexpected dtype"));
+    }
+    if !header.contains("'fortran_order': False") {
+        return Err(invalid_data(FORMAT, "fortran order isn't supported"));
+    }
+    let shape = header
+        .find("'shape':")
+        .and_then(|start| {
+            let rest = &header[start..];
+            let open = rest.find('(')?;
+            let close = rest.find(')')?;
+            rest[open + 1..close]
+                .split(',')
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+                .map(|x| x.parse::<usize>().ok())
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| invalid_data(FORMAT, "invalid shape"))?;
+
+    let len = shape
+        .iter()
+        .try_fold(1usize, |len, x| len.checked_mul(*x))
+        .ok_or_else(|| invalid_data(FORMAT, "shape overflows"))?;
+    // length is not trusted for the allocation, truncated data ends with UnexpectedEof
+    let mut data = Vec::new();
+    for _ in 0..len {
+        data.push(T::read_le(reader)?);
+    }
+    Ok((shape, data))
+}
+
+/// Entry of the central directory of zip.
+struct ZipEntry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Writer of npz archive, loaded by `numpy.load`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::export::NpzWriter;
+/// let mut npz = NpzWriter::new(Vec::new()).with_compression(true);
+/// npz.add_array("values", &[3], &[1f32, 0., -1.]).unwrap();
+/// npz.add_array("turns", &[3], &[-1i32, 1, 1]).unwrap();
+///
+/// let bytes = npz.finish().unwrap();
+/// assert_eq!(&bytes[..4], b"PK\x03\x04");
+/// ```
+pub struct NpzWriter<W: Write> {
+    writer: W,
+    compress: bool,
+    offset: u64,
+    entries: Vec<ZipEntry>,
+}
+
+impl<W: Write> NpzWriter<W> {
+    /// Construct a new `NpzWriter` storing the arrays without compression.
+    pub fn new(writer: W) -> NpzWriter<W> {
+        NpzWriter {
+            writer,
+            compress: false,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Deflate the arrays if `compress`, same as `numpy.savez_compressed`.
+    pub fn with_compression(mut self, compress: bool) -> NpzWriter<W> {
+        self.compress = compress;
+        self
+    }
+
+    /// Add the array with the name, `numpy.load` gives it as `npz[name]`.
+    ///
+    /// # Errors
+    /// - if writer raises the error.
+    /// - if the shape doesn't match the data or the archive exceeds 4GB, as `io::ErrorKind::InvalidInput`.
+    pub fn add_array<T: NpyElement>(
+        &mut self,
+        name: &str,
+        shape: &[usize],
+        data: &[T],
+    ) -> io::Result<()> {
+        let bytes = npy_bytes(shape, data)?;
+        let size = bytes.len();
+        let mut crc = Crc::new();
+        crc.update(&bytes);
+
+        let (method, payload) = if self.compress {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&bytes)?;
+            (8, encoder.finish()?)
+        } else {
+            (0, bytes)
+        };
+
+        let too_large = || invalid_input("npz archive exceeds 4GB");
+        let entry = ZipEntry {
+            name: format!("{}.npy", name),
+            method,
+            crc: crc.sum(),
+            compressed: to_u32(payload.len() as u64).ok_or_else(too_large)?,
+            size: to_u32(size as u64).ok_or_else(too_large)?,
+            offset: to_u32(self.offset).ok_or_else(too_large)?,
+        };
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        write_entry_info(&mut header, &entry);
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        header.extend_from_slice(entry.name.as_bytes());
+
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&payload)?;
+        self.offset += (header.len() + payload.len()) as u64;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Write the central directory and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut directory = Vec::new();
+        for entry in self.entries.iter() {
+            directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes()); // version made by
+            write_entry_info(&mut directory, entry);
+            directory.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+            directory.extend_from_slice(&0u16.to_le_bytes()); // comment length
+            directory.extend_from_slice(&0u16.to_le_bytes()); // disk number
+            directory.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+            directory.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+            directory.extend_from_slice(&entry.offset.to_le_bytes());
+            directory.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let too_large = || invalid_input("npz archive exceeds 4GB");
+        let offset = to_u32(self.offset).ok_or_else(too_large)?;
+        let num_entries = self.entries.len() as u16;
+
+        let mut end = Vec::new();
+        end.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        end.extend_from_slice(&0u16.to_le_bytes()); // disk of the directory
+        end.extend_from_slice(&num_entries.to_le_bytes());
+        end.extend_from_slice(&num_entries.to_le_bytes());
+        end.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        end.extend_from_slice(&offset.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // comment length
+
+        self.writer.write_all(&directory)?;
+        self.writer.write_all(&end)?;
+        Ok(self.writer)
+    }
+}
+
+fn to_u32(value: u64) -> Option<u32> {
+    if value > u64::from(u32::MAX) {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
+
+/// Write the fields shared by the local header and the central directory,
+/// from the version needed to extract to the file name length.
+fn write_entry_info(bytes: &mut Vec<u8>, entry: &ZipEntry) {
+    bytes.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+    bytes.extend_from_slice(&0u16.to_le_bytes()); // flags
+    bytes.extend_from_slice(&entry.method.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes()); // modification time
+    bytes.extend_from_slice(&0x21u16.to_le_bytes()); // modification date, 1980-01-01
+    bytes.extend_from_slice(&entry.crc.to_le_bytes());
+    bytes.extend_from_slice(&entry.compressed.to_le_bytes());
+    bytes.extend_from_slice(&entry.size.to_le_bytes());
+    bytes.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+}
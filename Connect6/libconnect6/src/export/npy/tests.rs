@@ -0,0 +1,126 @@
+use super::*;
+
+#[test]
+fn test_npy_header() {
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &[2, 2], &[1f32, 2., 3., 4.]).unwrap();
+
+    assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    assert_eq!((10 + header_len) % 64, 0);
+    assert_eq!(bytes.len(), 10 + header_len + 16);
+
+    let header = String::from_utf8(bytes[10..10 + header_len].to_vec()).unwrap();
+    assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }"));
+    assert!(header.ends_with('\n'));
+}
+
+#[test]
+fn test_npy_roundtrip() {
+    let data = (0..24).map(|x| x as f32 * 0.5).collect::<Vec<_>>();
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &[2, 3, 4], &data).unwrap();
+    let (shape, recovered) = read_npy::<f32, _>(&mut bytes.as_slice()).unwrap();
+    assert_eq!(shape, vec![2, 3, 4]);
+    assert_eq!(recovered, data);
+
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &[3], &[-1i8, 0, 1]).unwrap();
+    let (shape, recovered) = read_npy::<i8, _>(&mut bytes.as_slice()).unwrap();
+    assert_eq!(shape, vec![3]);
+    assert_eq!(recovered, vec![-1, 0, 1]);
+}
+
+#[test]
+fn test_npy_invalid() {
+    let mut bytes = Vec::new();
+    let err = write_npy(&mut bytes, &[2, 2], &[1f32, 2., 3.])
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    write_npy(&mut bytes, &[3], &[1f32, 2., 3.]).unwrap();
+    let err = read_npy::<i32, _>(&mut bytes.as_slice()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let header = format!(
+        "{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, 2), }}\n",
+        usize::MAX
+    );
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    let err = read_npy::<f32, _>(&mut bytes.as_slice()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // header claims more data than the file has
+    let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (1000000000, ), }\n";
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(&1f32.to_le_bytes());
+    let err = read_npy::<f32, _>(&mut bytes.as_slice()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+/// Read the entries of the zip archive, (name, method, data)
+fn read_zip(bytes: &[u8]) -> Vec<(String, u16, Vec<u8>)> {
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+    let u32_at =
+        |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+
+    let end = bytes.len() - 22;
+    assert_eq!(u32_at(end), 0x0605_4b50);
+    let num_entries = u16_at(end + 10) as usize;
+    let mut pos = u32_at(end + 16) as usize;
+
+    let mut entries = Vec::new();
+    for _ in 0..num_entries {
+        assert_eq!(u32_at(pos), 0x0201_4b50);
+        let method = u16_at(pos + 10);
+        let crc = u32_at(pos + 16);
+        let compressed = u32_at(pos + 20) as usize;
+        let name_len = u16_at(pos + 28) as usize;
+        let offset = u32_at(pos + 42) as usize;
+        let name = String::from_utf8(bytes[pos + 46..pos + 46 + name_len].to_vec()).unwrap();
+        pos += 46 + name_len;
+
+        assert_eq!(u32_at(offset), 0x0403_4b50);
+        let start = offset + 30 + u16_at(offset + 26) as usize;
+        let payload = &bytes[start..start + compressed];
+        let data = match method {
+            0 => payload.to_vec(),
+            _ => {
+                let mut decoder = ::flate2::read::DeflateDecoder::new(payload);
+                let mut data = Vec::new();
+                decoder.read_to_end(&mut data).unwrap();
+                data
+            }
+        };
+        let mut hasher = Crc::new();
+        hasher.update(&data);
+        assert_eq!(hasher.sum(), crc);
+        entries.push((name, method, data));
+    }
+    entries
+}
+
+#[test]
+fn test_npz() {
+    for compress in [false, true].iter() {
+        let mut npz = NpzWriter::new(Vec::new()).with_compression(*compress);
+        npz.add_array("values", &[4], &[0f32; 4]).unwrap();
+        npz.add_array("turns", &[2, 2], &[-1i32, 1, 1, -1]).unwrap();
+        let bytes = npz.finish().unwrap();
+
+        let entries = read_zip(&bytes);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "values.npy");
+        assert_eq!(entries[1].0, "turns.npy");
+        assert_eq!(entries[0].1, if *compress { 8 } else { 0 });
+
+        let (shape, turns) = read_npy::<i32, _>(&mut entries[1].2.as_slice()).unwrap();
+        assert_eq!(shape, vec![2, 2]);
+        assert_eq!(turns, vec![-1, 1, 1, -1]);
+    }
+}
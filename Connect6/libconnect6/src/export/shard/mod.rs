@@ -0,0 +1,437 @@
+//! Chunked binary shard of the training samples with the index.
+//!
+//! Samples are grouped into chunks, optionally deflated, and the index of the chunks is written at the end,
+//! so the reader could seek to any chunk without scanning the file. All numbers are little-endian.
+//!
+//! ```text
+//! header : magic b"C6SD" | version u32 | board_size u32 | flags u32 (bit 0: deflated chunks)
+//! chunks : records of the samples, deflated if flagged
+//! index  : offset u64 | length u64 | num_samples u32, for each chunk
+//! footer : index offset u64 | num_chunks u32 | num_samples u64 | magic b"C6SI"
+//!
+//! record : turn i8 | row u16 | col u16 | value f32
+//!          | cells i8 x board_size^2, { -1: Black, 0: None, 1: White }
+//!          | policy f32 x board_size^2
+//! ```
+use agent::PlayResult;
+use export::io::{
+    invalid_data, read_f32, read_u16, read_u32, read_u64, write_f32, write_u16, write_u32,
+    write_u64,
+};
+use export::TrainingBatch;
+use game::Player;
+use replay::{samples, Sample};
+use {Board, GenericBoard};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Magic number of the shard header.
+const MAGIC: &[u8; 4] = b"C6SD";
+/// Magic number of the shard footer.
+const INDEX_MAGIC: &[u8; 4] = b"C6SI";
+/// Version of the shard format.
+const VERSION: u32 = 1;
+/// Flag of the deflated chunks.
+const FLAG_DEFLATE: u32 = 1;
+/// Length of the header in bytes.
+const HEADER_LEN: u64 = 16;
+/// Length of the footer in bytes.
+const FOOTER_LEN: u64 = 24;
+/// Length of the index entry in bytes.
+const INDEX_ENTRY_LEN: u64 = 20;
+/// Name of the format in the error messages.
+const FORMAT: &str = "shard";
+
+/// Index of the chunk.
+#[derive(Clone, Debug, PartialEq)]
+struct ChunkIndex {
+    offset: u64,
+    length: u64,
+    num_samples: u32,
+}
+
+/// Writer of the shard.
+///
+/// Header is written with the first chunk, and the index with `finish`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::Agent, export::ShardWriter, policy::RandomPolicy};
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+///
+/// let path = std::env::temp_dir().join("connect6_shard_doctest.bin");
+/// let mut writer = ShardWriter::create(&path, 7).unwrap().with_compression(true);
+/// writer.push_result(&result).unwrap();
+/// writer.finish().unwrap();
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct ShardWriter<W: Write> {
+    writer: W,
+    board_size: usize,
+    chunk_size: usize,
+    compress: bool,
+    chunk: Vec<u8>,
+    chunk_len: usize,
+    offset: u64,
+    index: Vec<ChunkIndex>,
+    num_samples: u64,
+}
+
+impl<W: Write> ShardWriter<W> {
+    /// Construct a new `ShardWriter` of 1024 samples per chunk without compression.
+    pub fn new(writer: W, board_size: usize) -> ShardWriter<W> {
+        ShardWriter {
+            writer,
+            board_size,
+            chunk_size: 1024,
+            compress: false,
+            chunk: Vec::new(),
+            chunk_len: 0,
+            offset: 0,
+            index: Vec::new(),
+            num_samples: 0,
+        }
+    }
+
+    /// Set the number of the samples per chunk.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> ShardWriter<W> {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Deflate the chunks if `compress`.
+    pub fn with_compression(mut self, compress: bool) -> ShardWriter<W> {
+        self.compress = compress;
+        self
+    }
+
+    /// Number of the pushed samples.
+    pub fn num_samples(&self) -> u64 {
+        self.num_samples
+    }
+
+    /// Push the sample, the chunk is flushed if it is full.
+    ///
+    /// # Errors
+    /// - if writer raises the error.
+    /// - if the board size of the sample differs, as `io::ErrorKind::InvalidInput`.
+    pub fn push(&mut self, sample: &Sample) -> io::Result<()> {
+        if sample.board.size() != self.board_size {
+            let msg = "board size of the sample differs from the shard";
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+
+        let chunk = &mut self.chunk;
+        chunk.push(sample.turn as i8 as u8);
+        write_u16(chunk, sample.pos.0 as u16)?;
+        write_u16(chunk, sample.pos.1 as u16)?;
+        write_f32(chunk, sample.value)?;
+        chunk.extend(sample.board.as_slice().iter().map(|x| *x as i8 as u8));
+        for prob in sample.policy.as_slice() {
+            write_f32(chunk, *prob)?;
+        }
+
+        self.chunk_len += 1;
+        self.num_samples += 1;
+        if self.chunk_len >= self.chunk_size {
+            self.flush_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Push the positions of the game, aborted game without the result is skipped.
+    pub fn push_result(&mut self, result: &PlayResult) -> io::Result<()> {
+        for sample in samples(result) {
+            self.push(&sample)?;
+        }
+        Ok(())
+    }
+
+    /// Write the header if nothing is written.
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.offset > 0 {
+            return Ok(());
+        }
+        let flags = if self.compress { FLAG_DEFLATE } else { 0 };
+        self.writer.write_all(MAGIC)?;
+        write_u32(&mut self.writer, VERSION)?;
+        write_u32(&mut self.writer, self.board_size as u32)?;
+        write_u32(&mut self.writer, flags)?;
+        self.offset = HEADER_LEN;
+        Ok(())
+    }
+
+    /// Write the buffered samples as a chunk.
+    fn flush_chunk(&mut self) -> io::Result<()> {
+        self.write_header()?;
+        if self.chunk_len == 0 {
+            return Ok(());
+        }
+
+        let chunk = ::std::mem::take(&mut self.chunk);
+        let payload = if self.compress {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&chunk)?;
+            encoder.finish()?
+        } else {
+            chunk
+        };
+        self.writer.write_all(&payload)?;
+
+        self.index.push(ChunkIndex {
+            offset: self.offset,
+            length: payload.len() as u64,
+            num_samples: self.chunk_len as u32,
+        });
+        self.offset += payload.len() as u64;
+        self.chunk_len = 0;
+        Ok(())
+    }
+
+    /// Flush the last chunk, write the index and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_chunk()?;
+
+        let index_offset = self.offset;
+        for chunk in self.index.iter() {
+            write_u64(&mut self.writer, chunk.offset)?;
+            write_u64(&mut self.writer, chunk.length)?;
+            write_u32(&mut self.writer, chunk.num_samples)?;
+        }
+        write_u64(&mut self.writer, index_offset)?;
+        write_u32(&mut self.writer, self.index.len() as u32)?;
+        write_u64(&mut self.writer, self.num_samples)?;
+        self.writer.write_all(INDEX_MAGIC)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl ShardWriter<BufWriter<File>> {
+    /// Create the shard file.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        board_size: usize,
+    ) -> io::Result<ShardWriter<BufWriter<File>>> {
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(ShardWriter::new(writer, board_size))
+    }
+}
+
+/// Reader of the shard, loading the chunks on demand.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::Agent, export::{ShardReader, ShardWriter}, policy::RandomPolicy};
+/// # use std::io::Cursor;
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+///
+/// let mut writer = ShardWriter::new(Vec::new(), 7).with_chunk_size(4);
+/// writer.push_result(&result).unwrap();
+/// let shard = writer.finish().unwrap();
+///
+/// let mut reader = ShardReader::new(Cursor::new(shard)).unwrap();
+/// assert_eq!(reader.board_size(), 7);
+/// assert_eq!(reader.num_samples(), result.path.len());
+///
+/// let mut num_samples = 0;
+/// for i in 0..reader.num_chunks() {
+///     num_samples += reader.read_chunk(i).unwrap().len();
+/// }
+/// assert_eq!(num_samples, result.path.len());
+/// ```
+pub struct ShardReader<R: Read + Seek> {
+    reader: R,
+    board_size: usize,
+    compress: bool,
+    index: Vec<ChunkIndex>,
+    num_samples: usize,
+}
+
+impl<R: Read + Seek> ShardReader<R> {
+    /// Construct a new `ShardReader`, reading the header and the index.
+    ///
+    /// # Errors
+    /// - if reader raises the error.
+    /// - if the data is not a shard, as `io::ErrorKind::InvalidData`.
+    pub fn new(mut reader: R) -> io::Result<ShardReader<R>> {
+        reader.seek(SeekFrom::Start(0))?;
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data(FORMAT, "invalid magic number"));
+        }
+        if read_u32(&mut reader)? != VERSION {
+            return Err(invalid_data(FORMAT, "unsupported version"));
+        }
+        let board_size = read_u32(&mut reader)? as usize;
+        // positions are written as u16
+        if board_size == 0 || board_size > u16::MAX as usize {
+            return Err(invalid_data(FORMAT, "invalid board size"));
+        }
+        let compress = read_u32(&mut reader)? & FLAG_DEFLATE != 0;
+
+        let end = reader.seek(SeekFrom::End(0))?;
+        if end < HEADER_LEN + FOOTER_LEN {
+            return Err(invalid_data(FORMAT, "missing index"));
+        }
+        reader.seek(SeekFrom::Start(end - FOOTER_LEN))?;
+        let index_offset = read_u64(&mut reader)?;
+        let num_chunks = read_u32(&mut reader)?;
+        let num_samples = read_u64(&mut reader)?;
+        reader.read_exact(&mut magic)?;
+        if &magic != INDEX_MAGIC {
+            return Err(invalid_data(FORMAT, "invalid magic number of the index"));
+        }
+        let index_len = num_chunks as u64 * INDEX_ENTRY_LEN;
+        if index_offset.checked_add(index_len) != Some(end - FOOTER_LEN) {
+            return Err(invalid_data(FORMAT, "index out of range"));
+        }
+
+        reader.seek(SeekFrom::Start(index_offset))?;
+        // number of the chunks is not trusted for the allocation
+        let mut index = Vec::new();
+        for _ in 0..num_chunks {
+            let chunk = ChunkIndex {
+                offset: read_u64(&mut reader)?,
+                length: read_u64(&mut reader)?,
+                num_samples: read_u32(&mut reader)?,
+            };
+            let end = chunk.offset.checked_add(chunk.length);
+            if end.filter(|end| *end <= index_offset).is_none() {
+                return Err(invalid_data(FORMAT, "chunk out of range"));
+            }
+            index.push(chunk);
+        }
+        let total = index.iter().map(|x| u64::from(x.num_samples)).sum::<u64>();
+        if total != num_samples {
+            return Err(invalid_data(
+                FORMAT,
+                "number of the samples differs from the index",
+            ));
+        }
+
+        Ok(ShardReader {
+            reader,
+            board_size,
+            compress,
+            index,
+            num_samples: num_samples as usize,
+        })
+    }
+
+    /// Length of one side of the board.
+    pub fn board_size(&self) -> usize {
+        self.board_size
+    }
+
+    /// Whether the chunks are deflated.
+    pub fn is_compressed(&self) -> bool {
+        self.compress
+    }
+
+    /// Number of the chunks.
+    pub fn num_chunks(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Number of the samples.
+    pub fn num_samples(&self) -> usize {
+        self.num_samples
+    }
+
+    /// Number of the samples of the chunk.
+    ///
+    /// # Panics
+    /// If the chunk is out of range.
+    pub fn chunk_len(&self, chunk: usize) -> usize {
+        self.index[chunk].num_samples as usize
+    }
+
+    /// Read the samples of the chunk.
+    ///
+    /// # Panics
+    /// If the chunk is out of range.
+    pub fn read_chunk(&mut self, chunk: usize) -> io::Result<Vec<Sample>> {
+        let index = self.index[chunk].clone();
+        self.reader.seek(SeekFrom::Start(index.offset))?;
+
+        let capacity = self.board_size * self.board_size;
+        let record_len = 9 + capacity * 5;
+        let chunk_len = record_len
+            .checked_mul(index.num_samples as usize)
+            .ok_or_else(|| invalid_data(FORMAT, "invalid length of the chunk"))?;
+
+        let mut payload = vec![0; index.length as usize];
+        self.reader.read_exact(&mut payload)?;
+        let data = if self.compress {
+            // inflate one more byte than expected to detect the longer chunk without the unbounded allocation
+            let mut data = Vec::new();
+            DeflateDecoder::new(payload.as_slice())
+                .take(chunk_len as u64 + 1)
+                .read_to_end(&mut data)?;
+            data
+        } else {
+            payload
+        };
+        if data.len() != chunk_len {
+            return Err(invalid_data(FORMAT, "invalid length of the chunk"));
+        }
+
+        let mut samples = Vec::with_capacity(index.num_samples as usize);
+        for record in data.chunks(record_len) {
+            let mut reader = &record[1..];
+            let row = read_u16(&mut reader)? as usize;
+            let col = read_u16(&mut reader)? as usize;
+            let value = read_f32(&mut reader)?;
+            if row >= self.board_size || col >= self.board_size {
+                return Err(invalid_data(FORMAT, "position out of the board"));
+            }
+
+            let cells = reader[..capacity]
+                .iter()
+                .map(|x| Player::from(i32::from(*x as i8)))
+                .collect();
+            let mut reader = &reader[capacity..];
+            let mut policy = Vec::with_capacity(capacity);
+            for _ in 0..capacity {
+                policy.push(read_f32(&mut reader)?);
+            }
+
+            samples.push(Sample {
+                turn: Player::from(i32::from(record[0] as i8)),
+                board: Board::from_vec(self.board_size, cells).unwrap(),
+                pos: (row, col),
+                policy: GenericBoard::from_vec(self.board_size, policy).unwrap(),
+                value,
+            });
+        }
+        Ok(samples)
+    }
+
+    /// Read the chunk as `TrainingBatch`.
+    pub fn read_batch(&mut self, chunk: usize) -> io::Result<TrainingBatch> {
+        let samples = self.read_chunk(chunk)?;
+        Ok(TrainingBatch::from_samples(self.board_size, &samples))
+    }
+}
+
+impl ShardReader<BufReader<File>> {
+    /// Open the shard file.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<ShardReader<BufReader<File>>> {
+        ShardReader::new(BufReader::new(File::open(path)?))
+    }
+}
@@ -0,0 +1,176 @@
+use super::*;
+use agent::Agent;
+use policy::{AlphaZero, HyperParameter, RandomEvaluator, RandomPolicy};
+
+use std::io::Cursor;
+
+fn play(size: usize) -> PlayResult {
+    let param = HyperParameter::light_weight();
+    let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::with_seed(0)), param);
+    Agent::new(&mut policy)
+        .with_board_size(size)
+        .play()
+        .unwrap()
+}
+
+#[test]
+fn test_roundtrip() {
+    let result = play(5);
+    let expected = samples(&result);
+
+    for compress in [false, true].iter() {
+        let mut writer = ShardWriter::new(Vec::new(), 5)
+            .with_chunk_size(4)
+            .with_compression(*compress);
+        writer.push_result(&result).unwrap();
+        assert_eq!(writer.num_samples(), expected.len() as u64);
+        let shard = writer.finish().unwrap();
+
+        let mut reader = ShardReader::new(Cursor::new(shard)).unwrap();
+        assert_eq!(reader.board_size(), 5);
+        assert_eq!(reader.is_compressed(), *compress);
+        assert_eq!(reader.num_samples(), expected.len());
+        assert_eq!(reader.num_chunks(), expected.chunks(4).count());
+
+        let mut recovered = Vec::new();
+        for i in 0..reader.num_chunks() {
+            let chunk = reader.read_chunk(i).unwrap();
+            assert_eq!(chunk.len(), reader.chunk_len(i));
+            recovered.extend(chunk);
+        }
+        assert_eq!(recovered, expected);
+    }
+}
+
+#[test]
+fn test_compression() {
+    let mut policy = RandomPolicy::with_seed(0);
+    let result = Agent::new(&mut policy).with_board_size(9).play().unwrap();
+
+    let write = |compress: bool| {
+        let mut writer = ShardWriter::new(Vec::new(), 9).with_compression(compress);
+        writer.push_result(&result).unwrap();
+        writer.finish().unwrap()
+    };
+    // one-hot policies are sparse
+    assert!(write(true).len() < write(false).len());
+}
+
+#[test]
+fn test_read_batch() {
+    let result = play(5);
+    let mut writer = ShardWriter::new(Vec::new(), 5).with_chunk_size(3);
+    writer.push_result(&result).unwrap();
+    let shard = writer.finish().unwrap();
+
+    let mut reader = ShardReader::new(Cursor::new(shard)).unwrap();
+    let batch = reader.read_batch(1).unwrap();
+    let expected = TrainingBatch::from_samples(5, &samples(&result)[3..6]);
+    assert_eq!(batch, expected);
+}
+
+#[test]
+fn test_empty() {
+    let shard = ShardWriter::new(Vec::new(), 5).finish().unwrap();
+    let reader = ShardReader::new(Cursor::new(shard)).unwrap();
+    assert_eq!(reader.num_chunks(), 0);
+    assert_eq!(reader.num_samples(), 0);
+}
+
+#[test]
+fn test_invalid() {
+    let mut writer = ShardWriter::new(Vec::new(), 7);
+    let err = writer.push_result(&play(5)).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut writer = ShardWriter::new(Vec::new(), 5);
+    writer.push_result(&play(5)).unwrap();
+    let mut shard = writer.finish().unwrap();
+
+    let len = shard.len();
+    shard[len - 1] = b'X';
+    let err = ShardReader::new(Cursor::new(shard.clone())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    shard[0] = b'X';
+    let err = ShardReader::new(Cursor::new(shard)).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_corrupted_header() {
+    let mut writer = ShardWriter::new(Vec::new(), 5);
+    writer.push_result(&play(5)).unwrap();
+    let shard = writer.finish().unwrap();
+    let len = shard.len();
+
+    let corrupt = |at: usize, bytes: &[u8]| {
+        let mut shard = shard.clone();
+        shard[at..at + bytes.len()].copy_from_slice(bytes);
+        ShardReader::new(Cursor::new(shard))
+    };
+
+    // board size
+    let err = corrupt(8, &0u32.to_le_bytes()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let err = corrupt(8, &u32::MAX.to_le_bytes()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // number of the chunks
+    let err = corrupt(len - 16, &u32::MAX.to_le_bytes()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // offset and length of the first chunk overflow
+    let mut footer = [0; 8];
+    footer.copy_from_slice(&shard[len - 24..len - 16]);
+    let index_offset = u64::from_le_bytes(footer) as usize;
+    let err = corrupt(index_offset, &u64::MAX.to_le_bytes())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let err = corrupt(index_offset + 8, &u64::MAX.to_le_bytes())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // number of the samples differs from the index
+    let err = corrupt(len - 12, &u64::MAX.to_le_bytes()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_corrupted_chunk() {
+    for compress in [false, true].iter() {
+        let mut writer = ShardWriter::new(Vec::new(), 5).with_compression(*compress);
+        writer.push_result(&play(5)).unwrap();
+        let mut shard = writer.finish().unwrap();
+        let len = shard.len();
+
+        // chunk is longer than its number of the samples
+        let mut footer = [0; 8];
+        footer.copy_from_slice(&shard[len - 24..len - 16]);
+        let index_offset = u64::from_le_bytes(footer) as usize;
+        shard[index_offset + 16..index_offset + 20].copy_from_slice(&1u32.to_le_bytes());
+        shard[len - 12..len - 4].copy_from_slice(&1u64.to_le_bytes());
+
+        let mut reader = ShardReader::new(Cursor::new(shard)).unwrap();
+        assert_eq!(reader.num_samples(), 1);
+        let err = reader.read_chunk(0).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn test_file() {
+    let path = ::std::env::temp_dir().join("connect6_shard_test.bin");
+    let mut writer = ShardWriter::create(&path, 5)
+        .unwrap()
+        .with_compression(true);
+    writer.push_result(&play(5)).unwrap();
+    writer.finish().unwrap();
+
+    let mut reader = ShardReader::open(&path).unwrap();
+    assert!(reader.num_samples() > 0);
+    assert_eq!(reader.read_chunk(0).unwrap().len(), reader.num_samples());
+    ::std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,191 @@
+//! Feature tensors and training targets of the positions.
+use agent::PlayResult;
+use export::NpzWriter;
+use game::Player;
+use replay::{samples, Sample};
+use Board;
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of the feature planes of a position.
+pub const NUM_PLANES: usize = 3;
+
+/// Encode the position as `NUM_PLANES` planes of row-major ordered `size * size` cells.
+///
+/// - plane 0: stones of the player on turn.
+/// - plane 1: stones of the opponent.
+/// - plane 2: filled with 1 if the player on turn is black, otherwise 0.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{export::{features, NUM_PLANES}, game::Player, Board};
+/// let mut board = Board::new(3);
+/// board[0][0] = Player::Black;
+/// board[1][1] = Player::White;
+///
+/// let planes = features(Player::White, &board);
+/// assert_eq!(planes.len(), NUM_PLANES * 9);
+/// assert_eq!(planes[4], 1.);
+/// assert_eq!(planes[9], 1.);
+/// assert!(planes[18..].iter().all(|x| *x == 0.));
+/// ```
+pub fn features(turn: Player, board: &Board) -> Vec<f32> {
+    let capacity = board.size() * board.size();
+    let mut planes = vec![0.; NUM_PLANES * capacity];
+    for (i, cell) in board.as_slice().iter().enumerate() {
+        if *cell == Player::None {
+            continue;
+        }
+        let plane = if *cell == turn { 0 } else { 1 };
+        planes[plane * capacity + i] = 1.;
+    }
+    if turn == Player::Black {
+        for x in planes[2 * capacity..].iter_mut() {
+            *x = 1.;
+        }
+    }
+    planes
+}
+
+/// Batch of the feature tensors and training targets, flattened in row-major order.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::Agent, export::{TrainingBatch, NUM_PLANES}, policy::RandomPolicy};
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+///
+/// let batch = TrainingBatch::from_results(7, &[result]);
+/// assert_eq!(batch.features.len(), batch.len() * NUM_PLANES * 49);
+/// assert_eq!(batch.policies.len(), batch.len() * 49);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrainingBatch {
+    /// length of one side of the board
+    pub board_size: usize,
+    /// features of shape (len, NUM_PLANES, board_size, board_size)
+    pub features: Vec<f32>,
+    /// policy targets of shape (len, board_size * board_size)
+    pub policies: Vec<f32>,
+    /// value targets of shape (len,), from the perspective of the player on turn
+    pub values: Vec<f32>,
+}
+
+impl TrainingBatch {
+    /// Construct a new empty `TrainingBatch`.
+    pub fn new(board_size: usize) -> TrainingBatch {
+        TrainingBatch {
+            board_size,
+            features: Vec::new(),
+            policies: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Construct a `TrainingBatch` from the samples.
+    ///
+    /// # Panics
+    /// If the board size of any sample differs from `board_size`.
+    pub fn from_samples(board_size: usize, samples: &[Sample]) -> TrainingBatch {
+        let mut batch = TrainingBatch::new(board_size);
+        for sample in samples {
+            batch.push(sample);
+        }
+        batch
+    }
+
+    /// Construct a `TrainingBatch` from the positions of the games.
+    ///
+    /// # Panics
+    /// If the board size of any game differs from `board_size`.
+    pub fn from_results(board_size: usize, results: &[PlayResult]) -> TrainingBatch {
+        let mut batch = TrainingBatch::new(board_size);
+        for result in results {
+            for sample in samples(result) {
+                batch.push(&sample);
+            }
+        }
+        batch
+    }
+
+    /// Number of the positions.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Push the sample to the batch.
+    ///
+    /// # Panics
+    /// If the board size of the sample differs from `board_size`.
+    pub fn push(&mut self, sample: &Sample) {
+        assert_eq!(sample.board.size(), self.board_size, "board size mismatch");
+        self.features.extend(features(sample.turn, &sample.board));
+        self.policies.extend_from_slice(sample.policy.as_slice());
+        self.values.push(sample.value);
+    }
+
+    /// Shape of the features, (len, NUM_PLANES, board_size, board_size).
+    pub fn feature_shape(&self) -> [usize; 4] {
+        [self.len(), NUM_PLANES, self.board_size, self.board_size]
+    }
+
+    /// Shape of the policy targets, (len, board_size * board_size).
+    pub fn policy_shape(&self) -> [usize; 2] {
+        [self.len(), self.board_size * self.board_size]
+    }
+
+    /// Write the batch as npz archive of `features`, `policies` and `values`, deflated if `compress`.
+    pub fn write_npz<W: Write>(&self, writer: W, compress: bool) -> io::Result<W> {
+        let mut npz = NpzWriter::new(writer).with_compression(compress);
+        npz.add_array("features", &self.feature_shape(), &self.features)?;
+        npz.add_array("policies", &self.policy_shape(), &self.policies)?;
+        npz.add_array("values", &[self.len()], &self.values)?;
+        npz.finish()
+    }
+
+    /// Save the batch to the npz file, loaded by `numpy.load(path)`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, export::TrainingBatch, policy::RandomPolicy};
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+    /// let batch = TrainingBatch::from_results(7, &[result]);
+    ///
+    /// let path = std::env::temp_dir().join("connect6_export_doctest.npz");
+    /// batch.save_npz(&path, true).unwrap();
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn save_npz<P: AsRef<Path>>(&self, path: P, compress: bool) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        self.write_npz(writer, compress)?.flush()
+    }
+
+    /// Save the batch to `features.npy`, `policies.npy` and `values.npy` in the directory.
+    pub fn save_npy<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let save = |name: &str, shape: &[usize], data: &[f32]| -> io::Result<()> {
+            let mut writer = BufWriter::new(File::create(dir.join(name))?);
+            super::write_npy(&mut writer, shape, data)?;
+            writer.flush()
+        };
+        save("features.npy", &self.feature_shape(), &self.features)?;
+        save("policies.npy", &self.policy_shape(), &self.policies)?;
+        save("values.npy", &[self.len()], &self.values)
+    }
+}
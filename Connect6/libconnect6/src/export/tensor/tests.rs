@@ -0,0 +1,79 @@
+use super::*;
+use agent::Agent;
+use export::read_npy;
+use policy::{AlphaZero, HyperParameter, RandomEvaluator, RandomPolicy};
+
+use std::fs;
+use std::io::BufReader;
+
+#[test]
+fn test_features() {
+    let mut board = Board::new(4);
+    board[0][1] = Player::Black;
+    board[2][3] = Player::White;
+
+    let black = features(Player::Black, &board);
+    let white = features(Player::White, &board);
+    assert_eq!(black[1], 1.);
+    assert_eq!(black[16 + 11], 1.);
+    assert_eq!(white[11], 1.);
+    assert_eq!(white[16 + 1], 1.);
+
+    assert_eq!(black.iter().sum::<f32>(), 2. + 16.);
+    assert_eq!(white.iter().sum::<f32>(), 2.);
+}
+
+#[test]
+fn test_from_results() {
+    let param = HyperParameter::light_weight();
+    let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::with_seed(0)), param);
+    let results = [Agent::new(&mut policy).with_board_size(5).play().unwrap()];
+
+    let batch = TrainingBatch::from_results(5, &results);
+    let expected = samples(&results[0]);
+    assert_eq!(batch.len(), expected.len());
+    assert_eq!(batch.feature_shape(), [batch.len(), NUM_PLANES, 5, 5]);
+    assert_eq!(batch.policy_shape(), [batch.len(), 25]);
+    assert_eq!(batch.features.len(), batch.len() * NUM_PLANES * 25);
+
+    for (i, sample) in expected.iter().enumerate() {
+        assert_eq!(batch.values[i], sample.value);
+        assert_eq!(
+            &batch.policies[i * 25..(i + 1) * 25],
+            sample.policy.as_slice()
+        );
+        let planes = features(sample.turn, &sample.board);
+        let size = NUM_PLANES * 25;
+        assert_eq!(&batch.features[i * size..(i + 1) * size], planes.as_slice());
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_size_mismatch() {
+    let mut policy = RandomPolicy::with_seed(0);
+    let result = Agent::new(&mut policy).with_board_size(5).play().unwrap();
+    TrainingBatch::from_results(7, &[result]);
+}
+
+#[test]
+fn test_save_npy() {
+    let mut policy = RandomPolicy::with_seed(0);
+    let result = Agent::new(&mut policy).with_board_size(5).play().unwrap();
+    let batch = TrainingBatch::from_results(5, &[result]);
+
+    let dir = ::std::env::temp_dir().join("connect6_export_npy_test");
+    batch.save_npy(&dir).unwrap();
+
+    let mut reader = BufReader::new(fs::File::open(dir.join("features.npy")).unwrap());
+    let (shape, features) = read_npy::<f32, _>(&mut reader).unwrap();
+    assert_eq!(shape, batch.feature_shape().to_vec());
+    assert_eq!(features, batch.features);
+
+    let mut reader = BufReader::new(fs::File::open(dir.join("values.npy")).unwrap());
+    let (shape, values) = read_npy::<f32, _>(&mut reader).unwrap();
+    assert_eq!(shape, vec![batch.len()]);
+    assert_eq!(values, batch.values);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
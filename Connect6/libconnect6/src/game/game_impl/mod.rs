@@ -11,22 +11,29 @@
 //! let winner = game.is_game_end();
 //! assert_eq!(winner, Player::None);
 //! ```
-use game::Player;
-use {Board, BOARD_SIZE};
+use game::{search_from_with, search_with, Player, Rule, WinningLine, Zobrist};
+use {Board, BOARD_SIZE, MAX_BOARD_SIZE};
 
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
 use std::error;
 use std::fmt;
 use std::io;
+use std::str::FromStr;
 
 #[cfg(test)]
 mod tests;
 
 /// Result of setting stone
-#[derive(Debug, PartialEq)]
+///
+/// `line` represents the continuous stones if the stone makes the player win.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SetResult {
     pub player: Player,
     pub num_remain: i32,
     pub position: (usize, usize),
+    pub line: Option<WinningLine>,
 }
 
 impl SetResult {
@@ -36,6 +43,7 @@ impl SetResult {
             player: Player::None,
             num_remain: 0,
             position: (0, 0),
+            line: None,
         }
     }
 
@@ -49,7 +57,7 @@ impl SetResult {
     /// let position = (0, 0);
     ///
     /// let play_result = SetResult::with_game(&game, position);
-    /// let expected = SetResult{ player: Player::Black, num_remain: 1, position: (0, 0) };
+    /// let expected = SetResult{ player: Player::Black, num_remain: 1, position: (0, 0), line: None };
     /// assert_eq!(play_result, expected);
     /// ```
     pub fn with_game(game: &Game, position: (usize, usize)) -> SetResult {
@@ -57,8 +65,44 @@ impl SetResult {
             player: game.turn,
             num_remain: game.num_remain,
             position,
+            line: search_from_with(&game.board, position, &game.rule),
+        }
+    }
+}
+
+/// Status of the game.
+///
+/// `Draw` represents the full board without winner,
+/// it can be distinguished with the game which is not finished, `Ongoing`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameStatus {
+    Ongoing,
+    Win(Player),
+    Draw,
+}
+
+impl GameStatus {
+    /// Return winner if the status is `Win`, else Player::None
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{GameStatus, Player};
+    /// assert_eq!(GameStatus::Win(Player::White).winner(), Player::White);
+    /// assert_eq!(GameStatus::Draw.winner(), Player::None);
+    /// ```
+    pub fn winner(&self) -> Player {
+        match self {
+            &GameStatus::Win(player) => player,
+            _ => Player::None,
         }
     }
+
+    /// Return true if the game is finished, win or draw.
+    pub fn is_end(&self) -> bool {
+        *self != GameStatus::Ongoing
+    }
 }
 
 /// Error for invalid position of setting stone on game Connect6.
@@ -99,6 +143,63 @@ impl error::Error for AlreadySetPositionError {
     }
 }
 
+/// Error for the diagram which could not be parsed as a game.
+#[derive(Debug, Clone)]
+struct InvalidDiagramError {
+    msg: String,
+}
+
+impl InvalidDiagramError {
+    fn boxed(msg: String) -> Box<error::Error + Send> {
+        Box::new(InvalidDiagramError { msg })
+    }
+}
+
+impl fmt::Display for InvalidDiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid diagram: {}", self.msg)
+    }
+}
+
+impl error::Error for InvalidDiagramError {
+    fn description(&self) -> &str {
+        "invalid diagram"
+    }
+}
+
+/// Error for the position which could not be continued as a game.
+#[derive(Debug, Clone)]
+struct InvalidStateError {
+    msg: String,
+}
+
+impl InvalidStateError {
+    fn boxed(msg: String) -> Box<error::Error + Send> {
+        Box::new(InvalidStateError { msg })
+    }
+}
+
+impl fmt::Display for InvalidStateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid state: {}", self.msg)
+    }
+}
+
+impl error::Error for InvalidStateError {
+    fn description(&self) -> &str {
+        "invalid state"
+    }
+}
+
+/// Check the board size, columns and rows are labeled with a letter.
+fn check_size(size: usize) -> Result<(), Box<error::Error + Send>> {
+    if size == 0 || size > MAX_BOARD_SIZE {
+        let msg = format!("board size {} out of [1, {}]", size, MAX_BOARD_SIZE);
+        return Err(InvalidStateError::boxed(msg));
+    }
+    Ok(())
+}
+
 /// Implementation of Game Connect6
 ///
 /// It defines the game connect6 with some visualization utilities.
@@ -114,20 +215,475 @@ impl error::Error for AlreadySetPositionError {
 /// let winner = game.is_game_end();
 /// assert_eq!(winner, Player::None);
 /// ```
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "GameState")
+)]
 pub struct Game {
     turn: Player,
     num_remain: i32,
     board: Board,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    zobrist: Zobrist,
+    rule: Rule,
+    history: Vec<SetResult>,
+    undone: Vec<SetResult>,
 }
 
 impl Game {
-    /// Construct a new `Game`
+    /// Construct a new `Game` with default board size, `BOARD_SIZE`.
     pub fn new() -> Game {
+        Game::with_size(BOARD_SIZE)
+    }
+
+    /// Construct a new `Game` with given length of one side.
+    ///
+    /// # Panics
+    /// If `size` is 0 or larger than `MAX_BOARD_SIZE`, columns and rows are labeled with a letter.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Game;
+    /// let game = Game::with_size(19);
+    /// assert_eq!(game.get_board().size(), 19);
+    /// ```
+    pub fn with_size(size: usize) -> Game {
+        Game::with_rule(size, Rule::default())
+    }
+
+    /// Construct a new `Game` with given length of one side and rule.
+    ///
+    /// # Panics
+    /// If `size` is 0 or larger than `MAX_BOARD_SIZE`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player, Rule};
+    /// let mut game = Game::with_rule(15, Rule::gomoku());
+    /// game.set((0, 0)).unwrap();
+    /// assert_eq!(game.get_turn(), Player::White);
+    /// assert_eq!(game.get_remain(), 1);
+    /// ```
+    pub fn with_rule(size: usize, rule: Rule) -> Game {
+        assert!(
+            size > 0 && size <= MAX_BOARD_SIZE,
+            "invalid board size {}",
+            size
+        );
         Game {
             turn: Player::Black,
-            num_remain: 1,
-            board: [[Player::None; BOARD_SIZE]; BOARD_SIZE],
+            num_remain: rule.opening_stones,
+            board: Board::new(size),
+            zobrist: Zobrist::new(size),
+            rule,
+            history: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// Construct a `Game` from the ascii diagram of [print](#method.print).
+    ///
+    /// The side to move and the number of remaining stones are inferred from the number of stones,
+    /// and the history of the constructed game is empty.
+    /// The column header line is optional.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let diagram = "0 A B C D E F
+    ///                a _ _ _ _ _ _
+    ///                b _ X _ _ _ _
+    ///                c _ _ O O _ _
+    ///                d _ _ _ _ _ _
+    ///                e _ _ _ _ _ _
+    ///                f _ _ _ _ _ _";
+    ///
+    /// let game = Game::from_diagram(diagram).unwrap();
+    /// assert_eq!(game.get_size(), 6);
+    /// assert_eq!(game.get_board()[1][1], Player::Black);
+    /// assert_eq!(game.get_turn(), Player::Black);
+    /// assert_eq!(game.get_remain(), 2);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If the diagram is malformed.
+    /// 2. If no legal game could reach the number of stones of each player.
+    pub fn from_diagram(diagram: &str) -> Result<Game, Box<error::Error + Send>> {
+        Self::from_diagram_with_rule(diagram, Rule::default())
+    }
+
+    /// Construct a `Game` from the ascii diagram under the given rule.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player, Rule};
+    /// let diagram = "a X O X _ _
+    ///                b _ _ _ _ _
+    ///                c _ _ _ _ _
+    ///                d _ _ _ _ _
+    ///                e _ _ _ _ _";
+    ///
+    /// let game = Game::from_diagram_with_rule(diagram, Rule::gomoku()).unwrap();
+    /// assert_eq!(game.get_turn(), Player::White);
+    /// assert_eq!(game.get_remain(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If the diagram is malformed.
+    /// 2. If no legal game could reach the number of stones of each player.
+    pub fn from_diagram_with_rule(
+        diagram: &str,
+        rule: Rule,
+    ) -> Result<Game, Box<error::Error + Send>> {
+        let board = Self::parse_diagram(diagram)?;
+        let num_black = board
+            .as_slice()
+            .iter()
+            .filter(|x| **x == Player::Black)
+            .count();
+        let num_white = board
+            .as_slice()
+            .iter()
+            .filter(|x| **x == Player::White)
+            .count();
+
+        // replay the turn order, black q stones and then p stones each
+        let (mut turn, mut num_remain) = (Player::Black, rule.opening_stones);
+        let (mut black, mut white) = (0, 0);
+        while black + white < num_black + num_white {
+            match turn {
+                Player::Black => black += 1,
+                _ => white += 1,
+            }
+            num_remain -= 1;
+            if num_remain <= 0 {
+                num_remain = rule.stones_per_turn;
+                turn.mut_switch();
+            }
+        }
+        if (black, white) != (num_black, num_white) {
+            let msg = format!(
+                "unreachable stone counts, black {} white {}",
+                num_black, num_white
+            );
+            return Err(InvalidDiagramError::boxed(msg));
+        }
+
+        let mut game = Game::with_rule(board.size(), rule);
+        game.turn = turn;
+        game.num_remain = num_remain;
+        game.zobrist = Zobrist::from_board(&board);
+        game.board = board;
+        Ok(game)
+    }
+
+    /// Construct a `Game` from the ascii diagram with given side to move and number of remaining stones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let diagram = "a X _ _ _ _ _
+    ///                b _ _ _ _ _ _
+    ///                c _ _ _ _ _ _
+    ///                d _ _ _ _ _ _
+    ///                e _ _ _ _ _ _
+    ///                f _ _ _ _ _ _";
+    ///
+    /// assert!(Game::from_diagram_with_turn(diagram, Player::White, 2).is_ok());
+    /// assert!(Game::from_diagram_with_turn(diagram, Player::Black, 1).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If the diagram is malformed.
+    /// 2. If no legal game could reach the number of stones of each player.
+    /// 3. If given turn is not the one of the game with the number of stones.
+    pub fn from_diagram_with_turn(
+        diagram: &str,
+        turn: Player,
+        num_remain: i32,
+    ) -> Result<Game, Box<error::Error + Send>> {
+        let game = Self::from_diagram(diagram)?;
+        if (game.turn, game.num_remain) != (turn, num_remain) {
+            let msg = format!(
+                "expected turn {:?} with {} stones, but {:?} with {}",
+                turn, num_remain, game.turn, game.num_remain
+            );
+            return Err(InvalidDiagramError::boxed(msg));
+        }
+        Ok(game)
+    }
+
+    /// Construct a `Game` from the board with given side to move and number of remaining stones.
+    ///
+    /// Unlike [from_diagram](#method.from_diagram), the number of stones of each player is not checked,
+    /// so that the position of the handicap or the composed problem can be given.
+    /// The history of the constructed game is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::{Game, Player}, Board};
+    /// let mut board = Board::new(9);
+    /// board[4][4] = Player::Black;
+    /// board[4][5] = Player::Black;
+    ///
+    /// let game = Game::from_position(board.clone(), Player::White, 2).unwrap();
+    /// assert_eq!(game.get_board()[4][5], Player::Black);
+    /// assert_eq!(game.get_turn(), Player::White);
+    ///
+    /// assert!(Game::from_position(board.clone(), Player::None, 2).is_err());
+    /// assert!(Game::from_position(board, Player::White, 3).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If given turn is `Player::None`.
+    /// 2. If the number of remaining stones exceeds the stones of a turn, or is not positive.
+    /// 3. If the game is already finished on the board.
+    pub fn from_position(
+        board: Board,
+        turn: Player,
+        num_remain: i32,
+    ) -> Result<Game, Box<error::Error + Send>> {
+        Self::from_position_with_rule(board, turn, num_remain, Rule::default())
+    }
+
+    /// Construct a `Game` from the board under the given rule,
+    /// reference [from_position](#method.from_position).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::{Game, Player, Rule}, Board};
+    /// let mut board = Board::new(9);
+    /// board[4][4] = Player::Black;
+    ///
+    /// let game = Game::from_position_with_rule(board.clone(), Player::White, 1, Rule::gomoku());
+    /// assert!(game.is_ok());
+    /// assert!(Game::from_position_with_rule(board, Player::White, 2, Rule::gomoku()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If given turn is `Player::None`.
+    /// 2. If the number of remaining stones exceeds the stones of a turn, or is not positive.
+    /// 3. If the game is already finished on the board.
+    /// 4. If the board is larger than `MAX_BOARD_SIZE`.
+    pub fn from_position_with_rule(
+        board: Board,
+        turn: Player,
+        num_remain: i32,
+        rule: Rule,
+    ) -> Result<Game, Box<error::Error + Send>> {
+        check_size(board.size())?;
+        if turn == Player::None {
+            return Err(InvalidStateError::boxed("no side to move".to_string()));
+        }
+        let max_remain = rule.stones_per_turn.max(rule.opening_stones);
+        if num_remain < 1 || num_remain > max_remain {
+            let msg = format!(
+                "{} remaining stones, expected 1 to {}",
+                num_remain, max_remain
+            );
+            return Err(InvalidStateError::boxed(msg));
+        }
+        let winner = search_with(&board, &rule);
+        if winner != Player::None {
+            let msg = format!("{:?} already won", winner);
+            return Err(InvalidStateError::boxed(msg));
+        }
+        if board.as_slice().iter().all(|x| *x != Player::None) {
+            return Err(InvalidStateError::boxed("board is full".to_string()));
+        }
+
+        let mut game = Game::with_rule(board.size(), rule);
+        game.turn = turn;
+        game.num_remain = num_remain;
+        game.zobrist = Zobrist::from_board(&board);
+        game.board = board;
+        Ok(game)
+    }
+
+    /// Construct a `Game` with the handicap stones of given player,
+    /// then the opponent plays the full turn of `stones_per_turn` stones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let game = Game::with_handicap(15, Player::Black, &[(7, 7), (3, 3)]).unwrap();
+    /// assert_eq!(game.get_board()[3][3], Player::Black);
+    /// assert_eq!(game.get_turn(), Player::White);
+    /// assert_eq!(game.get_remain(), 2);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If given player is `Player::None`.
+    /// 2. If the handicap stones are out of the board or duplicated.
+    /// 3. If the handicap stones already finish the game.
+    /// 4. If the board size is 0 or larger than `MAX_BOARD_SIZE`.
+    pub fn with_handicap(
+        size: usize,
+        player: Player,
+        stones: &[(usize, usize)],
+    ) -> Result<Game, Box<error::Error + Send>> {
+        Self::with_handicap_with_rule(size, player, stones, Rule::default())
+    }
+
+    /// Construct a `Game` with the handicap stones under the given rule,
+    /// reference [with_handicap](#method.with_handicap).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player, Rule};
+    /// let game = Game::with_handicap_with_rule(15, Player::Black, &[(7, 7)], Rule::gomoku()).unwrap();
+    /// assert_eq!(game.get_turn(), Player::White);
+    /// assert_eq!(game.get_remain(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If given player is `Player::None`.
+    /// 2. If the handicap stones are out of the board or duplicated.
+    /// 3. If the handicap stones already finish the game.
+    /// 4. If the board size is 0 or larger than `MAX_BOARD_SIZE`.
+    pub fn with_handicap_with_rule(
+        size: usize,
+        player: Player,
+        stones: &[(usize, usize)],
+        rule: Rule,
+    ) -> Result<Game, Box<error::Error + Send>> {
+        check_size(size)?;
+        if player == Player::None {
+            return Err(InvalidStateError::boxed("no handicap player".to_string()));
+        }
+        let mut board = Board::new(size);
+        for &(row, col) in stones.iter() {
+            if row >= size || col >= size {
+                return Err(Box::new(InvalidPositionError { row, col }));
+            }
+            if board[row][col] != Player::None {
+                return Err(Box::new(AlreadySetPositionError { row, col }));
+            }
+            board[row][col] = player;
+        }
+        let num_remain = rule.stones_per_turn;
+        Self::from_position_with_rule(board, player.switch(), num_remain, rule)
+    }
+
+    /// Construct a `Game` by playing the opening line from the empty board.
+    ///
+    /// Unlike the other positional constructors, the opening moves are kept in the history.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let game = Game::from_opening(15, &[(7, 7), (6, 6), (8, 8)]).unwrap();
+    /// assert_eq!(game.ply(), 3);
+    /// assert_eq!(game.get_turn(), Player::Black);
+    /// assert_eq!(game.get_remain(), 2);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If some moves raise Err at [set](#method.set).
+    /// 2. If the opening line already finishes the game.
+    /// 3. If the board size is 0 or larger than `MAX_BOARD_SIZE`.
+    pub fn from_opening(
+        size: usize,
+        moves: &[(usize, usize)],
+    ) -> Result<Game, Box<error::Error + Send>> {
+        Self::from_opening_with_rule(size, moves, Rule::default())
+    }
+
+    /// Construct a `Game` by playing the opening line under the given rule,
+    /// reference [from_opening](#method.from_opening).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player, Rule};
+    /// let game = Game::from_opening_with_rule(15, &[(7, 7), (6, 6)], Rule::gomoku()).unwrap();
+    /// assert_eq!(game.get_turn(), Player::Black);
+    /// assert_eq!(game.get_remain(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. If some moves raise Err at [set](#method.set).
+    /// 2. If the opening line already finishes the game.
+    /// 3. If the board size is 0 or larger than `MAX_BOARD_SIZE`.
+    pub fn from_opening_with_rule(
+        size: usize,
+        moves: &[(usize, usize)],
+        rule: Rule,
+    ) -> Result<Game, Box<error::Error + Send>> {
+        check_size(size)?;
+        let mut game = Game::with_rule(size, rule);
+        for pos in moves.iter() {
+            let result = game.set(*pos)?;
+            if let Some(line) = result.line {
+                let msg = format!("{:?} won by the opening", line.player);
+                return Err(InvalidStateError::boxed(msg));
+            }
+        }
+        Ok(game)
+    }
+
+    /// Parse the board from the ascii diagram.
+    fn parse_diagram(diagram: &str) -> Result<Board, Box<error::Error + Send>> {
+        let mut lines = diagram
+            .lines()
+            .map(|x| x.split_whitespace().collect::<Vec<_>>())
+            .filter(|x| !x.is_empty())
+            .peekable();
+        // skip the column header
+        if lines.peek().map(|x| x[0]) == Some("0") {
+            lines.next();
+        }
+        let rows = lines.collect::<Vec<_>>();
+        if rows.is_empty() {
+            return Err(InvalidDiagramError::boxed("empty diagram".to_string()));
         }
+
+        // rows are labeled with the lowercase letters
+        let size = rows.len();
+        if size > MAX_BOARD_SIZE {
+            let msg = format!("{} rows, expected at most {}", size, MAX_BOARD_SIZE);
+            return Err(InvalidDiagramError::boxed(msg));
+        }
+        let mut board = Board::new(size);
+        for (i, row) in rows.iter().enumerate() {
+            let label = (b'a' + i as u8) as char;
+            if row[0] != label.to_string() {
+                return Err(InvalidDiagramError::boxed(format!(
+                    "expected row {}",
+                    label
+                )));
+            }
+            if row.len() != size + 1 {
+                let msg = format!(
+                    "row {} has {} cells, expected {}",
+                    label,
+                    row.len() - 1,
+                    size
+                );
+                return Err(InvalidDiagramError::boxed(msg));
+            }
+            for (j, cell) in row[1..].iter().enumerate() {
+                board[i][j] = match *cell {
+                    "X" => Player::Black,
+                    "O" => Player::White,
+                    "_" => Player::None,
+                    _ => return Err(InvalidDiagramError::boxed(format!("unknown cell {}", cell))),
+                };
+            }
+        }
+        Ok(board)
     }
 
     /// Set the stone of current player with given position as zero-indexed (row, col).
@@ -138,17 +694,91 @@ impl Game {
     /// # use connect6::game::{Game, Player, SetResult};
     /// let mut game = Game::new();
     /// let result = game.set((3, 4));
-    /// let expected = SetResult{ player: Player::Black, num_remain: 0, position: (3, 4) };
+    /// let expected = SetResult{ player: Player::Black, num_remain: 0, position: (3, 4), line: None };
     /// assert_eq!(result.unwrap(), expected);
     /// ```
     ///
+    /// If the stone completes the six-in-a-row, `SetResult::line` represents it.
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Path, Player};
+    /// let mut game = Game::new();
+    /// for i in 0..5 {
+    ///     game.set((0, i)).unwrap(); // black
+    ///     game.set((1, i)).unwrap(); // white
+    ///     game.set((2, i)).unwrap(); // white
+    ///     game.set((3, i)).unwrap(); // black
+    /// }
+    /// let line = game.set((0, 5)).unwrap().line.unwrap();
+    /// assert_eq!(line.player, Player::Black);
+    /// assert_eq!(line.path, Path::Right);
+    /// assert_eq!(line.stones, (0..6).map(|i| (0, i)).collect::<Vec<_>>());
+    /// ```
+    ///
     /// # Errors
     /// 1. If given position out of board.
     /// 2. If other stone place already in given position.
     pub fn set(&mut self, pos: (usize, usize)) -> Result<SetResult, Box<error::Error + Send>> {
+        let result = self.place(pos)?;
+        // new move invalidates the undone moves
+        self.undone.clear();
+        Ok(result)
+    }
+
+    /// Take back the last stone and return its `SetResult`, None if there is no move.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let mut game = Game::new();
+    /// game.set((0, 0)).unwrap();
+    ///
+    /// let result = game.undo().unwrap();
+    /// assert_eq!(result.position, (0, 0));
+    /// assert_eq!(game.get_turn(), Player::Black);
+    /// assert_eq!(game.get_remain(), 1);
+    /// assert_eq!(game.ply(), 0);
+    /// ```
+    pub fn undo(&mut self) -> Option<SetResult> {
+        let result = self.history.pop()?;
+        let (row, col) = result.position;
+        self.board[row][col] = Player::None;
+        self.zobrist.toggle(row, col, result.player);
+        // restore turn state before the stone was placed
+        self.turn = result.player;
+        self.num_remain = result.num_remain + 1;
+
+        self.undone.push(result.clone());
+        Some(result)
+    }
+
+    /// Replay the last undone stone and return its `SetResult`, None if there is nothing to redo.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let mut game = Game::new();
+    /// game.set((0, 0)).unwrap();
+    /// game.undo();
+    ///
+    /// let result = game.redo().unwrap();
+    /// assert_eq!(result.position, (0, 0));
+    /// assert_eq!(game.get_turn(), Player::White);
+    /// assert!(game.redo().is_none());
+    /// ```
+    pub fn redo(&mut self) -> Option<SetResult> {
+        let undone = self.undone.pop()?;
+        self.place(undone.position).ok()
+    }
+
+    /// Set the stone without invalidating the undone moves.
+    fn place(&mut self, pos: (usize, usize)) -> Result<SetResult, Box<error::Error + Send>> {
         let (row, col) = pos;
+        let size = self.board.size();
         // position param validation
-        if row >= BOARD_SIZE || col >= BOARD_SIZE {
+        if row >= size || col >= size {
             return Err(Box::new(InvalidPositionError { row, col }));
         }
         // in-board validation
@@ -156,13 +786,15 @@ impl Game {
             return Err(Box::new(AlreadySetPositionError { row, col }));
         }
         self.board[row][col] = self.turn;
+        self.zobrist.toggle(row, col, self.turn);
 
         self.num_remain -= 1;
         let result = SetResult::with_game(self, pos);
+        self.history.push(result.clone());
 
         // if turn end, switch player
         if self.num_remain <= 0 {
-            self.num_remain = 2;
+            self.num_remain = self.rule.stones_per_turn;
             self.turn.mut_switch();
         }
         Ok(result)
@@ -173,6 +805,35 @@ impl Game {
         &self.board
     }
 
+    /// Return incrementally updated Zobrist hash of the board
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Game;
+    /// let mut game = Game::new();
+    /// let empty = game.zobrist().key();
+    ///
+    /// game.set((0, 0)).unwrap();
+    /// assert_ne!(game.zobrist().key(), empty);
+    ///
+    /// game.undo();
+    /// assert_eq!(game.zobrist().key(), empty);
+    /// ```
+    pub fn zobrist(&self) -> &Zobrist {
+        &self.zobrist
+    }
+
+    /// Return rule of the game
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Return length of one side
+    pub fn get_size(&self) -> usize {
+        self.board.size()
+    }
+
     /// Return current player
     pub fn get_turn(&self) -> Player {
         self.turn
@@ -183,6 +844,30 @@ impl Game {
         self.num_remain
     }
 
+    /// Return ordered list of the stones placed on the board
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let mut game = Game::new();
+    /// game.set((0, 0)).unwrap();
+    /// game.set((1, 1)).unwrap();
+    ///
+    /// let history = game.history();
+    /// assert_eq!(history.len(), 2);
+    /// assert_eq!(history[1].player, Player::White);
+    /// assert_eq!(history[1].position, (1, 1));
+    /// ```
+    pub fn history(&self) -> &[SetResult] {
+        &self.history
+    }
+
+    /// Return number of stones placed on the board
+    pub fn ply(&self) -> usize {
+        self.history.len()
+    }
+
     /// Print the board status
     ///
     /// # Examples
@@ -216,14 +901,20 @@ impl Game {
     /// ```
     pub fn print(&self, writer: &mut io::Write) -> io::Result<usize> {
         // generate ascii canvas
+        let size = self.board.size();
         let mut paint = Paint::new(writer);
-        paint.push(b"0 A B C D E F G H I J K L M N O\n");
+        paint.push(b"0");
+        for i in 0..size {
+            let col_name = [' ' as u8, 0x41 + i as u8];
+            paint.push(&col_name);
+        }
+        paint.push_one('\n' as u8);
 
-        for i in 0..BOARD_SIZE {
+        for i in 0..size {
             let row_name = [0x61 + i as u8, ' ' as u8];
             paint.push(&row_name);
 
-            for j in 0..BOARD_SIZE {
+            for j in 0..size {
                 match self.board[i][j] {
                     Player::Black => paint.push(b"X "),
                     Player::White => paint.push(b"O "),
@@ -236,6 +927,31 @@ impl Game {
         paint.write()
     }
 
+    /// Return the number of the stones on the board.
+    ///
+    /// Unlike [ply](#method.ply), it counts the stones of the game constructed from the position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Player};
+    /// let game = Game::with_handicap(15, Player::Black, &[(7, 7), (3, 3)]).unwrap();
+    /// assert_eq!(game.ply(), 0);
+    /// assert_eq!(game.num_stones(), 2);
+    /// ```
+    pub fn num_stones(&self) -> usize {
+        self.board
+            .as_slice()
+            .iter()
+            .filter(|x| **x != Player::None)
+            .count()
+    }
+
+    /// Return true if there is no empty cell on the board
+    pub fn is_full(&self) -> bool {
+        self.board.as_slice().iter().all(|x| *x != Player::None)
+    }
+
     /// Return game winner if game end, else Player::None
     ///
     /// # Examples
@@ -247,8 +963,108 @@ impl Game {
     /// assert_eq!(game.is_game_end(), Player::None);
     /// ```
     pub fn is_game_end(&self) -> Player {
-        use super::search_winner::search;
-        search(&self.board)
+        search_with(&self.board, &self.rule)
+    }
+
+    /// Return status of the game, win, draw or ongoing.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, GameStatus, Player};
+    /// let mut game = Game::with_size(1);
+    /// assert_eq!(game.status(), GameStatus::Ongoing);
+    ///
+    /// game.set((0, 0)).unwrap();
+    /// assert_eq!(game.status(), GameStatus::Draw);
+    /// ```
+    pub fn status(&self) -> GameStatus {
+        let winner = search_with(&self.board, &self.rule);
+        if winner != Player::None {
+            GameStatus::Win(winner)
+        } else if self.is_full() {
+            GameStatus::Draw
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+}
+
+/// Serialized state of `Game`, it is validated and the hash of the board is recomputed on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct GameState {
+    turn: Player,
+    num_remain: i32,
+    board: Board,
+    #[serde(default)]
+    rule: Rule,
+    history: Vec<SetResult>,
+    undone: Vec<SetResult>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GameState> for Game {
+    type Error = String;
+
+    /// Validate the rule, the turn and that the history is replayed onto the board.
+    fn try_from(state: GameState) -> Result<Game, String> {
+        let rule = state.rule;
+        if rule.win_length == 0 || rule.stones_per_turn <= 0 || rule.opening_stones <= 0 {
+            return Err(format!("invalid rule {:?}", rule));
+        }
+        let max_remain = rule.stones_per_turn.max(rule.opening_stones);
+        if state.turn == Player::None || state.num_remain <= 0 || state.num_remain > max_remain {
+            return Err(format!(
+                "invalid turn {:?} with {} remaining stones",
+                state.turn, state.num_remain
+            ));
+        }
+
+        // take back the history from the last stone, checking the turn before each stone
+        let mut board = state.board.clone();
+        let mut turn = (state.turn, state.num_remain);
+        for result in state.history.iter().rev() {
+            let (row, col) = result.position;
+            if row >= board.size() || col >= board.size() || board[row][col] != result.player {
+                return Err(format!(
+                    "history {:?} doesn't match the board",
+                    result.position
+                ));
+            }
+            let next = if result.num_remain <= 0 {
+                (result.player.switch(), rule.stones_per_turn)
+            } else {
+                (result.player, result.num_remain)
+            };
+            if result.player == Player::None || result.num_remain < 0 || next != turn {
+                return Err(format!(
+                    "history {:?} doesn't match the turn",
+                    result.position
+                ));
+            }
+            board[row][col] = Player::None;
+            turn = (result.player, result.num_remain + 1);
+        }
+
+        Ok(Game {
+            turn: state.turn,
+            num_remain: state.num_remain,
+            zobrist: Zobrist::from_board(&state.board),
+            board: state.board,
+            rule,
+            history: state.history,
+            undone: state.undone,
+        })
+    }
+}
+
+impl FromStr for Game {
+    type Err = Box<error::Error + Send>;
+
+    /// Parse the game from the ascii diagram, reference [from_diagram](#method.from_diagram).
+    fn from_str(diagram: &str) -> Result<Game, Self::Err> {
+        Game::from_diagram(diagram)
     }
 }
 
@@ -1,4 +1,5 @@
 use super::*;
+use game::{Path, Rule};
 
 #[test]
 fn test_new() {
@@ -7,10 +8,45 @@ fn test_new() {
     assert_eq!(game.get_turn(), Player::Black);
     assert_eq!(game.get_remain(), 1);
 
-    let sample_board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let sample_board = Board::new(BOARD_SIZE);
     assert_eq!(*game.get_board(), sample_board);
 }
 
+#[test]
+fn test_with_size() {
+    let mut game = Game::with_size(9);
+    assert_eq!(game.get_size(), 9);
+    assert_eq!(*game.get_board(), Board::new(9));
+
+    assert!(game.set((8, 8)).is_ok());
+    match game.set((9, 0)) {
+        Ok(_) => assert!(false),
+        Err(e) => assert_eq!(e.description(), "invalid position"),
+    };
+}
+
+#[test]
+#[should_panic]
+fn test_with_size_over() {
+    Game::with_size(MAX_BOARD_SIZE + 1);
+}
+
+#[test]
+fn test_size_bound() {
+    let game = Game::with_size(MAX_BOARD_SIZE);
+    let mut buffer = Vec::new();
+    game.print(&mut buffer).unwrap();
+    let diagram = String::from_utf8(buffer).unwrap();
+    assert!(diagram.starts_with("0 A B"));
+    assert!(diagram.lines().last().unwrap().starts_with("z _"));
+
+    let board = Board::new(MAX_BOARD_SIZE + 1);
+    assert!(Game::from_position(board, Player::Black, 1).is_err());
+    assert!(Game::with_handicap(0, Player::Black, &[]).is_err());
+    assert!(Game::with_handicap(MAX_BOARD_SIZE + 1, Player::Black, &[]).is_err());
+    assert!(Game::from_opening(MAX_BOARD_SIZE + 1, &[]).is_err());
+}
+
 #[test]
 fn test_play() {
     let mut game = Game::new();
@@ -20,6 +56,7 @@ fn test_play() {
         player: Player::Black,
         num_remain: 0,
         position: (0, 0),
+        line: None,
     };
 
     assert_eq!(result, expected);
@@ -38,6 +75,97 @@ fn test_play() {
     };
 }
 
+#[test]
+fn test_undo_redo() {
+    let mut game = Game::new();
+    assert!(game.undo().is_none());
+    assert!(game.redo().is_none());
+
+    let positions = [(0, 0), (1, 1), (1, 2), (2, 2), (2, 3)];
+    for pos in positions.iter() {
+        game.set(*pos).unwrap();
+    }
+    assert_eq!(game.ply(), 5);
+    assert_eq!(game.turn, Player::White);
+    assert_eq!(game.num_remain, 2);
+
+    let states = [
+        (Player::Black, 1),
+        (Player::Black, 2),
+        (Player::White, 1),
+        (Player::White, 2),
+    ];
+    for (pos, state) in positions.iter().rev().zip(states.iter()) {
+        let result = game.undo().unwrap();
+        assert_eq!(result.position, *pos);
+        assert_eq!(game.board[pos.0][pos.1], Player::None);
+        assert_eq!((game.turn, game.num_remain), *state);
+    }
+
+    let result = game.undo().unwrap();
+    assert_eq!(result.position, (0, 0));
+    assert_eq!(game.turn, Player::Black);
+    assert_eq!(game.num_remain, 1);
+    assert_eq!(game.ply(), 0);
+    assert_eq!(game.board, Board::new(BOARD_SIZE));
+    assert_eq!(game.zobrist, Zobrist::new(BOARD_SIZE));
+
+    for pos in positions.iter() {
+        let result = game.redo().unwrap();
+        assert_eq!(result.position, *pos);
+    }
+    assert!(game.redo().is_none());
+    assert_eq!(game.turn, Player::White);
+    assert_eq!(game.num_remain, 2);
+    assert_eq!(game.zobrist, Zobrist::from_board(&game.board));
+
+    let history = game
+        .history()
+        .iter()
+        .map(|x| x.position)
+        .collect::<Vec<_>>();
+    assert_eq!(history, positions);
+}
+
+#[test]
+fn test_set_clear_redo() {
+    let mut game = Game::new();
+    game.set((0, 0)).unwrap();
+    game.set((1, 1)).unwrap();
+
+    game.undo();
+    game.set((2, 2)).unwrap();
+    assert!(game.redo().is_none());
+    assert_eq!(game.board[1][1], Player::None);
+    assert_eq!(game.history()[1].position, (2, 2));
+}
+
+#[test]
+fn test_set_winning_line() {
+    let mut game = Game::new();
+    game.set((7, 0)).unwrap();
+    for i in 0..5 {
+        let result = game.set((i, 1)).unwrap();
+        assert!(result.line.is_none());
+        game.set((i, 2)).unwrap();
+
+        if i < 4 {
+            game.set((i + 8, 3)).unwrap();
+            game.set((i + 8, 4)).unwrap();
+        }
+    }
+    // black turn, not continuous
+    game.set((13, 3)).unwrap();
+    game.set((14, 3)).unwrap();
+
+    let result = game.set((5, 1)).unwrap();
+    let line = result.line.unwrap();
+    assert_eq!(line.player, Player::White);
+    assert_eq!(line.path, Path::Down);
+    assert_eq!(line.stones, (0..6).map(|i| (i, 1)).collect::<Vec<_>>());
+    assert_eq!(game.history().last().unwrap().line, Some(line));
+}
+
 #[test]
 fn test_print() {
     //        let game = Game::new();
@@ -50,3 +178,373 @@ fn test_is_game_end() {
     let game = Game::new();
     assert_eq!(game.is_game_end(), Player::None);
 }
+
+#[test]
+fn test_status() {
+    let mut game = Game::with_size(6);
+    assert_eq!(game.status(), GameStatus::Ongoing);
+    assert!(!game.status().is_end());
+
+    // black (0, 0), white and black fill the rows alternatively
+    game.set((0, 0)).unwrap();
+    for i in 1..6 {
+        game.set((1, i)).unwrap();
+        game.set((2, i)).unwrap();
+        game.set((3, i)).unwrap();
+        game.set((0, i)).unwrap();
+    }
+    assert_eq!(game.status(), GameStatus::Win(Player::Black));
+    assert_eq!(game.status().winner(), Player::Black);
+    assert!(game.status().is_end());
+}
+
+#[test]
+fn test_status_draw() {
+    let mut game = Game::with_size(3);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(game.status(), GameStatus::Ongoing);
+            game.set((i, j)).unwrap();
+        }
+    }
+    assert!(game.is_full());
+    assert_eq!(game.status(), GameStatus::Draw);
+    assert_eq!(game.status().winner(), Player::None);
+}
+
+#[test]
+fn test_from_diagram() {
+    let mut game = Game::with_size(9);
+    let positions = [(0, 0), (1, 1), (1, 2), (2, 2), (2, 3), (8, 8), (8, 0)];
+    for pos in positions.iter() {
+        game.set(*pos).unwrap();
+    }
+    let mut diagram = Vec::new();
+    game.print(&mut diagram).unwrap();
+
+    let parsed = String::from_utf8(diagram).unwrap().parse::<Game>().unwrap();
+    assert_eq!(parsed.board, game.board);
+    assert_eq!(parsed.turn, game.turn);
+    assert_eq!(parsed.num_remain, game.num_remain);
+    assert_eq!(parsed.zobrist, game.zobrist);
+    assert_eq!(parsed.ply(), 0);
+}
+
+#[test]
+fn test_from_diagram_empty_board() {
+    let game = Game::from_diagram("a _ _\nb _ _").unwrap();
+    assert_eq!(game.get_size(), 2);
+    assert_eq!(game.get_turn(), Player::Black);
+    assert_eq!(game.get_remain(), 1);
+}
+
+#[test]
+fn test_from_diagram_invalid() {
+    let cases = [
+        "",
+        "a _ _\nc _ _",
+        "a _ _\nb _",
+        "a _ Y\nb _ _",
+        // white first
+        "a O _\nb _ _",
+        // black placed three stones
+        "a X X\nb X O",
+    ];
+    for case in cases.iter() {
+        match Game::from_diagram(case) {
+            Ok(_) => assert!(false),
+            Err(err) => assert_eq!(err.description(), "invalid diagram"),
+        }
+    }
+
+    // rows over the lowercase letters
+    let row = vec!["_"; 27].join(" ");
+    let diagram = (0..27)
+        .map(|_| format!("a {}", row))
+        .collect::<Vec<_>>()
+        .join("\n");
+    match Game::from_diagram(&diagram) {
+        Ok(_) => assert!(false),
+        Err(err) => assert!(err.to_string().contains("27 rows")),
+    }
+}
+
+#[test]
+fn test_from_diagram_with_turn() {
+    let diagram = "a X O\nb _ _";
+    let game = Game::from_diagram_with_turn(diagram, Player::White, 1).unwrap();
+    assert_eq!(game.get_turn(), Player::White);
+    assert_eq!(game.get_remain(), 1);
+
+    assert!(Game::from_diagram_with_turn(diagram, Player::White, 2).is_err());
+    assert!(Game::from_diagram_with_turn(diagram, Player::Black, 1).is_err());
+
+    match Game::from_diagram_with_turn(diagram, Player::Black, 2) {
+        Ok(_) => assert!(false),
+        Err(err) => assert!(err
+            .to_string()
+            .contains("expected turn Black with 2 stones, but White with 1")),
+    }
+}
+
+#[test]
+fn test_with_rule_gomoku() {
+    let mut game = Game::with_rule(9, Rule::gomoku());
+    assert_eq!(game.get_rule(), Rule::gomoku());
+
+    for i in 0..4 {
+        assert_eq!(game.get_turn(), Player::Black);
+        assert_eq!(game.get_remain(), 1);
+        game.set((0, i)).unwrap();
+        assert_eq!(game.get_turn(), Player::White);
+        game.set((1, i)).unwrap();
+    }
+    assert_eq!(game.status(), GameStatus::Ongoing);
+
+    let result = game.set((0, 4)).unwrap();
+    let line = result.line.unwrap();
+    assert_eq!(line.player, Player::Black);
+    assert_eq!(line.stones.len(), 5);
+    assert_eq!(game.status(), GameStatus::Win(Player::Black));
+
+    game.undo();
+    assert_eq!(game.get_turn(), Player::Black);
+    assert_eq!(game.get_remain(), 1);
+}
+
+#[test]
+fn test_with_rule_opening() {
+    let mut game = Game::with_rule(9, Rule::new(6, 3, 2));
+    let expected = [
+        (Player::Black, 2),
+        (Player::Black, 1),
+        (Player::White, 3),
+        (Player::White, 2),
+        (Player::White, 1),
+        (Player::Black, 3),
+    ];
+    for (i, &(turn, num_remain)) in expected.iter().enumerate() {
+        assert_eq!((game.get_turn(), game.get_remain()), (turn, num_remain));
+        game.set((i, 0)).unwrap();
+    }
+}
+
+#[test]
+fn test_with_rule_exact() {
+    let mut game = Game::with_rule(9, Rule::connect6().with_overline(false));
+    let record = [
+        (0, 0),
+        (8, 0),
+        (6, 2),
+        (0, 1),
+        (0, 2),
+        (8, 4),
+        (6, 6),
+        (0, 4),
+        (0, 5),
+        (4, 0),
+        (4, 8),
+        (0, 6),
+    ];
+    for pos in record.iter() {
+        let result = game.set(*pos).unwrap();
+        assert!(result.line.is_none());
+    }
+    // black connects seven stones, overline does not win
+    let result = game.set((0, 3)).unwrap();
+    assert!(result.line.is_none());
+    assert_eq!(game.status(), GameStatus::Ongoing);
+}
+
+#[test]
+fn test_from_diagram_with_rule() {
+    let diagram = "a X O X\nb O _ _\nc _ _ _";
+    let game = Game::from_diagram_with_rule(diagram, Rule::gomoku()).unwrap();
+    assert_eq!(game.get_turn(), Player::Black);
+    assert_eq!(game.get_remain(), 1);
+    assert_eq!(game.get_rule(), Rule::gomoku());
+
+    // black could not be ahead by two stones
+    let diagram = "a X X X\nb O _ _\nc _ _ _";
+    assert!(Game::from_diagram_with_rule(diagram, Rule::gomoku()).is_err());
+}
+
+#[test]
+fn test_from_position() {
+    let mut board = Board::new(9);
+    board[0][0] = Player::White;
+    board[0][1] = Player::White;
+    board[0][2] = Player::White;
+
+    let mut game = Game::from_position(board.clone(), Player::Black, 1).unwrap();
+    assert_eq!(*game.get_board(), board);
+    assert_eq!(game.ply(), 0);
+    assert_eq!(game.zobrist().key(), Zobrist::from_board(&board).key());
+
+    game.set((4, 4)).unwrap();
+    assert_eq!(game.get_turn(), Player::White);
+    assert_eq!(game.get_remain(), 2);
+
+    assert!(Game::from_position(board.clone(), Player::None, 1).is_err());
+    assert!(Game::from_position(board.clone(), Player::Black, 0).is_err());
+    assert!(Game::from_position(board.clone(), Player::Black, 3).is_err());
+
+    // already finished
+    for i in 3..6 {
+        board[0][i] = Player::White;
+    }
+    match Game::from_position(board, Player::Black, 2) {
+        Ok(_) => assert!(false),
+        Err(err) => assert_eq!(err.description(), "invalid state"),
+    }
+
+    // no empty cell
+    let board = Board::filled(1, Player::Black);
+    assert!(Game::from_position(board, Player::White, 1).is_err());
+}
+
+#[test]
+fn test_with_handicap() {
+    let stones = [(4, 4), (4, 6), (6, 4)];
+    let game = Game::with_handicap(9, Player::Black, &stones).unwrap();
+    for &(row, col) in stones.iter() {
+        assert_eq!(game.get_board()[row][col], Player::Black);
+    }
+    assert_eq!(game.get_turn(), Player::White);
+    assert_eq!(game.get_remain(), 2);
+
+    let game = Game::with_handicap(9, Player::White, &[]).unwrap();
+    assert_eq!(game.get_turn(), Player::Black);
+
+    assert!(Game::with_handicap(9, Player::None, &stones).is_err());
+    assert!(Game::with_handicap(9, Player::Black, &[(9, 0)]).is_err());
+    assert!(Game::with_handicap(9, Player::Black, &[(1, 1), (1, 1)]).is_err());
+}
+
+#[test]
+fn test_from_opening() {
+    let opening = [(4, 4), (3, 3), (5, 5)];
+    let game = Game::from_opening(9, &opening).unwrap();
+    assert_eq!(game.ply(), 3);
+    assert_eq!(game.get_turn(), Player::Black);
+    assert_eq!(game.get_remain(), 2);
+    assert_eq!(game.get_board()[3][3], Player::White);
+
+    assert!(Game::from_opening(9, &[(0, 0), (0, 0)]).is_err());
+
+    let winning = [
+        (0, 0),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (0, 2),
+        (2, 0),
+        (2, 2),
+        (0, 3),
+        (0, 4),
+        (3, 0),
+        (3, 3),
+        (0, 5),
+    ];
+    match Game::from_opening(9, &winning) {
+        Ok(_) => assert!(false),
+        Err(err) => assert_eq!(err.description(), "invalid state"),
+    }
+}
+
+#[test]
+fn test_with_rule_positional() {
+    let game = Game::with_handicap_with_rule(9, Player::Black, &[(4, 4)], Rule::gomoku()).unwrap();
+    assert_eq!(game.get_turn(), Player::White);
+    assert_eq!(game.get_remain(), 1);
+    assert_eq!(game.get_rule(), Rule::gomoku());
+
+    let stones = [(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)];
+    assert!(Game::with_handicap(9, Player::Black, &stones).is_ok());
+    assert!(Game::with_handicap_with_rule(9, Player::Black, &stones, Rule::gomoku()).is_err());
+
+    let game = Game::from_opening_with_rule(9, &[(4, 4), (3, 3)], Rule::gomoku()).unwrap();
+    assert_eq!(game.ply(), 2);
+    assert_eq!(game.get_turn(), Player::Black);
+    assert_eq!(game.get_remain(), 1);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use bincode;
+    use serde_json;
+
+    let mut game = Game::with_size(9);
+    for pos in [(0, 0), (1, 1), (1, 2), (2, 2)].iter() {
+        game.set(*pos).unwrap();
+    }
+    game.undo();
+
+    let check = |parsed: Game| {
+        assert_eq!(parsed.turn, game.turn);
+        assert_eq!(parsed.num_remain, game.num_remain);
+        assert_eq!(parsed.board, game.board);
+        assert_eq!(parsed.zobrist, game.zobrist);
+        assert_eq!(parsed.history, game.history);
+        assert_eq!(parsed.undone, game.undone);
+    };
+
+    let json = serde_json::to_string(&game).unwrap();
+    assert!(!json.contains("zobrist"));
+    check(serde_json::from_str(&json).unwrap());
+
+    let encoded = bincode::serialize(&game).unwrap();
+    check(bincode::deserialize(&encoded).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_invalid() {
+    use serde_json;
+
+    let mut board = Board::new(9);
+    board[4][4] = Player::Black;
+    let mut game = Game::from_position(board, Player::White, 2).unwrap();
+    game.set((0, 0)).unwrap();
+    game.set((1, 1)).unwrap();
+    let json = serde_json::to_string(&game).unwrap();
+    let parsed = serde_json::from_str::<Game>(&json).unwrap();
+    assert_eq!(parsed.history, game.history);
+
+    let invalid = [
+        // stone of the history isn't on the board
+        json.replace(r#""position":[1,1]"#, r#""position":[2,2]"#),
+        // history out of the board
+        json.replace(r#""position":[1,1]"#, r#""position":[9,9]"#),
+        // turn doesn't follow the history
+        json.replace(r#""turn":-1,"num_remain":2"#, r#""turn":1,"num_remain":2"#),
+        json.replace(r#""turn":-1,"num_remain":2"#, r#""turn":-1,"num_remain":1"#),
+        json.replace(r#""turn":-1,"num_remain":2"#, r#""turn":-1,"num_remain":3"#),
+        // rule without stones
+        json.replace(r#""stones_per_turn":2"#, r#""stones_per_turn":0"#),
+    ];
+    for case in invalid.iter() {
+        assert_ne!(case, &json);
+        assert!(serde_json::from_str::<Game>(case).is_err());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_set_result() {
+    use serde_json;
+
+    let result = SetResult {
+        player: Player::White,
+        num_remain: 1,
+        position: (3, 4),
+        line: None,
+    };
+    let json = serde_json::to_string(&result).unwrap();
+    assert_eq!(
+        json,
+        r#"{"player":1,"num_remain":1,"position":[3,4],"line":null}"#
+    );
+    assert_eq!(serde_json::from_str::<SetResult>(&json).unwrap(), result);
+}
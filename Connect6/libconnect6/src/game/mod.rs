@@ -11,10 +11,18 @@
 //! let winner = game.is_game_end();
 //! assert_eq!(winner, Player::None);
 //! ```
-pub use self::game_impl::{Game, Paint, SetResult};
+pub use self::game_impl::{Game, GameStatus, Paint, SetResult};
 pub use self::player::Player;
-pub use self::search_winner::{search, Block, Cumulative, Path};
+pub use self::rule::Rule;
+pub use self::search_winner::{
+    search, search_from, search_from_with, search_with, Block, Cumulative, Path, WinningLine,
+};
+pub use self::zobrist::Zobrist;
+
+pub(crate) use self::zobrist::mix;
 
 mod game_impl;
 mod player;
+mod rule;
 mod search_winner;
+mod zobrist;
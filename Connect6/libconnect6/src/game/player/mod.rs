@@ -1,5 +1,8 @@
 use std::default::Default;
 
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
 #[cfg(test)]
 mod tests;
 
@@ -59,3 +62,24 @@ impl From<i32> for Player {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+impl Serialize for Player {
+    /// Serialize as integer, { -1: Black, 0: None, 1: White }
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(*self as i32)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Player {
+    /// Deserialize from integer, { -1: Black, 0: None, 1: White }
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Player, D::Error> {
+        match i32::deserialize(deserializer)? {
+            -1 => Ok(Player::Black),
+            0 => Ok(Player::None),
+            1 => Ok(Player::White),
+            num => Err(de::Error::custom(format!("invalid player {}", num))),
+        }
+    }
+}
@@ -26,3 +26,16 @@ fn test_mut_switch() {
     player.mut_switch();
     assert_eq!(player, Player::Black);
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde_json;
+
+    for player in [Player::Black, Player::None, Player::White].iter() {
+        let json = serde_json::to_string(player).unwrap();
+        assert_eq!(json, (*player as i32).to_string());
+        assert_eq!(serde_json::from_str::<Player>(&json).unwrap(), *player);
+    }
+    assert!(serde_json::from_str::<Player>("2").is_err());
+}
@@ -0,0 +1,189 @@
+//! Rule set of k-in-a-row games, generalized Connect(k, p, q).
+//!
+//! - k : number of the continuous stones to win.
+//! - p : number of the stones placed in each turn.
+//! - q : number of the stones placed in the first turn, by black.
+//! - overline : whether more than k continuous stones also win.
+//!
+//! Default rule is Connect6, k = 6, p = 2, q = 1 with overline.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::game::{Game, Player, Rule};
+//! let mut game = Game::with_rule(15, Rule::gomoku());
+//! for i in 0..4 {
+//!     game.set((0, i)).unwrap(); // black
+//!     game.set((1, i)).unwrap(); // white
+//! }
+//! let result = game.set((0, 4)).unwrap();
+//! assert_eq!(result.line.unwrap().player, Player::Black);
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+/// Rule set of k-in-a-row games, win length k, stones per turn p, opening stones q and overline.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rule {
+    pub win_length: usize,
+    pub stones_per_turn: i32,
+    pub opening_stones: i32,
+    pub overline: bool,
+}
+
+impl Default for Rule {
+    /// Generate default rule, Connect6
+    fn default() -> Rule {
+        Rule::connect6()
+    }
+}
+
+impl Rule {
+    /// Construct a new `Rule` with win length k, stones per turn p and opening stones q.
+    ///
+    /// Overline is allowed, reference [with_overline](#method.with_overline).
+    ///
+    /// # Panics
+    /// If any of k, p, q is zero.
+    pub fn new(win_length: usize, stones_per_turn: i32, opening_stones: i32) -> Rule {
+        assert!(win_length > 0, "win length should be positive");
+        assert!(stones_per_turn > 0, "stones per turn should be positive");
+        assert!(opening_stones > 0, "opening stones should be positive");
+        Rule {
+            win_length,
+            stones_per_turn,
+            opening_stones,
+            overline: true,
+        }
+    }
+
+    /// Connect6, k = 6, p = 2, q = 1 with overline.
+    pub fn connect6() -> Rule {
+        Rule::new(6, 2, 1)
+    }
+
+    /// Freestyle gomoku, k = 5, p = 1, q = 1 with overline.
+    pub fn gomoku() -> Rule {
+        Rule::new(5, 1, 1)
+    }
+
+    /// Set whether more than k continuous stones also win.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Rule;
+    /// let exact_six = Rule::connect6().with_overline(false);
+    /// assert!(!exact_six.overline);
+    /// ```
+    pub fn with_overline(mut self, overline: bool) -> Rule {
+        self.overline = overline;
+        self
+    }
+
+    /// Return true if the continuous stones of given length win.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Rule;
+    /// let rule = Rule::connect6();
+    /// assert!(!rule.is_win(5));
+    /// assert!(rule.is_win(7));
+    /// assert!(!rule.with_overline(false).is_win(7));
+    /// ```
+    pub fn is_win(&self, length: usize) -> bool {
+        if self.overline {
+            length >= self.win_length
+        } else {
+            length == self.win_length
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Write the rule as notation, `Connect6`, `Gomoku` or `Connect(k,p,q)`.
+    ///
+    /// Rule without overline is written as `Connect(k,p,q,exact)`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Rule;
+    /// assert_eq!(Rule::connect6().to_string(), "Connect6");
+    /// assert_eq!(Rule::new(4, 1, 1).to_string(), "Connect(4,1,1)");
+    /// assert_eq!(Rule::gomoku().with_overline(false).to_string(), "Connect(5,1,1,exact)");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if *self == Rule::connect6() {
+            write!(f, "Connect6")
+        } else if *self == Rule::gomoku() {
+            write!(f, "Gomoku")
+        } else {
+            write!(
+                f,
+                "Connect({},{},{}",
+                self.win_length, self.stones_per_turn, self.opening_stones
+            )?;
+            if !self.overline {
+                write!(f, ",exact")?;
+            }
+            write!(f, ")")
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// Parse the rule from the notation written by `Display`, case insensitive.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Rule;
+    /// assert_eq!("gomoku".parse::<Rule>(), Ok(Rule::gomoku()));
+    /// assert_eq!("Connect(6,2,1)".parse::<Rule>(), Ok(Rule::connect6()));
+    /// assert!("Connect(6,0,1)".parse::<Rule>().is_err());
+    /// ```
+    fn from_str(text: &str) -> Result<Rule, String> {
+        let lower = text.trim().to_lowercase();
+        match lower.as_str() {
+            "connect6" => return Ok(Rule::connect6()),
+            "gomoku" => return Ok(Rule::gomoku()),
+            _ => (),
+        }
+
+        let invalid = || format!("invalid rule {}", text);
+        if !lower.starts_with("connect(") || !lower.ends_with(')') {
+            return Err(invalid());
+        }
+        let params = lower["connect(".len()..lower.len() - 1]
+            .split(',')
+            .map(|x| x.trim())
+            .collect::<Vec<_>>();
+
+        let overline = match params.get(3) {
+            None => true,
+            Some(&"exact") if params.len() == 4 => false,
+            _ => return Err(invalid()),
+        };
+        let win_length = params[0].parse::<usize>().map_err(|_| invalid())?;
+        let stones_per_turn = params
+            .get(1)
+            .and_then(|x| x.parse::<i32>().ok())
+            .ok_or_else(invalid)?;
+        let opening_stones = params
+            .get(2)
+            .and_then(|x| x.parse::<i32>().ok())
+            .ok_or_else(invalid)?;
+        if win_length == 0 || stones_per_turn <= 0 || opening_stones <= 0 {
+            return Err(invalid());
+        }
+        Ok(Rule::new(win_length, stones_per_turn, opening_stones).with_overline(overline))
+    }
+}
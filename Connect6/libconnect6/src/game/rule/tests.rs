@@ -0,0 +1,67 @@
+use super::*;
+
+#[test]
+fn test_default() {
+    let rule = Rule::default();
+    assert_eq!(rule, Rule::connect6());
+    assert_eq!(rule.win_length, 6);
+    assert_eq!(rule.stones_per_turn, 2);
+    assert_eq!(rule.opening_stones, 1);
+    assert!(rule.overline);
+}
+
+#[test]
+fn test_gomoku() {
+    let rule = Rule::gomoku();
+    assert_eq!(rule.win_length, 5);
+    assert_eq!(rule.stones_per_turn, 1);
+    assert_eq!(rule.opening_stones, 1);
+}
+
+#[test]
+fn test_is_win() {
+    let rule = Rule::connect6();
+    assert!(!rule.is_win(5));
+    assert!(rule.is_win(6));
+    assert!(rule.is_win(8));
+
+    let exact = rule.with_overline(false);
+    assert!(!exact.is_win(5));
+    assert!(exact.is_win(6));
+    assert!(!exact.is_win(7));
+}
+
+#[test]
+#[should_panic]
+fn test_new_zero_length() {
+    Rule::new(0, 1, 1);
+}
+
+#[test]
+fn test_notation() {
+    let rules = [
+        Rule::connect6(),
+        Rule::gomoku(),
+        Rule::new(4, 1, 1),
+        Rule::new(6, 3, 2).with_overline(false),
+        Rule::connect6().with_overline(false),
+    ];
+    for rule in rules.iter() {
+        assert_eq!(rule.to_string().parse::<Rule>(), Ok(*rule));
+    }
+    assert_eq!(" CONNECT( 5, 1, 1 ) ".parse::<Rule>(), Ok(Rule::gomoku()));
+
+    let invalid = [
+        "",
+        "connect",
+        "Connect(6,2)",
+        "Connect(6,2,1,2)",
+        "Connect(6,2,1,exact,exact)",
+        "Connect(0,2,1)",
+        "Connect(6,-1,1)",
+        "Connect(6,2,x)",
+    ];
+    for case in invalid.iter() {
+        assert!(case.parse::<Rule>().is_err(), "{}", case);
+    }
+}
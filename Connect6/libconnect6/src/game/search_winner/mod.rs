@@ -13,8 +13,14 @@
 //! let winner = search(game.get_board());
 //! assert_eq!(winner, Player::None);
 //! ```
-use game::Player;
-use {Board, BOARD_SIZE};
+//!
+//! For the game played stone by stone, `search_from` finds the winner incrementally
+//! by scanning only four lines through the last stone.
+//!
+//! `search_with` and `search_from_with` find the winner under the given `Rule`,
+//! k continuous stones with or without overline.
+use game::{Player, Rule};
+use Board;
 
 #[cfg(test)]
 mod tests;
@@ -23,7 +29,8 @@ mod tests;
 ///
 /// For top-left to bottom-right search, only four direction is required to find the continuous 6 stones.
 /// Right-Horizontal, Down-Vertial, RightDown-Diagonal, LeftDown-Diagonal.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Path {
     Right,
     Down,
@@ -31,6 +38,36 @@ pub enum Path {
     LeftDown,
 }
 
+impl Path {
+    /// Get a (row, col) step of the direction.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::Path;
+    /// assert_eq!(Path::LeftDown.delta(), (1, -1));
+    /// ```
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            &Path::Right => (0, 1),
+            &Path::Down => (1, 0),
+            &Path::RightDown => (1, 1),
+            &Path::LeftDown => (1, -1),
+        }
+    }
+}
+
+/// Continuous stones of the winner.
+///
+/// `stones` is ordered along the direction `path`, it may contain more than k stones if overline is allowed.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WinningLine {
+    pub player: Player,
+    pub path: Path,
+    pub stones: Vec<(usize, usize)>,
+}
+
 /// Number of the continuous stones for each directions.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Cumulative {
@@ -93,22 +130,26 @@ impl Cumulative {
 ///
 /// `flag` represent index of previous arrays.
 /// Method swap can be implemented as just flip the flag bit.
+/// Each array has `size + 2` cells for padding both sides of the row.
 pub struct Block {
     flag: usize,
-    mem: [[Cumulative; BOARD_SIZE + 2]; 2],
+    mem: [Vec<Cumulative>; 2],
 }
 
 impl Block {
-    /// Construct a new Block.
-    pub fn new() -> Block {
+    /// Construct a new Block for the board with given length of one side.
+    pub fn new(size: usize) -> Block {
         Block {
             flag: 0,
-            mem: [[Cumulative::new(); BOARD_SIZE + 2]; 2],
+            mem: [
+                vec![Cumulative::new(); size + 2],
+                vec![Cumulative::new(); size + 2],
+            ],
         }
     }
 
     /// Get a tuple representation of block, (prev, current).
-    pub fn as_tuple(&self) -> (&[Cumulative; BOARD_SIZE + 2], &[Cumulative; BOARD_SIZE + 2]) {
+    pub fn as_tuple(&self) -> (&[Cumulative], &[Cumulative]) {
         let f = self.flag;
         (&self.mem[f], &self.mem[1 - f])
     }
@@ -124,7 +165,7 @@ impl Block {
     /// ```rust
     /// # extern crate connect6;
     /// # use connect6::game::{Block, Path};
-    /// let block = Block::new();
+    /// let block = Block::new(15);
     /// let (prev, current) = block.as_tuple();
     /// let result = block.get_prev(1, &Path::Right);
     /// assert_eq!(*result, current[0]);
@@ -145,12 +186,12 @@ impl Block {
     /// ```rust
     /// # extern crate connect6;
     /// # use connect6::game::{Block, Path};
-    /// let mut block = Block::new();
+    /// let mut block = Block::new(15);
     /// block.update_now(|row| row.iter_mut().for_each(|c| *c.get_mut(&Path::Right) = 1));
     /// ```
     pub fn update_now<F>(&mut self, update: F)
     where
-        F: Fn(&mut [Cumulative; BOARD_SIZE + 2]),
+        F: Fn(&mut [Cumulative]),
     {
         let f = self.flag;
         let now = &mut self.mem[1 - f];
@@ -162,23 +203,23 @@ impl Block {
     /// # Examples
     /// ```rust
     /// # extern crate connect6;
-    /// # use connect6::{game::{Cumulative, Block}, BOARD_SIZE};
-    /// let mut block = Block::new();
+    /// # use connect6::game::{Cumulative, Block};
+    /// let mut block = Block::new(15);
     /// let current_backup = {
     ///     let (_, current) = block.as_tuple();
-    ///     *current
+    ///     current.to_vec()
     /// };
     /// block.update_row();
     /// let (prev, current) = block.as_tuple();
-    /// assert_eq!(*prev, current_backup);
-    /// assert_eq!(*current, [Cumulative::new(); BOARD_SIZE+2]);
+    /// assert_eq!(*prev, current_backup[..]);
+    /// assert_eq!(*current, [Cumulative::new(); 17][..]);
     /// ```
     pub fn update_row(&mut self) {
         self.flag = 1 - self.flag;
         let now = &mut self.mem[1 - self.flag];
 
-        for i in 0..BOARD_SIZE + 2 {
-            now[i] = Cumulative::new();
+        for cell in now.iter_mut() {
+            *cell = Cumulative::new();
         }
     }
 }
@@ -199,42 +240,71 @@ impl Block {
 /// assert_eq!(winner, Player::None);
 /// ```
 pub fn search(table: &Board) -> Player {
-    let mut black = Block::new();
-    let mut white = Block::new();
+    search_with(table, &Rule::default())
+}
+
+/// Algorithm for finding winner under the given rule.
+///
+/// Without overline, continuous stones win only if the next cell on the direction breaks them.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::{Player, Rule, search_with}, Board};
+/// let mut board = Board::new(15);
+/// for i in 0..5 {
+///     board[0][i] = Player::White;
+/// }
+/// assert_eq!(search_with(&board, &Rule::gomoku()), Player::White);
+///
+/// board[0][5] = Player::White;
+/// assert_eq!(search_with(&board, &Rule::gomoku().with_overline(false)), Player::None);
+/// ```
+pub fn search_with(table: &Board, rule: &Rule) -> Player {
+    let size = table.size();
+    let win_length = rule.win_length as i32;
+    let mut black = Block::new(size);
+    let mut white = Block::new(size);
+
+    // true if the continuous stones through given position end on the direction
+    let is_end = |row: usize, col: usize, path: &Path| {
+        let (dr, dc) = path.delta();
+        let (r, c) = (row as isize + dr, col as isize + dc);
+        let size = size as isize;
+        r < 0 || c < 0 || r >= size || c >= size || table[r as usize][c as usize] != table[row][col]
+    };
 
     // update the block if cell has stones
-    fn path_iter(block: &mut Block, col: usize) -> bool {
-        // convert to one-indexed array, for convenience
-        let col = col + 1;
+    let path_iter = |block: &mut Block, row: usize, col: usize| -> bool {
         let paths = [Path::Right, Path::Down, Path::RightDown, Path::LeftDown];
 
         for path in paths.iter() {
-            // update with previous cell
-            let updated = block.get_prev(col, path).get(path) + 1;
-            // find continuous six stones
-            if updated >= 6 {
+            // update with previous cell, convert to one-indexed array, for convenience
+            let updated = block.get_prev(col + 1, path).get(path) + 1;
+            // find continuous k stones, exactly k if overline is not allowed
+            if updated == win_length && (rule.overline || is_end(row, col, path)) {
                 return true;
             }
 
-            block.update_now(|now| *now[col].get_mut(path) = updated);
+            block.update_now(|now| *now[col + 1].get_mut(path) = updated);
         }
         false
-    }
+    };
 
-    for row in 0..BOARD_SIZE {
+    for row in 0..size {
         black.update_row();
         white.update_row();
 
-        for col in 0..BOARD_SIZE {
+        for col in 0..size {
             match table[row][col] {
                 Player::None => continue,
                 Player::Black => {
-                    if path_iter(&mut black, col) {
+                    if path_iter(&mut black, row, col) {
                         return Player::Black;
                     }
                 }
                 Player::White => {
-                    if path_iter(&mut white, col) {
+                    if path_iter(&mut white, row, col) {
                         return Player::White;
                     }
                 }
@@ -244,3 +314,88 @@ pub fn search(table: &Board) -> Player {
 
     Player::None
 }
+
+/// Find the winner incrementally with the four lines through given position.
+///
+/// It assumes that there was no winner before the stone of given position was placed,
+/// and return the continuous stones with its direction if the stone completes them.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::{Path, Player, search_from}, Board};
+/// let mut board = Board::new(15);
+/// for i in 0..6 {
+///     board[i][i] = Player::Black;
+/// }
+///
+/// let line = search_from(&board, (5, 5)).unwrap();
+/// assert_eq!(line.player, Player::Black);
+/// assert_eq!(line.path, Path::RightDown);
+/// assert_eq!(line.stones, (0..6).map(|i| (i, i)).collect::<Vec<_>>());
+/// ```
+pub fn search_from(table: &Board, pos: (usize, usize)) -> Option<WinningLine> {
+    search_from_with(table, pos, &Rule::default())
+}
+
+/// Find the winner incrementally under the given rule.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::{Player, Rule, search_from_with}, Board};
+/// let exact_six = Rule::connect6().with_overline(false);
+/// let mut board = Board::new(15);
+/// for i in 0..7 {
+///     board[i][0] = Player::Black;
+/// }
+/// assert!(search_from_with(&board, (6, 0), &exact_six).is_none());
+/// assert!(search_from_with(&board, (6, 0), &Rule::connect6()).is_some());
+/// ```
+pub fn search_from_with(table: &Board, pos: (usize, usize), rule: &Rule) -> Option<WinningLine> {
+    let (row, col) = pos;
+    let size = table.size() as isize;
+    if row as isize >= size || col as isize >= size {
+        return None;
+    }
+    let player = table[row][col];
+    if player == Player::None {
+        return None;
+    }
+
+    // next position of the same player on given direction
+    let step = |(row, col): (usize, usize), (dr, dc): (isize, isize)| {
+        let (r, c) = (row as isize + dr, col as isize + dc);
+        if r < 0 || c < 0 || r >= size || c >= size || table[r as usize][c as usize] != player {
+            None
+        } else {
+            Some((r as usize, c as usize))
+        }
+    };
+
+    let paths = [Path::Right, Path::Down, Path::RightDown, Path::LeftDown];
+    for path in paths.iter() {
+        let (dr, dc) = path.delta();
+        // move backward to the start of the continuous stones
+        let mut start = pos;
+        while let Some(prev) = step(start, (-dr, -dc)) {
+            start = prev;
+        }
+
+        let mut stones = vec![start];
+        let mut now = start;
+        while let Some(next) = step(now, (dr, dc)) {
+            stones.push(next);
+            now = next;
+        }
+
+        if rule.is_win(stones.len()) {
+            return Some(WinningLine {
+                player,
+                path: *path,
+                stones,
+            });
+        }
+    }
+    None
+}
@@ -1,5 +1,6 @@
 use super::*;
 use rand;
+use BOARD_SIZE;
 
 #[cfg(test)]
 mod block_tests {
@@ -17,25 +18,29 @@ mod block_tests {
     fn rand_block() -> Block {
         Block {
             flag: 0,
-            mem: [[rand_cumulative(); BOARD_SIZE + 2]; 2],
+            mem: [
+                vec![rand_cumulative(); BOARD_SIZE + 2],
+                vec![rand_cumulative(); BOARD_SIZE + 2],
+            ],
         }
     }
 
-    fn get_tuple(block: &Block) -> ([Cumulative; BOARD_SIZE + 2], [Cumulative; BOARD_SIZE + 2]) {
+    fn get_tuple(block: &Block) -> (Vec<Cumulative>, Vec<Cumulative>) {
         let (prev, now) = block.as_tuple();
-        (*prev, *now)
+        (prev.to_vec(), now.to_vec())
     }
 
     #[test]
     fn test_block_new() {
-        let block = Block::new();
+        let block = Block::new(BOARD_SIZE);
         assert_eq!(block.flag, 0);
-        assert_eq!(block.mem, [[Cumulative::new(); BOARD_SIZE + 2]; 2]);
+        assert_eq!(block.mem[0], vec![Cumulative::new(); BOARD_SIZE + 2]);
+        assert_eq!(block.mem[1], vec![Cumulative::new(); BOARD_SIZE + 2]);
     }
 
     #[test]
     fn test_as_tuple() {
-        let mut block = Block::new();
+        let mut block = Block::new(BOARD_SIZE);
         block.mem[0][0] = rand_cumulative();
         block.mem[1][0] = rand_cumulative();
 
@@ -67,7 +72,7 @@ mod block_tests {
 
     #[test]
     fn test_update_now() {
-        let mut block = Block::new();
+        let mut block = Block::new(BOARD_SIZE);
         let crand = rand_cumulative();
 
         block.update_now(|now| now[0] = crand.clone());
@@ -78,15 +83,15 @@ mod block_tests {
 
     #[test]
     fn test_update_row() {
-        let mut block = Block::new();
-        let crand = [rand_cumulative(); BOARD_SIZE + 2];
+        let mut block = Block::new(BOARD_SIZE);
+        let crand = vec![rand_cumulative(); BOARD_SIZE + 2];
 
         block.mem[1] = crand.clone();
         block.update_row();
 
         let (prev, now) = block.as_tuple();
-        assert_eq!(*prev, crand);
-        assert_eq!(*now, [Cumulative::new(); BOARD_SIZE + 2]);
+        assert_eq!(*prev, crand[..]);
+        assert_eq!(*now, [Cumulative::new(); BOARD_SIZE + 2][..]);
     }
 }
 
@@ -95,7 +100,7 @@ mod search_tests {
     use super::*;
 
     fn new_table() -> Board {
-        [[Player::None; BOARD_SIZE]; BOARD_SIZE]
+        Board::new(BOARD_SIZE)
     }
 
     #[test]
@@ -214,4 +219,151 @@ mod search_tests {
         cross_test!(left_down => table, 1);
         cross_test!(left_down => table, BOARD_SIZE-6);
     }
+
+    #[test]
+    fn test_sized_search() {
+        for size in [6, 9, 19].iter() {
+            let size = *size;
+            let mut table = Board::new(size);
+            for i in 0..5 {
+                table[size - 1][size - 1 - i] = Player::White;
+            }
+            assert_eq!(search(&table), Player::None);
+
+            table[size - 1][size - 6] = Player::White;
+            assert_eq!(search(&table), Player::White);
+        }
+    }
+}
+
+#[cfg(test)]
+mod search_from_tests {
+    use super::*;
+
+    #[test]
+    fn test_search_from_none() {
+        let mut table = Board::new(BOARD_SIZE);
+        assert!(search_from(&table, (0, 0)).is_none());
+        assert!(search_from(&table, (BOARD_SIZE, 0)).is_none());
+
+        for i in 0..5 {
+            table[3][i] = Player::Black;
+        }
+        table[3][5] = Player::White;
+        assert!(search_from(&table, (3, 4)).is_none());
+        assert!(search_from(&table, (3, 5)).is_none());
+    }
+
+    #[test]
+    fn test_search_from_paths() {
+        let cases = [
+            (Path::Right, (2, 3)),
+            (Path::Down, (4, 0)),
+            (Path::RightDown, (1, 1)),
+            (Path::LeftDown, (0, BOARD_SIZE - 1)),
+        ];
+        for (path, start) in cases.iter() {
+            let (dr, dc) = path.delta();
+            let stones = (0..6)
+                .map(|i| {
+                    let row = start.0 as isize + dr * i;
+                    let col = start.1 as isize + dc * i;
+                    (row as usize, col as usize)
+                })
+                .collect::<Vec<_>>();
+
+            let mut table = Board::new(BOARD_SIZE);
+            for &(row, col) in stones.iter() {
+                table[row][col] = Player::White;
+            }
+
+            // search from every stone in the line
+            for pos in stones.iter() {
+                let line = search_from(&table, *pos).unwrap();
+                assert_eq!(line.player, Player::White);
+                assert_eq!(line.path, *path);
+                assert_eq!(line.stones, stones);
+            }
+            assert_eq!(search(&table), Player::White);
+        }
+    }
+
+    #[test]
+    fn test_search_from_boundary() {
+        let size = 6;
+        let mut table = Board::new(size);
+        for i in 0..size {
+            table[i][size - 1 - i] = Player::Black;
+        }
+
+        let line = search_from(&table, (size - 1, 0)).unwrap();
+        assert_eq!(line.path, Path::LeftDown);
+        assert_eq!(line.stones[0], (0, size - 1));
+        assert_eq!(line.stones[5], (size - 1, 0));
+    }
+
+    #[test]
+    fn test_search_from_overline() {
+        let mut table = Board::new(BOARD_SIZE);
+        for i in 0..7 {
+            table[i][2] = Player::Black;
+        }
+
+        let line = search_from(&table, (3, 2)).unwrap();
+        assert_eq!(line.path, Path::Down);
+        assert_eq!(line.stones.len(), 7);
+    }
+}
+
+#[cfg(test)]
+mod search_with_tests {
+    use super::*;
+
+    #[test]
+    fn test_search_with_gomoku() {
+        let rule = Rule::gomoku();
+        let mut table = Board::new(BOARD_SIZE);
+        for i in 0..4 {
+            table[i][BOARD_SIZE - 1 - i] = Player::Black;
+        }
+        assert_eq!(search_with(&table, &rule), Player::None);
+
+        table[4][BOARD_SIZE - 5] = Player::Black;
+        assert_eq!(search_with(&table, &rule), Player::Black);
+        assert_eq!(search(&table), Player::None);
+
+        let line = search_from_with(&table, (2, BOARD_SIZE - 3), &rule).unwrap();
+        assert_eq!(line.path, Path::LeftDown);
+        assert_eq!(line.stones.len(), 5);
+    }
+
+    #[test]
+    fn test_search_with_exact() {
+        let rule = Rule::connect6().with_overline(false);
+        let paths = [Path::Right, Path::Down, Path::RightDown, Path::LeftDown];
+        for path in paths.iter() {
+            let (dr, dc) = path.delta();
+            let start = if dc < 0 { (0, 8) } else { (0, 0) };
+            let pos = |i: isize| {
+                let row = start.0 as isize + dr * i;
+                let col = start.1 as isize + dc * i;
+                (row as usize, col as usize)
+            };
+
+            let mut table = Board::new(BOARD_SIZE);
+            for i in 0..6 {
+                let (row, col) = pos(i);
+                table[row][col] = Player::White;
+            }
+            assert_eq!(search_with(&table, &rule), Player::White);
+            assert!(search_from_with(&table, pos(3), &rule).is_some());
+
+            // overline
+            let (row, col) = pos(6);
+            table[row][col] = Player::White;
+            assert_eq!(search_with(&table, &rule), Player::None);
+            assert!(search_from_with(&table, pos(6), &rule).is_none());
+            assert_eq!(search(&table), Player::White);
+        }
+    }
 }
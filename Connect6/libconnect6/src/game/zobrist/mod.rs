@@ -0,0 +1,137 @@
+//! Incremental Zobrist hashing of the board.
+//!
+//! Each (row, col, player) has a fixed random key, and the hash of the board is XOR of the keys of the stones.
+//! Placing or removing a stone toggles one key, so the hash is updated in O(1).
+//!
+//! `Zobrist` also keeps the keys of the eight symmetric boards (rotations and reflections),
+//! so that `canonical` gives the same key for the transpositions under the symmetry.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::game::{Player, Zobrist};
+//! let mut zobrist = Zobrist::new(15);
+//! let empty = zobrist.key();
+//!
+//! zobrist.toggle(3, 4, Player::Black);
+//! assert_ne!(zobrist.key(), empty);
+//!
+//! zobrist.toggle(3, 4, Player::Black);
+//! assert_eq!(zobrist.key(), empty);
+//! ```
+use game::Player;
+use Board;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of the symmetries of the square board.
+const NUM_SYMMETRY: usize = 8;
+
+/// SplitMix64 finalizer, generates the fixed random key from the index.
+///
+/// It is also used to derive the seeds of the policies, `policy::derive_seed`.
+pub(crate) fn mix(x: u64) -> u64 {
+    let mut x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Key of the stone of given player, zero for `Player::None`.
+fn cell_key(row: usize, col: usize, player: Player) -> u64 {
+    let color = match player {
+        Player::None => return 0,
+        Player::Black => 0,
+        Player::White => 1,
+    };
+    mix(((row as u64) << 33) | ((col as u64) << 1) | color)
+}
+
+/// Incremental Zobrist hash of the board with its eight symmetries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Zobrist {
+    size: usize,
+    keys: [u64; NUM_SYMMETRY],
+}
+
+impl Zobrist {
+    /// Construct a `Zobrist` of the empty board with given length of one side.
+    pub fn new(size: usize) -> Zobrist {
+        // distinguish the empty boards of different size
+        let empty = mix(!(size as u64));
+        Zobrist {
+            size,
+            keys: [empty; NUM_SYMMETRY],
+        }
+    }
+
+    /// Construct a `Zobrist` from the stones of the board.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Game, Zobrist};
+    /// let mut game = Game::new();
+    /// game.set((0, 0)).unwrap();
+    /// assert_eq!(Zobrist::from_board(game.get_board()), *game.zobrist());
+    /// ```
+    pub fn from_board(board: &Board) -> Zobrist {
+        let size = board.size();
+        let mut zobrist = Zobrist::new(size);
+        for row in 0..size {
+            for col in 0..size {
+                zobrist.toggle(row, col, board[row][col]);
+            }
+        }
+        zobrist
+    }
+
+    /// Length of one side.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Toggle the stone of given player at given position, it places or removes the stone.
+    pub fn toggle(&mut self, row: usize, col: usize, player: Player) {
+        let last = self.size - 1;
+        // identity, rotations and reflections of the position
+        let positions = [
+            (row, col),
+            (col, last - row),
+            (last - row, last - col),
+            (last - col, row),
+            (row, last - col),
+            (col, row),
+            (last - row, col),
+            (last - col, last - row),
+        ];
+        for (key, &(r, c)) in self.keys.iter_mut().zip(positions.iter()) {
+            *key ^= cell_key(r, c, player);
+        }
+    }
+
+    /// Hash of the board.
+    pub fn key(&self) -> u64 {
+        self.keys[0]
+    }
+
+    /// Symmetry invariant hash of the board, the minimum key of the eight symmetries.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::game::{Player, Zobrist};
+    /// let mut zobrist = Zobrist::new(15);
+    /// zobrist.toggle(0, 0, Player::Black);
+    ///
+    /// let mut rotated = Zobrist::new(15);
+    /// rotated.toggle(14, 14, Player::Black);
+    ///
+    /// assert_ne!(zobrist.key(), rotated.key());
+    /// assert_eq!(zobrist.canonical(), rotated.canonical());
+    /// ```
+    pub fn canonical(&self) -> u64 {
+        *self.keys.iter().min().unwrap()
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+use rand;
+use {BitBoard, BOARD_SIZE};
+
+fn rand_board(size: usize) -> Board {
+    let mut board = Board::new(size);
+    for row in 0..size {
+        for col in 0..size {
+            board[row][col] = Player::from(rand::random::<i32>() % 3 - 1);
+        }
+    }
+    board
+}
+
+#[test]
+fn test_new() {
+    let zobrist = Zobrist::new(BOARD_SIZE);
+    assert_eq!(zobrist.size(), BOARD_SIZE);
+    assert_eq!(zobrist, Zobrist::from_board(&Board::new(BOARD_SIZE)));
+    assert_ne!(zobrist.key(), Zobrist::new(9).key());
+}
+
+#[test]
+fn test_toggle() {
+    let mut zobrist = Zobrist::new(BOARD_SIZE);
+    zobrist.toggle(1, 2, Player::Black);
+    zobrist.toggle(3, 4, Player::White);
+
+    let mut other = Zobrist::new(BOARD_SIZE);
+    other.toggle(3, 4, Player::White);
+    other.toggle(1, 2, Player::Black);
+    assert_eq!(zobrist, other);
+
+    let mut swapped = Zobrist::new(BOARD_SIZE);
+    swapped.toggle(1, 2, Player::White);
+    swapped.toggle(3, 4, Player::Black);
+    assert_ne!(zobrist.key(), swapped.key());
+
+    zobrist.toggle(0, 0, Player::None);
+    assert_eq!(zobrist, other);
+}
+
+#[test]
+fn test_incremental() {
+    let board = rand_board(BOARD_SIZE);
+    let mut zobrist = Zobrist::new(BOARD_SIZE);
+    for row in 0..BOARD_SIZE {
+        for col in 0..BOARD_SIZE {
+            zobrist.toggle(row, col, board[row][col]);
+        }
+    }
+    assert_eq!(zobrist, Zobrist::from_board(&board));
+}
+
+#[test]
+fn test_canonical() {
+    for size in [6, 9, 15].iter() {
+        let board = rand_board(*size);
+        let zobrist = Zobrist::from_board(&board);
+
+        for symmetry in BitBoard::from_board(&board).symmetries() {
+            let other = Zobrist::from_board(&symmetry.to_board());
+            assert_eq!(zobrist.canonical(), other.canonical());
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use super::session_api::{ApiResponse, SessionApi};
+
+use tiny_http::{Header, Request, Response, Server};
+
+use std::error::Error;
+use std::io::{self, Read};
+use std::net::{SocketAddr, ToSocketAddrs};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum length of the request body in bytes, longer request is rejected with 413.
+pub const MAX_BODY_LEN: usize = 64 * 1024;
+
+/// HTTP server serving the game sessions of `SessionApi`.
+///
+/// Requests are handled one by one on the calling thread, so the engines of the sessions
+/// don't need any synchronization. Responses are JSON with the status code of `SessionApi`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::http::HttpServer;
+/// # use std::io::{Read, Write};
+/// # use std::net::TcpStream;
+/// # use std::thread;
+/// let mut server = HttpServer::bind("127.0.0.1:0").unwrap();
+/// let addr = server.local_addr();
+///
+/// let client = thread::spawn(move || {
+///     let mut stream = TcpStream::connect(addr).unwrap();
+///     write!(stream, "POST /sessions HTTP/1.0\r\nContent-Length: 11\r\n\r\n{{\"size\": 7}}").unwrap();
+///     let mut response = String::new();
+///     stream.read_to_string(&mut response).unwrap();
+///     response
+/// });
+///
+/// server.handle_request().unwrap();
+/// let response = client.join().unwrap();
+/// assert!(response.starts_with("HTTP/1.0 201"));
+/// assert!(response.contains(r#""size":7"#));
+/// ```
+pub struct HttpServer {
+    server: Server,
+    api: SessionApi,
+}
+
+impl HttpServer {
+    /// Construct a new `HttpServer` listening on given address.
+    ///
+    /// # Errors
+    /// If the address couldn't be bound.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<HttpServer, Box<Error + Send>> {
+        let server = Server::http(addr).map_err(|e| e as Box<Error + Send>)?;
+        Ok(HttpServer {
+            server,
+            api: SessionApi::new(),
+        })
+    }
+
+    /// Return the local address of the server.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.server.server_addr()
+    }
+
+    /// Return the sessions of the server.
+    pub fn api(&mut self) -> &mut SessionApi {
+        &mut self.api
+    }
+
+    /// Serve the requests until the listener fails.
+    pub fn serve(&mut self) -> io::Result<()> {
+        loop {
+            self.handle_request()?;
+        }
+    }
+
+    /// Wait for the next request and respond to it.
+    ///
+    /// # Errors
+    /// If the listener failed to receive the request.
+    /// Failure of the response is logged and ignored, it's only the loss of the client.
+    pub fn handle_request(&mut self) -> io::Result<()> {
+        let mut request = self.server.recv()?;
+        let response = self.dispatch(&mut request);
+        let json = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+
+        let status = response.status;
+        let method = request.method().as_str().to_string();
+        let url = request.url().to_string();
+        info!("{} {} {}", method, url, status);
+
+        let response = Response::from_string(response.body)
+            .with_status_code(status)
+            .with_header(json);
+        if let Err(e) = request.respond(response) {
+            warn!("failed to respond {} {}: {}", method, url, e);
+        }
+        Ok(())
+    }
+
+    fn dispatch(&mut self, request: &mut Request) -> ApiResponse {
+        let too_large =
+            || ApiResponse::error(413, &format!("body longer than {} bytes", MAX_BODY_LEN));
+        if request.body_length().map(|len| len > MAX_BODY_LEN) == Some(true) {
+            return too_large();
+        }
+        // length could be missing with the chunked transfer
+        let mut body = String::new();
+        let mut reader = request.as_reader().take(MAX_BODY_LEN as u64 + 1);
+        if let Err(e) = reader.read_to_string(&mut body) {
+            return ApiResponse::error(400, &format!("invalid body: {}", e));
+        }
+        if body.len() > MAX_BODY_LEN {
+            return too_large();
+        }
+        let method = request.method().as_str().to_string();
+        self.api.handle(&method, request.url(), &body)
+    }
+}
@@ -0,0 +1,139 @@
+use super::*;
+use http::{EngineReply, MoveEntry, SessionState};
+use serde_json;
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+
+/// Send the HTTP/1.0 request and return the status code and the body of the response.
+fn request(addr: SocketAddr, method: &str, url: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.0\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        url,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let mut parts = response.splitn(2, "\r\n\r\n");
+    let head = parts.next().unwrap();
+    let body = parts.next().unwrap_or("").to_string();
+
+    assert!(head
+        .to_lowercase()
+        .contains("content-type: application/json"));
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, body)
+}
+
+/// Serve the given number of requests on the background thread.
+fn spawn(num_requests: usize) -> (SocketAddr, thread::JoinHandle<()>) {
+    let mut server = HttpServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr();
+    let handle = thread::spawn(move || {
+        for _ in 0..num_requests {
+            server.handle_request().unwrap();
+        }
+    });
+    (addr, handle)
+}
+
+#[test]
+fn test_session() {
+    let (addr, handle) = spawn(6);
+
+    let body = r#"{"size": 7, "engine": {"policy": "random", "seed": 7}}"#;
+    let (status, body) = request(addr, "POST", "/sessions", body);
+    assert_eq!(status, 201);
+    let state: SessionState = serde_json::from_str(&body).unwrap();
+    assert_eq!(state.size, 7);
+
+    let url = format!("/sessions/{}", state.id);
+    let (status, _) = request(
+        addr,
+        "POST",
+        &format!("{}/moves", url),
+        r#"{"moves": ["D4"]}"#,
+    );
+    assert_eq!(status, 200);
+
+    let (status, body) = request(addr, "POST", &format!("{}/engine", url), "");
+    assert_eq!(status, 200);
+    let reply: EngineReply = serde_json::from_str(&body).unwrap();
+    assert_eq!(reply.moves.len(), 2);
+
+    let (status, body) = request(addr, "GET", &format!("{}/moves", url), "");
+    assert_eq!(status, 200);
+    let history: Vec<MoveEntry> = serde_json::from_str(&body).unwrap();
+    let positions = history.into_iter().map(|x| x.position).collect::<Vec<_>>();
+    assert_eq!(positions[0], "D4");
+    assert_eq!(positions[1..], reply.moves[..]);
+
+    let (status, body) = request(addr, "DELETE", &url, "");
+    assert_eq!(status, 200);
+    assert_eq!(serde_json::from_str::<SessionState>(&body).unwrap().ply, 3);
+
+    let (status, body) = request(addr, "GET", &url, "");
+    assert_eq!(status, 404);
+    assert!(body.contains("error"));
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_invalid_request() {
+    let (addr, handle) = spawn(2);
+
+    let (status, body) = request(addr, "POST", "/sessions", "{size: 7}");
+    assert_eq!(status, 400);
+    assert!(body.starts_with(r#"{"error":"invalid body"#));
+
+    let (status, _) = request(addr, "PUT", "/sessions", "");
+    assert_eq!(status, 405);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_body_limit() {
+    let (addr, handle) = spawn(3);
+
+    let body = format!(r#"{{"size": 7, "pad": "{}"}}"#, " ".repeat(MAX_BODY_LEN));
+    let (status, body) = request(addr, "POST", "/sessions", &body);
+    assert_eq!(status, 413);
+    assert!(body.contains("error"));
+
+    // length of the chunked body is not known until it's read
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    write!(
+        stream,
+        "POST /sessions HTTP/1.1\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+    )
+    .unwrap();
+    let chunk = "a".repeat(MAX_BODY_LEN / 2);
+    for _ in 0..3 {
+        write!(stream, "{:x}\r\n{}\r\n", chunk.len(), chunk).unwrap();
+    }
+    write!(stream, "0\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+
+    let (status, _) = request(addr, "POST", "/sessions", r#"{"size": 7}"#);
+    assert_eq!(status, 201);
+
+    handle.join().unwrap();
+}
@@ -0,0 +1,48 @@
+//! JSON-over-HTTP game session API, enabled by the feature `http`.
+//!
+//! `HttpServer` exposes the game sessions to the web front ends.
+//! Each session owns a `Game` and the engine configured by `EngineConfig`,
+//! the built-in policy by name, the same as `PolicySpec` of the command line, and its parameters.
+//! Routing is done by `SessionApi`, so that it can be used with other HTTP frameworks.
+//!
+//! Positions are written in move-list notation of the record, `H8`, and players as integers,
+//! { -1: Black, 0: None, 1: White }. Result is one of `B+`, `W+`, `Draw` and `*` for the ongoing game.
+//! Failures are reported as `{"error": message}` with the status code,
+//! and the request body longer than `MAX_BODY_LEN` is rejected with 413 by `HttpServer`.
+//!
+//! - `GET /sessions` : states of every session.
+//! - `POST /sessions` : create the session, `{"size": 15, "engine": EngineConfig}`, both are optional,
+//!   429 if there are `MAX_SESSIONS` sessions already.
+//! - `GET /sessions/{id}` : state of the session, board, turn, remaining stones and result.
+//! - `DELETE /sessions/{id}` : remove the session.
+//! - `GET /sessions/{id}/moves` : move history, `[{"ply": 1, "player": -1, "position": "H8"}, ..]`.
+//! - `POST /sessions/{id}/moves` : play the stones in order, `{"moves": ["H8"]}`,
+//!   none of them is played if any of them is invalid.
+//! - `POST /sessions/{id}/undo` : take back the last stone.
+//! - `POST /sessions/{id}/engine` : engine plays the remaining stones of the turn, `resign` if it gave up.
+//! - `POST /sessions/{id}/playout` : engine plays both sides until the game end,
+//!   stopped at the time limit of `SessionApi::with_playout_time` leaving the game ongoing.
+//! - `GET /sessions/{id}/config`, `PUT /sessions/{id}/config` : engine configuration of the session.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::http::SessionApi;
+//! let mut api = SessionApi::new();
+//! let response = api.handle("POST", "/sessions", r#"{"size": 7, "engine": {"policy": "random"}}"#);
+//! assert_eq!(response.status, 201);
+//!
+//! let response = api.handle("POST", "/sessions/1/moves", r#"{"moves": ["D4"]}"#);
+//! assert_eq!(response.status, 200);
+//!
+//! let response = api.handle("POST", "/sessions/1/engine", "");
+//! assert!(response.body.contains(r#""moves":["#));
+//!
+//! let response = api.handle("GET", "/sessions/1/moves", "");
+//! assert!(response.body.starts_with(r#"[{"ply":1,"player":-1,"position":"D4"}"#));
+//! ```
+pub use self::http_server::*;
+pub use self::session_api::*;
+
+mod http_server;
+mod session_api;
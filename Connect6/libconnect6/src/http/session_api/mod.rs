@@ -0,0 +1,556 @@
+use game::{Game, Player};
+use policy::{BuiltinPolicy, HyperParameter, Policy, PolicyKind, POLICY_NAMES};
+use record::{format_pos, format_result, parse_pos};
+use {BOARD_SIZE, MAX_BOARD_SIZE};
+
+use serde;
+use serde_json;
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of the iterations of `default` or the simulations of `alphazero` per stone.
+pub const MAX_NUM_SIMULATION: i32 = 10_000;
+
+/// Maximum number of the sessions, creation is rejected with 429 over it.
+pub const MAX_SESSIONS: usize = 256;
+
+/// Maximum time of the engine searching one stone in the playout.
+pub const STONE_TIME_LIMIT: Duration = Duration::from_secs(1);
+
+/// Default time limit of the playout, the game is left ongoing if it's not over in time.
+pub const PLAYOUT_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+/// Engine configuration of the session, built-in policy by name with its parameters, missing fields are filled with the default.
+///
+/// - policy : name of the built-in policy, one of `POLICY_NAMES`, default `default`.
+/// - num_iter : number of the iterations of `default`, default of the policy if missing.
+/// - param : `HyperParameter` of `alphazero` and `light`, default of the policy if missing.
+/// - seed : seed of the policy, the policy with the seed makes the same selections on the same games.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # extern crate serde_json;
+/// # use connect6::http::EngineConfig;
+/// # use connect6::policy::PolicyKind;
+/// let text = r#"{"policy": "alphazero", "param": {"num_simulation": 10, "epsilon": 0.25, "dirichlet_alpha": 0.03, "c_puct": 1.0}}"#;
+/// let config: EngineConfig = serde_json::from_str(text).unwrap();
+/// match config.kind().unwrap() {
+///     PolicyKind::AlphaZero(param) => assert_eq!(param.num_simulation, 10),
+///     _ => unreachable!(),
+/// }
+///
+/// let config: EngineConfig = serde_json::from_str(r#"{"policy": "mcts"}"#).unwrap();
+/// assert_eq!(config.kind(), Ok(PolicyKind::Default { num_iter: 50 }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineConfig {
+    pub policy: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_iter: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param: Option<HyperParameter>,
+    pub seed: Option<u64>,
+}
+
+impl EngineConfig {
+    /// Construct a new `EngineConfig`, `DefaultPolicy` with 50 iterations.
+    pub fn new() -> EngineConfig {
+        EngineConfig {
+            policy: "default".to_string(),
+            num_iter: None,
+            param: None,
+            seed: None,
+        }
+    }
+
+    /// Kind of the built-in policy with the parameters of the configuration.
+    ///
+    /// # Errors
+    /// - if the policy is unknown or the parameter is given to the other policy.
+    /// - if the parameters are invalid, reference `PolicyKind::validate`.
+    /// - if the number of the iterations or the simulations is larger than `MAX_NUM_SIMULATION`.
+    pub fn kind(&self) -> Result<PolicyKind, String> {
+        let mut kind = PolicyKind::from_name(&self.policy).ok_or_else(|| {
+            format!(
+                "unknown policy {}, expected one of {}",
+                self.policy,
+                POLICY_NAMES.join(", ")
+            )
+        })?;
+        match kind {
+            PolicyKind::Random => (),
+            PolicyKind::Default { ref mut num_iter } => {
+                *num_iter = self.num_iter.unwrap_or(*num_iter);
+            }
+            PolicyKind::AlphaZero(ref mut param) => *param = self.param.unwrap_or(*param),
+        }
+        let (other, num_simulation) = match kind {
+            PolicyKind::Random => (self.num_iter.is_some() || self.param.is_some(), 0),
+            PolicyKind::Default { num_iter } => (self.param.is_some(), num_iter),
+            PolicyKind::AlphaZero(param) => (self.num_iter.is_some(), param.num_simulation),
+        };
+        if other {
+            return Err(format!(
+                "parameter of the other policy than {}",
+                self.policy
+            ));
+        }
+        kind.validate()?;
+        if num_simulation > MAX_NUM_SIMULATION {
+            return Err(format!(
+                "{} simulations, expected at most {}",
+                num_simulation, MAX_NUM_SIMULATION
+            ));
+        }
+        Ok(kind)
+    }
+
+    /// Construct the policy of the configuration.
+    ///
+    /// # Errors
+    /// If the configuration is invalid, reference `EngineConfig::kind`.
+    pub fn build(&self) -> Result<BuiltinPolicy, String> {
+        self.kind().map(|kind| kind.build(self.seed))
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> EngineConfig {
+        EngineConfig::new()
+    }
+}
+
+/// State of the session.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub id: u64,
+    pub size: usize,
+    pub turn: Player,
+    pub remain: i32,
+    pub ply: usize,
+    pub result: String,
+    pub board: Vec<Vec<Player>>,
+    pub engine: EngineConfig,
+}
+
+/// Stone of the move history.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoveEntry {
+    pub ply: usize,
+    pub player: Player,
+    pub position: String,
+}
+
+/// Stones played by the engine, `resign` if it gave up without any stone.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EngineReply {
+    pub moves: Vec<String>,
+    pub resign: bool,
+    pub state: SessionState,
+}
+
+/// Response of the api, status code and JSON body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl ApiResponse {
+    /// Construct the failed response, `{"error": msg}` with given status code.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::http::ApiResponse;
+    /// let response = ApiResponse::error(400, "invalid body");
+    /// assert_eq!(response.body, r#"{"error":"invalid body"}"#);
+    /// ```
+    pub fn error(status: u16, msg: &str) -> ApiResponse {
+        ApiResponse::from(ApiError::new(status, msg.to_string()))
+    }
+
+    fn json<T: serde::Serialize>(status: u16, value: &T) -> ApiResponse {
+        match serde_json::to_string(value) {
+            Ok(body) => ApiResponse { status, body },
+            Err(e) => ApiResponse::from(ApiError::new(500, e.to_string())),
+        }
+    }
+}
+
+/// Body of the failed response.
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Error of the request, status code and the message.
+struct ApiError {
+    status: u16,
+    msg: String,
+}
+
+impl ApiError {
+    fn new(status: u16, msg: String) -> ApiError {
+        ApiError { status, msg }
+    }
+
+    fn bad_request<E: ToString>(e: E) -> ApiError {
+        ApiError::new(400, e.to_string())
+    }
+}
+
+impl From<ApiError> for ApiResponse {
+    fn from(err: ApiError) -> ApiResponse {
+        let body = ErrorBody { error: err.msg };
+        ApiResponse {
+            status: err.status,
+            body: serde_json::to_string(&body).unwrap_or_default(),
+        }
+    }
+}
+
+/// Request body of the session creation.
+#[derive(Deserialize)]
+#[serde(default)]
+struct NewSession {
+    size: usize,
+    engine: EngineConfig,
+}
+
+impl Default for NewSession {
+    fn default() -> NewSession {
+        NewSession {
+            size: BOARD_SIZE,
+            engine: EngineConfig::new(),
+        }
+    }
+}
+
+/// Request body of the moves.
+#[derive(Deserialize)]
+struct Moves {
+    moves: Vec<String>,
+}
+
+/// Parse the request body, empty body is parsed as the empty object.
+fn parse_body<'a, T: serde::Deserialize<'a>>(body: &'a str) -> Result<T, ApiError> {
+    let body = if body.trim().is_empty() { "{}" } else { body };
+    serde_json::from_str(body).map_err(|e| ApiError::bad_request(format!("invalid body: {}", e)))
+}
+
+/// Game and the engine of the session.
+struct Session {
+    game: Game,
+    engine: EngineConfig,
+    policy: BuiltinPolicy,
+}
+
+impl Session {
+    fn new(size: usize, engine: EngineConfig) -> Result<Session, ApiError> {
+        let policy = engine.build().map_err(ApiError::bad_request)?;
+        Ok(Session {
+            game: Game::with_size(size),
+            engine,
+            policy,
+        })
+    }
+
+    fn state(&self, id: u64) -> SessionState {
+        SessionState {
+            id,
+            size: self.game.get_size(),
+            turn: self.game.get_turn(),
+            remain: self.game.get_remain(),
+            ply: self.game.ply(),
+            result: format_result(self.game.status()).to_string(),
+            board: self
+                .game
+                .get_board()
+                .iter()
+                .map(|row| row.to_vec())
+                .collect(),
+            engine: self.engine.clone(),
+        }
+    }
+
+    fn history(&self) -> Vec<MoveEntry> {
+        self.game
+            .history()
+            .iter()
+            .enumerate()
+            .map(|(i, x)| MoveEntry {
+                ply: i + 1,
+                player: x.player,
+                position: format_pos(x.position),
+            })
+            .collect()
+    }
+
+    fn check_ongoing(&self) -> Result<(), ApiError> {
+        if self.game.status().is_end() {
+            Err(ApiError::new(409, "game is over".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Take back the given number of stones.
+    fn rollback(&mut self, num_stones: usize) {
+        for _ in 0..num_stones {
+            self.game.undo();
+        }
+    }
+
+    /// Play the stones in order, none of them is played if any of them is invalid.
+    fn play(&mut self, moves: &[String]) -> Result<(), ApiError> {
+        let positions = moves
+            .iter()
+            .map(|x| {
+                parse_pos(x).ok_or_else(|| ApiError::bad_request(format!("invalid position {}", x)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if positions.is_empty() {
+            return Err(ApiError::bad_request("no moves"));
+        }
+        for (i, pos) in positions.iter().enumerate() {
+            let result = self.check_ongoing().and_then(|_| {
+                self.game
+                    .set(*pos)
+                    .map_err(|e| ApiError::bad_request(format!("{}: {}", moves[i], e)))
+            });
+            if let Err(e) = result {
+                self.rollback(i);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Engine plays the remaining stones of the turn.
+    fn genmove(&mut self) -> Result<Vec<String>, ApiError> {
+        self.check_ongoing()?;
+        let player = self.game.get_turn();
+        let mut moves = Vec::new();
+        while self.game.get_turn() == player && !self.game.status().is_end() {
+            let pos = match self.policy.next(&self.game) {
+                Some(pos) => pos,
+                None => break,
+            };
+            if let Err(e) = self.game.set(pos) {
+                self.rollback(moves.len());
+                return Err(ApiError::new(
+                    500,
+                    format!("engine selected {}: {}", format_pos(pos), e),
+                ));
+            }
+            moves.push(format_pos(pos));
+        }
+        Ok(moves)
+    }
+
+    /// Engine plays both sides until the game end or the time limit.
+    ///
+    /// Each stone is searched until `STONE_TIME_LIMIT`, so the single request couldn't hold the server
+    /// longer than `time_limit` and one more stone.
+    fn playout(&mut self, time_limit: Duration) -> Result<(), ApiError> {
+        self.check_ongoing()?;
+        let end = Instant::now() + time_limit;
+        let result = self.playout_until(end);
+        self.policy.set_deadline(None);
+        result
+    }
+
+    fn playout_until(&mut self, end: Instant) -> Result<(), ApiError> {
+        while !self.game.status().is_end() {
+            let now = Instant::now();
+            if now >= end {
+                break;
+            }
+            self.policy
+                .set_deadline(Some(cmp::min(now + STONE_TIME_LIMIT, end)));
+            let pos = match self.policy.next(&self.game) {
+                Some(pos) => pos,
+                None => break,
+            };
+            self.game.set(pos).map_err(|e| {
+                ApiError::new(500, format!("engine selected {}: {}", format_pos(pos), e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Game sessions routing the JSON requests, reference [http](index.html) for the endpoints.
+///
+/// Sessions are identified by the sequential ids starting from 1.
+pub struct SessionApi {
+    sessions: BTreeMap<u64, Session>,
+    num_session: u64,
+    playout_time: Duration,
+}
+
+impl SessionApi {
+    /// Construct a new `SessionApi` without sessions.
+    pub fn new() -> SessionApi {
+        SessionApi {
+            sessions: BTreeMap::new(),
+            num_session: 0,
+            playout_time: PLAYOUT_TIME_LIMIT,
+        }
+    }
+
+    /// Construct a `SessionApi` with given time limit of the playout, default is `PLAYOUT_TIME_LIMIT`.
+    pub fn with_playout_time(mut self, playout_time: Duration) -> SessionApi {
+        self.playout_time = playout_time;
+        self
+    }
+
+    /// Handle the request with given method, url and body.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::http::SessionApi;
+    /// let mut api = SessionApi::new();
+    /// assert_eq!(api.handle("GET", "/sessions", "").body, "[]");
+    ///
+    /// let response = api.handle("GET", "/sessions/3", "");
+    /// assert_eq!(response.status, 404);
+    /// assert_eq!(response.body, r#"{"error":"session 3 not found"}"#);
+    /// ```
+    pub fn handle(&mut self, method: &str, url: &str, body: &str) -> ApiResponse {
+        let path = url.split('?').next().unwrap_or("");
+        let segments = path
+            .split('/')
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>();
+        match self.route(method, &segments, body) {
+            Ok(response) => response,
+            Err(err) => ApiResponse::from(err),
+        }
+    }
+
+    fn route(
+        &mut self,
+        method: &str,
+        segments: &[&str],
+        body: &str,
+    ) -> Result<ApiResponse, ApiError> {
+        if segments.first() != Some(&"sessions") || segments.len() > 3 {
+            return Err(ApiError::new(
+                404,
+                format!("unknown path /{}", segments.join("/")),
+            ));
+        }
+        if segments.len() == 1 {
+            return match method {
+                "GET" => {
+                    let states = self
+                        .sessions
+                        .iter()
+                        .map(|(id, session)| session.state(*id))
+                        .collect::<Vec<_>>();
+                    Ok(ApiResponse::json(200, &states))
+                }
+                "POST" => {
+                    let request: NewSession = parse_body(body)?;
+                    if request.size == 0 || request.size > MAX_BOARD_SIZE {
+                        return Err(ApiError::bad_request(format!(
+                            "board size {} out of [1, {}]",
+                            request.size, MAX_BOARD_SIZE
+                        )));
+                    }
+                    if self.sessions.len() >= MAX_SESSIONS {
+                        return Err(ApiError::new(
+                            429,
+                            format!("too many sessions, at most {}", MAX_SESSIONS),
+                        ));
+                    }
+                    let session = Session::new(request.size, request.engine)?;
+                    self.num_session += 1;
+                    let id = self.num_session;
+                    let state = session.state(id);
+                    self.sessions.insert(id, session);
+                    Ok(ApiResponse::json(201, &state))
+                }
+                _ => Err(not_allowed(method)),
+            };
+        }
+
+        let id = segments[1]
+            .parse::<u64>()
+            .ok()
+            .filter(|id| self.sessions.contains_key(id))
+            .ok_or_else(|| ApiError::new(404, format!("session {} not found", segments[1])))?;
+
+        if segments.len() == 2 {
+            return match method {
+                "GET" => Ok(ApiResponse::json(200, &self.sessions[&id].state(id))),
+                "DELETE" => {
+                    let session = self.sessions.remove(&id).unwrap();
+                    Ok(ApiResponse::json(200, &session.state(id)))
+                }
+                _ => Err(not_allowed(method)),
+            };
+        }
+
+        let session = self.sessions.get_mut(&id).unwrap();
+        match (method, segments[2]) {
+            ("GET", "moves") => Ok(ApiResponse::json(200, &session.history())),
+            ("POST", "moves") => {
+                let request: Moves = parse_body(body)?;
+                session.play(&request.moves)?;
+                Ok(ApiResponse::json(200, &session.state(id)))
+            }
+            ("POST", "undo") => {
+                if session.game.undo().is_none() {
+                    return Err(ApiError::new(409, "no move to undo".to_string()));
+                }
+                Ok(ApiResponse::json(200, &session.state(id)))
+            }
+            ("POST", "engine") => {
+                let moves = session.genmove()?;
+                let reply = EngineReply {
+                    resign: moves.is_empty(),
+                    moves,
+                    state: session.state(id),
+                };
+                Ok(ApiResponse::json(200, &reply))
+            }
+            ("POST", "playout") => {
+                session.playout(self.playout_time)?;
+                Ok(ApiResponse::json(200, &session.state(id)))
+            }
+            ("GET", "config") => Ok(ApiResponse::json(200, &session.engine)),
+            ("PUT", "config") => {
+                let engine: EngineConfig = parse_body(body)?;
+                session.policy = engine.build().map_err(ApiError::bad_request)?;
+                session.engine = engine;
+                Ok(ApiResponse::json(200, &session.engine))
+            }
+            (_, "moves") | (_, "undo") | (_, "engine") | (_, "playout") | (_, "config") => {
+                Err(not_allowed(method))
+            }
+            (_, action) => Err(ApiError::new(404, format!("unknown action {}", action))),
+        }
+    }
+}
+
+impl Default for SessionApi {
+    fn default() -> SessionApi {
+        SessionApi::new()
+    }
+}
+
+/// Error of the method not allowed on the path.
+fn not_allowed(method: &str) -> ApiError {
+    ApiError::new(405, format!("method {} not allowed", method))
+}
@@ -0,0 +1,300 @@
+use super::*;
+use game::GameStatus;
+
+fn create(api: &mut SessionApi, body: &str) -> SessionState {
+    let response = api.handle("POST", "/sessions", body);
+    assert_eq!(response.status, 201, "{}", response.body);
+    serde_json::from_str(&response.body).unwrap()
+}
+
+fn state(response: ApiResponse) -> SessionState {
+    assert_eq!(response.status, 200, "{}", response.body);
+    serde_json::from_str(&response.body).unwrap()
+}
+
+fn history(api: &mut SessionApi, id: u64) -> Vec<String> {
+    let response = api.handle("GET", &format!("/sessions/{}/moves", id), "");
+    let moves: Vec<MoveEntry> = serde_json::from_str(&response.body).unwrap();
+    moves.into_iter().map(|x| x.position).collect()
+}
+
+fn config(text: &str) -> EngineConfig {
+    serde_json::from_str(text).unwrap()
+}
+
+#[test]
+fn test_engine_config() {
+    let default = config("{}");
+    assert_eq!(default, EngineConfig::new());
+    assert_eq!(default.kind(), Ok(PolicyKind::Default { num_iter: 50 }));
+
+    let random = config(r#"{"policy": "random", "seed": 3}"#);
+    assert_eq!(random.kind(), Ok(PolicyKind::Random));
+    assert_eq!(random.seed, Some(3));
+
+    let json = serde_json::to_string(&random).unwrap();
+    assert_eq!(json, r#"{"policy":"random","seed":3}"#);
+    assert_eq!(config(&json), random);
+
+    // names and defaults of the registry
+    let light = config(r#"{"policy": "light"}"#);
+    assert_eq!(
+        light.kind(),
+        Ok(PolicyKind::AlphaZero(HyperParameter::light_weight()))
+    );
+    let mcts = config(r#"{"policy": "mcts", "num_iter": 7}"#);
+    assert_eq!(mcts.kind(), Ok(PolicyKind::Default { num_iter: 7 }));
+
+    assert!(config(r#"{"policy": "human"}"#).kind().is_err());
+    assert!(config(r#"{"policy": "random", "num_iter": 7}"#)
+        .kind()
+        .is_err());
+    assert!(config(r#"{"policy": "light", "num_iter": 7}"#)
+        .kind()
+        .is_err());
+}
+
+#[test]
+fn test_engine_bounds() {
+    assert!(config(r#"{"num_iter": 0}"#).kind().is_err());
+    assert!(config(r#"{"num_iter": 10001}"#).kind().is_err());
+    assert!(config(r#"{"num_iter": 10000}"#).kind().is_ok());
+
+    let param = |num_simulation: i32, dirichlet_alpha: f64| {
+        let text = format!(
+            r#"{{"policy": "alphazero", "param": {{"num_simulation": {}, "epsilon": 0.25, "dirichlet_alpha": {}, "c_puct": 1.0}}}}"#,
+            num_simulation, dirichlet_alpha
+        );
+        config(&text).kind()
+    };
+    assert!(param(800, 0.03).is_ok());
+    assert!(param(-1, 0.03).is_err());
+    assert!(param(i32::MAX, 0.03).is_err());
+    assert!(param(800, 0.).is_err());
+
+    let mut api = SessionApi::new();
+    let body = r#"{"engine": {"num_iter": 2000000000}}"#;
+    assert_eq!(api.handle("POST", "/sessions", body).status, 400);
+    assert_eq!(
+        api.handle("POST", "/sessions", r#"{"engine": {"policy": "human"}}"#)
+            .status,
+        400
+    );
+
+    create(&mut api, r#"{"size": 7}"#);
+    assert_eq!(
+        api.handle("PUT", "/sessions/1/config", r#"{"num_iter": -5}"#)
+            .status,
+        400
+    );
+    assert_eq!(
+        state(api.handle("GET", "/sessions/1", "")).engine,
+        EngineConfig::new()
+    );
+}
+
+#[test]
+fn test_max_sessions() {
+    let mut api = SessionApi::new();
+    for _ in 0..MAX_SESSIONS {
+        create(&mut api, r#"{"size": 7}"#);
+    }
+    assert_eq!(api.handle("POST", "/sessions", "").status, 429);
+
+    state(api.handle("DELETE", "/sessions/1", ""));
+    assert_eq!(create(&mut api, "").id, MAX_SESSIONS as u64 + 1);
+}
+
+#[test]
+fn test_sessions() {
+    let mut api = SessionApi::new();
+    let first = create(&mut api, "");
+    assert_eq!(first.id, 1);
+    assert_eq!(first.size, BOARD_SIZE);
+    assert_eq!(first.turn, Player::Black);
+    assert_eq!(first.result, "*");
+    assert_eq!(first.engine, EngineConfig::new());
+
+    let second = create(&mut api, r#"{"size": 7, "engine": {"policy": "random"}}"#);
+    assert_eq!(second.id, 2);
+    assert_eq!(second.board.len(), 7);
+    assert_eq!(second.engine.policy, "random");
+
+    let states: Vec<SessionState> =
+        serde_json::from_str(&api.handle("GET", "/sessions", "").body).unwrap();
+    assert_eq!(states, vec![first.clone(), second.clone()]);
+    assert_eq!(state(api.handle("GET", "/sessions/2?verbose", "")), second);
+
+    assert_eq!(state(api.handle("DELETE", "/sessions/1", "")), first);
+    assert_eq!(api.handle("GET", "/sessions/1", "").status, 404);
+    assert_eq!(create(&mut api, "").id, 3);
+}
+
+#[test]
+fn test_moves() {
+    let mut api = SessionApi::new();
+    create(&mut api, r#"{"size": 7}"#);
+
+    let after = state(api.handle("POST", "/sessions/1/moves", r#"{"moves": ["D4", "c3"]}"#));
+    assert_eq!(after.ply, 2);
+    assert_eq!(after.turn, Player::White);
+    assert_eq!(after.remain, 1);
+    assert_eq!(after.board[3][3], Player::Black);
+    assert_eq!(after.board[2][2], Player::White);
+
+    // none of the moves is played if any of them is invalid
+    let response = api.handle("POST", "/sessions/1/moves", r#"{"moves": ["E5", "D4"]}"#);
+    assert_eq!(response.status, 400);
+    let response = api.handle("POST", "/sessions/1/moves", r#"{"moves": ["E5", "Z"]}"#);
+    assert_eq!(response.status, 400);
+    assert_eq!(history(&mut api, 1), vec!["D4", "C3"]);
+
+    let response = api.handle("GET", "/sessions/1/moves", "");
+    assert_eq!(
+        response.body,
+        r#"[{"ply":1,"player":-1,"position":"D4"},{"ply":2,"player":1,"position":"C3"}]"#
+    );
+
+    let after = state(api.handle("POST", "/sessions/1/undo", ""));
+    assert_eq!(after.ply, 1);
+    assert_eq!(after.board[2][2], Player::None);
+    state(api.handle("POST", "/sessions/1/undo", ""));
+    assert_eq!(api.handle("POST", "/sessions/1/undo", "").status, 409);
+}
+
+#[test]
+fn test_game_over() {
+    let mut api = SessionApi::new();
+    create(&mut api, r#"{"size": 7}"#);
+
+    let moves =
+        r#"{"moves": ["A1", "A7", "C7", "B1", "C1", "E7", "G7", "D1", "E1", "A5", "C5", "F1"]}"#;
+    let after = state(api.handle("POST", "/sessions/1/moves", moves));
+    assert_eq!(after.result, format_result(GameStatus::Win(Player::Black)));
+
+    assert_eq!(
+        api.handle("POST", "/sessions/1/moves", r#"{"moves": ["G4"]}"#)
+            .status,
+        409
+    );
+    assert_eq!(api.handle("POST", "/sessions/1/engine", "").status, 409);
+    assert_eq!(api.handle("POST", "/sessions/1/playout", "").status, 409);
+
+    // stones after the end are rejected with the whole request
+    state(api.handle("POST", "/sessions/1/undo", ""));
+    let response = api.handle("POST", "/sessions/1/moves", r#"{"moves": ["F1", "G4"]}"#);
+    assert_eq!(response.status, 409);
+    assert_eq!(state(api.handle("GET", "/sessions/1", "")).ply, 11);
+}
+
+#[test]
+fn test_engine() {
+    let mut api = SessionApi::new();
+    create(
+        &mut api,
+        r#"{"size": 7, "engine": {"policy": "random", "seed": 1}}"#,
+    );
+
+    let response = api.handle("POST", "/sessions/1/engine", "");
+    assert_eq!(response.status, 200, "{}", response.body);
+    let reply: EngineReply = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(reply.moves.len(), 1);
+    assert!(!reply.resign);
+    assert_eq!(reply.state.turn, Player::White);
+
+    let response = api.handle("POST", "/sessions/1/engine", "");
+    let reply: EngineReply = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(reply.moves.len(), 2);
+    assert_eq!(reply.state.turn, Player::Black);
+    assert_eq!(history(&mut api, 1)[1..], reply.moves[..]);
+
+    // same seed, same selections
+    create(
+        &mut api,
+        r#"{"size": 7, "engine": {"policy": "random", "seed": 1}}"#,
+    );
+    api.handle("POST", "/sessions/2/engine", "");
+    api.handle("POST", "/sessions/2/engine", "");
+    assert_eq!(history(&mut api, 1), history(&mut api, 2));
+}
+
+#[test]
+fn test_playout() {
+    let mut api = SessionApi::new();
+    create(&mut api, r#"{"size": 7, "engine": {"policy": "random"}}"#);
+    state(api.handle("POST", "/sessions/1/moves", r#"{"moves": ["D4"]}"#));
+
+    let after = state(api.handle("POST", "/sessions/1/playout", ""));
+    assert_ne!(after.result, "*");
+    assert_eq!(history(&mut api, 1)[0], "D4");
+    assert_eq!(history(&mut api, 1).len(), after.ply);
+}
+
+#[test]
+fn test_playout_time() {
+    let mut api = SessionApi::new().with_playout_time(Duration::from_millis(100));
+    let body = format!(
+        r#"{{"size": 19, "engine": {{"policy": "default", "num_iter": {}}}}}"#,
+        MAX_NUM_SIMULATION
+    );
+    create(&mut api, &body);
+
+    // playout is stopped at the time limit, leaving the game ongoing
+    let before = Instant::now();
+    let after = state(api.handle("POST", "/sessions/1/playout", ""));
+    assert!(before.elapsed() < Duration::from_secs(5));
+    assert_eq!(after.result, "*");
+    assert!(after.ply > 0);
+    assert_eq!(history(&mut api, 1).len(), after.ply);
+}
+
+#[test]
+fn test_config() {
+    let mut api = SessionApi::new();
+    create(&mut api, r#"{"size": 7}"#);
+
+    let response = api.handle("GET", "/sessions/1/config", "");
+    let config: EngineConfig = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(config, EngineConfig::new());
+
+    let body = r#"{"policy": "alphazero", "param": {"num_simulation": 4, "epsilon": 0.25, "dirichlet_alpha": 0.03, "c_puct": 1.0}}"#;
+    let response = api.handle("PUT", "/sessions/1/config", body);
+    assert_eq!(response.status, 200, "{}", response.body);
+    let config: EngineConfig = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(config.policy, "alphazero");
+    assert_eq!(config.param.unwrap().num_simulation, 4);
+    assert_eq!(state(api.handle("GET", "/sessions/1", "")).engine, config);
+
+    let response = api.handle("POST", "/sessions/1/engine", "");
+    assert_eq!(response.status, 200, "{}", response.body);
+    assert_eq!(history(&mut api, 1).len(), 1);
+}
+
+#[test]
+fn test_errors() {
+    let mut api = SessionApi::new();
+    assert_eq!(api.handle("GET", "/", "").status, 404);
+    assert_eq!(api.handle("GET", "/games", "").status, 404);
+    assert_eq!(api.handle("PATCH", "/sessions", "").status, 405);
+    assert_eq!(api.handle("POST", "/sessions", "{").status, 400);
+    assert_eq!(
+        api.handle("POST", "/sessions", r#"{"size": 0}"#).status,
+        400
+    );
+    assert_eq!(
+        api.handle("POST", "/sessions", r#"{"size": 27}"#).status,
+        400
+    );
+
+    create(&mut api, "");
+    assert_eq!(api.handle("GET", "/sessions/x", "").status, 404);
+    assert_eq!(api.handle("PUT", "/sessions/1", "").status, 405);
+    assert_eq!(api.handle("GET", "/sessions/1/undo", "").status, 405);
+    assert_eq!(api.handle("GET", "/sessions/1/board", "").status, 404);
+    assert_eq!(api.handle("GET", "/sessions/1/moves/1", "").status, 404);
+    assert_eq!(api.handle("POST", "/sessions/1/moves", "").status, 400);
+
+    let response = api.handle("POST", "/sessions/1/moves", r#"{"moves": []}"#);
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body, r#"{"error":"no moves"}"#);
+}
@@ -6,21 +6,61 @@
 //! It also provides multi-threading async agent and combined MCTS
 //! [AlphaZero](https://arxiv.org/abs/1712.01815) with some hyperparameters control.
 //!
+//! # Features
+//! - serde : derive `Serialize` and `Deserialize` for `Game`, `Player`, `SetResult`,
+//!   `agent::Path`, `PlayResult`, `policy::HyperParameter` and `arena::Stats`.
+//!   `Player` is written as integer, { -1: Black, 0: None, 1: White }, same as the language interfaces.
+//!
+//! ```ignore
+//! let json = serde_json::to_string(&game).unwrap();
+//! let game: Game = serde_json::from_str(&json).unwrap();
+//! ```
+//!
+//! - http : JSON-over-HTTP game session API, [http](http/index.html). It implies the serde feature.
+//!
+extern crate flate2;
 extern crate futures;
+#[macro_use]
+extern crate log;
 extern crate rand;
 extern crate tokio;
 
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
+#[cfg(any(feature = "http", all(test, feature = "serde")))]
+extern crate serde_json;
+#[cfg(feature = "http")]
+extern crate tiny_http;
+
 #[macro_use]
 mod macro_def;
 
 pub mod agent;
+pub mod arena;
+pub mod engine;
+pub mod export;
 pub mod game;
+#[cfg(feature = "http")]
+pub mod http;
 pub mod policy;
+pub mod record;
+pub mod replay;
+pub mod server;
+
+mod board;
+
+pub use board::{BitBoard, GenericBoard};
 
-/// Length of one side
+/// Default length of one side
 pub const BOARD_SIZE: usize = 15;
 /// Square of BOARD_SIZE
 pub const BOARD_CAPACITY: usize = BOARD_SIZE * BOARD_SIZE;
+/// Maximum length of one side accepted from the external inputs, columns are written as alphabets
+pub const MAX_BOARD_SIZE: usize = 26;
 
-/// Type alias of [[Player; BOARD_SIZE]; BOARD_SIZE];
-pub type Board = [[game::Player; BOARD_SIZE]; BOARD_SIZE];
+/// Type alias of GenericBoard<Player>
+pub type Board = GenericBoard<game::Player>;
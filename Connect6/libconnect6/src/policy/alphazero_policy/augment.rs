@@ -2,23 +2,24 @@ use super::*;
 use std::default::Default;
 use std::ops::Add;
 
-type GenericBoard<T> = [[T; BOARD_SIZE]; BOARD_SIZE];
-
-pub fn rotate_left<T: Copy + Default>(board: &mut GenericBoard<T>) {
-    let mut rotate = [[Default::default(); BOARD_SIZE]; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            rotate[BOARD_SIZE - j - 1][i] = board[i][j];
+#[cfg(test)]
+pub fn rotate_left<T: Clone + Default>(board: &mut GenericBoard<T>) {
+    let size = board.size();
+    let mut rotate = GenericBoard::new(size);
+    for i in 0..size {
+        for j in 0..size {
+            rotate[size - j - 1][i] = board[i][j].clone();
         }
     }
     *board = rotate;
 }
 
-pub fn rotate_right<T: Copy + Default>(board: &mut GenericBoard<T>) {
-    let mut rotate = [[Default::default(); BOARD_SIZE]; BOARD_SIZE];
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            rotate[j][BOARD_SIZE - i - 1] = board[i][j];
+pub fn rotate_right<T: Clone + Default>(board: &mut GenericBoard<T>) {
+    let size = board.size();
+    let mut rotate = GenericBoard::new(size);
+    for i in 0..size {
+        for j in 0..size {
+            rotate[j][size - i - 1] = board[i][j].clone();
         }
     }
     *board = rotate;
@@ -26,21 +27,23 @@ pub fn rotate_right<T: Copy + Default>(board: &mut GenericBoard<T>) {
 
 pub fn flip_vertical<T>(board: &mut GenericBoard<T>) {
     // axis |
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE / 2 {
-            // swap(board[i][BOARD_SIZE - j], board[i][j]);
-            board[i].swap(BOARD_SIZE - j - 1, j);
+    let size = board.size();
+    for i in 0..size {
+        for j in 0..size / 2 {
+            // swap(board[i][size - j], board[i][j]);
+            board[i].swap(size - j - 1, j);
         }
     }
 }
 
-pub fn flip_horizontal<T: Copy>(board: &mut GenericBoard<T>) {
+pub fn flip_horizontal<T: Clone>(board: &mut GenericBoard<T>) {
     // axis --
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE / 2 {
-            // swap(board[BOARD_SIZE - j][i], board[j][i]);
-            let tmp = board[BOARD_SIZE - j - 1][i];
-            board[BOARD_SIZE - j - 1][i] = board[j][i];
+    let size = board.size();
+    for i in 0..size {
+        for j in 0..size / 2 {
+            // swap(board[size - j][i], board[j][i]);
+            let tmp = board[size - j - 1][i].clone();
+            board[size - j - 1][i] = board[j][i].clone();
             board[j][i] = tmp;
         }
     }
@@ -50,35 +53,63 @@ pub fn sum_board<T>(board1: &mut GenericBoard<T>, board2: &GenericBoard<T>)
 where
     T: Add<T, Output = T> + Copy + Default,
 {
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
+    let size = board1.size();
+    for i in 0..size {
+        for j in 0..size {
             board1[i][j] = board1[i][j] + board2[i][j];
         }
     }
 }
 
-pub fn augment_way8(board: &Board) -> Vec<Board> {
-    let mut vec = Vec::with_capacity(8);
-    let mut board = *board;
-
-    for _ in 0..4 {
-        rotate_left(&mut board);
-        vec.push(board);
+/// Transform the board into the `index`-th of the 8 symmetries.
+///
+/// Odd index flips the board vertically, then it is rotated right `index / 2` times.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::Player, policy::{symmetry, symmetry_pos}, Board};
+/// let mut board = Board::new(7);
+/// board[0][1] = Player::Black;
+/// for index in 0..8 {
+///     let (row, col) = symmetry_pos(7, (0, 1), index);
+///     assert_eq!(symmetry(&board, index)[row][col], Player::Black);
+/// }
+/// ```
+pub fn symmetry<T: Clone + Default>(board: &GenericBoard<T>, index: usize) -> GenericBoard<T> {
+    let mut board = board.clone();
+    if index % 2 == 1 {
+        flip_vertical(&mut board);
+    }
+    for _ in 0..(index / 2) % 4 {
+        rotate_right(&mut board);
+    }
+    board
+}
 
-        let mut copied = board;
-        flip_vertical(&mut copied);
-        vec.push(copied);
+/// Transform the position into the `index`-th of the 8 symmetries, same as `symmetry`.
+pub fn symmetry_pos(size: usize, pos: (usize, usize), index: usize) -> (usize, usize) {
+    let (mut row, mut col) = pos;
+    if index % 2 == 1 {
+        col = size - col - 1;
+    }
+    for _ in 0..(index / 2) % 4 {
+        let rotated = (col, size - row - 1);
+        row = rotated.0;
+        col = rotated.1;
     }
-    vec
+    (row, col)
+}
+
+pub fn augment_way8(board: &BitBoard) -> Vec<Board> {
+    board.symmetries().iter().map(BitBoard::to_board).collect()
 }
 
-pub fn recover_way8(
-    mut probs: Vec<[[f32; BOARD_SIZE]; BOARD_SIZE]>,
-) -> [[f32; BOARD_SIZE]; BOARD_SIZE] {
-    let mut total = [[0.; BOARD_SIZE]; BOARD_SIZE];
+pub fn recover_way8(mut probs: Vec<GenericBoard<f32>>) -> GenericBoard<f32> {
+    let mut total = GenericBoard::new(probs[0].size());
     for i in 0..4 {
         flip_vertical(&mut probs[i * 2 + 1]);
-        let flipped = probs[i * 2 + 1];
+        let flipped = probs[i * 2 + 1].clone();
         sum_board(&mut probs[i * 2], &flipped);
         for _ in 0..(i + 1) {
             rotate_right(&mut probs[i * 2]);
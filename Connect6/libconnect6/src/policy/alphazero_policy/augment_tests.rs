@@ -1,9 +1,10 @@
 use super::*;
 use rand;
+use BOARD_SIZE;
 
 #[test]
 fn test_rotate_left() {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     board[0][0] = Player::Black;
     board[0][1] = Player::Black;
     board[0][BOARD_SIZE - 1] = Player::White;
@@ -26,7 +27,7 @@ fn test_rotate_left() {
 
 #[test]
 fn test_rotate_right() {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     board[0][0] = Player::Black;
     board[0][1] = Player::Black;
     board[0][BOARD_SIZE - 1] = Player::White;
@@ -49,7 +50,7 @@ fn test_rotate_right() {
 
 #[test]
 fn test_flip_vertical() {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     board[0][0] = Player::Black;
     board[0][1] = Player::Black;
     board[0][BOARD_SIZE - 1] = Player::White;
@@ -72,7 +73,7 @@ fn test_flip_vertical() {
 
 #[test]
 fn test_flip_horizontal() {
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     board[0][0] = Player::Black;
     board[0][1] = Player::Black;
     board[0][BOARD_SIZE - 1] = Player::White;
@@ -96,8 +97,8 @@ fn test_flip_horizontal() {
 #[test]
 fn test_sum_board() {
     let max = BOARD_SIZE * BOARD_SIZE;
-    let mut board1 = [[0; BOARD_SIZE]; BOARD_SIZE];
-    let mut board2 = [[0; BOARD_SIZE]; BOARD_SIZE];
+    let mut board1 = GenericBoard::new(BOARD_SIZE);
+    let mut board2 = GenericBoard::new(BOARD_SIZE);
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             board1[i][j] = i * BOARD_SIZE + j;
@@ -113,22 +114,16 @@ fn test_sum_board() {
 
 #[test]
 fn test_augment_and_recover() {
-    fn p2f(board: &Board) -> [[f32; BOARD_SIZE]; BOARD_SIZE] {
-        let mut table = [[0.; BOARD_SIZE]; BOARD_SIZE];
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                table[i][j] = board[i][j] as i32 as f32;
-            }
-        }
-        table
+    fn p2f(board: &Board) -> GenericBoard<f32> {
+        board.map(|x| *x as i32 as f32)
     }
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             board[i][j] = Player::from(rand::random::<i32>() % 3 - 1);
         }
     }
-    let augmented = augment::augment_way8(&board);
+    let augmented = augment::augment_way8(&BitBoard::from_board(&board));
     assert_eq!(augmented.len(), 8);
 
     for i in 0..8 {
@@ -140,3 +135,52 @@ fn test_augment_and_recover() {
     let recovered = augment::recover_way8(converted);
     assert_eq!(recovered, p2f(&board));
 }
+
+#[test]
+fn test_sized_augment_and_recover() {
+    for size in [6, 9, 19].iter() {
+        let mut board = Board::new(*size);
+        for i in 0..*size {
+            for j in 0..*size {
+                board[i][j] = Player::from(rand::random::<i32>() % 3 - 1);
+            }
+        }
+        let augmented = augment::augment_way8(&BitBoard::from_board(&board));
+        assert!(augmented.iter().all(|x| x.size() == *size));
+
+        let converted = augmented
+            .iter()
+            .map(|x| x.map(|x| *x as i32 as f32))
+            .collect::<Vec<_>>();
+        let recovered = augment::recover_way8(converted);
+        assert_eq!(recovered, board.map(|x| *x as i32 as f32));
+    }
+}
+
+#[test]
+fn test_symmetry() {
+    let size = 7;
+    let mut board = Board::new(size);
+    for i in 0..size {
+        for j in 0..size {
+            board[i][j] = Player::from(rand::random::<i32>() % 3 - 1);
+        }
+    }
+    // the same symmetries as augment_way8
+    let mut augmented = augment::augment_way8(&BitBoard::from_board(&board));
+    let mut symmetries = (0..8).map(|i| symmetry(&board, i)).collect::<Vec<_>>();
+    let key = |x: &Board| x.as_slice().iter().map(|p| *p as i32).collect::<Vec<_>>();
+    augmented.sort_by_key(&key);
+    symmetries.sort_by_key(&key);
+    assert_eq!(augmented, symmetries);
+
+    for index in 0..8 {
+        let transformed = symmetry(&board, index);
+        for i in 0..size {
+            for j in 0..size {
+                let (row, col) = symmetry_pos(size, (i, j), index);
+                assert_eq!(transformed[row][col], board[i][j]);
+            }
+        }
+    }
+}
@@ -4,15 +4,18 @@
 //! and [Mastering Chess and Shogi by Self-Play with a General Reinforcement Learning Algorithm](https://arxiv.org/abs/1712.01815).
 //!
 use game::{Game, Player};
-use policy::{diff_board, Policy, Simulate};
-use {Board, BOARD_CAPACITY, BOARD_SIZE};
+use policy::{derive_seed, entropy_rng, seeded_rng, Policy, SearchInfo, Simulate};
+use {BitBoard, Board, GenericBoard};
 
 use rand::distributions::{Dirichlet, Distribution};
 use rand::prelude::{thread_rng, IteratorRandom};
+use rand::rngs::StdRng;
 use rand::Rng;
-use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Instant;
+
+pub use self::augment::{symmetry, symmetry_pos};
 
 mod augment;
 
@@ -21,7 +24,7 @@ mod augment_tests;
 #[cfg(test)]
 mod tests;
 
-/// Tree node, get next node as hash value of board
+/// Tree node, get next node as Zobrist hash of board
 #[derive(Clone, Debug)]
 struct Node {
     visit: i32,
@@ -29,9 +32,9 @@ struct Node {
     q_sum: f32,
     q_value: f32,
     n_prob: f32,
-    prob: [[f32; BOARD_SIZE]; BOARD_SIZE],
+    prob: GenericBoard<f32>,
     num_player: usize,
-    board: Board,
+    board: BitBoard,
     next_node: Vec<u64>,
 }
 
@@ -40,35 +43,32 @@ impl Node {
     ///
     /// It generate the number of stones in board.
     /// To avoid the overhead, use method `new_with_num`
-    fn new(board: &Board) -> Node {
-        let num_player = board
-            .iter()
-            .map(|x| x.iter().filter(|x| **x != Player::None).count())
-            .sum();
+    fn new(board: &BitBoard) -> Node {
+        let num_player = board.num_stones();
         Node {
             visit: 0,
             value: 0.,
             q_sum: 0.,
             q_value: 0.,
             n_prob: 0.,
-            prob: [[0.; BOARD_SIZE]; BOARD_SIZE],
+            prob: GenericBoard::new(board.size()),
             num_player,
-            board: *board,
+            board: board.clone(),
             next_node: Vec::new(),
         }
     }
 
     /// Construct a Node with given number of stones(player) in board
-    fn new_with_num(board: &Board, num_player: usize) -> Node {
+    fn new_with_num(board: &BitBoard, num_player: usize) -> Node {
         Node {
             visit: 0,
             value: 0.,
             q_sum: 0.,
             q_value: 0.,
             n_prob: 0.,
-            prob: [[0.; BOARD_SIZE]; BOARD_SIZE],
+            prob: GenericBoard::new(board.size()),
             num_player,
-            board: *board,
+            board: board.clone(),
             next_node: Vec::new(),
         }
     }
@@ -77,6 +77,15 @@ impl Node {
     fn recalc_q(&mut self) {
         self.q_value = self.q_sum / self.visit as f32;
     }
+
+    /// Mean value of the node, evaluated value if it isn't searched yet
+    fn mean_value(&self) -> f32 {
+        if self.visit > 1 {
+            self.q_value
+        } else {
+            self.value
+        }
+    }
 }
 
 /// Hyperparameter for implementing `AlphaZero`.
@@ -87,7 +96,8 @@ impl Node {
 /// - dirichlet_alpha : param for diriclet random distribution, default 0.03.
 /// - c_puct : param for modulating q_value and probability, default 1.
 ///
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HyperParameter {
     pub num_simulation: i32,
     pub epsilon: f32,
@@ -125,47 +135,79 @@ impl HyperParameter {
 }
 
 /// Evaluator for applying value, policy approximator to `AlphaZero`.
+///
+/// Policies should have the same size as the given boards.
 pub trait Evaluator {
-    fn eval(
-        &self,
-        turn: Player,
-        board: &Vec<Board>,
-    ) -> Option<(Vec<f32>, Vec<[[f32; BOARD_SIZE]; BOARD_SIZE]>)>;
+    fn eval(&self, turn: Player, board: &Vec<Board>) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)>;
+
+    /// reseed the random number generator, deterministic evaluator ignores it
+    fn seed(&self, _seed: u64) {}
 }
 
 /// Evaluator for test, Random Value Evaluator
-pub struct RandomEvaluator {}
+pub struct RandomEvaluator {
+    rng: Mutex<StdRng>,
+}
 
 impl RandomEvaluator {
+    /// Construct a new `RandomEvaluator`
+    pub fn new() -> RandomEvaluator {
+        RandomEvaluator {
+            rng: Mutex::new(entropy_rng()),
+        }
+    }
+
+    /// Construct a `RandomEvaluator` with given seed, it returns the same values in the same order.
+    pub fn with_seed(seed: u64) -> RandomEvaluator {
+        RandomEvaluator {
+            rng: Mutex::new(seeded_rng(seed)),
+        }
+    }
+
     /// Generate random board: f32 with range (-1, 1)
-    pub fn rand_board() -> [[f32; BOARD_SIZE]; BOARD_SIZE] {
-        let mut board = [[0.; BOARD_SIZE]; BOARD_SIZE];
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                board[i][j] = thread_rng().gen_range(-1., 1.);
+    pub fn rand_board(size: usize) -> GenericBoard<f32> {
+        RandomEvaluator::rand_board_from(&mut thread_rng(), size)
+    }
+
+    /// Generate random board from given rng
+    fn rand_board_from<R: Rng>(rng: &mut R, size: usize) -> GenericBoard<f32> {
+        let mut board = GenericBoard::new(size);
+        for i in 0..size {
+            for j in 0..size {
+                board[i][j] = rng.gen_range(-1., 1.);
             }
         }
         board
     }
 }
 
+impl Default for RandomEvaluator {
+    fn default() -> RandomEvaluator {
+        RandomEvaluator::new()
+    }
+}
+
 impl Evaluator for RandomEvaluator {
-    fn eval(
-        &self,
-        _: Player,
-        board: &Vec<Board>,
-    ) -> Option<(Vec<f32>, Vec<[[f32; BOARD_SIZE]; BOARD_SIZE]>)> {
+    fn eval(&self, _: Player, board: &Vec<Board>) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
         let len = board.len();
         let mut values = Vec::with_capacity(len);
         let mut policies = Vec::with_capacity(len);
 
-        for _ in 0..len {
-            values.push(thread_rng().gen_range(-1., 1.));
-            policies.push(RandomEvaluator::rand_board());
+        let mut rng = self.rng.lock().ok()?;
+        for b in board.iter() {
+            values.push(rng.gen_range(-1., 1.));
+            policies.push(RandomEvaluator::rand_board_from(&mut *rng, b.size()));
         }
 
         Some((values, policies))
     }
+
+    /// reseed the random number generator
+    fn seed(&self, seed: u64) {
+        if let Ok(mut rng) = self.rng.lock() {
+            *rng = seeded_rng(seed);
+        }
+    }
 }
 
 /// Implementation of policy `AlphaZero` based on combined MCTS with non-linear value approximator.
@@ -178,7 +220,7 @@ impl Evaluator for RandomEvaluator {
 /// # extern crate connect6;
 /// # use connect6::{agent::Agent, policy::{AlphaZero, HyperParameter, RandomEvaluator}};
 /// let param = HyperParameter::light_weight();
-/// let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator {}), param);
+/// let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
 /// let result = Agent::new(&mut policy).play();
 /// assert!(result.is_ok());
 /// ```
@@ -187,6 +229,9 @@ pub struct AlphaZero {
     map: HashMap<u64, Node>,
     param: HyperParameter,
     evaluator: Box<Evaluator + Send>,
+    info: Option<SearchInfo>,
+    rng: StdRng,
+    deadline: Option<Instant>,
 }
 
 impl AlphaZero {
@@ -196,6 +241,9 @@ impl AlphaZero {
             map: HashMap::new(),
             param: HyperParameter::default(),
             evaluator,
+            info: None,
+            rng: entropy_rng(),
+            deadline: None,
         }
     }
 
@@ -205,6 +253,9 @@ impl AlphaZero {
             map: HashMap::new(),
             param,
             evaluator,
+            info: None,
+            rng: entropy_rng(),
+            deadline: None,
         }
     }
 
@@ -215,19 +266,15 @@ impl AlphaZero {
     ///
     /// # Errors
     /// - if `self.evaluator` returns `None` object
-    fn get_from(
-        &self,
-        turn: Player,
-        board: &Board,
-    ) -> Option<(f32, [[f32; BOARD_SIZE]; BOARD_SIZE])> {
+    fn get_from(&self, turn: Player, board: &BitBoard) -> Option<(f32, GenericBoard<f32>)> {
         let (value_vec, policy_vec) = self.evaluator.eval(turn, &augment::augment_way8(board))?;
         let value = value_vec.iter().sum::<f32>() / 8.;
 
         let mut recovered = augment::recover_way8(policy_vec);
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
+        for i in 0..board.size() {
+            for j in 0..board.size() {
                 // masking already set point
-                recovered[i][j] *= (board[i][j] == Player::None) as i32 as f32;
+                recovered[i][j] *= (board.get(i, j) == Player::None) as i32 as f32;
             }
         }
         Some((value, recovered))
@@ -240,20 +287,21 @@ impl AlphaZero {
     ///
     /// # Panics
     /// - if result of `prob` is NaN.
-    fn maximum_from(&self, sim: &Simulate) -> Option<u64> {
-        let node = sim.node.borrow();
-        let tree_node = self.map.get(&hash(&node.board)).unwrap();
+    fn maximum_from(&mut self, sim: &Simulate) -> Option<u64> {
+        // borrow self.map apart from self.rng
+        let map = &self.map;
+        let tree_node = map.get(&sim.key()).unwrap();
         let child_nodes = tree_node
             .next_node
             .iter()
-            .map(|x| self.map.get(x).unwrap())
+            .map(|x| (*x, map.get(x).unwrap()))
             .collect::<Vec<_>>();
         if child_nodes.is_empty() {
             // couldn't get maximum value from empty child
             return None;
         } else if child_nodes.len() == 1 {
             // heuristic
-            return Some(hash(&child_nodes[0].board));
+            return Some(child_nodes[0].0);
         }
 
         // exploit, exploration
@@ -262,7 +310,7 @@ impl AlphaZero {
         let dirichlet = Dirichlet::new_with_param(alpha, child_nodes.len());
 
         let c_puct = self.param.c_puct;
-        let visit_sum = child_nodes.iter().map(|x| x.visit).sum::<i32>() as f32;
+        let visit_sum = child_nodes.iter().map(|(_, x)| x.visit).sum::<i32>() as f32;
         let puct = |node: &Node, noise: f64| {
             let noise = noise as f32;
             let visit = node.visit as f32;
@@ -280,8 +328,8 @@ impl AlphaZero {
         let prob = |(node, noise): (&Node, f64)| unary(node.q_value) + c_puct * puct(node, noise);
         let probs = child_nodes
             .into_iter()
-            .zip(dirichlet.sample(&mut thread_rng()))
-            .map(|n| (n.0, prob(n)))
+            .zip(dirichlet.sample(&mut self.rng))
+            .map(|((key, node), noise)| (key, prob((node, noise))))
             .collect::<Vec<_>>();
 
         let max = probs
@@ -291,8 +339,8 @@ impl AlphaZero {
         probs
             .iter()
             .filter(|(_, p)| *p == max.1)
-            .choose(&mut thread_rng())
-            .map(|(n, _)| hash(&n.board))
+            .choose(&mut self.rng)
+            .map(|(key, _)| *key)
     }
 
     /// Initialize Policy
@@ -301,7 +349,7 @@ impl AlphaZero {
     /// `Init` initialize the tree with given `Simulate`
     fn init(&mut self, sim: &Simulate) {
         let node = sim.node.borrow();
-        let hashed = hash(&node.board);
+        let hashed = node.zobrist.key();
         self.map.entry(hashed).or_insert(Node::new(&node.board));
     }
 
@@ -310,13 +358,12 @@ impl AlphaZero {
     /// # Errors
     /// - if given simulation is end game.
     /// - if method couldn't find any different positions between maximum value node and given.
-    fn select(&self, sim: &Simulate) -> Option<(usize, usize)> {
-        let node = sim.node.borrow();
-        let tree_node = self.map.get(&hash(&node.board)).unwrap();
+    fn select(&mut self, sim: &Simulate) -> Option<(usize, usize)> {
         let hashed = self.maximum_from(sim);
+        let tree_node = self.map.get(&sim.key()).unwrap();
         if let Some(hashed) = hashed {
             let node = self.map.get(&hashed).unwrap();
-            diff_board(&node.board, &tree_node.board)
+            node.board.diff(&tree_node.board)
         } else {
             None
         }
@@ -329,13 +376,13 @@ impl AlphaZero {
     /// # Panics
     /// - if method couldn't get value and prob from pyobject.
     fn expand(&mut self, sim: &Simulate) {
-        let board = sim.board();
-        let parent_hashed = hash(&board);
+        let board = sim.bitboard();
+        let parent_hashed = sim.key();
         {
             // borrow self.map: HashMap
             let cost = sim.turn as i32 as f32;
             let node = self.map.get_mut(&parent_hashed).unwrap();
-            if node.num_player == BOARD_CAPACITY {
+            if node.num_player == node.board.capacity() {
                 let winner = sim.search_winner();
                 if winner == sim.turn {
                     // current player win
@@ -352,7 +399,7 @@ impl AlphaZero {
                 // borrow mut self.map: HasMap
                 let parent_node = self.map.get_mut(&parent_hashed).unwrap();
                 parent_node.value = value;
-                parent_node.prob = prob;
+                parent_node.prob = prob.clone();
                 parent_node.visit += 1;
                 parent_node.num_player + 1
             };
@@ -360,8 +407,8 @@ impl AlphaZero {
             let mut hashed_vec = Vec::new();
             for (row, col) in sim.possible() {
                 let sim = sim.simulate(row, col);
-                let board = sim.board();
-                let hashed = hash(&board);
+                let board = sim.bitboard();
+                let hashed = sim.key();
                 hashed_vec.push(hashed);
 
                 let tree_node = self
@@ -386,7 +433,7 @@ impl AlphaZero {
     fn update(&mut self, sim: &Simulate, path: &Vec<(usize, usize)>) {
         if let Some((row, col)) = path.last() {
             let value = {
-                let node = self.map.get(&hash(&sim.board())).unwrap();
+                let node = self.map.get(&sim.key()).unwrap();
                 node.value
             };
             {
@@ -394,13 +441,13 @@ impl AlphaZero {
                 let mut sim = sim.deep_clone();
                 sim.rollback_in(*row, *col);
 
-                let node = self.map.get_mut(&hash(&sim.board())).unwrap();
+                let node = self.map.get_mut(&sim.key()).unwrap();
                 node.q_sum += value;
             }
             let mut sim = sim.deep_clone();
             for (row, col) in path.iter().rev() {
                 sim.rollback_in(*row, *col);
-                let node = self.map.get_mut(&hash(&sim.board())).unwrap();
+                let node = self.map.get_mut(&sim.key()).unwrap();
                 node.visit += 1;
                 node.recalc_q();
             }
@@ -432,7 +479,7 @@ impl AlphaZero {
     /// - If boards of selected child node and parent node have no difference.
     fn policy(&self, sim: &Simulate) -> Option<(usize, usize)> {
         let node = sim.node.borrow();
-        let tree_node = self.map.get(&hash(&node.board)).unwrap();
+        let tree_node = self.map.get(&node.zobrist.key()).unwrap();
         let child_node = tree_node
             .next_node
             .iter()
@@ -448,15 +495,38 @@ impl AlphaZero {
         child_node
             .into_iter()
             .max_by(|n1, n2| prob(n1).partial_cmp(&prob(n2)).unwrap())
-            .map(|max_node| diff_board(&node.board, &max_node.board).unwrap())
+            .map(|max_node| node.board.diff(&max_node.board).unwrap())
     }
-}
 
-/// Generate hash value from given board
-fn hash(board: &Board) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    board.hash(&mut hasher);
-    hasher.finish()
+    /// Generate the search data of the selected position, visit count distribution and values
+    fn info(&self, sim: &Simulate, selected: Option<(usize, usize)>) -> SearchInfo {
+        let node = sim.node.borrow();
+        let tree_node = self.map.get(&node.zobrist.key()).unwrap();
+
+        // values of the tree are white-positive
+        let sign = sim.turn as i32 as f32;
+        let mut visits = GenericBoard::new(node.board.size());
+        let mut q_value = 0.;
+
+        let child_node = tree_node.next_node.iter().map(|x| self.map.get(x).unwrap());
+        let visit_sum = child_node.clone().map(|x| x.visit).sum::<i32>();
+        for child in child_node {
+            let (row, col) = node.board.diff(&child.board).unwrap();
+            visits[row][col] = child.visit as f32 / visit_sum.max(1) as f32;
+            if selected == Some((row, col)) {
+                q_value = child.mean_value() * sign;
+            }
+        }
+        // terminal children aren't visited, e.g. the last empty cell
+        if let (0, Some((row, col))) = (visit_sum, selected) {
+            visits[row][col] = 1.;
+        }
+        SearchInfo {
+            visits,
+            value: tree_node.mean_value() * sign,
+            q_value,
+        }
+    }
 }
 
 impl Policy for AlphaZero {
@@ -465,13 +535,19 @@ impl Policy for AlphaZero {
         let simulate = Simulate::from_game(game);
         self.init(&simulate);
 
-        for _ in 0..self.param.num_simulation {
+        for i in 0..self.param.num_simulation {
+            // at least one simulation, to expand the root
+            if i > 0 && self.deadline.map(|x| Instant::now() >= x) == Some(true) {
+                break;
+            }
             self.search(&simulate);
         }
         let res = self.policy(&simulate);
+        self.info = Some(self.info(&simulate, res));
 
         // remove siblings
-        let node = self.map.get(&hash(&simulate.board())).unwrap().clone();
+        let key = simulate.key();
+        let node = self.map.get(&key).unwrap().clone();
         let num_player = node.num_player;
         let sibling = self
             .map
@@ -484,7 +560,23 @@ impl Policy for AlphaZero {
             self.map.remove(&hashed);
         }
         // add root
-        self.map.insert(hash(&node.board), node);
+        self.map.insert(key, node);
         res
     }
+
+    /// Visit count distribution and values of the last search
+    fn search_info(&self) -> Option<SearchInfo> {
+        self.info.clone()
+    }
+
+    /// reseed the random number generators of the dirichlet noise, tie-breaking and the evaluator
+    fn seed(&mut self, seed: u64) {
+        self.rng = seeded_rng(derive_seed(seed, 0));
+        self.evaluator.seed(derive_seed(seed, 1));
+    }
+
+    /// simulations are stopped at the deadline, `num_simulation` is the upper bound
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
 }
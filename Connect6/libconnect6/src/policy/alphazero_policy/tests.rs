@@ -1,14 +1,15 @@
 use super::*;
 use agent::Agent;
+use {BOARD_CAPACITY, BOARD_SIZE};
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_select() {
     let game = Game::new();
     let mut sim = Simulate::from_game(&game);
 
-    let rand_eval = Box::new(RandomEvaluator {});
+    let rand_eval = Box::new(RandomEvaluator::new());
     let mut policy = AlphaZero::new(rand_eval);
     policy.init(&sim);
 
@@ -44,7 +45,7 @@ fn test_expand() {
     let game = Game::new();
     let mut sim = Simulate::from_game(&game);
 
-    let rand_eval = Box::new(RandomEvaluator {});
+    let rand_eval = Box::new(RandomEvaluator::new());
     let mut policy = AlphaZero::new(rand_eval);
     policy.init(&sim);
 
@@ -54,7 +55,7 @@ fn test_expand() {
     policy.expand(&sim);
 
     let sim = Simulate::new();
-    let root = policy.map.get(&hash(&sim.board()));
+    let root = policy.map.get(&sim.key());
     assert!(root.is_some());
 
     let root = root.unwrap();
@@ -62,16 +63,15 @@ fn test_expand() {
     assert_ne!(root.value, 0.);
     assert_eq!(root.q_value, 0.);
     assert_eq!(root.n_prob, 0.);
-    assert_ne!(root.prob, [[0.; BOARD_SIZE]; BOARD_SIZE]);
+    assert_ne!(root.prob, GenericBoard::new(BOARD_SIZE));
     assert_eq!(root.num_player, 0);
-    assert_eq!(root.board, sim.board());
+    assert_eq!(root.board, sim.bitboard());
     assert_eq!(root.next_node.len(), BOARD_CAPACITY);
 
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             let sim = sim.simulate(i, j);
-            let board = sim.board();
-            let hashed = hash(&board);
+            let hashed = sim.key();
 
             assert!(policy.map.contains_key(&hashed));
             assert!(root.next_node.contains(&hashed));
@@ -80,7 +80,7 @@ fn test_expand() {
             assert_eq!(node.visit, 0);
             assert_eq!(node.value, 0.);
             assert_eq!(node.n_prob, root.prob[i][j]);
-            assert_eq!(node.prob, [[0.; BOARD_SIZE]; BOARD_SIZE]);
+            assert_eq!(node.prob, GenericBoard::new(BOARD_SIZE));
             assert_eq!(node.num_player, 1);
             assert_eq!(node.next_node.len(), 0);
         }
@@ -92,7 +92,7 @@ fn test_update() {
     let game = Game::new();
     let sim = Simulate::from_game(&game);
 
-    let rand_eval = Box::new(RandomEvaluator {});
+    let rand_eval = Box::new(RandomEvaluator::new());
     let mut policy = AlphaZero::new(rand_eval);
     policy.init(&sim);
 
@@ -106,7 +106,7 @@ fn test_update() {
         policy.expand(&sim);
         policy.update(&sim, &path);
     }
-    let node = policy.map.get(&hash(game.get_board()));
+    let node = policy.map.get(&game.zobrist().key());
     assert!(node.is_some());
 
     let node = node.unwrap();
@@ -128,7 +128,7 @@ fn test_update() {
     assert_ne!(child.n_prob, 0.);
     assert_eq!(node.q_value * 2., child.value);
 
-    let diff = diff_board(&node.board, &child.board);
+    let diff = node.board.diff(&child.board);
     assert!(diff.is_some());
 
     let (row, col) = diff.unwrap();
@@ -140,7 +140,7 @@ fn test_policy() {
     let game = Game::new();
     let sim = Simulate::from_game(&game);
 
-    let rand_eval = Box::new(RandomEvaluator {});
+    let rand_eval = Box::new(RandomEvaluator::new());
     let mut policy = AlphaZero::new(rand_eval);
     policy.init(&sim);
 
@@ -165,7 +165,7 @@ fn test_policy() {
     let pos = sim.possible().iter().position(|x| *x == (row, col));
     assert!(pos.is_some());
 
-    let node = policy.map.get(&hash(&sim.board()));
+    let node = policy.map.get(&sim.key());
     assert!(node.is_some());
 
     let child = node
@@ -177,7 +177,7 @@ fn test_policy() {
     assert_eq!(child.len(), 1);
 
     let child_node = policy.map.get(child[0]).unwrap();
-    let diff = diff_board(game.get_board(), &child_node.board);
+    let diff = BitBoard::from_board(game.get_board()).diff(&child_node.board);
     assert!(diff.is_some());
     assert_eq!((row, col), diff.unwrap());
 }
@@ -185,7 +185,7 @@ fn test_policy() {
 #[test]
 fn test_self_play() {
     let param = HyperParameter::light_weight();
-    let rand_eval = Box::new(RandomEvaluator {});
+    let rand_eval = Box::new(RandomEvaluator::new());
     let mut policy = AlphaZero::with_param(rand_eval, param);
     let mut mcts = Agent::new(&mut policy);
 
@@ -202,3 +202,83 @@ fn test_self_play() {
     }
     assert!(true);
 }
+
+#[test]
+fn test_sized_self_play() {
+    let param = HyperParameter::light_weight();
+    let rand_eval = Box::new(RandomEvaluator::new());
+    let mut policy = AlphaZero::with_param(rand_eval, param);
+
+    let result = Agent::new(&mut policy).with_board_size(7).play();
+    let result = result.map_err(|_| assert!(false)).unwrap();
+    for path in result.path.iter() {
+        assert_eq!(path.board.size(), 7);
+        assert!(path.pos.0 < 7 && path.pos.1 < 7);
+    }
+}
+
+#[test]
+fn test_value() {
+    /// Evaluator favouring white.
+    struct WhiteEvaluator {}
+
+    impl Evaluator for WhiteEvaluator {
+        fn eval(
+            &self,
+            _: Player,
+            board: &Vec<Board>,
+        ) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
+            let values = board.iter().map(|_| 0.5).collect();
+            let policies = board.iter().map(|x| GenericBoard::new(x.size())).collect();
+            Some((values, policies))
+        }
+    }
+
+    let param = HyperParameter::light_weight();
+    let mut policy = AlphaZero::with_param(Box::new(WhiteEvaluator {}), param);
+    assert_eq!(policy.value(), None);
+
+    // value is from the perspective of the current player
+    let mut game = Game::with_size(7);
+    policy.next(&game).unwrap();
+    let black_value = policy.value().unwrap();
+    assert!(-1. <= black_value && black_value < 0.);
+
+    game.set((0, 0)).unwrap();
+    policy.next(&game).unwrap();
+    let white_value = policy.value().unwrap();
+    assert!(0. < white_value && white_value <= 1.);
+}
+
+#[test]
+fn test_deadline() {
+    let mut param = HyperParameter::light_weight();
+    param.num_simulation = i32::MAX;
+    let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
+    policy.set_deadline(Some(Instant::now() + Duration::from_millis(50)));
+
+    let now = Instant::now();
+    assert!(policy.next(&Game::with_size(7)).is_some());
+    assert!(now.elapsed() < Duration::from_secs(10));
+    assert!(policy.search_info().is_some());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_param() {
+    use serde_json;
+
+    // format of `pyconnect6.dump_param`
+    let json = r#"{"num_simulation": 10, "epsilon": 0.5, "dirichlet_alpha": 0.1,
+                   "c_puct": 2, "debug": false, "num_game_thread": 1, "board_size": 15}"#;
+    let param = serde_json::from_str::<HyperParameter>(json).unwrap();
+    assert_eq!(param.num_simulation, 10);
+    assert_eq!(param.epsilon, 0.5);
+    assert_eq!(param.dirichlet_alpha, 0.1);
+    assert_eq!(param.c_puct, 2.);
+
+    let dumped = serde_json::to_string(&param).unwrap();
+    let parsed = serde_json::from_str::<HyperParameter>(&dumped).unwrap();
+    assert_eq!(parsed.num_simulation, param.num_simulation);
+    assert_eq!(parsed.c_puct, param.c_puct);
+}
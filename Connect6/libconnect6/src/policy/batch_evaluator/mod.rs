@@ -0,0 +1,231 @@
+//! Evaluator service batching the evaluations of the parallel games.
+//!
+//! `AlphaZero` evaluates the 8 augmented boards of a single leaf on each call of `Evaluator::eval`.
+//! With multiple games in parallel, `BatchEvaluator` gathers the leaves of the games on the worker thread
+//! and evaluates them with a single call, to make the inference of the neural network efficient.
+//!
+//! Each game gets `BatchHandle` as its evaluator, which submits the boards to the worker and waits the result.
+//! Worker makes the batch up to `max_batch` leaves, or until `timeout` passes from the first leaf,
+//! or until every live handle is waiting. Handle is live from its first evaluation until it is dropped.
+//!
+//! Games should run together to be batched, set `AsyncAgent::with_num_threads` to the number of games.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::agent::AsyncAgent;
+//! # use connect6::policy::{AlphaZero, BatchEvaluator, HyperParameter, RandomEvaluator};
+//! let batch = BatchEvaluator::new(Box::new(RandomEvaluator::new()));
+//! let param = HyperParameter::light_weight();
+//! let policy_gen = || AlphaZero::with_param(Box::new(batch.handle()), param);
+//!
+//! let async_agent = AsyncAgent::new(policy_gen)
+//!     .with_board_size(7)
+//!     .with_num_threads(4);
+//! let result = async_agent.run(4);
+//! # assert!(result.iter().all(|x| x.is_ok()));
+//! ```
+use game::Player;
+use policy::Evaluator;
+use {Board, GenericBoard};
+
+use std::panic;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Values and policies of the boards, return type of `Evaluator::eval`.
+type Evaluation = Option<(Vec<f32>, Vec<GenericBoard<f32>>)>;
+
+/// Evaluation request of a leaf, sent from the handle to the worker.
+struct Request {
+    turn: Player,
+    boards: Vec<Board>,
+    reply: Sender<Evaluation>,
+}
+
+impl Request {
+    /// Requests of the same key are evaluated in the same call.
+    fn key(&self) -> (Player, Option<usize>) {
+        (self.turn, self.boards.first().map(|x| x.size()))
+    }
+}
+
+/// Evaluator service batching the evaluations of the parallel games.
+///
+/// It owns the evaluator on the worker thread, and the games evaluate the boards through `BatchHandle`.
+/// Worker finishes when the service and all handles are dropped.
+///
+/// Batched evaluator is shared by the games, so `Evaluator::seed` of the handle is ignored.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::Player, policy::{BatchEvaluator, Evaluator, RandomEvaluator}, Board};
+/// # use std::time::Duration;
+/// let evaluator = Box::new(RandomEvaluator::new());
+/// let batch = BatchEvaluator::with_param(evaluator, 32, Duration::from_millis(1));
+///
+/// let handle = batch.handle();
+/// let (values, policies) = handle.eval(Player::Black, &vec![Board::new(7)]).unwrap();
+/// assert_eq!(values.len(), 1);
+/// assert_eq!(policies[0].size(), 7);
+/// ```
+pub struct BatchEvaluator {
+    sender: Sender<Request>,
+    live: Arc<AtomicUsize>,
+}
+
+impl BatchEvaluator {
+    /// Construct a new `BatchEvaluator`, batch of 16 leaves with 5ms timeout.
+    pub fn new(evaluator: Box<Evaluator + Send>) -> BatchEvaluator {
+        BatchEvaluator::with_param(evaluator, 16, Duration::from_millis(5))
+    }
+
+    /// Construct a `BatchEvaluator` with the maximum number of leaves in a batch and the time to wait for them.
+    pub fn with_param(
+        evaluator: Box<Evaluator + Send>,
+        max_batch: usize,
+        timeout: Duration,
+    ) -> BatchEvaluator {
+        let (sender, receiver) = mpsc::channel();
+        let live = Arc::new(AtomicUsize::new(0));
+        let worker_live = live.clone();
+        thread::spawn(move || {
+            work(
+                &*evaluator,
+                receiver,
+                &worker_live,
+                max_batch.max(1),
+                timeout,
+            );
+        });
+        BatchEvaluator { sender, live }
+    }
+
+    /// Get the handle submitting the evaluations to the service, one for each game.
+    pub fn handle(&self) -> BatchHandle {
+        BatchHandle {
+            sender: self.sender.clone(),
+            live: self.live.clone(),
+            registered: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Evaluator of a game, evaluating the boards by `BatchEvaluator`.
+///
+/// It returns `None` if the batched evaluation failed or panicked.
+pub struct BatchHandle {
+    sender: Sender<Request>,
+    live: Arc<AtomicUsize>,
+    registered: AtomicBool,
+}
+
+impl Clone for BatchHandle {
+    /// Cloned handle is live from its own first evaluation.
+    fn clone(&self) -> BatchHandle {
+        BatchHandle {
+            sender: self.sender.clone(),
+            live: self.live.clone(),
+            registered: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Drop for BatchHandle {
+    fn drop(&mut self) {
+        if self.registered.load(Ordering::SeqCst) {
+            self.live.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Evaluator for BatchHandle {
+    /// Submit the boards to the worker and wait the result
+    fn eval(&self, turn: Player, board: &Vec<Board>) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
+        if !self.registered.swap(true, Ordering::SeqCst) {
+            self.live.fetch_add(1, Ordering::SeqCst);
+        }
+        let (reply, receiver) = mpsc::channel();
+        let request = Request {
+            turn,
+            boards: board.clone(),
+            reply,
+        };
+        self.sender.send(request).ok()?;
+        receiver.recv().ok()?
+    }
+}
+
+/// Gather the requests into the batch and evaluate them, until all senders are dropped.
+fn work(
+    evaluator: &Evaluator,
+    receiver: Receiver<Request>,
+    live: &AtomicUsize,
+    max_batch: usize,
+    timeout: Duration,
+) {
+    while let Ok(first) = receiver.recv() {
+        let deadline = Instant::now() + timeout;
+        let mut batch = vec![first];
+        // flush early if every game is waiting for the evaluation
+        while batch.len() < max_batch && batch.len() < live.load(Ordering::SeqCst) {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            match receiver.recv_timeout(deadline - now) {
+                Ok(request) => batch.push(request),
+                Err(_) => break,
+            }
+        }
+        evaluate(evaluator, batch);
+    }
+}
+
+/// Evaluate the batch grouped by the turn and the board size, and reply to each request.
+fn evaluate(evaluator: &Evaluator, batch: Vec<Request>) {
+    let mut groups: Vec<Vec<Request>> = Vec::new();
+    for request in batch {
+        match groups.iter().position(|x| x[0].key() == request.key()) {
+            Some(idx) => groups[idx].push(request),
+            None => groups.push(vec![request]),
+        }
+    }
+
+    for group in groups {
+        let turn = group[0].turn;
+        let boards = group
+            .iter()
+            .flat_map(|x| x.boards.iter().cloned())
+            .collect::<Vec<_>>();
+
+        // panic of the evaluator fails the batch, not the service
+        let len = boards.len();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| evaluator.eval(turn, &boards)));
+        match result {
+            Ok(Some((values, policies))) if values.len() == len && policies.len() == len => {
+                // split the result in the order of the requests
+                let mut values = values.into_iter();
+                let mut policies = policies.into_iter();
+                for request in group {
+                    let len = request.boards.len();
+                    let value = values.by_ref().take(len).collect();
+                    let policy = policies.by_ref().take(len).collect();
+                    let _ = request.reply.send(Some((value, policy)));
+                }
+            }
+            _ => {
+                for request in group {
+                    let _ = request.reply.send(None);
+                }
+            }
+        }
+    }
+}
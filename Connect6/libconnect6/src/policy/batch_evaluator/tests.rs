@@ -0,0 +1,165 @@
+use super::*;
+use agent::AsyncAgent;
+use policy::{AlphaZero, HyperParameter, RandomEvaluator};
+
+use std::sync::{Barrier, Mutex};
+
+/// Evaluator recording the turn and the number of boards of each call,
+/// value of the board is the stone at (0, 0).
+struct RecordEvaluator {
+    calls: Arc<Mutex<Vec<(Player, usize)>>>,
+}
+
+impl Evaluator for RecordEvaluator {
+    fn eval(&self, turn: Player, board: &Vec<Board>) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
+        self.calls.lock().unwrap().push((turn, board.len()));
+        let values = board.iter().map(|x| x[0][0] as i32 as f32).collect();
+        let policies = board.iter().map(|x| GenericBoard::new(x.size())).collect();
+        Some((values, policies))
+    }
+}
+
+/// Evaluator failing in several ways.
+enum FaultyEvaluator {
+    Empty,
+    Panic,
+}
+
+impl Evaluator for FaultyEvaluator {
+    fn eval(&self, _: Player, _: &Vec<Board>) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
+        match self {
+            FaultyEvaluator::Empty => Some((Vec::new(), Vec::new())),
+            FaultyEvaluator::Panic => panic!("faulty evaluator"),
+        }
+    }
+}
+
+fn board_with(size: usize, player: Player) -> Board {
+    let mut board = Board::new(size);
+    board[0][0] = player;
+    board
+}
+
+#[test]
+fn test_batch() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let evaluator = Box::new(RecordEvaluator {
+        calls: calls.clone(),
+    });
+    let batch = BatchEvaluator::with_param(evaluator, 8, Duration::from_secs(5));
+
+    let players = [Player::Black, Player::White, Player::None, Player::Black];
+    let barrier = Arc::new(Barrier::new(players.len() + 1));
+    let threads = players
+        .iter()
+        .map(|player| {
+            let handle = batch.handle();
+            let barrier = barrier.clone();
+            let boards = vec![board_with(7, *player); 2];
+            thread::spawn(move || {
+                // first evaluation makes the handle live
+                handle.eval(Player::Black, &boards).unwrap();
+                barrier.wait();
+                barrier.wait();
+                handle.eval(Player::Black, &boards)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    barrier.wait();
+    calls.lock().unwrap().clear();
+    let now = Instant::now();
+    barrier.wait();
+
+    // each game gets its own result
+    for (thread, player) in threads.into_iter().zip(players.iter()) {
+        let (values, policies) = thread.join().unwrap().unwrap();
+        assert_eq!(values, vec![*player as i32 as f32; 2]);
+        assert_eq!(policies.len(), 2);
+    }
+    // flushed without timeout, all games were waiting
+    assert!(now.elapsed() < Duration::from_secs(4));
+    assert_eq!(*calls.lock().unwrap(), vec![(Player::Black, 8)]);
+}
+
+#[test]
+fn test_evaluate_group() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let evaluator = RecordEvaluator {
+        calls: calls.clone(),
+    };
+
+    let keys = vec![
+        (Player::Black, 7),
+        (Player::White, 7),
+        (Player::Black, 9),
+        (Player::Black, 7),
+    ];
+    let (batch, receivers): (Vec<_>, Vec<_>) = keys
+        .iter()
+        .map(|(turn, size)| {
+            let (reply, receiver) = mpsc::channel();
+            let request = Request {
+                turn: *turn,
+                boards: vec![board_with(*size, Player::White)],
+                reply,
+            };
+            (request, receiver)
+        })
+        .unzip();
+    evaluate(&evaluator, batch);
+
+    // different turns or board sizes are evaluated separately
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![(Player::Black, 2), (Player::White, 1), (Player::Black, 1)]
+    );
+    for (receiver, (_, size)) in receivers.into_iter().zip(keys.iter()) {
+        let (values, policies) = receiver.recv().unwrap().unwrap();
+        assert_eq!(values, vec![1.]);
+        assert_eq!(policies[0].size(), *size);
+    }
+}
+
+#[test]
+fn test_batch_failed() {
+    let board = vec![Board::new(7)];
+
+    let batch = BatchEvaluator::new(Box::new(FaultyEvaluator::Empty));
+    assert!(batch.handle().eval(Player::Black, &board).is_none());
+
+    // service is alive after the panic
+    let batch = BatchEvaluator::new(Box::new(FaultyEvaluator::Panic));
+    let handle = batch.handle();
+    assert!(handle.eval(Player::Black, &board).is_none());
+    assert!(handle.eval(Player::Black, &board).is_none());
+}
+
+#[test]
+fn test_async_agent() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let evaluator = Box::new(RecordEvaluator {
+        calls: calls.clone(),
+    });
+    let batch = BatchEvaluator::new(evaluator);
+
+    let param = HyperParameter::light_weight();
+    let policy_gen = || AlphaZero::with_param(Box::new(batch.handle()), param);
+    let async_agent = AsyncAgent::new(policy_gen)
+        .with_board_size(7)
+        .with_num_threads(3);
+
+    let result = async_agent.run(3);
+    assert!(result.iter().all(|x| x.is_ok()));
+    assert!(calls.lock().unwrap().iter().all(|(_, len)| len % 8 == 0));
+}
+
+#[test]
+fn test_random_evaluator() {
+    let batch = BatchEvaluator::new(Box::new(RandomEvaluator::new()));
+    let handle = batch.handle().clone();
+
+    let (values, policies) = handle.eval(Player::White, &vec![Board::new(9); 8]).unwrap();
+    assert_eq!(values.len(), 8);
+    assert!(policies.iter().all(|x| x.size() == 9));
+}
@@ -0,0 +1,242 @@
+//! Built-in policies selectable by name with parameters.
+//!
+//! Policy is specified as `name[:key=value,...]`, such as `random` or `alphazero:num_simulation=50,c_puct=1.5`.
+//!
+//! - random : `RandomPolicy`, parameter `seed`.
+//! - default : `DefaultPolicy` (alias `mcts`), parameters `num_iter`, `seed`.
+//! - alphazero : `AlphaZero` with `RandomEvaluator`, parameters `num_simulation`, `epsilon`,
+//!   `dirichlet_alpha`, `c_puct` and `seed`.
+//! - light : alphazero with the light weight parameters.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{agent::Agent, policy::PolicySpec};
+//! let spec = "alphazero:num_simulation=2,seed=42".parse::<PolicySpec>().unwrap();
+//! let mut policy = spec.build();
+//! let result = Agent::new(&mut policy).with_board_size(7).play();
+//! assert!(result.is_ok());
+//! ```
+use game::Game;
+use policy::{
+    AlphaZero, DefaultPolicy, HyperParameter, Policy, RandomEvaluator, RandomPolicy, SearchInfo,
+};
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+
+#[cfg(test)]
+mod tests;
+
+/// Names of the built-in policies.
+pub const POLICY_NAMES: &[&str] = &["random", "default", "mcts", "alphazero", "light"];
+
+/// Kind of the built-in policy with its parameters.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PolicyKind {
+    Random,
+    Default { num_iter: i32 },
+    AlphaZero(HyperParameter),
+}
+
+impl PolicyKind {
+    /// Kind of the built-in policy with the default parameters, name is case insensitive.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::policy::PolicyKind;
+    /// assert_eq!(PolicyKind::from_name("MCTS"), Some(PolicyKind::Default { num_iter: 50 }));
+    /// assert_eq!(PolicyKind::from_name("human"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<PolicyKind> {
+        match name.to_lowercase().as_str() {
+            "random" => Some(PolicyKind::Random),
+            "default" | "mcts" => Some(PolicyKind::Default { num_iter: 50 }),
+            "alphazero" => Some(PolicyKind::AlphaZero(HyperParameter::new())),
+            "light" => Some(PolicyKind::AlphaZero(HyperParameter::light_weight())),
+            _ => None,
+        }
+    }
+
+    /// Validate the parameters.
+    ///
+    /// # Errors
+    /// - if the number of the iterations or the simulations isn't positive.
+    /// - if `epsilon` isn't in range [0, 1], `dirichlet_alpha` isn't positive or `c_puct` isn't finite.
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            PolicyKind::Random => Ok(()),
+            PolicyKind::Default { num_iter } if num_iter <= 0 => {
+                Err(format!("num_iter {} isn't positive", num_iter))
+            }
+            PolicyKind::Default { .. } => Ok(()),
+            PolicyKind::AlphaZero(param) => {
+                if param.num_simulation <= 0 {
+                    Err(format!(
+                        "num_simulation {} isn't positive",
+                        param.num_simulation
+                    ))
+                } else if !(param.epsilon >= 0. && param.epsilon <= 1.) {
+                    Err(format!("epsilon {} isn't in range [0, 1]", param.epsilon))
+                } else if !(param.dirichlet_alpha > 0. && param.dirichlet_alpha.is_finite()) {
+                    Err(format!(
+                        "dirichlet_alpha {} isn't positive",
+                        param.dirichlet_alpha
+                    ))
+                } else if !param.c_puct.is_finite() {
+                    Err(format!("c_puct {} isn't finite", param.c_puct))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Construct the policy of the kind, policy with the seed makes the same selections on the same games.
+    pub fn build(&self, seed: Option<u64>) -> BuiltinPolicy {
+        let mut policy = match *self {
+            PolicyKind::Random => BuiltinPolicy::Random(RandomPolicy::new()),
+            PolicyKind::Default { num_iter } => {
+                BuiltinPolicy::Default(DefaultPolicy::with_num_iter(num_iter))
+            }
+            PolicyKind::AlphaZero(param) => {
+                let evaluator = Box::new(RandomEvaluator::new());
+                BuiltinPolicy::AlphaZero(AlphaZero::with_param(evaluator, param))
+            }
+        };
+        if let Some(seed) = seed {
+            policy.seed(seed);
+        }
+        policy
+    }
+}
+
+/// Specification of the built-in policy, parsed from `name[:key=value,...]`.
+#[derive(Clone, Debug)]
+pub struct PolicySpec {
+    pub kind: PolicyKind,
+    pub seed: Option<u64>,
+    text: String,
+}
+
+impl PolicySpec {
+    /// Construct the policy of the specification.
+    ///
+    /// Policy with the seed makes the same selections on the same games.
+    pub fn build(&self) -> BuiltinPolicy {
+        self.kind.build(self.seed)
+    }
+}
+
+impl fmt::Display for PolicySpec {
+    /// Write the specification as given.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Parse the value of the parameter.
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value {} of parameter {}", value, key))
+}
+
+impl FromStr for PolicySpec {
+    type Err = String;
+
+    /// Parse the specification, unknown policy, unknown parameter and invalid value are rejected.
+    fn from_str(text: &str) -> Result<PolicySpec, String> {
+        let text = text.trim();
+        let mut split = text.splitn(2, ':');
+        let name = split.next().unwrap_or("").to_lowercase();
+        let params = split.next().unwrap_or("");
+
+        let mut kind = PolicyKind::from_name(&name).ok_or_else(|| {
+            format!(
+                "unknown policy {}, expected one of {}",
+                name,
+                POLICY_NAMES.join(", ")
+            )
+        })?;
+
+        let mut seed = None;
+        for param in params
+            .split(',')
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+        {
+            let mut split = param.splitn(2, '=');
+            let key = split.next().unwrap().trim();
+            let value = split
+                .next()
+                .ok_or_else(|| format!("parameter {} has no value", key))?
+                .trim();
+
+            match (key, &mut kind) {
+                ("seed", _) => seed = Some(parse_value(key, value)?),
+                ("num_iter", PolicyKind::Default { num_iter }) => {
+                    *num_iter = parse_value(key, value)?
+                }
+                ("num_simulation", PolicyKind::AlphaZero(param)) => {
+                    param.num_simulation = parse_value(key, value)?
+                }
+                ("epsilon", PolicyKind::AlphaZero(param)) => {
+                    param.epsilon = parse_value(key, value)?
+                }
+                ("dirichlet_alpha", PolicyKind::AlphaZero(param)) => {
+                    param.dirichlet_alpha = parse_value(key, value)?
+                }
+                ("c_puct", PolicyKind::AlphaZero(param)) => param.c_puct = parse_value(key, value)?,
+                _ => return Err(format!("unknown parameter {} of policy {}", key, name)),
+            }
+        }
+        kind.validate()?;
+
+        Ok(PolicySpec {
+            kind,
+            seed,
+            text: text.to_string(),
+        })
+    }
+}
+
+/// Built-in policy, dispatching to the policy of the kind.
+pub enum BuiltinPolicy {
+    Random(RandomPolicy),
+    Default(DefaultPolicy),
+    AlphaZero(AlphaZero),
+}
+
+impl BuiltinPolicy {
+    fn as_policy(&mut self) -> &mut Policy {
+        match self {
+            BuiltinPolicy::Random(policy) => policy,
+            BuiltinPolicy::Default(policy) => policy,
+            BuiltinPolicy::AlphaZero(policy) => policy,
+        }
+    }
+}
+
+impl Policy for BuiltinPolicy {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        self.as_policy().next(game)
+    }
+
+    fn search_info(&self) -> Option<SearchInfo> {
+        match self {
+            BuiltinPolicy::AlphaZero(policy) => policy.search_info(),
+            _ => None,
+        }
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.as_policy().seed(seed)
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.as_policy().set_deadline(deadline)
+    }
+}
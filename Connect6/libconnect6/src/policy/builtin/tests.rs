@@ -0,0 +1,88 @@
+use super::*;
+use agent::Agent;
+
+#[test]
+fn test_parse_name() {
+    let spec = "random".parse::<PolicySpec>().unwrap();
+    assert!(matches!(spec.kind, PolicyKind::Random));
+    assert_eq!(spec.seed, None);
+
+    let spec = "MCTS".parse::<PolicySpec>().unwrap();
+    assert!(matches!(spec.kind, PolicyKind::Default { num_iter } if num_iter == 50));
+
+    let spec = "light".parse::<PolicySpec>().unwrap();
+    assert!(matches!(spec.kind, PolicyKind::AlphaZero(param) if param.num_simulation == 2));
+    assert_eq!(spec.to_string(), "light");
+
+    assert!("human".parse::<PolicySpec>().is_err());
+    assert!("".parse::<PolicySpec>().is_err());
+}
+
+#[test]
+fn test_parse_params() {
+    let spec = "default:num_iter=10, seed=42"
+        .parse::<PolicySpec>()
+        .unwrap();
+    assert!(matches!(spec.kind, PolicyKind::Default { num_iter } if num_iter == 10));
+    assert_eq!(spec.seed, Some(42));
+
+    let spec = "alphazero:num_simulation=20,epsilon=0.5,dirichlet_alpha=0.1,c_puct=2"
+        .parse::<PolicySpec>()
+        .unwrap();
+    match spec.kind {
+        PolicyKind::AlphaZero(param) => {
+            assert_eq!(param.num_simulation, 20);
+            assert_eq!(param.epsilon, 0.5);
+            assert_eq!(param.dirichlet_alpha, 0.1);
+            assert_eq!(param.c_puct, 2.);
+        }
+        _ => panic!("expected alphazero"),
+    }
+
+    // parameter of the other policy
+    assert!("random:num_iter=10".parse::<PolicySpec>().is_err());
+    assert!("default:num_iter".parse::<PolicySpec>().is_err());
+    assert!("default:num_iter=ten".parse::<PolicySpec>().is_err());
+    assert!("alphazero:unknown=1".parse::<PolicySpec>().is_err());
+}
+
+#[test]
+fn test_validate() {
+    assert!("default:num_iter=0".parse::<PolicySpec>().is_err());
+    assert!("alphazero:num_simulation=-1".parse::<PolicySpec>().is_err());
+    assert!("light:epsilon=1.5".parse::<PolicySpec>().is_err());
+    assert!("light:dirichlet_alpha=0".parse::<PolicySpec>().is_err());
+    assert!("light:c_puct=inf".parse::<PolicySpec>().is_err());
+    assert!("light:epsilon=NaN".parse::<PolicySpec>().is_err());
+
+    for name in POLICY_NAMES.iter() {
+        assert!(PolicyKind::from_name(name).unwrap().validate().is_ok());
+    }
+}
+
+#[test]
+fn test_build_seeded() {
+    for text in ["random:seed=7", "default:num_iter=5,seed=7", "light:seed=7"].iter() {
+        let spec = text.parse::<PolicySpec>().unwrap();
+        let mut policy1 = spec.build();
+        let mut policy2 = spec.build();
+
+        let result1 = Agent::new(&mut policy1).with_board_size(7).play().unwrap();
+        let result2 = Agent::new(&mut policy2).with_board_size(7).play().unwrap();
+        let moves1 = result1.path.iter().map(|x| x.pos).collect::<Vec<_>>();
+        let moves2 = result2.path.iter().map(|x| x.pos).collect::<Vec<_>>();
+        assert_eq!(moves1, moves2);
+    }
+}
+
+#[test]
+fn test_search_info() {
+    let game = Game::with_size(7);
+    let mut policy = "light".parse::<PolicySpec>().unwrap().build();
+    assert!(policy.next(&game).is_some());
+    assert!(policy.search_info().is_some());
+
+    let mut policy = "random".parse::<PolicySpec>().unwrap().build();
+    assert!(policy.next(&game).is_some());
+    assert!(policy.search_info().is_none());
+}
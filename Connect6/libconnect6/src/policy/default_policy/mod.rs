@@ -10,49 +10,51 @@
 //! ```
 use game::{Game, Player};
 use policy::simulate::Simulate;
-use policy::Policy;
-use {Board, BOARD_SIZE};
+use policy::{entropy_rng, seeded_rng, Policy};
+use {BitBoard, Board};
 
-use rand;
-use rand::prelude::{thread_rng, SliceRandom};
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+use std::time::Instant;
 
 #[cfg(test)]
 mod tests;
 
-/// Tree node, get child node as hash value of board array
+/// Tree node, get child node as Zobrist hash of board
 struct Node {
     visit: i32,
     black_win: i32,
-    board: Board,
+    board: BitBoard,
     next_node: Vec<u64>,
 }
 
 impl Node {
     /// Construct a new `Node`
-    fn new(board: &Board) -> Node {
+    fn new(board: &BitBoard) -> Node {
         Node {
             visit: 0,
             black_win: 0,
-            board: *board,
+            board: board.clone(),
             next_node: Vec::new(),
         }
     }
 }
 
-/// generate hash value of board
+/// generate hash value of board, both array form `Board` and packed `BitBoard`
 ///
 /// # Examples
 /// ```rust
 /// # extern crate connect6;
-/// # use connect6::{game::{Game, Player}, policy::hash, BOARD_SIZE};
+/// # use connect6::{game::Game, policy::hash, BitBoard, Board, BOARD_SIZE};
 /// let game = Game::new();
 /// let hashed = hash(game.get_board());
-/// assert_eq!(hashed, hash(&[[Player::None; BOARD_SIZE]; BOARD_SIZE]));
+/// assert_eq!(hashed, hash(&Board::new(BOARD_SIZE)));
+/// assert_eq!(hash(&BitBoard::new(9)), hash(&BitBoard::new(9)));
 /// ```
-pub fn hash(board: &Board) -> u64 {
+pub fn hash<T: Hash>(board: &T) -> u64 {
     let mut hasher = DefaultHasher::new();
     board.hash(&mut hasher);
     hasher.finish()
@@ -72,8 +74,9 @@ pub fn hash(board: &Board) -> u64 {
 /// assert_eq!(diff, Some((0, 0)));
 /// ```
 pub fn diff_board(board1: &Board, board2: &Board) -> Option<(usize, usize)> {
-    for row in 0..BOARD_SIZE {
-        for col in 0..BOARD_SIZE {
+    let size = board1.size();
+    for row in 0..size {
+        for col in 0..size {
             if board1[row][col] != board2[row][col] {
                 return Some((row, col));
             }
@@ -94,6 +97,8 @@ pub fn diff_board(board1: &Board, board2: &Board) -> Option<(usize, usize)> {
 pub struct DefaultPolicy {
     num_iter: i32,
     map: HashMap<u64, Node>,
+    rng: StdRng,
+    deadline: Option<Instant>,
 }
 
 impl DefaultPolicy {
@@ -102,6 +107,8 @@ impl DefaultPolicy {
         DefaultPolicy {
             num_iter: 50,
             map: HashMap::new(),
+            rng: entropy_rng(),
+            deadline: None,
         }
     }
 
@@ -110,6 +117,8 @@ impl DefaultPolicy {
         DefaultPolicy {
             num_iter,
             map: HashMap::new(),
+            rng: entropy_rng(),
+            deadline: None,
         }
     }
 
@@ -118,8 +127,8 @@ impl DefaultPolicy {
     /// For the first tree search, tree must be initialized with game status.
     /// `Init` initialize the tree with given `Simulate`
     fn init(&mut self, sim: &Simulate) {
-        let board = sim.board();
-        self.map.entry(hash(&board)).or_insert(Node::new(&board));
+        let board = sim.bitboard();
+        self.map.entry(sim.key()).or_insert(Node::new(&board));
     }
 
     /// Select the position of the highest winning probability.
@@ -127,7 +136,7 @@ impl DefaultPolicy {
     /// *Note* Given simulation must be initialized by `init` or `expand`.
     fn select(&self, sim: &Simulate) -> Option<(usize, usize)> {
         let node = sim.node.borrow();
-        let tree_node = self.map.get(&hash(&node.board)).unwrap();
+        let tree_node = self.map.get(&node.zobrist.key()).unwrap();
 
         // `Node` structure is based on player Black.
         // To calculate probability of given player, it should condition on given player and apply unary function.
@@ -149,7 +158,7 @@ impl DefaultPolicy {
             let max_node = self.map.get(hashed).unwrap();
             // if child_node has meaningful probability
             if prob(max_node) != 0. {
-                let pos = diff_board(&max_node.board, &node.board);
+                let pos = max_node.board.diff(&node.board);
                 return pos;
             }
         }
@@ -158,21 +167,19 @@ impl DefaultPolicy {
 
     /// Expand the tree in given simulation
     fn expand(&mut self, sim: &Simulate) -> (usize, usize) {
-        let mut rng = rand::thread_rng();
         let (row, col) = {
             let node = sim.node.borrow();
-            *node.possible.choose(&mut rng).unwrap()
+            *node.possible.choose(&mut self.rng).unwrap()
         };
         // simulate random selected position
-        let board = sim.simulate(row, col).board();
-        let hashed_board = hash(&board);
+        let (board, hashed_board) = {
+            let child = sim.simulate(row, col);
+            (child.bitboard(), child.key())
+        };
         // generate node
         self.map.insert(hashed_board, Node::new(&board));
 
-        let parent_node = {
-            let node = sim.node.borrow();
-            self.map.get_mut(&hash(&node.board)).unwrap()
-        };
+        let parent_node = self.map.get_mut(&sim.key()).unwrap();
         // make connection between parent and child
         parent_node.next_node.push(hashed_board);
 
@@ -185,19 +192,19 @@ impl DefaultPolicy {
     /// If random simulation of child node is end with no one win, method will be returned without update.
     fn update(&mut self, sim: &Simulate, path: &Vec<(usize, usize)>) {
         let mut simulate = sim.deep_clone();
-        let mut rng = rand::thread_rng();
         // random simulation
-        while simulate.search_winner() == Player::None {
+        let mut win = simulate.search_winner();
+        while win == Player::None {
             let (row, col) = {
                 let node = simulate.node.borrow();
-                match node.possible.choose(&mut rng) {
+                match node.possible.choose(&mut self.rng) {
                     Some(pos) => *pos,
                     None => break,
                 }
             };
             simulate.simulate_in(row, col);
+            win = simulate.search_winner_from(row, col);
         }
-        let win = simulate.search_winner();
         if win == Player::None {
             return;
         }
@@ -206,7 +213,7 @@ impl DefaultPolicy {
         // update parent node
         let mut sim = sim.deep_clone();
         let mut update = |sim: &Simulate| {
-            let node = self.map.get_mut(&hash(&sim.board())).unwrap();
+            let node = self.map.get_mut(&sim.key()).unwrap();
             node.visit += 1;
             node.black_win += black_win;
         };
@@ -246,12 +253,12 @@ impl DefaultPolicy {
     }
 
     /// Generate the policy, prob based selection or else random selection.
-    fn policy(&self, sim: &Simulate) -> Option<(usize, usize)> {
+    fn policy(&mut self, sim: &Simulate) -> Option<(usize, usize)> {
         let res = if let Some(pos) = self.select(sim) {
             pos
         } else {
             let node = sim.node.borrow();
-            *node.possible.choose(&mut thread_rng()).unwrap()
+            *node.possible.choose(&mut self.rng).unwrap()
         };
         Some(res)
     }
@@ -261,11 +268,24 @@ impl Policy for DefaultPolicy {
     /// Select position based on pure MCTS.
     fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
         // Simulation
-        for _ in 0..self.num_iter {
+        for i in 0..self.num_iter {
+            if i > 0 && self.deadline.map(|x| Instant::now() >= x) == Some(true) {
+                break;
+            }
             self.search(game);
         }
         let simulate = Simulate::from_game(game);
         // generate
         self.policy(&simulate)
     }
+
+    /// reseed the random number generator of expansion and random simulation
+    fn seed(&mut self, seed: u64) {
+        self.rng = seeded_rng(seed);
+    }
+
+    /// iterations are stopped at the deadline, `num_iter` is the upper bound
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
 }
@@ -1,7 +1,9 @@
 use super::*;
 use agent::Agent;
+use game::Zobrist;
+use BOARD_SIZE;
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_select() {
@@ -51,8 +53,7 @@ fn test_expand() {
     assert!(sim.validate(row, col));
     assert_eq!(policy.map.len(), 2);
 
-    let board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-    let node = policy.map.get(&hash(&board));
+    let node = policy.map.get(&Zobrist::new(BOARD_SIZE).key());
     assert!(node.is_some());
 
     let hashed = &node.unwrap().next_node;
@@ -83,8 +84,7 @@ fn test_update() {
     assert!(true);
     assert_eq!(policy.map.len(), 2);
 
-    let board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-    let node = policy.map.get(&hash(&board));
+    let node = policy.map.get(&Zobrist::new(BOARD_SIZE).key());
     assert!(node.is_some());
 
     let parent = node.unwrap();
@@ -100,12 +100,7 @@ fn test_update() {
     assert_eq!(child.visit, 1);
     assert_eq!(child.black_win, parent.black_win);
 
-    let num = child
-        .board
-        .iter()
-        .flat_map(|x| x.iter().filter(|y| **y != Player::None))
-        .count();
-    assert_eq!(num, 1);
+    assert_eq!(child.board.num_stones(), 1);
 }
 
 #[test]
@@ -144,3 +139,24 @@ fn test_self_play() {
     }
     assert!(true);
 }
+
+#[test]
+fn test_deadline() {
+    let mut policy = DefaultPolicy::with_num_iter(i32::MAX);
+    policy.set_deadline(Some(Instant::now() + Duration::from_millis(50)));
+
+    let now = Instant::now();
+    assert!(policy.next(&Game::with_size(7)).is_some());
+    assert!(now.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_seed() {
+    let play = |seed: u64| {
+        let mut policy = DefaultPolicy::with_num_iter(5);
+        policy.seed(seed);
+        let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+        result.path.iter().map(|x| x.pos).collect::<Vec<_>>()
+    };
+    assert_eq!(play(3), play(3));
+}
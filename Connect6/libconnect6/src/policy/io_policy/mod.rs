@@ -19,7 +19,6 @@
 //! ```
 use game::Game;
 use policy::Policy;
-use BOARD_SIZE;
 
 use std::io;
 
@@ -67,7 +66,8 @@ impl<'a, 'b> IoPolicy<'a, 'b> {
 
 impl<'a, 'b> Policy for IoPolicy<'a, 'b> {
     /// validate user input from stdin and passing it to `Agent`
-    fn next(&mut self, _game: &Game) -> Option<(usize, usize)> {
+    fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        let size = game.get_size();
         let mut pos = None;
         // until make the possible selection
         loop {
@@ -93,7 +93,7 @@ impl<'a, 'b> Policy for IoPolicy<'a, 'b> {
                     // validation
                     let row = row.unwrap() as usize - 0x61;
                     let col = col.unwrap() as usize - 0x41;
-                    if row < BOARD_SIZE && col < BOARD_SIZE {
+                    if row < size && col < size {
                         pos = Some((row, col));
                         break;
                     }
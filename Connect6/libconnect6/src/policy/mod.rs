@@ -9,6 +9,10 @@
 //! - IoPolicy : read user input.
 //! - MultiPolicy : Black-White seperable policy, pass two different policies as initialize parameter.
 //! - DefaultPolicy : Pure Monte Carlo tree search implementation.
+//! - BuiltinPolicy : built-in policies selectable by name with `PolicySpec`, such as `alphazero:num_simulation=50`.
+//!
+//! Randomness of the policies can be fixed with `Policy::seed` to reproduce the games.
+//! Evaluations of the parallel `AlphaZero` games can be batched with `BatchEvaluator`.
 //!
 //! # Examples
 //! ```rust
@@ -26,23 +30,63 @@
 //! assert!(result.is_err());
 //! ```
 pub use self::alphazero_policy::*;
+pub use self::batch_evaluator::*;
+pub use self::builtin::*;
 pub use self::default_policy::*;
 pub use self::io_policy::*;
 pub use self::multi_policy::*;
 pub use self::random_policy::*;
+pub use self::seed::*;
 pub use self::simulate::*;
 
 mod alphazero_policy;
+mod batch_evaluator;
+mod builtin;
 mod default_policy;
 mod io_policy;
 mod multi_policy;
 mod random_policy;
+mod seed;
 mod simulate;
 
 use game::Game;
+use GenericBoard;
+
+use std::time::Instant;
+
+/// Search data of the last selection, recorded in `agent::Path`.
+///
+/// Values are in range [-1, 1], from the perspective of the player who selected.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchInfo {
+    /// visit count distribution of the root children, training target of AlphaZero
+    pub visits: GenericBoard<f32>,
+    /// value of the root
+    pub value: f32,
+    /// q-value of the selected position
+    pub q_value: f32,
+}
 
 /// trait for playing game with Agent.
 pub trait Policy {
     /// generate next selection
     fn next(&mut self, game: &Game) -> Option<(usize, usize)>;
+
+    /// search data of the last selection, `None` if policy doesn't search
+    fn search_info(&self) -> Option<SearchInfo> {
+        None
+    }
+
+    /// estimated value of the last selection, default value of `search_info`
+    fn value(&self) -> Option<f32> {
+        self.search_info().map(|info| info.value)
+    }
+
+    /// reseed the random number generator, policy without randomness ignores it
+    fn seed(&mut self, _seed: u64) {}
+
+    /// stop the search of the next selections at the deadline, `None` for no limit,
+    /// policy without search ignores it
+    fn set_deadline(&mut self, _deadline: Option<Instant>) {}
 }
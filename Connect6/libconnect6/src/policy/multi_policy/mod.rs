@@ -14,7 +14,9 @@
 //! Agent::debug(&mut multi_policy).play().unwrap();
 //! ```
 use game::{Game, Player};
-use policy::Policy;
+use policy::{derive_seed, Policy, SearchInfo};
+
+use std::time::Instant;
 
 #[cfg(test)]
 mod tests;
@@ -37,6 +39,7 @@ mod tests;
 pub struct MultiPolicy<'a, 'b> {
     black_policy: &'a mut Policy,
     white_policy: &'b mut Policy,
+    last_turn: Player,
 }
 
 impl<'a, 'b> MultiPolicy<'a, 'b> {
@@ -54,6 +57,7 @@ impl<'a, 'b> MultiPolicy<'a, 'b> {
         MultiPolicy {
             black_policy,
             white_policy,
+            last_turn: Player::None,
         }
     }
 }
@@ -61,6 +65,7 @@ impl<'a, 'b> MultiPolicy<'a, 'b> {
 impl<'a, 'b> Policy for MultiPolicy<'a, 'b> {
     /// Condition on `game.turn` to pass policy seperately
     fn next(&mut self, game: &Game) -> Option<(usize, usize)> {
+        self.last_turn = game.get_turn();
         match game.get_turn() {
             Player::None => {
                 panic!("seperate_policy::init couldn't get next policy for player none")
@@ -69,4 +74,34 @@ impl<'a, 'b> Policy for MultiPolicy<'a, 'b> {
             Player::White => self.white_policy.next(game),
         }
     }
+
+    /// Search data of the policy which made the last selection
+    fn search_info(&self) -> Option<SearchInfo> {
+        match self.last_turn {
+            Player::None => None,
+            Player::Black => self.black_policy.search_info(),
+            Player::White => self.white_policy.search_info(),
+        }
+    }
+
+    /// Value of the policy which made the last selection
+    fn value(&self) -> Option<f32> {
+        match self.last_turn {
+            Player::None => None,
+            Player::Black => self.black_policy.value(),
+            Player::White => self.white_policy.value(),
+        }
+    }
+
+    /// Reseed both policies with the different derived seeds
+    fn seed(&mut self, seed: u64) {
+        self.black_policy.seed(derive_seed(seed, 0));
+        self.white_policy.seed(derive_seed(seed, 1));
+    }
+
+    /// Set the deadline of both policies
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.black_policy.set_deadline(deadline);
+        self.white_policy.set_deadline(deadline);
+    }
 }
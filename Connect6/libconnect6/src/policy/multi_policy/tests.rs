@@ -24,3 +24,56 @@ fn test_multi_policy() {
     }
     assert!(true);
 }
+
+#[test]
+fn test_value() {
+    struct ValuePolicy(f32);
+
+    impl Policy for ValuePolicy {
+        fn next(&mut self, _: &Game) -> Option<(usize, usize)> {
+            None
+        }
+
+        fn value(&self) -> Option<f32> {
+            Some(self.0)
+        }
+    }
+
+    let mut black_policy = ValuePolicy(-1.);
+    let mut white_policy = ValuePolicy(1.);
+    let mut policy = MultiPolicy::new(&mut black_policy, &mut white_policy);
+    assert_eq!(policy.value(), None);
+
+    // value of the policy which made the last selection
+    let mut game = Game::new();
+    policy.next(&game);
+    assert_eq!(policy.value(), Some(-1.));
+
+    game.set((0, 0)).unwrap();
+    policy.next(&game);
+    assert_eq!(policy.value(), Some(1.));
+}
+
+#[test]
+fn test_seed() {
+    struct SeedPolicy(Option<u64>);
+
+    impl Policy for SeedPolicy {
+        fn next(&mut self, _: &Game) -> Option<(usize, usize)> {
+            None
+        }
+
+        fn seed(&mut self, seed: u64) {
+            self.0 = Some(seed);
+        }
+    }
+
+    let mut black_policy = SeedPolicy(None);
+    let mut white_policy = SeedPolicy(None);
+    MultiPolicy::new(&mut black_policy, &mut white_policy).seed(5);
+
+    // both policies are seeded with the different seeds
+    assert!(black_policy.0.is_some());
+    assert!(white_policy.0.is_some());
+    assert_ne!(black_policy.0, white_policy.0);
+}
@@ -9,10 +9,10 @@
 //! assert!(result.is_ok());
 //! ```
 use game::Game;
-use policy::{Policy, Simulate};
+use policy::{entropy_rng, seeded_rng, Policy, Simulate};
 
-use rand;
 use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
 
 #[cfg(test)]
 mod tests;
@@ -27,12 +27,33 @@ mod tests;
 /// let result = Agent::new(&mut policy).play();
 /// assert!(result.is_ok());
 /// ```
-pub struct RandomPolicy {}
+pub struct RandomPolicy {
+    rng: StdRng,
+}
 
 impl RandomPolicy {
     /// Construct a new RandomPolicy
     pub fn new() -> RandomPolicy {
-        RandomPolicy {}
+        RandomPolicy { rng: entropy_rng() }
+    }
+
+    /// Construct a RandomPolicy with given seed, it makes the same selections on the same games.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, policy::RandomPolicy};
+    /// let mut policy1 = RandomPolicy::with_seed(42);
+    /// let mut policy2 = RandomPolicy::with_seed(42);
+    /// let result1 = Agent::new(&mut policy1).play().unwrap();
+    /// let result2 = Agent::new(&mut policy2).play().unwrap();
+    /// assert_eq!(result1.winner, result2.winner);
+    /// assert_eq!(result1.path.len(), result2.path.len());
+    /// ```
+    pub fn with_seed(seed: u64) -> RandomPolicy {
+        RandomPolicy {
+            rng: seeded_rng(seed),
+        }
     }
 }
 
@@ -42,6 +63,11 @@ impl Policy for RandomPolicy {
         let sim = Simulate::from_game(game);
         let node = sim.node.borrow();
         // choose position from vector `possible`
-        node.possible.choose(&mut rand::thread_rng()).map(|x| *x)
+        node.possible.choose(&mut self.rng).map(|x| *x)
+    }
+
+    /// reseed the random number generator
+    fn seed(&mut self, seed: u64) {
+        self.rng = seeded_rng(seed);
     }
 }
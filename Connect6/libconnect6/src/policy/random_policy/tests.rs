@@ -21,3 +21,18 @@ fn test_random_play() {
     }
     assert!(true);
 }
+
+#[test]
+fn test_seed() {
+    let play = |policy: &mut RandomPolicy| {
+        let result = Agent::new(policy).play().unwrap();
+        result.path.iter().map(|x| x.pos).collect::<Vec<_>>()
+    };
+    let path = play(&mut RandomPolicy::with_seed(10));
+    assert_eq!(path, play(&mut RandomPolicy::with_seed(10)));
+    assert_ne!(path, play(&mut RandomPolicy::with_seed(11)));
+
+    let mut policy = RandomPolicy::new();
+    policy.seed(10);
+    assert_eq!(path, play(&mut policy));
+}
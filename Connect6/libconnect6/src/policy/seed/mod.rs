@@ -0,0 +1,49 @@
+//! Seeded random number generators for reproducible games.
+//!
+//! Policies, evaluators and agents draw randomness from their own `StdRng`.
+//! It is seeded from the entropy by default, or from the given seed with `Policy::seed`.
+//! Seeds of the sub-components, such as each game of `AsyncAgent`, are derived with `derive_seed`.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # extern crate rand;
+//! # use connect6::policy::{derive_seed, seeded_rng};
+//! # use rand::Rng;
+//! let mut rng1 = seeded_rng(derive_seed(42, 0));
+//! let mut rng2 = seeded_rng(derive_seed(42, 0));
+//! assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+//! ```
+use game::mix;
+use rand::rngs::StdRng;
+use rand::{FromEntropy, SeedableRng};
+
+#[cfg(test)]
+mod tests;
+
+/// Derive the seed of the `index`-th sub-component from the given seed.
+///
+/// It mixes the seed and the index with SplitMix64 finalizer,
+/// derived seeds of the different indices are uncorrelated.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::policy::derive_seed;
+/// assert_eq!(derive_seed(42, 1), derive_seed(42, 1));
+/// assert_ne!(derive_seed(42, 1), derive_seed(42, 2));
+/// assert_ne!(derive_seed(42, 1), derive_seed(43, 1));
+/// ```
+pub fn derive_seed(seed: u64, index: u64) -> u64 {
+    mix(seed ^ mix(index))
+}
+
+/// Construct a `StdRng` from the given seed.
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Construct a `StdRng` seeded from the entropy, default rng of the policies.
+pub fn entropy_rng() -> StdRng {
+    StdRng::from_entropy()
+}
@@ -0,0 +1,27 @@
+use super::*;
+use rand::Rng;
+
+#[test]
+fn test_derive_seed() {
+    let seeds = (0..100).map(|i| derive_seed(0, i)).collect::<Vec<_>>();
+    let mut sorted = seeds.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), seeds.len());
+
+    assert_eq!(derive_seed(7, 3), derive_seed(7, 3));
+    assert_ne!(derive_seed(7, 3), derive_seed(3, 7));
+}
+
+#[test]
+fn test_seeded_rng() {
+    let mut rng1 = seeded_rng(1);
+    let mut rng2 = seeded_rng(1);
+    let mut rng3 = seeded_rng(2);
+
+    let seq1 = (0..10).map(|_| rng1.gen::<u32>()).collect::<Vec<_>>();
+    let seq2 = (0..10).map(|_| rng2.gen::<u32>()).collect::<Vec<_>>();
+    let seq3 = (0..10).map(|_| rng3.gen::<u32>()).collect::<Vec<_>>();
+    assert_eq!(seq1, seq2);
+    assert_ne!(seq1, seq3);
+}
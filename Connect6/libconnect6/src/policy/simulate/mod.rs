@@ -3,6 +3,8 @@
 //! It provides simulation structure and some utilies to make next decision in policy.
 //!
 //! It shares possible selections and board by `Node` to make simulation without copying it.
+//! The board of `Node` is packed as `BitBoard`, `board` converts it to the array form.
+//! `Node` also updates the Zobrist hash of the board incrementally, it is used as the key of the search tree.
 //! It generate new simulation with `simulate` and recover the shared memory `Node` when it drop.
 //! It can simulate itself mutablely by `simulate_in` and recover it by `rollback_in`.
 //!
@@ -20,8 +22,8 @@
 //! let board = sim.board();
 //! assert_eq!(board[0][0], Player::None);
 //! ```
-use game::{search, Game, Player};
-use {Board, BOARD_SIZE};
+use game::{Game, Player, Rule, Zobrist};
+use {BitBoard, Board, BOARD_SIZE};
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -31,41 +33,57 @@ mod tests;
 
 /// Shared memory for making simulation wihout copying board and possible selections.
 pub struct Node {
-    pub board: Board,
+    pub board: BitBoard,
+    pub zobrist: Zobrist,
     pub possible: Vec<(usize, usize)>,
 }
 
 impl Node {
     /// Make all possible selections within the board.
     #[inline]
-    fn possible() -> Vec<(usize, usize)> {
-        (0..BOARD_SIZE)
-            .flat_map(|x| (0..BOARD_SIZE).map(move |y| (x, y)))
+    fn possible(size: usize) -> Vec<(usize, usize)> {
+        (0..size)
+            .flat_map(|x| (0..size).map(move |y| (x, y)))
             .collect()
     }
 
-    /// Construct a new `Node`
-    fn new() -> Node {
+    /// Construct a new `Node` with given length of one side.
+    fn new(size: usize) -> Node {
         Node {
-            board: [[Player::None; BOARD_SIZE]; BOARD_SIZE],
-            possible: Self::possible(),
+            board: BitBoard::new(size),
+            zobrist: Zobrist::new(size),
+            possible: Self::possible(size),
         }
     }
 
     /// Construct a `Node` from the board
     ///
     /// It make possible selections depending on the board status.
-    fn from_board(board: &Board) -> Node {
-        let possible = Self::possible()
-            .into_iter()
-            .filter(|(r, c)| board[*r][*c] == Player::None)
-            .collect();
-
+    fn from_board(board: &BitBoard, zobrist: Zobrist) -> Node {
         Node {
-            board: *board,
-            possible,
+            board: board.clone(),
+            zobrist,
+            possible: board.positions(Player::None),
         }
     }
+
+    /// Place the stone of given player and update the hash.
+    fn place(&mut self, row: usize, col: usize, player: Player) {
+        let item = self.possible.iter().position(|x| *x == (row, col));
+        self.possible.remove(item.unwrap());
+
+        self.board.set(row, col, player);
+        self.zobrist.toggle(row, col, player);
+    }
+
+    /// Remove the stone of given position and update the hash.
+    fn remove(&mut self, row: usize, col: usize) {
+        let player = self.board.get(row, col);
+        self.zobrist.toggle(row, col, player);
+        self.board.clear(row, col);
+
+        self.possible.push((row, col));
+    }
 }
 
 /// Game simulator with shared memory for efficient tree searching
@@ -93,18 +111,48 @@ impl Node {
 pub struct Simulate {
     pub turn: Player,
     pub num_remain: i32,
+    pub rule: Rule,
     pub pos: Option<(usize, usize)>,
     pub node: Rc<RefCell<Node>>,
 }
 
 impl Simulate {
-    /// Construct a new `Simulate`.
+    /// Construct a new `Simulate` with default board size, `BOARD_SIZE`.
     pub fn new() -> Simulate {
+        Simulate::with_size(BOARD_SIZE)
+    }
+
+    /// Construct a new `Simulate` with given length of one side.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::policy::Simulate;
+    /// let sim = Simulate::with_size(9);
+    /// assert_eq!(sim.possible().len(), 81);
+    /// ```
+    pub fn with_size(size: usize) -> Simulate {
+        Simulate::with_rule(size, Rule::default())
+    }
+
+    /// Construct a new `Simulate` with given length of one side and rule.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::{Player, Rule}, policy::Simulate};
+    /// let mut sim = Simulate::with_rule(9, Rule::gomoku());
+    /// sim.simulate_in(0, 0);
+    /// assert_eq!(sim.turn, Player::White);
+    /// assert_eq!(sim.num_remain, 1);
+    /// ```
+    pub fn with_rule(size: usize, rule: Rule) -> Simulate {
         Simulate {
             turn: Player::Black,
-            num_remain: 1,
+            num_remain: rule.opening_stones,
+            rule,
             pos: None,
-            node: Rc::new(RefCell::new(Node::new())),
+            node: Rc::new(RefCell::new(Node::new(size))),
         }
     }
 
@@ -121,8 +169,12 @@ impl Simulate {
         Simulate {
             turn: game.get_turn(),
             num_remain: game.get_remain(),
+            rule: game.get_rule(),
             pos: None,
-            node: Rc::new(RefCell::new(Node::from_board(game.get_board()))),
+            node: Rc::new(RefCell::new(Node::from_board(
+                &BitBoard::from_board(game.get_board()),
+                *game.zobrist(),
+            ))),
         }
     }
 
@@ -132,20 +184,67 @@ impl Simulate {
     /// By this reason, we require the `deep_clone` implementation which makes the *deep* copy of `Node`.
     pub fn deep_clone(&self) -> Simulate {
         let node = self.node.borrow();
-        let board = node.board;
 
         Simulate {
             turn: self.turn,
             num_remain: self.num_remain,
+            rule: self.rule,
             pos: None,
-            node: Rc::new(RefCell::new(Node::from_board(&board))),
+            node: Rc::new(RefCell::new(Node::from_board(&node.board, node.zobrist))),
         }
     }
 
-    /// Get the board from node.
+    /// Get the board from node as the array form.
     pub fn board(&self) -> Board {
         let node = self.node.borrow();
-        node.board
+        node.board.to_board()
+    }
+
+    /// Get the packed board from node.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, policy::Simulate};
+    /// let sim = Simulate::new();
+    /// let sim2 = sim.simulate(0, 0);
+    /// assert_eq!(sim2.bitboard().get(0, 0), Player::Black);
+    /// assert_eq!(sim2.bitboard().to_board(), sim2.board());
+    /// ```
+    pub fn bitboard(&self) -> BitBoard {
+        let node = self.node.borrow();
+        node.board.clone()
+    }
+
+    /// Get the Zobrist hash of the board, key of the search tree.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::policy::Simulate;
+    /// let mut sim = Simulate::new();
+    /// let empty = sim.key();
+    ///
+    /// sim.simulate_in(0, 0);
+    /// assert_ne!(sim.key(), empty);
+    ///
+    /// sim.rollback_in(0, 0);
+    /// assert_eq!(sim.key(), empty);
+    /// ```
+    pub fn key(&self) -> u64 {
+        self.node.borrow().zobrist.key()
+    }
+
+    /// Get the symmetry invariant Zobrist hash of the board.
+    ///
+    /// Transpositions under the rotation and reflection have the same key.
+    pub fn canonical_key(&self) -> u64 {
+        self.node.borrow().zobrist.canonical()
+    }
+
+    /// Get the length of one side.
+    pub fn size(&self) -> usize {
+        self.node.borrow().board.size()
     }
 
     /// Get the possible selections from node.
@@ -165,8 +264,26 @@ impl Simulate {
     /// assert_eq!(game.is_game_end(), sim.search_winner());
     /// ```
     pub fn search_winner(&self) -> Player {
-        let board = &self.node.borrow().board;
-        search(board)
+        self.node.borrow().board.search_with(&self.rule)
+    }
+
+    /// Find the winner of game with the four lines through given position.
+    ///
+    /// It assumes that there was no winner before the stone of given position was placed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, policy::Simulate};
+    /// let mut sim = Simulate::new();
+    /// sim.simulate_in(0, 0);
+    /// assert_eq!(sim.search_winner_from(0, 0), Player::None);
+    /// ```
+    pub fn search_winner_from(&self, row: usize, col: usize) -> Player {
+        self.node
+            .borrow()
+            .board
+            .search_from_with(row, col, &self.rule)
     }
 
     /// Validate the position, check invalid position err or already selected position err.
@@ -183,11 +300,11 @@ impl Simulate {
     /// assert!(!sim2.validate(0, 0));
     /// ```
     pub fn validate(&self, row: usize, col: usize) -> bool {
-        if row >= BOARD_SIZE || col >= BOARD_SIZE {
+        let board = &self.node.borrow().board;
+        if row >= board.size() || col >= board.size() {
             return false;
         }
-        let board = &self.node.borrow().board;
-        if board[row][col] != Player::None {
+        if board.get(row, col) != Player::None {
             return false;
         }
         true
@@ -223,22 +340,19 @@ impl Simulate {
     /// assert_eq!(sim.board()[0][0], Player::None);
     /// ```
     pub fn simulate(&self, row: usize, col: usize) -> Simulate {
-        let mut node = self.node.borrow_mut();
         // remove given position from possible selections
-        let item = node.possible.iter().position(|x| *x == (row, col));
-        node.possible.remove(item.unwrap());
-
-        node.board[row][col] = self.turn;
+        self.node.borrow_mut().place(row, col, self.turn);
         // switching turn
         let (turn, num_remain) = if self.num_remain <= 1 {
-            (self.turn.switch(), 2)
+            (self.turn.switch(), self.rule.stones_per_turn)
         } else {
-            (self.turn, 1)
+            (self.turn, self.num_remain - 1)
         };
 
         Simulate {
             turn,
             num_remain,
+            rule: self.rule,
             pos: Some((row, col)),
             node: self.node.clone(),
         }
@@ -260,15 +374,11 @@ impl Simulate {
     /// assert_eq!(sim.board()[0][0], Player::Black);
     /// ```
     pub fn simulate_in(&mut self, row: usize, col: usize) {
-        let mut node = self.node.borrow_mut();
-        let item = node.possible.iter().position(|x| *x == (row, col));
-        node.possible.remove(item.unwrap());
-
-        node.board[row][col] = self.turn;
+        self.node.borrow_mut().place(row, col, self.turn);
         self.num_remain -= 1;
 
         if self.num_remain <= 0 {
-            self.num_remain = 2;
+            self.num_remain = self.rule.stones_per_turn;
             self.turn.mut_switch();
         }
     }
@@ -288,15 +398,15 @@ impl Simulate {
     /// assert_eq!(sim.board()[0][0], Player::None);
     /// ```
     pub fn rollback_in(&mut self, row: usize, col: usize) {
-        let mut node = self.node.borrow_mut();
-
-        node.board[row][col] = Player::None;
-        node.possible.push((row, col));
+        let player = self.node.borrow().board.get(row, col);
+        self.node.borrow_mut().remove(row, col);
 
-        self.num_remain += 1;
-        if self.num_remain > 2 {
+        // if the stone ended the turn of its player, it was the last one of the turn
+        if player == self.turn {
+            self.num_remain += 1;
+        } else {
+            self.turn = player;
             self.num_remain = 1;
-            self.turn.mut_switch();
         }
     }
 }
@@ -304,9 +414,7 @@ impl Simulate {
 impl Drop for Simulate {
     fn drop(&mut self) {
         if let Some((row, col)) = self.pos {
-            let mut node = self.node.borrow_mut();
-            node.possible.push((row, col));
-            node.board[row][col] = Player::None;
+            self.node.borrow_mut().remove(row, col);
         }
     }
 }
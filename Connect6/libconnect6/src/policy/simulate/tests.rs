@@ -15,10 +15,21 @@ mod simulate_tests {
         assert_eq!(simulate.pos, None);
 
         let node = simulate.node.borrow();
-        assert_eq!(node.board, [[Player::None; BOARD_SIZE]; BOARD_SIZE]);
+        assert_eq!(node.board, BitBoard::new(BOARD_SIZE));
         assert_eq!(node.possible.len(), BOARD_CAPACITY);
     }
 
+    #[test]
+    fn test_with_size() {
+        let simulate = Simulate::with_size(9);
+        assert_eq!(simulate.size(), 9);
+
+        let node = simulate.node.borrow();
+        assert_eq!(node.board, BitBoard::new(9));
+        assert_eq!(node.possible.len(), 81);
+        assert!(!simulate.validate(9, 0));
+    }
+
     #[test]
     fn test_from_game() {
         let game = Game::new();
@@ -29,7 +40,7 @@ mod simulate_tests {
         assert_eq!(simulate.pos, None);
 
         let node = simulate.node.borrow();
-        assert_eq!(node.board, [[Player::None; BOARD_SIZE]; BOARD_SIZE]);
+        assert_eq!(node.board, BitBoard::new(BOARD_SIZE));
         assert_eq!(node.possible.len(), BOARD_CAPACITY);
     }
 
@@ -41,7 +52,7 @@ mod simulate_tests {
         {
             // borrow_mut simulate.node: Rc<RefCell<Node>>
             let mut node = simulate.node.borrow_mut();
-            node.board[0][0] = Player::Black;
+            node.board.set(0, 0, Player::Black);
         }
         assert_eq!(simulate.board()[0][0], Player::Black);
         assert_eq!(cloned.board()[0][0], Player::None);
@@ -56,7 +67,7 @@ mod simulate_tests {
         {
             // borrow_mut simulate.node: Rc<RefCell<Node>>
             let mut node = simulate.node.borrow_mut();
-            node.board[0][0] = Player::Black;
+            node.board.set(0, 0, Player::Black);
         }
         assert!(!simulate.validate(0, 0));
 
@@ -80,6 +91,46 @@ mod simulate_tests {
         assert_eq!(simulate.next_turn(), Player::Black);
     }
 
+    #[test]
+    fn test_search_winner_from() {
+        let mut simulate = Simulate::new();
+        // black (0, 0), then white and black take two columns each
+        simulate.simulate_in(0, 0);
+        for i in 0..5 {
+            simulate.simulate_in(i, 5);
+            simulate.simulate_in(i, 6);
+            assert_eq!(simulate.search_winner_from(i, 6), Player::None);
+
+            simulate.simulate_in(i + 1, 0);
+            simulate.simulate_in(i + 1, 1);
+        }
+        assert_eq!(simulate.search_winner_from(5, 1), Player::None);
+        assert_eq!(simulate.search_winner_from(5, 0), Player::Black);
+        assert_eq!(simulate.search_winner(), Player::Black);
+    }
+
+    #[test]
+    fn test_key() {
+        let mut game = Game::new();
+        game.set((0, 0)).unwrap();
+
+        let mut simulate = Simulate::from_game(&game);
+        assert_eq!(simulate.key(), game.zobrist().key());
+        {
+            let sim = simulate.simulate(1, 1);
+            game.set((1, 1)).unwrap();
+            assert_eq!(sim.key(), game.zobrist().key());
+            assert_eq!(sim.deep_clone().key(), sim.key());
+            game.undo();
+        }
+        assert_eq!(simulate.key(), game.zobrist().key());
+
+        simulate.simulate_in(2, 2);
+        simulate.simulate_in(3, 3);
+        let node = simulate.node.borrow();
+        assert_eq!(node.zobrist, Zobrist::from_board(&node.board.to_board()));
+    }
+
     #[test]
     fn test_simulate() {
         let game = Game::new();
@@ -87,7 +138,7 @@ mod simulate_tests {
         {
             let sim_aa = simulate.simulate(0, 0);
             let node = sim_aa.node.borrow();
-            assert_eq!(node.board[0][0], Player::Black);
+            assert_eq!(node.board.get(0, 0), Player::Black);
             assert_eq!(sim_aa.turn, Player::White);
             assert_eq!(sim_aa.num_remain, 2);
 
@@ -95,7 +146,7 @@ mod simulate_tests {
             assert!(index.is_none());
         }
         let node = simulate.node.borrow();
-        assert_eq!(node.board[0][0], Player::None);
+        assert_eq!(node.board.get(0, 0), Player::None);
 
         let index = node.possible.iter().position(|x| *x == (0, 0));
         assert!(index.is_some());
@@ -108,7 +159,7 @@ mod simulate_tests {
         simulate.simulate_in(0, 0);
 
         let node = simulate.node.borrow();
-        assert_eq!(node.board[0][0], Player::Black);
+        assert_eq!(node.board.get(0, 0), Player::Black);
         assert_eq!(simulate.turn, Player::White);
         assert_eq!(simulate.num_remain, 2);
 
@@ -124,7 +175,7 @@ mod simulate_tests {
         simulate.simulate_in(0, 0);
         {
             let node = simulate.node.borrow();
-            assert_eq!(node.board[0][0], Player::Black);
+            assert_eq!(node.board.get(0, 0), Player::Black);
         }
 
         simulate.rollback_in(0, 0);
@@ -133,7 +184,51 @@ mod simulate_tests {
         assert_eq!(simulate.pos, None);
 
         let node = simulate.node.borrow();
-        assert_eq!(node.board, [[Player::None; BOARD_SIZE]; BOARD_SIZE]);
+        assert_eq!(node.board, BitBoard::new(BOARD_SIZE));
         assert_eq!(node.possible.len(), BOARD_CAPACITY);
     }
+    #[test]
+    fn test_with_rule() {
+        let rule = Rule::new(5, 3, 2);
+        let mut simulate = Simulate::with_rule(9, rule);
+        assert_eq!(simulate.rule, rule);
+
+        let expected = [
+            (Player::Black, 2),
+            (Player::Black, 1),
+            (Player::White, 3),
+            (Player::White, 2),
+            (Player::White, 1),
+            (Player::Black, 3),
+        ];
+        for i in 0..expected.len() - 1 {
+            assert_eq!((simulate.turn, simulate.num_remain), expected[i]);
+            {
+                let child = simulate.simulate(i, 1);
+                assert_eq!((child.turn, child.num_remain), expected[i + 1]);
+            }
+            simulate.simulate_in(i, 0);
+        }
+
+        // rollback recovers the turn state of each stone
+        for i in (0..expected.len() - 1).rev() {
+            simulate.rollback_in(i, 0);
+            assert_eq!((simulate.turn, simulate.num_remain), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_search_winner_with_rule() {
+        let game = Game::with_rule(9, Rule::gomoku());
+        let mut simulate = Simulate::from_game(&game);
+        for i in 0..4 {
+            simulate.simulate_in(0, i);
+            simulate.simulate_in(1, i);
+        }
+        assert_eq!(simulate.search_winner(), Player::None);
+
+        simulate.simulate_in(0, 4);
+        assert_eq!(simulate.search_winner(), Player::Black);
+        assert_eq!(simulate.search_winner_from(0, 4), Player::Black);
+    }
 }
@@ -0,0 +1,603 @@
+//! Game record with move-list notation.
+//!
+//! `GameRecord` writes and parses the textual record of the game.
+//! Record consists of header metadata as `[Key "Value"]` and the list of moves grouped by turn.
+//! Position is written as column letters and one-based row number, `(0, 0)` is `A1` and `(7, 7)` is `H8`.
+//!
+//! ```text
+//! [Black "RandomPolicy"]
+//! [White "DefaultPolicy"]
+//! [Rule "Connect6"]
+//! [Size "15"]
+//! [Result "W+"]
+//! [Date "2018.10.18"]
+//!
+//! 1. H8
+//! 2. G7 G9
+//! 3. ...
+//! ```
+//!
+//! Game started from the setup position, such as handicap stones or `Agent::play_from`,
+//! writes the setup stones and the side to move with its remaining stones.
+//!
+//! ```text
+//! [AddBlack "D4 E5"]
+//! [AddWhite ""]
+//! [ToMove "W 2"]
+//! ```
+//!
+//! Rule is written as the notation of [Rule](../game/struct.Rule.html), such as `Connect6` or `Gomoku`,
+//! and the moves are grouped by the stones of each turn under the rule.
+//!
+//! Result is one of `B+` (black win), `W+` (white win), `Draw` and `*` (ongoing).
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{game::Game, record::GameRecord};
+//! let mut game = Game::new();
+//! game.set((7, 7)).unwrap();
+//! game.set((6, 6)).unwrap();
+//!
+//! let mut record = GameRecord::from_game(&game);
+//! record.black = "alice".to_string();
+//!
+//! let text = record.to_string();
+//! let parsed = text.parse::<GameRecord>().unwrap();
+//! assert_eq!(parsed, record);
+//! assert_eq!(parsed.moves, vec![(7, 7), (6, 6)]);
+//! ```
+use agent::{EndReason, Path, PlayResult};
+use game::{Game, GameStatus, Player, Rule};
+use {Board, BOARD_SIZE, MAX_BOARD_SIZE};
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// Error for invalid game record.
+#[derive(Debug, Clone)]
+struct InvalidRecordError {
+    msg: String,
+}
+
+impl InvalidRecordError {
+    fn boxed(msg: String) -> Box<Error + Send> {
+        Box::new(InvalidRecordError { msg })
+    }
+}
+
+impl fmt::Display for InvalidRecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid record: {}", self.msg)
+    }
+}
+
+impl Error for InvalidRecordError {
+    fn description(&self) -> &str {
+        "invalid record"
+    }
+}
+
+/// Check the length of one side is in range [1, MAX_BOARD_SIZE].
+fn check_size(size: usize) -> Result<(), Box<Error + Send>> {
+    if size == 0 || size > MAX_BOARD_SIZE {
+        let msg = format!("size {} out of [1, {}]", size, MAX_BOARD_SIZE);
+        return Err(InvalidRecordError::boxed(msg));
+    }
+    Ok(())
+}
+
+/// Write the position as move-list notation, column letters and one-based row number.
+///
+/// Columns after `Z` continue with two letters as `AA`, `AB`, .., so that any size of board can be written.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::record::format_pos;
+/// assert_eq!(format_pos((0, 0)), "A1");
+/// assert_eq!(format_pos((9, 2)), "C10");
+/// assert_eq!(format_pos((0, 26)), "AA1");
+/// ```
+pub fn format_pos(pos: (usize, usize)) -> String {
+    let (row, mut col) = pos;
+    // bijective base-26, A is 1 and Z is 26
+    let mut letters = Vec::new();
+    loop {
+        letters.push(b'A' + (col % 26) as u8);
+        if col < 26 {
+            break;
+        }
+        col = col / 26 - 1;
+    }
+    letters.reverse();
+    format!("{}{}", String::from_utf8(letters).unwrap(), row + 1)
+}
+
+/// Parse the position from move-list notation, case insensitive.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::record::parse_pos;
+/// assert_eq!(parse_pos("H8"), Some((7, 7)));
+/// assert_eq!(parse_pos("c10"), Some((9, 2)));
+/// assert_eq!(parse_pos("AB3"), Some((2, 27)));
+/// assert_eq!(parse_pos("8H"), None);
+/// ```
+pub fn parse_pos(token: &str) -> Option<(usize, usize)> {
+    let num_letters = token
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(token.len());
+    if num_letters == 0 {
+        return None;
+    }
+    let (letters, digits) = token.split_at(num_letters);
+    let mut col: usize = 0;
+    for c in letters.chars() {
+        let digit = (c.to_ascii_uppercase() as usize) - ('A' as usize) + 1;
+        col = col.checked_mul(26)?.checked_add(digit)?;
+    }
+    let row = digits.parse::<usize>().ok()?;
+    if row == 0 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((row - 1, col - 1))
+}
+
+/// Write the game status as result notation.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::{GameStatus, Player}, record::format_result};
+/// assert_eq!(format_result(GameStatus::Win(Player::Black)), "B+");
+/// assert_eq!(format_result(GameStatus::Ongoing), "*");
+/// ```
+pub fn format_result(status: GameStatus) -> &'static str {
+    match status {
+        GameStatus::Win(Player::Black) => "B+",
+        GameStatus::Win(Player::White) => "W+",
+        GameStatus::Draw => "Draw",
+        _ => "*",
+    }
+}
+
+/// Parse the game status from result notation.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::{GameStatus, Player}, record::parse_result};
+/// assert_eq!(parse_result("W+"), Some(GameStatus::Win(Player::White)));
+/// assert_eq!(parse_result("Draw"), Some(GameStatus::Draw));
+/// assert_eq!(parse_result("X+"), None);
+/// ```
+pub fn parse_result(result: &str) -> Option<GameStatus> {
+    match result {
+        "B+" => Some(GameStatus::Win(Player::Black)),
+        "W+" => Some(GameStatus::Win(Player::White)),
+        "Draw" => Some(GameStatus::Draw),
+        "*" => Some(GameStatus::Ongoing),
+        _ => None,
+    }
+}
+
+/// Escape the header value.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Parse the header line, `[Key "Value"]`.
+fn parse_header(line: &str) -> Option<(&str, String)> {
+    let line = line.trim();
+    if !line.starts_with('[') || !line.ends_with(']') {
+        return None;
+    }
+    let inner = &line[1..line.len() - 1];
+    let sep = inner.find(char::is_whitespace)?;
+    let (key, value) = inner.split_at(sep);
+
+    let value = value.trim();
+    if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+        return None;
+    }
+    // unescape
+    let mut unescaped = String::new();
+    let mut chars = value[1..value.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.push(chars.next()?);
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Some((key, unescaped))
+}
+
+/// Write the stones as space separated move-list notation.
+fn format_stones(stones: &[(usize, usize)]) -> String {
+    let stones = stones.iter().map(|x| format_pos(*x)).collect::<Vec<_>>();
+    stones.join(" ")
+}
+
+/// Parse the space separated stones in move-list notation.
+fn parse_stones(text: &str) -> Result<Vec<(usize, usize)>, Box<Error + Send>> {
+    text.split_whitespace()
+        .map(|x| parse_pos(x).ok_or_else(|| InvalidRecordError::boxed(format!("stone {}", x))))
+        .collect()
+}
+
+/// Parse the side to move and its remaining stones, such as `W 2`.
+fn parse_to_move(text: &str) -> Option<(Player, i32)> {
+    let mut split = text.split_whitespace();
+    let turn = match split.next()? {
+        "B" => Player::Black,
+        "W" => Player::White,
+        _ => return None,
+    };
+    let num_remain = split.next()?.parse().ok()?;
+    if split.next().is_some() {
+        return None;
+    }
+    Some((turn, num_remain))
+}
+
+/// Setup position of the record, stones placed before the moves and the side to move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Setup {
+    pub black: Vec<(usize, usize)>,
+    pub white: Vec<(usize, usize)>,
+    pub turn: Player,
+    pub num_remain: i32,
+}
+
+impl Setup {
+    /// Construct the `Setup` from the board and the side to move,
+    /// None if it is the regular start, empty board and the opening stones of black.
+    fn from_board(board: &Board, turn: Player, num_remain: i32, rule: &Rule) -> Option<Setup> {
+        let mut setup = Setup {
+            black: Vec::new(),
+            white: Vec::new(),
+            turn,
+            num_remain,
+        };
+        let size = board.size();
+        for row in 0..size {
+            for col in 0..size {
+                match board[row][col] {
+                    Player::Black => setup.black.push((row, col)),
+                    Player::White => setup.white.push((row, col)),
+                    Player::None => (),
+                }
+            }
+        }
+
+        let empty = setup.black.is_empty() && setup.white.is_empty();
+        if empty && turn == Player::Black && num_remain == rule.opening_stones {
+            None
+        } else {
+            Some(setup)
+        }
+    }
+}
+
+/// Textual record of the game, header metadata and list of moves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    pub black: String,
+    pub white: String,
+    pub rule: Rule,
+    pub size: usize,
+    pub setup: Option<Setup>,
+    pub result: GameStatus,
+    pub date: String,
+    pub moves: Vec<(usize, usize)>,
+}
+
+impl GameRecord {
+    /// Construct an empty `GameRecord` with given length of one side.
+    ///
+    /// Unknown metadata are written as `?`.
+    pub fn new(size: usize) -> GameRecord {
+        GameRecord {
+            black: "?".to_string(),
+            white: "?".to_string(),
+            rule: Rule::default(),
+            size,
+            setup: None,
+            result: GameStatus::Ongoing,
+            date: "?".to_string(),
+            moves: Vec::new(),
+        }
+    }
+
+    /// Construct a `GameRecord` from the history of the game.
+    ///
+    /// Stones placed without the history, such as handicap stones, are written as the setup.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::{Game, Player}, record::GameRecord};
+    /// let mut game = Game::with_handicap(15, Player::Black, &[(3, 3)]).unwrap();
+    /// game.set((7, 7)).unwrap();
+    ///
+    /// let record = GameRecord::from_game(&game);
+    /// assert_eq!(record.setup.as_ref().unwrap().black, vec![(3, 3)]);
+    /// assert_eq!(record.moves, vec![(7, 7)]);
+    /// assert_eq!(record.to_game().unwrap().get_board(), game.get_board());
+    /// ```
+    pub fn from_game(game: &Game) -> GameRecord {
+        let mut record = GameRecord::new(game.get_size());
+        record.rule = game.get_rule();
+        record.result = game.status();
+        record.moves = game.history().iter().map(|x| x.position).collect();
+
+        // take back the history to find the setup position
+        let (turn, num_remain) = match game.history().first() {
+            Some(first) => (first.player, first.num_remain + 1),
+            None => (game.get_turn(), game.get_remain()),
+        };
+        let mut board = game.get_board().clone();
+        for &(row, col) in record.moves.iter() {
+            board[row][col] = Player::None;
+        }
+        record.setup = Setup::from_board(&board, turn, num_remain, &record.rule);
+        record
+    }
+
+    /// Construct a `GameRecord` from the result of `Agent`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, policy::RandomPolicy, record::GameRecord};
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).play().unwrap();
+    ///
+    /// let record = GameRecord::from_play_result(&result);
+    /// assert_eq!(record.moves.len(), result.path.len());
+    /// assert_eq!(record.result, result.status);
+    /// ```
+    pub fn from_play_result(result: &PlayResult) -> GameRecord {
+        GameRecord::from_play_result_with_rule(result, Rule::default())
+    }
+
+    /// Construct a `GameRecord` from the result of `Agent` played under the given rule,
+    /// reference [Agent::with_rule](../agent/struct.Agent.html#method.with_rule).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, game::Rule, policy::RandomPolicy, record::GameRecord};
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).with_rule(Rule::gomoku()).play().unwrap();
+    ///
+    /// let record = GameRecord::from_play_result_with_rule(&result, Rule::gomoku());
+    /// assert_eq!(record.rule, Rule::gomoku());
+    /// assert_eq!(record.to_game().unwrap().status(), result.status);
+    /// ```
+    pub fn from_play_result_with_rule(result: &PlayResult, rule: Rule) -> GameRecord {
+        let size = result.path.first().map_or(BOARD_SIZE, |x| x.board.size());
+        let mut record = GameRecord::new(size);
+        record.rule = rule;
+        record.result = result.status;
+        record.moves = result.path.iter().map(|x| x.pos).collect();
+
+        if let Some(first) = result.path.first() {
+            let num_stones = result
+                .path
+                .iter()
+                .take_while(|x| x.turn == first.turn)
+                .count();
+            let mut num_remain = num_stones as i32;
+            // first turn cut by the end of the game is regarded as the full turn
+            if num_stones == result.path.len() {
+                let full = if first.turn == Player::Black {
+                    rule.opening_stones
+                } else {
+                    rule.stones_per_turn
+                };
+                num_remain = num_remain.max(full);
+            }
+            record.setup = Setup::from_board(&first.board, first.turn, num_remain, &rule);
+        }
+        record
+    }
+
+    /// Construct the game at the setup position, before the moves.
+    ///
+    /// # Errors
+    /// - if the size isn't in range [1, MAX_BOARD_SIZE].
+    /// - if the setup stones are out of the board or duplicated.
+    /// - if the setup position is invalid, reference
+    ///   [Game::from_position_with_rule](../game/struct.Game.html#method.from_position_with_rule).
+    pub fn setup_game(&self) -> Result<Game, Box<Error + Send>> {
+        check_size(self.size)?;
+        let setup = match self.setup {
+            Some(ref setup) => setup,
+            None => return Ok(Game::with_rule(self.size, self.rule)),
+        };
+
+        let mut board = Board::new(self.size);
+        let black = setup.black.iter().map(|x| (*x, Player::Black));
+        let white = setup.white.iter().map(|x| (*x, Player::White));
+        for ((row, col), player) in black.chain(white) {
+            if row >= self.size || col >= self.size || board[row][col] != Player::None {
+                let msg = format!("setup stone {}", format_pos((row, col)));
+                return Err(InvalidRecordError::boxed(msg));
+            }
+            board[row][col] = player;
+        }
+        Game::from_position_with_rule(board, setup.turn, setup.num_remain, self.rule)
+    }
+
+    /// Replay the moves and return the game.
+    ///
+    /// # Errors
+    /// - if the setup is invalid, reference [setup_game](#method.setup_game).
+    /// - if the move is invalid in the game, reference [Game::set](../game/struct.Game.html#method.set).
+    pub fn to_game(&self) -> Result<Game, Box<Error + Send>> {
+        let mut game = self.setup_game()?;
+        for pos in self.moves.iter() {
+            game.set(*pos)?;
+        }
+        Ok(game)
+    }
+
+    /// Replay the moves and convert to `PlayResult`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, record::GameRecord};
+    /// let text = "[Size \"15\"]\n[Result \"*\"]\n\n1. H8\n2. G7 G9\n";
+    /// let record = text.parse::<GameRecord>().unwrap();
+    ///
+    /// let result = record.to_play_result().unwrap();
+    /// assert_eq!(result.path.len(), 3);
+    /// assert_eq!(result.path[1].turn, Player::White);
+    /// assert_eq!(result.path[1].board[7][7], Player::Black);
+    /// ```
+    ///
+    /// # Errors
+    /// - if the setup is invalid, reference [setup_game](#method.setup_game).
+    /// - if the move is invalid in the game, reference [Game::set](../game/struct.Game.html#method.set).
+    pub fn to_play_result(&self) -> Result<PlayResult, Box<Error + Send>> {
+        let mut game = self.setup_game()?;
+        let mut path = Vec::with_capacity(self.moves.len());
+        for pos in self.moves.iter() {
+            path.push(Path {
+                turn: game.get_turn(),
+                board: game.get_board().clone(),
+                pos: *pos,
+                search: None,
+                elapsed: Duration::default(),
+            });
+            game.set(*pos)?;
+        }
+        Ok(PlayResult {
+            winner: self.result.winner(),
+            status: self.result,
+            reason: EndReason::from_status(self.result),
+            would_resign: Player::None,
+            path,
+        })
+    }
+}
+
+impl fmt::Display for GameRecord {
+    /// Write the record as move-list notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[Black \"{}\"]", escape(&self.black))?;
+        writeln!(f, "[White \"{}\"]", escape(&self.white))?;
+        writeln!(f, "[Rule \"{}\"]", self.rule)?;
+        writeln!(f, "[Size \"{}\"]", self.size)?;
+        if let Some(ref setup) = self.setup {
+            let turn = if setup.turn == Player::White {
+                "W"
+            } else {
+                "B"
+            };
+            writeln!(f, "[AddBlack \"{}\"]", format_stones(&setup.black))?;
+            writeln!(f, "[AddWhite \"{}\"]", format_stones(&setup.white))?;
+            writeln!(f, "[ToMove \"{} {}\"]", turn, setup.num_remain)?;
+        }
+        writeln!(f, "[Result \"{}\"]", format_result(self.result))?;
+        writeln!(f, "[Date \"{}\"]", escape(&self.date))?;
+        writeln!(f)?;
+
+        // first turn has the opening stones or the remaining stones of the setup,
+        // the others have the stones per turn
+        let first = self
+            .setup
+            .as_ref()
+            .map_or(self.rule.opening_stones, |x| x.num_remain);
+        let mut moves = self.moves.as_slice();
+        let mut num_stones = first.max(1) as usize;
+        let mut turn = 1;
+        while !moves.is_empty() {
+            let (stones, rest) = moves.split_at(num_stones.min(moves.len()));
+            let stones = stones.iter().map(|x| format_pos(*x)).collect::<Vec<_>>();
+            writeln!(f, "{}. {}", turn, stones.join(" "))?;
+
+            moves = rest;
+            num_stones = self.rule.stones_per_turn.max(1) as usize;
+            turn += 1;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = Box<Error + Send>;
+
+    /// Parse the record from move-list notation.
+    ///
+    /// Missing headers are set as default of `GameRecord::new` and unknown headers are ignored.
+    /// Size out of range [1, MAX_BOARD_SIZE] is rejected.
+    fn from_str(text: &str) -> Result<GameRecord, Self::Err> {
+        let mut record = GameRecord::new(BOARD_SIZE);
+        let (mut black, mut white, mut to_move) = (None, None, None);
+        for line in text.lines().map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            if line.starts_with('[') {
+                let (key, value) = parse_header(line)
+                    .ok_or_else(|| InvalidRecordError::boxed(format!("header {}", line)))?;
+                match key {
+                    "Black" => record.black = value,
+                    "White" => record.white = value,
+                    "Rule" => {
+                        record.rule = value.parse().map_err(InvalidRecordError::boxed)?;
+                    }
+                    "Date" => record.date = value,
+                    "AddBlack" => black = Some(parse_stones(&value)?),
+                    "AddWhite" => white = Some(parse_stones(&value)?),
+                    "ToMove" => {
+                        let parsed = parse_to_move(&value).ok_or_else(|| {
+                            InvalidRecordError::boxed(format!("to move {}", value))
+                        })?;
+                        to_move = Some(parsed);
+                    }
+                    "Size" => {
+                        record.size = value
+                            .parse()
+                            .map_err(|_| InvalidRecordError::boxed(format!("size {}", value)))?;
+                        check_size(record.size)?;
+                    }
+                    "Result" => {
+                        record.result = parse_result(&value)
+                            .ok_or_else(|| InvalidRecordError::boxed(format!("result {}", value)))?
+                    }
+                    _ => (),
+                }
+                continue;
+            }
+            for token in line.split_whitespace() {
+                // skip the turn number
+                if token.ends_with('.') {
+                    continue;
+                }
+                let pos = parse_pos(token)
+                    .ok_or_else(|| InvalidRecordError::boxed(format!("move {}", token)))?;
+                record.moves.push(pos);
+            }
+        }
+
+        if black.is_some() || white.is_some() || to_move.is_some() {
+            let (turn, num_remain) = to_move.unwrap_or((Player::Black, record.rule.opening_stones));
+            record.setup = Some(Setup {
+                black: black.unwrap_or_default(),
+                white: white.unwrap_or_default(),
+                turn,
+                num_remain,
+            });
+        }
+        Ok(record)
+    }
+}
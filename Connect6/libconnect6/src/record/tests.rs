@@ -0,0 +1,233 @@
+use super::*;
+use agent::Agent;
+use policy::RandomPolicy;
+
+#[test]
+fn test_pos_notation() {
+    for row in 0..19 {
+        for col in 0..19 {
+            let pos = (row, col);
+            assert_eq!(parse_pos(&format_pos(pos)), Some(pos));
+        }
+    }
+    for col in 0..1000 {
+        assert_eq!(parse_pos(&format_pos((0, col))), Some((0, col)));
+    }
+    assert_eq!(format_pos((0, 25)), "Z1");
+    assert_eq!(format_pos((0, 27 * 26)), "AAA1");
+    assert_eq!(parse_pos("A0"), None);
+    assert_eq!(parse_pos("A+1"), None);
+    assert_eq!(parse_pos("ZZZZZZZZZZZZZZZZZZZZ1"), None);
+    assert_eq!(parse_pos("1A"), None);
+    assert_eq!(parse_pos("A"), None);
+    assert_eq!(parse_pos(""), None);
+}
+
+#[test]
+fn test_display() {
+    let mut record = GameRecord::new(15);
+    record.black = "alice".to_string();
+    record.white = "\"bob\"".to_string();
+    record.result = GameStatus::Win(Player::White);
+    record.date = "2018.10.18".to_string();
+    record.moves = vec![(7, 7), (6, 6), (8, 6), (0, 14)];
+
+    let expected = "[Black \"alice\"]\n\
+                    [White \"\\\"bob\\\"\"]\n\
+                    [Rule \"Connect6\"]\n\
+                    [Size \"15\"]\n\
+                    [Result \"W+\"]\n\
+                    [Date \"2018.10.18\"]\n\
+                    \n\
+                    1. H8\n\
+                    2. G7 G9\n\
+                    3. O1\n";
+    assert_eq!(record.to_string(), expected);
+    assert_eq!(expected.parse::<GameRecord>().unwrap(), record);
+}
+
+#[test]
+fn test_display_rule() {
+    let mut record = GameRecord::new(15);
+    record.rule = Rule::gomoku();
+    record.moves = vec![(7, 7), (6, 6), (8, 6)];
+    let text = record.to_string();
+    assert!(text.contains("[Rule \"Gomoku\"]\n"));
+    assert!(text.ends_with("\n1. H8\n2. G7\n3. G9\n"));
+    assert_eq!(text.parse::<GameRecord>().unwrap(), record);
+
+    record.rule = Rule::new(6, 3, 2).with_overline(false);
+    record.moves.push((0, 0));
+    let text = record.to_string();
+    assert!(text.contains("[Rule \"Connect(6,3,2,exact)\"]\n"));
+    assert!(text.ends_with("\n1. H8 G7\n2. G9 A1\n"));
+    assert_eq!(text.parse::<GameRecord>().unwrap(), record);
+}
+
+#[test]
+fn test_setup() {
+    let mut game = Game::with_handicap(9, Player::Black, &[(4, 4), (3, 3)]).unwrap();
+    game.set((0, 0)).unwrap();
+    game.set((1, 1)).unwrap();
+    game.set((2, 2)).unwrap();
+
+    let record = GameRecord::from_game(&game);
+    let expected = Setup {
+        black: vec![(3, 3), (4, 4)],
+        white: vec![],
+        turn: Player::White,
+        num_remain: 2,
+    };
+    assert_eq!(record.setup, Some(expected));
+
+    let text = record.to_string();
+    assert!(text.contains("[AddBlack \"D4 E5\"]\n[AddWhite \"\"]\n[ToMove \"W 2\"]\n"));
+    assert!(text.ends_with("\n1. A1 B2\n2. C3\n"));
+    let parsed = text.parse::<GameRecord>().unwrap();
+    assert_eq!(parsed, record);
+
+    let replayed = parsed.to_game().unwrap();
+    assert_eq!(replayed.get_board(), game.get_board());
+    assert_eq!(replayed.get_turn(), game.get_turn());
+    assert_eq!(replayed.get_remain(), game.get_remain());
+    assert_eq!(replayed.history(), game.history());
+
+    // regular start doesn't write the setup
+    let game = Game::from_opening(9, &[(4, 4)]).unwrap();
+    assert_eq!(GameRecord::from_game(&game).setup, None);
+    assert!(!GameRecord::from_game(&game).to_string().contains("ToMove"));
+}
+
+#[test]
+fn test_setup_invalid() {
+    let cases = ["[ToMove \"X 1\"]", "[ToMove \"B\"]", "[AddWhite \"A0\"]"];
+    for case in cases.iter() {
+        let err = case.parse::<GameRecord>().unwrap_err();
+        assert_eq!(err.description(), "invalid record");
+    }
+
+    let cases = [
+        "[Size \"9\"]\n[AddBlack \"A1 A1\"]",
+        "[Size \"9\"]\n[AddBlack \"A1\"]\n[AddWhite \"A1\"]",
+        "[Size \"9\"]\n[AddBlack \"J10\"]",
+        "[Size \"9\"]\n[AddBlack \"A1\"]\n[ToMove \"W 3\"]",
+    ];
+    for case in cases.iter() {
+        let record = case.parse::<GameRecord>().unwrap();
+        assert!(record.to_game().is_err());
+    }
+}
+
+#[test]
+fn test_parse_default() {
+    let record = "1. H8 2. G7 G9".parse::<GameRecord>().unwrap();
+    assert_eq!(record.size, BOARD_SIZE);
+    assert_eq!(record.result, GameStatus::Ongoing);
+    assert_eq!(record.black, "?");
+    assert_eq!(record.moves, vec![(7, 7), (6, 6), (8, 6)]);
+}
+
+#[test]
+fn test_parse_invalid() {
+    let cases = [
+        "[Size \"big\"]",
+        "[Size \"0\"]",
+        "[Size \"27\"]",
+        "[Result \"B\"]",
+        "[Rule \"Connect(6,0,1)\"]",
+        "[Black alice]",
+        "1. H8 2. G7 9G",
+    ];
+    for case in cases.iter() {
+        let err = case.parse::<GameRecord>().unwrap_err();
+        assert_eq!(err.description(), "invalid record");
+    }
+}
+
+#[test]
+fn test_to_game() {
+    let mut record = GameRecord::new(9);
+    record.moves = vec![(0, 0), (1, 1), (1, 2)];
+    let game = record.to_game().unwrap();
+    assert_eq!(game.get_size(), 9);
+    assert_eq!(game.ply(), 3);
+    assert_eq!(GameRecord::from_game(&game), record);
+
+    record.moves.push((0, 0));
+    assert!(record.to_game().is_err());
+
+    for size in [0, MAX_BOARD_SIZE + 1].iter() {
+        let record = GameRecord::new(*size);
+        assert!(record.to_game().is_err());
+        assert!(record.to_play_result().is_err());
+    }
+}
+
+#[test]
+fn test_to_game_rule() {
+    let mut record = GameRecord::new(9);
+    record.rule = Rule::gomoku();
+    record.moves = vec![(0, 0), (1, 1), (1, 2)];
+    let game = record.to_game().unwrap();
+    assert_eq!(game.get_rule(), Rule::gomoku());
+    assert_eq!(game.get_turn(), Player::White);
+    assert_eq!(GameRecord::from_game(&game), record);
+
+    let result = record.to_play_result().unwrap();
+    assert_eq!(result.path[1].turn, Player::White);
+    assert_eq!(result.path[2].turn, Player::Black);
+    assert_eq!(
+        GameRecord::from_play_result_with_rule(&result, Rule::gomoku()),
+        record
+    );
+}
+
+#[test]
+fn test_play_result_conversion() {
+    let mut policy = RandomPolicy::new();
+    let result = Agent::new(&mut policy).with_board_size(9).play().unwrap();
+
+    let record = GameRecord::from_play_result(&result);
+    assert_eq!(record.size, 9);
+
+    let parsed = record.to_string().parse::<GameRecord>().unwrap();
+    let converted = parsed.to_play_result().unwrap();
+    assert_eq!(converted.winner, result.winner);
+    assert_eq!(converted.status, result.status);
+    // record doesn't keep the search data and timings
+    assert_eq!(converted.path.len(), result.path.len());
+    for (converted, path) in converted.path.iter().zip(result.path.iter()) {
+        assert_eq!(converted.turn, path.turn);
+        assert_eq!(converted.board, path.board);
+        assert_eq!(converted.pos, path.pos);
+    }
+}
+
+#[test]
+fn test_play_result_setup() {
+    let games = [
+        Game::with_handicap(9, Player::White, &[(4, 4)]).unwrap(),
+        Game::from_opening(9, &[(4, 4), (3, 3)]).unwrap(),
+        Game::with_handicap_with_rule(9, Player::Black, &[(4, 4)], Rule::gomoku()).unwrap(),
+    ];
+    for game in games.iter() {
+        let mut policy = RandomPolicy::new();
+        let rule = game.get_rule();
+        let result = Agent::new(&mut policy)
+            .with_rule(rule)
+            .play_from(game.clone())
+            .unwrap();
+
+        let record = GameRecord::from_play_result_with_rule(&result, rule);
+        assert!(record.setup.is_some());
+
+        let parsed = record.to_string().parse::<GameRecord>().unwrap();
+        assert_eq!(parsed, record);
+        let converted = parsed.to_play_result().unwrap();
+        assert_eq!(converted.path.len(), result.path.len());
+        for (converted, path) in converted.path.iter().zip(result.path.iter()) {
+            assert_eq!(converted.turn, path.turn);
+            assert_eq!(converted.board, path.board);
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Replay buffer of the self-play positions for training AlphaZero.
+//!
+//! `ReplayBuffer` ingests `PlayResult`s and stores each position with its training targets.
+//! Value target is the result of the game from the perspective of the player on turn,
+//! { 1: win, 0: draw, -1: loss }, and policy target is the visit distribution of the search,
+//! or the selected position if the policy doesn't search.
+//! Aborted games without the result, such as the policy without the move, have no value target and are skipped.
+//!
+//! Old positions are evicted in FIFO order over the capacity, or by the age in the number of games.
+//! Mini-batches are sampled with optional random 8-way symmetry, and the buffer is saved to and restored from the disk.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{agent::Agent, policy::RandomPolicy, replay::ReplayBuffer};
+//! let mut buffer = ReplayBuffer::new(10000).with_max_age(100);
+//!
+//! let mut policy = RandomPolicy::new();
+//! for _ in 0..2 {
+//!     let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+//!     buffer.push(&result);
+//! }
+//! let batch = buffer.sample(16, true);
+//! assert_eq!(batch.len(), 16);
+//! ```
+use agent::PlayResult;
+use export::io::{invalid_data, read_f32, read_u32, read_u64, write_f32, write_u32, write_u64};
+use game::{GameStatus, Player};
+use policy::{entropy_rng, seeded_rng, symmetry, symmetry_pos};
+use {Board, GenericBoard, MAX_BOARD_SIZE};
+
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// Magic number of the snapshot file.
+const MAGIC: &[u8; 4] = b"C6RB";
+/// Version of the snapshot format.
+const VERSION: u32 = 1;
+/// Name of the format in the error messages.
+const FORMAT: &str = "snapshot";
+
+/// Training sample, a position with its value and policy target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    /// player on turn
+    pub turn: Player,
+    /// board before the selection
+    pub board: Board,
+    /// selected position
+    pub pos: (usize, usize),
+    /// policy target, visit distribution or the selected position
+    pub policy: GenericBoard<f32>,
+    /// value target from the perspective of `turn`
+    pub value: f32,
+}
+
+impl Sample {
+    /// Transform the sample into the `index`-th of the 8 symmetries.
+    pub fn symmetry(&self, index: usize) -> Sample {
+        Sample {
+            turn: self.turn,
+            board: symmetry(&self.board, index),
+            pos: symmetry_pos(self.board.size(), self.pos, index),
+            policy: symmetry(&self.policy, index),
+            value: self.value,
+        }
+    }
+}
+
+/// Convert the positions of the game to the training samples.
+///
+/// Aborted game, `GameStatus::Ongoing` without the result, gives no samples.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::Agent, policy::RandomPolicy, replay};
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+///
+/// let samples = replay::samples(&result);
+/// assert_eq!(samples.len(), result.path.len());
+/// ```
+pub fn samples(result: &PlayResult) -> Vec<Sample> {
+    if result.status == GameStatus::Ongoing {
+        return Vec::new();
+    }
+    result
+        .path
+        .iter()
+        .map(|path| {
+            let value = if result.winner == Player::None {
+                0.
+            } else if result.winner == path.turn {
+                1.
+            } else {
+                -1.
+            };
+            let policy = match path.search {
+                Some(ref info) => info.visits.clone(),
+                None => {
+                    let mut policy = GenericBoard::new(path.board.size());
+                    policy[path.pos.0][path.pos.1] = 1.;
+                    policy
+                }
+            };
+            Sample {
+                turn: path.turn,
+                board: path.board.clone(),
+                pos: path.pos,
+                policy,
+                value,
+            }
+        })
+        .collect()
+}
+
+/// Stored sample with the index of the game it came from.
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    game: u64,
+    sample: Sample,
+}
+
+/// Replay buffer of the self-play positions.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::Agent, policy::RandomPolicy, replay::ReplayBuffer};
+/// let mut buffer = ReplayBuffer::new(100).with_seed(0);
+///
+/// let mut policy = RandomPolicy::new();
+/// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+/// buffer.push(&result);
+///
+/// assert_eq!(buffer.len(), result.path.len().min(100));
+/// let sample = &buffer.sample(1, false)[0];
+/// assert_eq!(sample.policy[sample.pos.0][sample.pos.1], 1.);
+/// ```
+pub struct ReplayBuffer {
+    entries: VecDeque<Entry>,
+    capacity: usize,
+    max_age: Option<u64>,
+    num_games: u64,
+    rng: StdRng,
+}
+
+impl ReplayBuffer {
+    /// Construct a new `ReplayBuffer` keeping at most `capacity` positions.
+    pub fn new(capacity: usize) -> ReplayBuffer {
+        ReplayBuffer {
+            entries: VecDeque::new(),
+            capacity,
+            max_age: None,
+            num_games: 0,
+            rng: entropy_rng(),
+        }
+    }
+
+    /// Keep only the positions of the latest `max_age` games.
+    pub fn with_max_age(mut self, max_age: u64) -> ReplayBuffer {
+        self.max_age = Some(max_age);
+        self.evict();
+        self
+    }
+
+    /// Seed the random number generator of the sampling.
+    pub fn with_seed(mut self, seed: u64) -> ReplayBuffer {
+        self.seed(seed);
+        self
+    }
+
+    /// Reseed the random number generator of the sampling.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = seeded_rng(seed);
+    }
+
+    /// Number of the stored positions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maximum number of the positions.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of the games pushed since the construction.
+    pub fn num_games(&self) -> u64 {
+        self.num_games
+    }
+
+    /// Iterate the stored samples from the oldest.
+    pub fn iter<'a>(&'a self) -> Box<Iterator<Item = &'a Sample> + 'a> {
+        Box::new(self.entries.iter().map(|x| &x.sample))
+    }
+
+    /// Remove all positions.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Push the positions of the game, and evict the old positions.
+    ///
+    /// Aborted game without the result is skipped and isn't counted in `num_games`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, game::Player, policy::RandomPolicy, replay::ReplayBuffer};
+    /// let mut buffer = ReplayBuffer::new(1000);
+    /// let mut policy = RandomPolicy::new();
+    /// let result = Agent::new(&mut policy).with_board_size(7).play().unwrap();
+    /// buffer.push(&result);
+    ///
+    /// for (sample, path) in buffer.iter().zip(result.path.iter()) {
+    ///     let expected = if result.winner == Player::None {
+    ///         0.
+    ///     } else if result.winner == path.turn {
+    ///         1.
+    ///     } else {
+    ///         -1.
+    ///     };
+    ///     assert_eq!(sample.value, expected);
+    /// }
+    /// ```
+    pub fn push(&mut self, result: &PlayResult) {
+        if result.status == GameStatus::Ongoing {
+            return;
+        }
+        let game = self.num_games;
+        self.num_games += 1;
+
+        for sample in samples(result) {
+            self.entries.push_back(Entry { game, sample });
+        }
+        self.evict();
+    }
+
+    /// Evict the positions over the capacity or older than the maximum age.
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+        if let Some(max_age) = self.max_age {
+            while let Some(true) = self
+                .entries
+                .front()
+                .map(|x| x.game + max_age < self.num_games)
+            {
+                self.entries.pop_front();
+            }
+        }
+    }
+
+    /// Sample the mini-batch of `num_sample` positions without replacement, at most the number of positions.
+    ///
+    /// If `augment` is true, each sample is transformed into one of the 8 symmetries at random.
+    pub fn sample(&mut self, num_sample: usize, augment: bool) -> Vec<Sample> {
+        let amount = num_sample.min(self.entries.len());
+        let indices = index::sample(&mut self.rng, self.entries.len(), amount);
+
+        let mut samples = Vec::with_capacity(amount);
+        for idx in indices.iter() {
+            let sample = &self.entries[idx].sample;
+            if augment {
+                let index = self.rng.gen_range(0, 8);
+                samples.push(sample.symmetry(index));
+            } else {
+                samples.push(sample.clone());
+            }
+        }
+        samples
+    }
+
+    /// Write the buffer in the binary snapshot format.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, policy::RandomPolicy, replay::ReplayBuffer};
+    /// let mut buffer = ReplayBuffer::new(100).with_max_age(10);
+    /// let mut policy = RandomPolicy::new();
+    /// buffer.push(&Agent::new(&mut policy).with_board_size(7).play().unwrap());
+    ///
+    /// let mut bytes = Vec::new();
+    /// buffer.write_to(&mut bytes).unwrap();
+    ///
+    /// let restored = ReplayBuffer::read_from(&mut bytes.as_slice()).unwrap();
+    /// assert_eq!(restored.len(), buffer.len());
+    /// assert!(restored.iter().eq(buffer.iter()));
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        write_u32(writer, VERSION)?;
+        write_u64(writer, self.capacity as u64)?;
+        write_u64(writer, self.max_age.map(|x| x + 1).unwrap_or(0))?;
+        write_u64(writer, self.num_games)?;
+        write_u64(writer, self.entries.len() as u64)?;
+
+        for entry in self.entries.iter() {
+            let sample = &entry.sample;
+            write_u64(writer, entry.game)?;
+            write_u32(writer, sample.board.size() as u32)?;
+            write_u32(writer, sample.turn as i32 as u32)?;
+            write_u32(writer, sample.pos.0 as u32)?;
+            write_u32(writer, sample.pos.1 as u32)?;
+            write_f32(writer, sample.value)?;
+
+            let cells = sample
+                .board
+                .as_slice()
+                .iter()
+                .map(|x| *x as i8 as u8)
+                .collect::<Vec<_>>();
+            writer.write_all(&cells)?;
+            for prob in sample.policy.as_slice() {
+                write_f32(writer, *prob)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the buffer from the binary snapshot format.
+    ///
+    /// # Errors
+    /// - if reader raises the error.
+    /// - if the data is not a snapshot of `ReplayBuffer`, as `io::ErrorKind::InvalidData`.
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<ReplayBuffer> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data(FORMAT, "invalid magic number"));
+        }
+        if read_u32(reader)? != VERSION {
+            return Err(invalid_data(FORMAT, "unsupported version"));
+        }
+        let capacity = read_u64(reader)? as usize;
+        let max_age = match read_u64(reader)? {
+            0 => None,
+            age => Some(age - 1),
+        };
+        let num_games = read_u64(reader)?;
+        let len = read_u64(reader)?;
+        if len > capacity as u64 {
+            return Err(invalid_data(FORMAT, "positions over the capacity"));
+        }
+
+        let mut entries = VecDeque::new();
+        for _ in 0..len {
+            let game = read_u64(reader)?;
+            let size = read_u32(reader)? as usize;
+            let turn = Player::from(read_u32(reader)? as i32);
+            let pos = (read_u32(reader)? as usize, read_u32(reader)? as usize);
+            let value = read_f32(reader)?;
+            if size == 0 || size > MAX_BOARD_SIZE {
+                return Err(invalid_data(FORMAT, "invalid board size"));
+            }
+            if pos.0 >= size || pos.1 >= size {
+                return Err(invalid_data(FORMAT, "invalid position"));
+            }
+
+            let mut cells = vec![0; size * size];
+            reader.read_exact(&mut cells)?;
+            let cells = cells
+                .iter()
+                .map(|x| Player::from(*x as i8 as i32))
+                .collect();
+            let board = Board::from_vec(size, cells).unwrap();
+
+            let mut probs = Vec::with_capacity(size * size);
+            for _ in 0..size * size {
+                probs.push(read_f32(reader)?);
+            }
+            let policy = GenericBoard::from_vec(size, probs).unwrap();
+
+            let sample = Sample {
+                turn,
+                board,
+                pos,
+                policy,
+                value,
+            };
+            entries.push_back(Entry { game, sample });
+        }
+
+        Ok(ReplayBuffer {
+            entries,
+            capacity,
+            max_age,
+            num_games,
+            rng: entropy_rng(),
+        })
+    }
+
+    /// Save the snapshot of the buffer to the file.
+    pub fn snapshot<P: AsRef<::std::path::Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Restore the buffer from the snapshot file.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{agent::Agent, policy::RandomPolicy, replay::ReplayBuffer};
+    /// let mut buffer = ReplayBuffer::new(100);
+    /// let mut policy = RandomPolicy::new();
+    /// buffer.push(&Agent::new(&mut policy).with_board_size(7).play().unwrap());
+    ///
+    /// let path = std::env::temp_dir().join("connect6_replay_doctest.bin");
+    /// buffer.snapshot(&path).unwrap();
+    /// let restored = ReplayBuffer::restore(&path).unwrap();
+    /// assert_eq!(restored.len(), buffer.len());
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn restore<P: AsRef<::std::path::Path>>(path: P) -> io::Result<ReplayBuffer> {
+        let mut reader = BufReader::new(File::open(path)?);
+        ReplayBuffer::read_from(&mut reader)
+    }
+}
@@ -0,0 +1,202 @@
+use super::*;
+use agent::Agent;
+use game::Player;
+use policy::{AlphaZero, HyperParameter, RandomEvaluator, RandomPolicy};
+
+fn play(size: usize) -> PlayResult {
+    let mut policy = RandomPolicy::with_seed(0);
+    Agent::new(&mut policy)
+        .with_board_size(size)
+        .play()
+        .unwrap()
+}
+
+fn fake_result(winner: Player, num_path: usize) -> PlayResult {
+    let mut result = play(5);
+    result.winner = winner;
+    result.path.truncate(num_path);
+    result
+}
+
+#[test]
+fn test_push_value() {
+    let mut buffer = ReplayBuffer::new(1000);
+    buffer.push(&fake_result(Player::Black, 4));
+    buffer.push(&fake_result(Player::None, 4));
+
+    let values = buffer.iter().map(|x| x.value).collect::<Vec<_>>();
+    assert_eq!(values, vec![1., -1., -1., 1., 0., 0., 0., 0.]);
+    assert_eq!(buffer.num_games(), 2);
+}
+
+#[test]
+fn test_push_aborted() {
+    let mut result = fake_result(Player::None, 4);
+    result.status = GameStatus::Ongoing;
+    assert!(samples(&result).is_empty());
+
+    let mut buffer = ReplayBuffer::new(1000);
+    buffer.push(&result);
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.num_games(), 0);
+}
+
+#[test]
+fn test_push_policy() {
+    let mut buffer = ReplayBuffer::new(1000);
+    let result = play(5);
+    buffer.push(&result);
+    for sample in buffer.iter() {
+        let sum: f32 = sample.policy.as_slice().iter().sum();
+        assert_eq!(sum, 1.);
+        assert_eq!(sample.policy[sample.pos.0][sample.pos.1], 1.);
+    }
+
+    let param = HyperParameter::light_weight();
+    let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::with_seed(0)), param);
+    let result = Agent::new(&mut policy).with_board_size(5).play().unwrap();
+
+    buffer.clear();
+    buffer.push(&result);
+    for (sample, path) in buffer.iter().zip(result.path.iter()) {
+        assert_eq!(sample.policy, path.search.as_ref().unwrap().visits);
+    }
+}
+
+#[test]
+fn test_evict_capacity() {
+    let mut buffer = ReplayBuffer::new(6);
+    buffer.push(&fake_result(Player::Black, 4));
+    buffer.push(&fake_result(Player::White, 4));
+
+    assert_eq!(buffer.len(), 6);
+    let values = buffer.iter().map(|x| x.value).collect::<Vec<_>>();
+    assert_eq!(values, vec![-1., 1., -1., 1., 1., -1.]);
+}
+
+#[test]
+fn test_evict_age() {
+    let mut buffer = ReplayBuffer::new(1000).with_max_age(2);
+    for _ in 0..3 {
+        buffer.push(&fake_result(Player::Black, 4));
+    }
+    assert_eq!(buffer.len(), 8);
+    assert_eq!(buffer.num_games(), 3);
+
+    let buffer = buffer.with_max_age(1);
+    assert_eq!(buffer.len(), 4);
+}
+
+#[test]
+fn test_sample() {
+    let mut buffer = ReplayBuffer::new(1000).with_seed(0);
+    buffer.push(&play(5));
+
+    let samples = buffer.sample(buffer.len() + 10, false);
+    assert_eq!(samples.len(), buffer.len());
+    for sample in buffer.iter() {
+        assert!(samples.contains(sample));
+    }
+
+    let mut buffer = ReplayBuffer::new(1000).with_seed(1);
+    let mut other = ReplayBuffer::new(1000).with_seed(1);
+    buffer.push(&play(5));
+    other.push(&play(5));
+    assert_eq!(buffer.sample(4, true), other.sample(4, true));
+}
+
+#[test]
+fn test_sample_augment() {
+    let mut buffer = ReplayBuffer::new(1000);
+    buffer.push(&play(5));
+
+    let originals = buffer.iter().cloned().collect::<Vec<_>>();
+    for sample in buffer.sample(buffer.len(), true) {
+        assert_eq!(sample.policy[sample.pos.0][sample.pos.1], 1.);
+        let found = originals.iter().any(|x| {
+            x.turn == sample.turn
+                && x.value == sample.value
+                && (0..8).any(|i| x.symmetry(i) == sample)
+        });
+        assert!(found);
+    }
+}
+
+#[test]
+fn test_snapshot() {
+    let mut buffer = ReplayBuffer::new(100).with_max_age(3);
+    buffer.push(&play(5));
+    buffer.push(&fake_result(Player::None, 3));
+
+    let mut bytes = Vec::new();
+    buffer.write_to(&mut bytes).unwrap();
+    let restored = ReplayBuffer::read_from(&mut bytes.as_slice()).unwrap();
+
+    assert_eq!(restored.capacity(), 100);
+    assert_eq!(restored.max_age, Some(3));
+    assert_eq!(restored.num_games(), 2);
+    assert!(restored.entries.iter().eq(buffer.entries.iter()));
+
+    let path = ::std::env::temp_dir().join("connect6_replay_test.bin");
+    buffer.snapshot(&path).unwrap();
+    let restored = ReplayBuffer::restore(&path).unwrap();
+    assert!(restored.entries.iter().eq(buffer.entries.iter()));
+    ::std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_snapshot_invalid() {
+    let mut buffer = ReplayBuffer::new(100);
+    buffer.push(&play(5));
+
+    let mut bytes = Vec::new();
+    buffer.write_to(&mut bytes).unwrap();
+
+    let mut wrong = bytes.clone();
+    wrong[0] = b'X';
+    let err = ReplayBuffer::read_from(&mut wrong.as_slice())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let truncated = &bytes[..bytes.len() - 1];
+    let err = ReplayBuffer::read_from(&mut &truncated[..]).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // number of the positions at 32, board size of the first position at 48
+    let mut corrupt = bytes.clone();
+    corrupt[32..40].copy_from_slice(&[0xFF; 8]);
+    let err = ReplayBuffer::read_from(&mut corrupt.as_slice())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let mut corrupt = bytes.clone();
+    corrupt[48..52].copy_from_slice(&[0xFF; 4]);
+    let err = ReplayBuffer::read_from(&mut corrupt.as_slice())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_sample_symmetry() {
+    let mut board = Board::new(3);
+    board[0][1] = Player::Black;
+    let mut policy = GenericBoard::new(3);
+    policy[0][1] = 1.;
+
+    let sample = Sample {
+        turn: Player::White,
+        board,
+        pos: (0, 1),
+        policy,
+        value: 1.,
+    };
+    for i in 0..8 {
+        let transformed = sample.symmetry(i);
+        let (row, col) = transformed.pos;
+        assert_eq!(transformed.board[row][col], Player::Black);
+        assert_eq!(transformed.policy[row][col], 1.);
+    }
+}
@@ -0,0 +1,286 @@
+//! Line based messages between the game server and its clients.
+use game::{GameStatus, Player};
+use record::{format_pos, format_result, parse_pos, parse_result};
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+/// Error for invalid message.
+#[derive(Debug, Clone)]
+struct InvalidMessageError {
+    msg: String,
+}
+
+impl InvalidMessageError {
+    fn boxed(msg: String) -> Box<Error + Send> {
+        Box::new(InvalidMessageError { msg })
+    }
+}
+
+impl fmt::Display for InvalidMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid message: {}", self.msg)
+    }
+}
+
+impl Error for InvalidMessageError {
+    fn description(&self) -> &str {
+        "invalid message"
+    }
+}
+
+/// Write the colour of the player, `black` or `white`.
+fn format_color(player: Player) -> &'static str {
+    match player {
+        Player::White => "white",
+        _ => "black",
+    }
+}
+
+/// Parse the colour of the player, `black` or `white`.
+fn parse_color(token: Option<&str>) -> Result<Player, Box<Error + Send>> {
+    match token.map(|x| x.to_lowercase()) {
+        Some(ref color) if color == "black" => Ok(Player::Black),
+        Some(ref color) if color == "white" => Ok(Player::White),
+        _ => Err(InvalidMessageError::boxed("expected colour".to_string())),
+    }
+}
+
+/// Parse the position in move-list notation.
+fn parse_position(token: Option<&str>) -> Result<(usize, usize), Box<Error + Send>> {
+    let token = token.ok_or_else(|| InvalidMessageError::boxed("expected position".to_string()))?;
+    parse_pos(token).ok_or_else(|| InvalidMessageError::boxed(format!("position {}", token)))
+}
+
+/// Parse the number.
+fn parse_number<T: FromStr>(token: Option<&str>) -> Result<T, Box<Error + Send>> {
+    let token = token.ok_or_else(|| InvalidMessageError::boxed("expected number".to_string()))?;
+    token
+        .parse()
+        .map_err(|_| InvalidMessageError::boxed(format!("number {}", token)))
+}
+
+/// Role of the client in the game.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Role {
+    Black,
+    White,
+    Spectator,
+}
+
+impl Role {
+    /// Player of the role, `Player::None` for the spectator.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate connect6;
+    /// # use connect6::{game::Player, server::Role};
+    /// assert_eq!(Role::White.player(), Player::White);
+    /// assert_eq!(Role::Spectator.player(), Player::None);
+    /// ```
+    pub fn player(&self) -> Player {
+        match *self {
+            Role::Black => Player::Black,
+            Role::White => Player::White,
+            Role::Spectator => Player::None,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let role = match *self {
+            Role::Black => "black",
+            Role::White => "white",
+            Role::Spectator => "spectator",
+        };
+        write!(f, "{}", role)
+    }
+}
+
+impl FromStr for Role {
+    type Err = Box<Error + Send>;
+
+    /// Parse the role, case insensitive.
+    fn from_str(role: &str) -> Result<Role, Self::Err> {
+        match role.to_lowercase().as_str() {
+            "black" => Ok(Role::Black),
+            "white" => Ok(Role::White),
+            "spectator" => Ok(Role::Spectator),
+            _ => Err(InvalidMessageError::boxed(format!("role {}", role))),
+        }
+    }
+}
+
+/// Message from the client to the server.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::server::{Command, Role};
+/// let command = "join white alice".parse::<Command>().unwrap();
+/// assert_eq!(command, Command::Join(Role::White, "alice".to_string()));
+///
+/// let command = "move H8".parse::<Command>().unwrap();
+/// assert_eq!(command, Command::Move((7, 7)));
+/// assert_eq!(command.to_string(), "move H8");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// `join role [name]` : join the game with the role and the name.
+    Join(Role, String),
+    /// `move position` : place a stone of the client.
+    Move((usize, usize)),
+    /// `resign` : give up the game.
+    Resign,
+    /// `quit` : leave the game.
+    Quit,
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Command::Join(role, ref name) => write!(f, "join {} {}", role, name),
+            Command::Move(pos) => write!(f, "move {}", format_pos(pos)),
+            Command::Resign => write!(f, "resign"),
+            Command::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = Box<Error + Send>;
+
+    /// Parse the command, whitespaces of the name are replaced with underscores.
+    fn from_str(line: &str) -> Result<Command, Self::Err> {
+        let mut tokens = line.split_whitespace();
+        let command = match tokens.next() {
+            Some("join") => {
+                let role = tokens
+                    .next()
+                    .ok_or_else(|| InvalidMessageError::boxed("expected role".to_string()))?
+                    .parse()?;
+                let name = tokens.collect::<Vec<_>>().join("_");
+                let name = if name.is_empty() {
+                    "?".to_string()
+                } else {
+                    name
+                };
+                Command::Join(role, name)
+            }
+            Some("move") => Command::Move(parse_position(tokens.next())?),
+            Some("resign") => Command::Resign,
+            Some("quit") => Command::Quit,
+            Some(command) => {
+                return Err(InvalidMessageError::boxed(format!("command {}", command)))
+            }
+            None => return Err(InvalidMessageError::boxed("empty command".to_string())),
+        };
+        Ok(command)
+    }
+}
+
+/// Message from the server to the clients.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{game::{GameStatus, Player}, server::Event};
+/// let event = Event::Turn(Player::White, 2);
+/// assert_eq!(event.to_string(), "turn white 2");
+///
+/// let event = "result B+ line".parse::<Event>().unwrap();
+/// assert_eq!(event, Event::End(GameStatus::Win(Player::Black), "line".to_string()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// `ok role` : client joined the game with the role.
+    Joined(Role),
+    /// `error message` : command of the client is rejected.
+    Error(String),
+    /// `board size positions..` : board size and the moves played so far.
+    Board(usize, Vec<(usize, usize)>),
+    /// `start black white` : both players joined, names of the players.
+    Start(String, String),
+    /// `move colour position` : stone is placed.
+    Move(Player, (usize, usize)),
+    /// `turn colour remain` : player to move and the number of remaining stones of the turn.
+    Turn(Player, i32),
+    /// `result status reason` : game end with result notation and the reason,
+    /// one of `line`, `full`, `resign` and `disconnect`.
+    End(GameStatus, String),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::Joined(role) => write!(f, "ok {}", role),
+            Event::Error(ref msg) => write!(f, "error {}", msg),
+            Event::Board(size, ref moves) => {
+                write!(f, "board {}", size)?;
+                for pos in moves.iter() {
+                    write!(f, " {}", format_pos(*pos))?;
+                }
+                Ok(())
+            }
+            Event::Start(ref black, ref white) => write!(f, "start {} {}", black, white),
+            Event::Move(player, pos) => {
+                write!(f, "move {} {}", format_color(player), format_pos(pos))
+            }
+            Event::Turn(player, remain) => write!(f, "turn {} {}", format_color(player), remain),
+            Event::End(status, ref reason) => {
+                write!(f, "result {} {}", format_result(status), reason)
+            }
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = Box<Error + Send>;
+
+    fn from_str(line: &str) -> Result<Event, Self::Err> {
+        let mut tokens = line.split_whitespace();
+        let event = match tokens.next() {
+            Some("ok") => Event::Joined(
+                tokens
+                    .next()
+                    .ok_or_else(|| InvalidMessageError::boxed("expected role".to_string()))?
+                    .parse()?,
+            ),
+            Some("error") => Event::Error(tokens.collect::<Vec<_>>().join(" ")),
+            Some("board") => {
+                let size = parse_number(tokens.next())?;
+                let moves = tokens
+                    .map(|x| parse_position(Some(x)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Event::Board(size, moves)
+            }
+            Some("start") => {
+                let black = tokens.next().unwrap_or("?").to_string();
+                let white = tokens.next().unwrap_or("?").to_string();
+                Event::Start(black, white)
+            }
+            Some("move") => {
+                let player = parse_color(tokens.next())?;
+                Event::Move(player, parse_position(tokens.next())?)
+            }
+            Some("turn") => {
+                let player = parse_color(tokens.next())?;
+                Event::Turn(player, parse_number(tokens.next())?)
+            }
+            Some("result") => {
+                let result = tokens.next().unwrap_or("");
+                let status = parse_result(result)
+                    .ok_or_else(|| InvalidMessageError::boxed(format!("result {}", result)))?;
+                Event::End(status, tokens.next().unwrap_or("").to_string())
+            }
+            Some(event) => return Err(InvalidMessageError::boxed(format!("event {}", event))),
+            None => return Err(InvalidMessageError::boxed("empty event".to_string())),
+        };
+        Ok(event)
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+#[test]
+fn test_role() {
+    for role in [Role::Black, Role::White, Role::Spectator].iter() {
+        assert_eq!(role.to_string().parse::<Role>().unwrap(), *role);
+    }
+    assert_eq!("BLACK".parse::<Role>().unwrap(), Role::Black);
+    assert!("red".parse::<Role>().is_err());
+}
+
+#[test]
+fn test_command() {
+    let commands = [
+        Command::Join(Role::Black, "alice".to_string()),
+        Command::Join(Role::Spectator, "?".to_string()),
+        Command::Move((0, 0)),
+        Command::Move((14, 14)),
+        Command::Resign,
+        Command::Quit,
+    ];
+    for command in commands.iter() {
+        assert_eq!(command.to_string().parse::<Command>().unwrap(), *command);
+    }
+
+    assert_eq!(
+        "join white the bot".parse::<Command>().unwrap(),
+        Command::Join(Role::White, "the_bot".to_string())
+    );
+    assert_eq!(
+        "join spectator".parse::<Command>().unwrap(),
+        Command::Join(Role::Spectator, "?".to_string())
+    );
+
+    assert!("".parse::<Command>().is_err());
+    assert!("join".parse::<Command>().is_err());
+    assert!("join red".parse::<Command>().is_err());
+    assert!("move".parse::<Command>().is_err());
+    assert!("move 8H".parse::<Command>().is_err());
+    assert!("fly".parse::<Command>().is_err());
+}
+
+#[test]
+fn test_event() {
+    let events = [
+        Event::Joined(Role::White),
+        Event::Error("not your turn".to_string()),
+        Event::Board(15, Vec::new()),
+        Event::Board(7, vec![(3, 3), (2, 2), (2, 4)]),
+        Event::Start("alice".to_string(), "bob".to_string()),
+        Event::Move(Player::Black, (7, 7)),
+        Event::Turn(Player::White, 2),
+        Event::End(GameStatus::Draw, "full".to_string()),
+        Event::End(GameStatus::Win(Player::White), "resign".to_string()),
+    ];
+    for event in events.iter() {
+        assert_eq!(event.to_string().parse::<Event>().unwrap(), *event);
+    }
+
+    assert_eq!(
+        Event::Board(7, vec![(3, 3), (2, 2)]).to_string(),
+        "board 7 D4 C3"
+    );
+    assert!("".parse::<Event>().is_err());
+    assert!("board".parse::<Event>().is_err());
+    assert!("board 7 D4 ?".parse::<Event>().is_err());
+    assert!("move red H8".parse::<Event>().is_err());
+    assert!("turn black two".parse::<Event>().is_err());
+    assert!("result X+ line".parse::<Event>().is_err());
+}
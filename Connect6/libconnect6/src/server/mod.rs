@@ -0,0 +1,58 @@
+//! Game server hosting the networked matches over TCP.
+//!
+//! `Server` hosts a game at a time, clients join it as black, white or spectator
+//! and the players send the moves. Server validates the moves with [Game::set](../game/struct.Game.html#method.set)
+//! and broadcasts the board updates and the result to every client.
+//! `RemotePolicy` connects any policy to the server as a player or a spectator.
+//!
+//! Protocol is line based text, positions are written in move-list notation of the record.
+//! Game starts when both players joined, and each stone is sent as a separate `move` on the `turn` of the player.
+//!
+//! ```text
+//! client                  server
+//! join black alice   ->
+//!                    <-   ok black
+//!                    <-   board 15
+//!                    <-   start alice bob        (white joined)
+//!                    <-   turn black 1
+//! move H8            ->
+//!                    <-   move black H8
+//!                    <-   turn white 2
+//!                    ...
+//!                    <-   result W+ line
+//! ```
+//!
+//! - Commands, [Command](enum.Command.html) : `join role [name]`, `move position`, `resign`, `quit`.
+//! - Events, [Event](enum.Event.html) : `ok role`, `error message`, `board size positions..`,
+//!   `start black white`, `move colour position`, `turn colour remain`, `result status reason`.
+//!
+//! Player leaving the started game loses it by `disconnect`.
+//!
+//! # Examples
+//! ```rust
+//! # extern crate connect6;
+//! # use connect6::{policy::RandomPolicy, server::{RemotePolicy, Role, Server}};
+//! # use std::thread;
+//! let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(7);
+//! let addr = server.local_addr().unwrap();
+//!
+//! let players = [Role::Black, Role::White].iter().map(|&role| {
+//!     thread::spawn(move || {
+//!         let mut policy = RandomPolicy::new();
+//!         RemotePolicy::new(&mut policy, role).play(addr)
+//!     })
+//! }).collect::<Vec<_>>();
+//!
+//! let record = server.serve().unwrap();
+//! for player in players {
+//!     assert_eq!(player.join().unwrap().unwrap(), record);
+//! }
+//! assert!(record.result.is_end());
+//! ```
+pub use self::message::{Command, Event, Role};
+pub use self::remote_policy::*;
+pub use self::server_impl::*;
+
+mod message;
+mod remote_policy;
+mod server_impl;
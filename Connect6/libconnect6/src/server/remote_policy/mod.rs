@@ -0,0 +1,169 @@
+use super::message::{Command, Event, Role};
+use agent::{EndReason, Observer};
+use game::Game;
+use policy::Policy;
+use record::GameRecord;
+use MAX_BOARD_SIZE;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Error for the event inconsistent with the game.
+fn invalid_data<E: ToString>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+/// Client adapter connecting the policy to the game server.
+///
+/// It mirrors the game with the events of the server, and selects the stones with the policy on its turn.
+/// Policy giving up the turn resigns the game. Spectator only follows the game.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::{agent::LogObserver, policy::RandomPolicy, server::{RemotePolicy, Role, Server}};
+/// # use std::thread;
+/// let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(7);
+/// let addr = server.local_addr().unwrap();
+/// let host = thread::spawn(move || server.serve().unwrap());
+///
+/// let white = thread::spawn(move || {
+///     let mut policy = RandomPolicy::new();
+///     RemotePolicy::new(&mut policy, Role::White).play(addr)
+/// });
+///
+/// let mut policy = RandomPolicy::new();
+/// let mut observer = LogObserver::new();
+/// let record = RemotePolicy::new(&mut policy, Role::Black)
+///     .with_name("random")
+///     .with_observer(&mut observer)
+///     .play(addr)
+///     .unwrap();
+///
+/// assert_eq!(record.black, "random");
+/// assert_eq!(record, host.join().unwrap());
+/// assert_eq!(record, white.join().unwrap().unwrap());
+/// ```
+pub struct RemotePolicy<'a> {
+    policy: &'a mut Policy,
+    role: Role,
+    name: String,
+    observers: Vec<&'a mut Observer>,
+}
+
+impl<'a> RemotePolicy<'a> {
+    /// Construct a new `RemotePolicy` joining the game with given role.
+    pub fn new(policy: &'a mut Policy, role: Role) -> RemotePolicy<'a> {
+        RemotePolicy {
+            policy,
+            role,
+            name: "?".to_string(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Construct a `RemotePolicy` with given name, whitespaces are replaced with underscores by the server.
+    pub fn with_name(mut self, name: &str) -> RemotePolicy<'a> {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Attach the observer notified with the moves of both players.
+    ///
+    /// Elapsed time is measured only for the moves of the policy, zero for the others.
+    pub fn with_observer(mut self, observer: &'a mut Observer) -> RemotePolicy<'a> {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Connect to the server, play the game and return its record.
+    ///
+    /// # Errors
+    /// 1. If the connection failed or closed before the game end.
+    /// 2. If the server rejected the join or the move, `InvalidInput` with the message of the server.
+    /// 3. If the event of the server is invalid or inconsistent with the game.
+    pub fn play<A: ToSocketAddrs>(&mut self, addr: A) -> io::Result<GameRecord> {
+        let stream = TcpStream::connect(addr)?;
+        let mut writer = stream.try_clone()?;
+        let reader = BufReader::new(stream);
+        writeln!(writer, "{}", Command::Join(self.role, self.name.clone()))?;
+
+        let mut game = Game::new();
+        let mut names = ("?".to_string(), "?".to_string());
+        let mut elapsed = Duration::default();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line.parse::<Event>().map_err(invalid_data)? {
+                Event::Joined(_) => (),
+                Event::Error(msg) => return Err(io::Error::new(io::ErrorKind::InvalidInput, msg)),
+                Event::Board(size, moves) => {
+                    if size == 0 || size > MAX_BOARD_SIZE {
+                        return Err(invalid_data(format!("invalid board size {}", size)));
+                    }
+                    game = Game::with_size(size);
+                    for pos in moves {
+                        game.set(pos).map_err(invalid_data)?;
+                    }
+                    for observer in self.observers.iter_mut() {
+                        observer.on_game_start(&game);
+                    }
+                }
+                Event::Start(black, white) => names = (black, white),
+                Event::Move(player, pos) => {
+                    if player != game.get_turn() {
+                        return Err(invalid_data(format!("move of {:?} out of turn", player)));
+                    }
+                    let result = game.set(pos).map_err(invalid_data)?;
+                    let elapsed = if player == self.role.player() {
+                        elapsed
+                    } else {
+                        Duration::default()
+                    };
+                    for observer in self.observers.iter_mut() {
+                        observer.on_move(&game, &result, elapsed);
+                    }
+                }
+                Event::Turn(player, _) => {
+                    if player == self.role.player() {
+                        let now = Instant::now();
+                        let command = match self.policy.next(&game) {
+                            Some(pos) => Command::Move(pos),
+                            None => Command::Resign,
+                        };
+                        elapsed = now.elapsed();
+                        writeln!(writer, "{}", command)?;
+                    }
+                }
+                Event::End(status, reason) => {
+                    let mut record = GameRecord::from_game(&game);
+                    record.result = status;
+                    record.black = names.0;
+                    record.white = names.1;
+
+                    if !self.observers.is_empty() {
+                        let mut result = record.to_play_result().map_err(invalid_data)?;
+                        if reason == "resign" || reason == "disconnect" {
+                            result.reason = EndReason::Resign;
+                        }
+                        for observer in self.observers.iter_mut() {
+                            observer.on_game_end(&game, &result);
+                        }
+                    }
+                    return Ok(record);
+                }
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "connection closed before the game end",
+        ))
+    }
+}
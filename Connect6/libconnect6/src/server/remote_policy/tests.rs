@@ -0,0 +1,125 @@
+use super::*;
+use game::{GameStatus, Player};
+use policy::RandomPolicy;
+use server::Server;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::thread;
+
+/// Policy giving up every turn.
+struct GiveUpPolicy;
+
+impl Policy for GiveUpPolicy {
+    fn next(&mut self, _: &Game) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+/// Policy selecting the same position every turn.
+struct IllegalPolicy;
+
+impl Policy for IllegalPolicy {
+    fn next(&mut self, _: &Game) -> Option<(usize, usize)> {
+        Some((0, 0))
+    }
+}
+
+fn spawn_server() -> (SocketAddr, thread::JoinHandle<GameRecord>) {
+    let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(7);
+    let addr = server.local_addr().unwrap();
+    (addr, thread::spawn(move || server.serve().unwrap()))
+}
+
+fn spawn_player<P>(
+    addr: SocketAddr,
+    role: Role,
+    mut policy: P,
+) -> thread::JoinHandle<io::Result<GameRecord>>
+where
+    P: Policy + Send + 'static,
+{
+    thread::spawn(move || {
+        RemotePolicy::new(&mut policy, role)
+            .with_name(&role.to_string())
+            .play(addr)
+    })
+}
+
+#[test]
+fn test_play() {
+    let (addr, host) = spawn_server();
+    let black = spawn_player(addr, Role::Black, RandomPolicy::with_seed(1));
+    let white = spawn_player(addr, Role::White, RandomPolicy::with_seed(2));
+
+    let record = host.join().unwrap();
+    assert_eq!(record.black, "black");
+    assert_eq!(record.white, "white");
+    assert_eq!(record.to_game().unwrap().status(), record.result);
+
+    assert_eq!(black.join().unwrap().unwrap(), record);
+    assert_eq!(white.join().unwrap().unwrap(), record);
+}
+
+#[test]
+fn test_resign() {
+    let (addr, host) = spawn_server();
+    let black = spawn_player(addr, Role::Black, GiveUpPolicy);
+    let white = spawn_player(addr, Role::White, RandomPolicy::new());
+
+    let record = host.join().unwrap();
+    assert_eq!(record.result, GameStatus::Win(Player::White));
+    assert!(record.moves.is_empty());
+    assert_eq!(black.join().unwrap().unwrap(), record);
+    assert_eq!(white.join().unwrap().unwrap(), record);
+}
+
+#[test]
+fn test_rejected() {
+    let (addr, host) = spawn_server();
+    let white = spawn_player(addr, Role::White, IllegalPolicy);
+
+    // second stone of the white is rejected, white leaves the game
+    let mut policy = RandomPolicy::new();
+    let result = RemotePolicy::new(&mut policy, Role::Black).play(addr);
+    assert!(white.join().unwrap().is_err());
+
+    let record = host.join().unwrap();
+    assert_eq!(record.result, GameStatus::Win(Player::Black));
+    assert_eq!(result.unwrap(), record);
+}
+
+#[test]
+fn test_connect_failed() {
+    let addr = {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        server.local_addr().unwrap()
+    };
+    let mut policy = RandomPolicy::new();
+    assert!(RemotePolicy::new(&mut policy, Role::Spectator)
+        .play(addr)
+        .is_err());
+}
+
+#[test]
+fn test_invalid_board() {
+    for line in ["board 0", "board 27"].iter() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let host = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut join = String::new();
+            reader.read_line(&mut join).unwrap();
+            writeln!(stream, "{}", line).unwrap();
+        });
+
+        let mut policy = RandomPolicy::new();
+        let err = RemotePolicy::new(&mut policy, Role::Spectator)
+            .play(addr)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        host.join().unwrap();
+    }
+}
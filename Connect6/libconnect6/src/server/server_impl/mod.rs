@@ -0,0 +1,452 @@
+use super::message::{Command, Event, Role};
+use game::{Game, GameStatus, Player};
+use record::GameRecord;
+use {BOARD_SIZE, MAX_BOARD_SIZE};
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// Interval of polling the listener for the new connection.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(10);
+/// Maximum length of the line from the client in bytes.
+const MAX_LINE_LEN: usize = 1024;
+/// Default maximum number of the connected clients.
+const MAX_CLIENTS: usize = 64;
+/// Timeout of sending the event, slow client shouldn't block the game.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Signal from the connection threads to the game loop.
+enum Signal {
+    Connected(usize, TcpStream),
+    Line(usize, String),
+    Closed(usize),
+}
+
+/// Read the lines of the connection until it is closed, connection sending the too long line is closed.
+fn read_lines(id: usize, stream: TcpStream, sender: Sender<Signal>, connected: Arc<AtomicUsize>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        match (&mut reader)
+            .take(MAX_LINE_LEN as u64 + 1)
+            .read_line(&mut line)
+        {
+            Ok(0) | Err(_) => break,
+            Ok(_) => (),
+        }
+        if !line.ends_with('\n') {
+            if line.len() > MAX_LINE_LEN {
+                let _ = reader.get_ref().shutdown(Shutdown::Both);
+                break;
+            }
+        } else {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        if sender.send(Signal::Line(id, line)).is_err() {
+            break;
+        }
+    }
+    connected.fetch_sub(1, Ordering::SeqCst);
+    let _ = sender.send(Signal::Closed(id));
+}
+
+/// Accept the connections until stopped, spawning the reader thread for each of them.
+fn accept_loop(
+    listener: TcpListener,
+    sender: Sender<Signal>,
+    stopped: Arc<AtomicBool>,
+    max_clients: usize,
+) {
+    let mut num_client = 0;
+    let connected = Arc::new(AtomicUsize::new(0));
+    while !stopped.load(Ordering::SeqCst) {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(_) => {
+                thread::sleep(ACCEPT_INTERVAL);
+                continue;
+            }
+        };
+        if connected.load(Ordering::SeqCst) >= max_clients {
+            let _ = stream.shutdown(Shutdown::Both);
+            continue;
+        }
+        let reader = match stream
+            .set_nonblocking(false)
+            .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+            .and_then(|_| stream.try_clone())
+        {
+            Ok(reader) => reader,
+            Err(_) => continue,
+        };
+
+        let id = num_client;
+        num_client += 1;
+        if sender.send(Signal::Connected(id, stream)).is_err() {
+            return;
+        }
+        connected.fetch_add(1, Ordering::SeqCst);
+        let sender = sender.clone();
+        let connected = connected.clone();
+        thread::spawn(move || read_lines(id, reader, sender, connected));
+    }
+}
+
+/// Index of the player seat.
+fn seat(player: Player) -> usize {
+    match player {
+        Player::White => 1,
+        _ => 0,
+    }
+}
+
+/// Connected client.
+struct Client {
+    stream: TcpStream,
+    role: Option<Role>,
+}
+
+impl Client {
+    /// Send the event, shut down the connection if it failed.
+    fn send(&mut self, event: &Event) {
+        if writeln!(self.stream, "{}", event).is_err() {
+            let _ = self.stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// State of the hosted game.
+struct Session {
+    game: Game,
+    clients: HashMap<usize, Client>,
+    names: [Option<String>; 2],
+    started: bool,
+}
+
+impl Session {
+    fn new(size: usize) -> Session {
+        Session {
+            game: Game::with_size(size),
+            clients: HashMap::new(),
+            names: [None, None],
+            started: false,
+        }
+    }
+
+    /// Send the event to the client.
+    ///
+    /// Connection failed to write is shut down,
+    /// so that the client is dropped by `Signal::Closed` from its reader thread as if it closed the connection.
+    fn send(&mut self, id: usize, event: &Event) {
+        if let Some(client) = self.clients.get_mut(&id) {
+            client.send(event);
+        }
+    }
+
+    /// Send the event to every client.
+    fn broadcast(&mut self, event: &Event) {
+        for client in self.clients.values_mut() {
+            client.send(event);
+        }
+    }
+
+    fn turn(&self) -> Event {
+        Event::Turn(self.game.get_turn(), self.game.get_remain())
+    }
+
+    /// Handle the signal, return the record if the game end.
+    fn handle(&mut self, signal: Signal) -> Option<GameRecord> {
+        match signal {
+            Signal::Connected(id, stream) => {
+                self.clients.insert(id, Client { stream, role: None });
+                None
+            }
+            Signal::Closed(id) => self.leave(id),
+            Signal::Line(id, line) => {
+                if line.trim().is_empty() {
+                    return None;
+                }
+                match line.parse::<Command>() {
+                    Ok(Command::Join(role, name)) => {
+                        if let Err(msg) = self.join(id, role, name) {
+                            self.send(id, &Event::Error(msg));
+                        }
+                        None
+                    }
+                    Ok(Command::Move(pos)) => self.play(id, pos).unwrap_or_else(|msg| {
+                        self.send(id, &Event::Error(msg));
+                        None
+                    }),
+                    Ok(Command::Resign) => self.resign(id).unwrap_or_else(|msg| {
+                        self.send(id, &Event::Error(msg));
+                        None
+                    }),
+                    Ok(Command::Quit) => {
+                        if let Some(client) = self.clients.get(&id) {
+                            let _ = client.stream.shutdown(Shutdown::Both);
+                        }
+                        self.leave(id)
+                    }
+                    Err(e) => {
+                        self.send(id, &Event::Error(e.to_string()));
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Join the client with the role, game starts when both players joined.
+    fn join(&mut self, id: usize, role: Role, name: String) -> Result<(), String> {
+        if let Some(joined) = self.clients.get(&id).and_then(|client| client.role) {
+            return Err(format!("already joined as {}", joined));
+        }
+        if role != Role::Spectator {
+            let name_seat = &mut self.names[seat(role.player())];
+            if name_seat.is_some() {
+                return Err(format!("{} is taken", role));
+            }
+            *name_seat = Some(name);
+        }
+        if let Some(client) = self.clients.get_mut(&id) {
+            client.role = Some(role);
+        }
+
+        let moves = self.game.history().iter().map(|x| x.position).collect();
+        self.send(id, &Event::Joined(role));
+        self.send(id, &Event::Board(self.game.get_size(), moves));
+        if self.started {
+            let turn = self.turn();
+            self.send(id, &turn);
+        } else if let (Some(black), Some(white)) = (self.names[0].clone(), self.names[1].clone()) {
+            self.started = true;
+            self.broadcast(&Event::Start(black, white));
+            let turn = self.turn();
+            self.broadcast(&turn);
+        }
+        Ok(())
+    }
+
+    /// Player of the client allowed to play.
+    fn player(&self, id: usize) -> Result<Player, String> {
+        let role = self
+            .clients
+            .get(&id)
+            .and_then(|client| client.role)
+            .ok_or_else(|| "join the game first".to_string())?;
+        if role == Role::Spectator {
+            return Err("spectator can't play".to_string());
+        }
+        if !self.started {
+            return Err("game is not started".to_string());
+        }
+        Ok(role.player())
+    }
+
+    /// Place the stone of the client.
+    fn play(&mut self, id: usize, pos: (usize, usize)) -> Result<Option<GameRecord>, String> {
+        let player = self.player(id)?;
+        if player != self.game.get_turn() {
+            return Err("not your turn".to_string());
+        }
+        let result = self.game.set(pos).map_err(|e| e.to_string())?;
+        self.broadcast(&Event::Move(player, pos));
+
+        if result.line.is_some() {
+            return Ok(Some(self.end(GameStatus::Win(player), "line")));
+        }
+        if self.game.is_full() {
+            return Ok(Some(self.end(GameStatus::Draw, "full")));
+        }
+        let turn = self.turn();
+        self.broadcast(&turn);
+        Ok(None)
+    }
+
+    /// Resign the game of the client.
+    fn resign(&mut self, id: usize) -> Result<Option<GameRecord>, String> {
+        let player = self.player(id)?;
+        Ok(Some(self.end(GameStatus::Win(player.switch()), "resign")))
+    }
+
+    /// Remove the client, player leaving the started game loses it.
+    fn leave(&mut self, id: usize) -> Option<GameRecord> {
+        let role = self.clients.remove(&id).and_then(|client| client.role);
+        match role {
+            Some(Role::Spectator) | None => None,
+            Some(role) => {
+                let player = role.player();
+                if self.started {
+                    Some(self.end(GameStatus::Win(player.switch()), "disconnect"))
+                } else {
+                    self.names[seat(player)] = None;
+                    None
+                }
+            }
+        }
+    }
+
+    /// Broadcast the result and make the record.
+    fn end(&mut self, status: GameStatus, reason: &str) -> GameRecord {
+        self.broadcast(&Event::End(status, reason.to_string()));
+
+        let mut record = GameRecord::from_game(&self.game);
+        record.result = status;
+        if let Some(ref black) = self.names[0] {
+            record.black = black.clone();
+        }
+        if let Some(ref white) = self.names[1] {
+            record.white = white.clone();
+        }
+        record
+    }
+
+    /// Close every connection.
+    fn close(&mut self) {
+        for client in self.clients.values() {
+            let _ = client.stream.shutdown(Shutdown::Both);
+        }
+        self.clients.clear();
+    }
+}
+
+/// Game server hosting the networked matches over TCP.
+///
+/// # Examples
+/// ```rust
+/// # extern crate connect6;
+/// # use connect6::server::Server;
+/// # use std::io::{BufRead, BufReader, Write};
+/// # use std::net::TcpStream;
+/// # use std::thread;
+/// let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(7);
+/// let addr = server.local_addr().unwrap();
+///
+/// let client = thread::spawn(move || {
+///     let mut white = TcpStream::connect(addr).unwrap();
+///     writeln!(white, "join white bob").unwrap();
+///     let mut events = BufReader::new(white).lines().map(|x| x.unwrap());
+///     assert_eq!(events.next().unwrap(), "ok white");
+///
+///     let mut black = TcpStream::connect(addr).unwrap();
+///     writeln!(black, "join black alice\nmove D4\nresign").unwrap();
+///     events.collect::<Vec<_>>()
+/// });
+///
+/// let record = server.serve().unwrap();
+/// assert_eq!(record.black, "alice");
+/// assert_eq!(record.moves, vec![(3, 3)]);
+///
+/// let events = client.join().unwrap();
+/// let expected = ["board 7", "start alice bob", "turn black 1", "move black D4", "turn white 2"];
+/// assert_eq!(events[..5], expected);
+/// assert_eq!(events[5], "result W+ resign");
+/// ```
+pub struct Server {
+    listener: TcpListener,
+    size: usize,
+    max_clients: usize,
+}
+
+impl Server {
+    /// Bind the server to given address.
+    ///
+    /// # Errors
+    /// If `TcpListener::bind` failed.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            size: BOARD_SIZE,
+            max_clients: MAX_CLIENTS,
+        })
+    }
+
+    /// Construct a server with given board size.
+    ///
+    /// # Panics
+    /// If `size` is 0 or larger than `MAX_BOARD_SIZE`, columns are written as alphabets.
+    pub fn with_board_size(mut self, size: usize) -> Server {
+        assert!(
+            size > 0 && size <= MAX_BOARD_SIZE,
+            "invalid board size {}",
+            size
+        );
+        self.size = size;
+        self
+    }
+
+    /// Construct a server with given maximum number of the connected clients, default is 64.
+    ///
+    /// Connections over the limit are closed immediately.
+    ///
+    /// # Panics
+    /// If `max_clients` is 0.
+    pub fn with_max_clients(mut self, max_clients: usize) -> Server {
+        assert!(max_clients > 0, "max_clients should be positive");
+        self.max_clients = max_clients;
+        self
+    }
+
+    /// Local address of the server, port is assigned by the os if it was bound to zero.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Host the game until its end and return the record.
+    ///
+    /// Connections are closed after the game end, so that `serve` can be called again for the next game.
+    ///
+    /// # Errors
+    /// If the listener couldn't be cloned or changed to the non-blocking mode.
+    pub fn serve(&self) -> io::Result<GameRecord> {
+        let listener = self.listener.try_clone()?;
+        listener.set_nonblocking(true)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let acceptor = {
+            let stopped = stopped.clone();
+            let max_clients = self.max_clients;
+            thread::spawn(move || accept_loop(listener, sender, stopped, max_clients))
+        };
+
+        let mut session = Session::new(self.size);
+        let record = loop {
+            let signal = match receiver.recv() {
+                Ok(signal) => signal,
+                Err(_) => {
+                    break Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "listener stopped before the game end",
+                    ))
+                }
+            };
+            if let Some(record) = session.handle(signal) {
+                break Ok(record);
+            }
+        };
+
+        stopped.store(true, Ordering::SeqCst);
+        let _ = acceptor.join();
+        // connections accepted after the game end are closed as well
+        for signal in receiver.try_iter() {
+            if let Signal::Connected(_, stream) = signal {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+        session.close();
+        record
+    }
+}
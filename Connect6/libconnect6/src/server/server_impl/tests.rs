@@ -0,0 +1,295 @@
+use super::*;
+use record::parse_pos;
+
+/// Raw client speaking the protocol line by line.
+struct TestClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl TestClient {
+    fn connect(addr: SocketAddr) -> TestClient {
+        let writer = TcpStream::connect(addr).unwrap();
+        writer
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        TestClient { reader, writer }
+    }
+
+    fn join(addr: SocketAddr, line: &str) -> TestClient {
+        let mut client = TestClient::connect(addr);
+        client.send(line);
+        assert!(matches!(client.recv(), Event::Joined(_)));
+        client
+    }
+
+    fn send(&mut self, line: &str) {
+        writeln!(self.writer, "{}", line).unwrap();
+    }
+
+    fn recv(&mut self) -> Event {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.parse().unwrap()
+    }
+
+    /// Skip the events until the game end.
+    fn recv_end(&mut self) -> Event {
+        loop {
+            let event = self.recv();
+            if let Event::End(..) = event {
+                return event;
+            }
+        }
+    }
+
+    fn is_closed(&mut self) -> bool {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap() == 0
+    }
+}
+
+fn is_error(event: Event) -> bool {
+    matches!(event, Event::Error(_))
+}
+
+fn spawn_server(size: usize) -> (SocketAddr, thread::JoinHandle<GameRecord>) {
+    let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(size);
+    let addr = server.local_addr().unwrap();
+    (addr, thread::spawn(move || server.serve().unwrap()))
+}
+
+#[test]
+fn test_serve() {
+    let (addr, host) = spawn_server(7);
+
+    let mut black = TestClient::join(addr, "join black alice");
+    assert_eq!(black.recv(), Event::Board(7, Vec::new()));
+
+    let mut spectator = TestClient::join(addr, "join spectator");
+    assert_eq!(spectator.recv(), Event::Board(7, Vec::new()));
+
+    let mut other = TestClient::connect(addr);
+    other.send("join black eve");
+    assert_eq!(other.recv(), Event::Error("black is taken".to_string()));
+    other.send("move A1");
+    assert_eq!(
+        other.recv(),
+        Event::Error("join the game first".to_string())
+    );
+    other.send("fly");
+    assert!(is_error(other.recv()));
+
+    black.send("move A1");
+    assert_eq!(
+        black.recv(),
+        Event::Error("game is not started".to_string())
+    );
+    black.send("join white alice");
+    assert!(is_error(black.recv()));
+
+    let mut white = TestClient::join(addr, "join white bob");
+    assert_eq!(white.recv(), Event::Board(7, Vec::new()));
+
+    let start = Event::Start("alice".to_string(), "bob".to_string());
+    for client in [&mut black, &mut white, &mut spectator, &mut other].iter_mut() {
+        assert_eq!(client.recv(), start);
+        assert_eq!(client.recv(), Event::Turn(Player::Black, 1));
+    }
+
+    white.send("move A7");
+    assert_eq!(white.recv(), Event::Error("not your turn".to_string()));
+    spectator.send("move A7");
+    assert_eq!(
+        spectator.recv(),
+        Event::Error("spectator can't play".to_string())
+    );
+    black.send("move H8");
+    assert!(is_error(black.recv()));
+
+    let moves = [
+        (Player::Black, "A1"),
+        (Player::White, "A7"),
+        (Player::White, "B7"),
+        (Player::Black, "B1"),
+        (Player::Black, "C1"),
+        (Player::White, "C7"),
+        (Player::White, "D7"),
+        (Player::Black, "D1"),
+        (Player::Black, "E1"),
+        (Player::White, "E7"),
+    ];
+    for &(player, pos) in moves.iter() {
+        let client = if player == Player::Black {
+            &mut black
+        } else {
+            &mut white
+        };
+        client.send(&format!("move {}", pos));
+
+        let moved = Event::Move(player, parse_pos(pos).unwrap());
+        for client in [&mut black, &mut white, &mut spectator].iter_mut() {
+            assert_eq!(client.recv(), moved);
+            assert!(matches!(client.recv(), Event::Turn(..)));
+        }
+    }
+    white.send("move A1");
+    assert!(is_error(white.recv()));
+
+    white.send("move F7");
+    let end = Event::End(GameStatus::Win(Player::White), "line".to_string());
+    for client in [&mut black, &mut white, &mut spectator].iter_mut() {
+        assert_eq!(client.recv(), Event::Move(Player::White, (6, 5)));
+        assert_eq!(client.recv(), end);
+        assert!(client.is_closed());
+    }
+
+    let record = host.join().unwrap();
+    assert_eq!(record.black, "alice");
+    assert_eq!(record.white, "bob");
+    assert_eq!(record.size, 7);
+    assert_eq!(record.result, GameStatus::Win(Player::White));
+    assert_eq!(record.moves.len(), 11);
+    assert_eq!(record.to_game().unwrap().status(), record.result);
+}
+
+#[test]
+fn test_join_started() {
+    let (addr, host) = spawn_server(7);
+    let mut black = TestClient::join(addr, "join black");
+    let mut white = TestClient::join(addr, "join white");
+    black.send("move D4");
+    while black.recv() != Event::Move(Player::Black, (3, 3)) {}
+
+    let mut spectator = TestClient::join(addr, "join spectator");
+    assert_eq!(spectator.recv(), Event::Board(7, vec![(3, 3)]));
+    assert_eq!(spectator.recv(), Event::Turn(Player::White, 2));
+
+    white.send("resign");
+    let end = Event::End(GameStatus::Win(Player::Black), "resign".to_string());
+    for client in [&mut black, &mut white, &mut spectator].iter_mut() {
+        assert_eq!(client.recv_end(), end);
+    }
+
+    let record = host.join().unwrap();
+    assert_eq!(record.result, GameStatus::Win(Player::Black));
+    assert_eq!(record.moves, vec![(3, 3)]);
+}
+
+#[test]
+fn test_leave() {
+    let server = Server::bind("127.0.0.1:0").unwrap().with_board_size(7);
+    let addr = server.local_addr().unwrap();
+    let client = thread::spawn(move || {
+        // seat is free again if the player left before the start
+        let mut black = TestClient::join(addr, "join black alice");
+        assert_eq!(black.recv(), Event::Board(7, Vec::new()));
+        black.send("quit");
+        assert!(black.is_closed());
+
+        let mut black = TestClient::join(addr, "join black eve");
+        let white = TestClient::join(addr, "join white bob");
+        drop(white);
+        assert_eq!(
+            black.recv_end(),
+            Event::End(GameStatus::Win(Player::Black), "disconnect".to_string())
+        );
+        // connections are closed after the listener is released to the next game
+        assert!(black.is_closed());
+
+        let mut black = TestClient::join(addr, "join black alice");
+        let mut white = TestClient::join(addr, "join white bob");
+        black.send("resign");
+        assert_eq!(
+            white.recv_end(),
+            Event::End(GameStatus::Win(Player::White), "resign".to_string())
+        );
+    });
+
+    let record = server.serve().unwrap();
+    assert_eq!(record.black, "eve");
+    assert_eq!(record.result, GameStatus::Win(Player::Black));
+
+    let record = server.serve().unwrap();
+    assert_eq!(record.black, "alice");
+    assert_eq!(record.result, GameStatus::Win(Player::White));
+    client.join().unwrap();
+}
+
+#[test]
+fn test_long_line() {
+    let (addr, host) = spawn_server(7);
+
+    let mut client = TestClient::connect(addr);
+    client.send(&"a".repeat(MAX_LINE_LEN + 1));
+    assert!(client.is_closed());
+
+    // line of the maximum length is still handled
+    let mut black = TestClient::connect(addr);
+    black.send(&format!("join black {}", "a".repeat(MAX_LINE_LEN - 11)));
+    assert_eq!(black.recv(), Event::Joined(Role::Black));
+    let _white = TestClient::join(addr, "join white bob");
+    black.send("resign");
+    assert_eq!(host.join().unwrap().black.len(), MAX_LINE_LEN - 11);
+}
+
+#[test]
+fn test_max_clients() {
+    let server = Server::bind("127.0.0.1:0")
+        .unwrap()
+        .with_board_size(7)
+        .with_max_clients(2);
+    let addr = server.local_addr().unwrap();
+    let host = thread::spawn(move || server.serve().unwrap());
+
+    let mut black = TestClient::join(addr, "join black alice");
+    let spectator = TestClient::join(addr, "join spectator");
+    let mut other = TestClient::connect(addr);
+    assert!(other.is_closed());
+
+    // connection is accepted again after the client left
+    drop(spectator);
+    assert_eq!(black.recv(), Event::Board(7, Vec::new()));
+    let mut white = loop {
+        let mut white = TestClient::connect(addr);
+        white.send("join white bob");
+        let mut line = String::new();
+        if white.reader.read_line(&mut line).unwrap() > 0 {
+            assert_eq!(line.parse::<Event>().unwrap(), Event::Joined(Role::White));
+            break white;
+        }
+        thread::sleep(ACCEPT_INTERVAL);
+    };
+    white.send("resign");
+    assert_eq!(host.join().unwrap().result, GameStatus::Win(Player::Black));
+}
+
+#[test]
+fn test_write_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let _client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (stream, _) = listener.accept().unwrap();
+    let reader = stream.try_clone().unwrap();
+    reader
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    let mut session = Session::new(7);
+    session.handle(Signal::Connected(0, stream));
+    session.clients[&0]
+        .stream
+        .shutdown(Shutdown::Write)
+        .unwrap();
+    session.broadcast(&Event::Joined(Role::Spectator));
+
+    // connection is shut down to be closed by its reader thread
+    let mut buf = [0; 1];
+    assert_eq!((&reader).read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_board_size() {
+    Server::bind("127.0.0.1:0").unwrap().with_board_size(27);
+}
@@ -16,7 +16,7 @@
 //! policy = lambda turn, board: (np.random.rand(len(board)), np.random.rand(len(board), board_size ** 2))
 //! play_result = pyconnect6.self_play(policy, param)
 //!
-//! win, path = play_result
+//! win, path, status = play_result
 //! print(win)
 //! ```
 
@@ -54,7 +54,8 @@ py_module_initializer!(
                     dirichlet_alpha: f64,
                     c_puct: f32,
                     debug: bool,
-                    num_game_thread: i32
+                    num_game_thread: i32,
+                    board_size: usize
                 )
             )
         ));
@@ -68,16 +69,23 @@ py_module_initializer!(
                     num_simulation: i32,
                     epsilon: f32,
                     dirichlet_alpha: f64,
-                    c_puct: f32
+                    c_puct: f32,
+                    board_size: usize
                 )
             )
         ));
+        try!(m.add(py, "ReplayBuffer", py.get_type::<pybind::PyReplayBuffer>()));
         try!(m.add(
             py,
             "test_echo_pyeval",
             py_fn!(
                 py,
-                test_echo_pyeval(object: PyObject, player: PyObject, boards: PyObject)
+                test_echo_pyeval(
+                    object: PyObject,
+                    player: PyObject,
+                    boards: PyObject,
+                    board_size: usize
+                )
             )
         ));
         Ok(())
@@ -86,6 +94,8 @@ py_module_initializer!(
 
 /// Returns Connect6 self-playing results with given python policy and hyper parameters
 ///
+/// With multiple games, boards of the games are evaluated together in a single python call.
+///
 /// # Arguments
 ///
 /// * `py` - Python GIL, provided by rust-cpython.
@@ -96,11 +106,16 @@ py_module_initializer!(
 /// * `c_puct` - f32, ratio of q-value and puct, hyperparameter of AlphaZero MCTS
 /// * `debug` - bool, enable debug mode. if enable, selection and board status will be printed
 /// * `num_game_thread` - i32, number of threads asynchronously self-playing connect6
+/// * `board_size` - usize, length of one side of the board
 ///
 /// # Panics
 ///
 /// If PyObject isn't callable object
 ///
+/// # Errors
+///
+/// RuntimeError if any game failed by panics or timeouts.
+///
 fn self_play(
     py: Python,
     object: PyObject,
@@ -110,6 +125,7 @@ fn self_play(
     c_puct: f32,
     debug: bool,
     num_game_thread: i32,
+    board_size: usize,
 ) -> PyResult<PyTuple> {
     let param = policy::HyperParameter {
         num_simulation,
@@ -120,32 +136,48 @@ fn self_play(
     if num_game_thread == 1 {
         let pyeval = Box::new(pybind::PyEval::new(object));
         let mut policy = policy::AlphaZero::with_param(pyeval, param);
-        let result = if debug {
-            agent::Agent::debug(&mut policy).play()
+        let agent = if debug {
+            agent::Agent::debug(&mut policy)
         } else {
-            agent::Agent::new(&mut policy).play()
+            agent::Agent::new(&mut policy)
         };
-        Ok(pybind::RunResultWrapper(&result.unwrap()).to_py_object(py))
+        let result = agent
+            .with_board_size(board_size)
+            .play()
+            .map_err(|e| PyErr::new::<exc::RuntimeError, _>(py, e.to_string()))?;
+        Ok(pybind::RunResultWrapper(&result).to_py_object(py))
     } else {
         let result = py.allow_threads(move || {
-            let policy_gen = || {
-                let object = {
-                    let gil = Python::acquire_gil();
-                    let py = gil.python();
-                    object.clone_ref(py)
-                };
-                let pyeval = Box::new(pybind::PyEval::new(object));
-                policy::AlphaZero::with_param(pyeval, param)
-            };
+            // evaluations of the games are batched into a single python call
+            let pyeval = Box::new(pybind::PyEval::new(object));
+            let batch = policy::BatchEvaluator::new(pyeval);
+            let policy_gen = || policy::AlphaZero::with_param(Box::new(batch.handle()), param);
             let async_agent = if debug {
                 agent::AsyncAgent::debug(policy_gen)
             } else {
                 agent::AsyncAgent::new(policy_gen)
-            };
+            }
+            .with_board_size(board_size)
+            .with_num_threads(num_game_thread as usize);
             async_agent.run(num_game_thread)
         });
+        // any failed game raises the error with the messages of the failures
+        let errors = result
+            .iter()
+            .filter_map(|x| x.as_ref().err().map(|e| e.to_string()))
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            let msg = format!(
+                "{} of {} games failed: {}",
+                errors.len(),
+                result.len(),
+                errors.join(", ")
+            );
+            return Err(PyErr::new::<exc::RuntimeError, _>(py, msg));
+        }
         let py_result = result
             .iter()
+            .filter_map(|x| x.as_ref().ok())
             .map(|x| pybind::RunResultWrapper(x).to_py_object(py).into_object())
             .collect::<Vec<_>>();
         Ok(PyTuple::new(py, py_result.as_slice()))
@@ -162,6 +194,7 @@ fn self_play(
 /// * `epsilon` - f32, ratio for applying exploit, exploration. lower epsilon, more exploit
 /// * `dirichlet_alpha` - f64, hyperparameter for dirichlet distribution
 /// * `c_puct` - f32, ratio of q-value and puct, hyperparameter of AlphaZero MCTS
+/// * `board_size` - usize, length of one side of the board
 ///
 /// # Panics
 ///
@@ -174,6 +207,7 @@ fn play_with(
     epsilon: f32,
     dirichlet_alpha: f64,
     c_puct: f32,
+    board_size: usize,
 ) -> PyResult<PyTuple> {
     let param = policy::HyperParameter {
         num_simulation,
@@ -189,35 +223,121 @@ fn play_with(
     let mut io_policy = policy::IoPolicy::new(&mut stdin, &mut stdout);
 
     let mut multi_policy = policy::MultiPolicy::new(&mut py_policy, &mut io_policy);
-    let result = agent::Agent::debug(&mut multi_policy).play();
+    let result = agent::Agent::debug(&mut multi_policy)
+        .with_board_size(board_size)
+        .play();
     Ok(pybind::RunResultWrapper(&result.unwrap()).to_py_object(py))
 }
 
+/// Convert python sequence of play results, raise `ValueError` if any board size differs from `board_size`
+fn results_from_py(
+    py: Python,
+    results: PyObject,
+    board_size: usize,
+) -> PyResult<Vec<agent::PlayResult>> {
+    let results = results
+        .iter(py)?
+        .map(|x| x.and_then(|x| pybind::result_from_py(py, x)))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let valid = results
+        .iter()
+        .flat_map(|x| x.path.iter())
+        .all(|x| x.board.size() == board_size);
+    if !valid {
+        let msg = "board size of the results differs from the given one";
+        return Err(PyErr::new::<exc::ValueError, _>(py, msg));
+    }
+    Ok(results)
+}
+
+/// Export the play results to the npz file of `features`, `policies` and `values`.
+///
+/// Returns the number of the exported positions.
+///
+/// # Arguments
+///
+/// * `py` - Python GIL, provided by rust-cpython.
+/// * `results` - PyObject, sequence of the play results returned by `self_play`.
+/// * `path` - String, path of the npz file.
+/// * `board_size` - usize, length of one side of the board
+/// * `compress` - bool, deflate the arrays, same as `numpy.savez_compressed`.
+///
+fn export_npz(
+    py: Python,
+    results: PyObject,
+    path: String,
+    board_size: usize,
+    compress: bool,
+) -> PyResult<usize> {
+    use connect6::export::TrainingBatch;
+
+    let results = results_from_py(py, results, board_size)?;
+    let batch = TrainingBatch::from_results(board_size, &results);
+    batch
+        .save_npz(&path, compress)
+        .map_err(|e| PyErr::new::<exc::IOError, _>(py, e.to_string()))?;
+    Ok(batch.len())
+}
+
+/// Export the play results to the chunked binary shard with the index.
+///
+/// Returns the number of the exported positions.
+///
+/// # Arguments
+///
+/// * `py` - Python GIL, provided by rust-cpython.
+/// * `results` - PyObject, sequence of the play results returned by `self_play`.
+/// * `path` - String, path of the shard file.
+/// * `board_size` - usize, length of one side of the board
+/// * `chunk_size` - usize, number of the positions per chunk.
+/// * `compress` - bool, deflate the chunks.
+///
+fn export_shard(
+    py: Python,
+    results: PyObject,
+    path: String,
+    board_size: usize,
+    chunk_size: usize,
+    compress: bool,
+) -> PyResult<usize> {
+    use connect6::export::ShardWriter;
+
+    let results = results_from_py(py, results, board_size)?;
+    let write = || -> std::io::Result<usize> {
+        let mut writer = ShardWriter::create(&path, board_size)?
+            .with_chunk_size(chunk_size)
+            .with_compression(compress);
+        for result in results.iter() {
+            writer.push_result(result)?;
+        }
+        let num_samples = writer.num_samples() as usize;
+        writer.finish()?;
+        Ok(num_samples)
+    };
+    write().map_err(|e| PyErr::new::<exc::IOError, _>(py, e.to_string()))
+}
+
 fn test_echo_pyeval(
     py: Python,
     object: PyObject,
     player: PyObject,
     boards: PyObject,
+    board_size: usize,
 ) -> PyResult<PyTuple> {
-    use connect6::{game::Player, policy::Evaluator, BOARD_CAPACITY, BOARD_SIZE};
+    use connect6::{game::Player, policy::Evaluator, Board};
 
     let player = Player::from(player.extract::<i32>(py).ok().unwrap());
     let boards = pybind::pyiter_to_vec::<i32>(py, boards).unwrap();
 
-    let len = boards.len() / BOARD_CAPACITY;
-    let mut recovered = Vec::new();
-
-    let mut idx = 0;
-    for _ in 0..len {
-        let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-        for j in 0..BOARD_SIZE {
-            for k in 0..BOARD_SIZE {
-                board[j][k] = Player::from(boards[idx]);
-                idx += 1;
-            }
-        }
-        recovered.push(board);
-    }
+    let capacity = board_size * board_size;
+    let recovered = boards
+        .chunks(capacity)
+        .map(|x| {
+            let cells = x.iter().map(|p| Player::from(*p)).collect();
+            Board::from_vec(board_size, cells).unwrap()
+        })
+        .collect::<Vec<_>>();
 
     let pyeval = pybind::PyEval::new(object);
     let (value, policy) = pyeval.eval(player, &recovered).unwrap();
@@ -228,14 +348,11 @@ fn test_echo_pyeval(
         .collect::<Vec<_>>();
     let value = value.into_py_object(py).into_object();
 
-    let float_seq = |x: [[f32; BOARD_SIZE]; BOARD_SIZE]| {
-        let mut ordered = Vec::with_capacity(BOARD_CAPACITY);
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                ordered.push(x[i][j].to_py_object(py));
-            }
-        }
-        ordered
+    let float_seq = |x: connect6::GenericBoard<f32>| {
+        x.as_slice()
+            .iter()
+            .map(|p| p.to_py_object(py))
+            .collect::<Vec<_>>()
     };
 
     let policy = policy.into_iter().map(float_seq).collect::<Vec<_>>();
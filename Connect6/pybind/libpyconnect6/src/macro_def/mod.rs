@@ -1,7 +1,6 @@
 //! Macro definition
 pub use self::support::{create_pypolicy, PyPolicy};
 
-use connect6::BOARD_CAPACITY;
 use cpython::*; // avoid unused import warning of `PythonObject`, `ToPyObject`
 
 mod support;
@@ -5,7 +5,13 @@ use rand;
 /// Python random policy
 py_class!(pub class PyPolicy |py| {
     def __call__(&self, _turn: PyObject, boards: PyObject) -> PyResult<PyObject> {
-        let len = boards.cast_into::<PyList>(py)?.len(py);
+        let boards = boards.cast_into::<PyList>(py)?;
+        let len = boards.len(py);
+        let capacity = if len > 0 {
+            boards.get_item(py, 0).cast_into::<PyList>(py)?.len(py)
+        } else {
+            0
+        };
 
         let value = (0..len)
             .map(|_| rand::random::<f32>().to_py_object(py).into_object())
@@ -13,7 +19,7 @@ py_class!(pub class PyPolicy |py| {
         let value = PyList::new(py, value.as_slice()).into_object();
 
         let policy = (0..len).map(|_| {
-            let rand_policy = (0..BOARD_CAPACITY)
+            let rand_policy = (0..capacity)
                 .map(|_| rand::random::<f32>().to_py_object(py).into_object())
                 .collect::<Vec<PyObject>>();
             PyList::new(py, rand_policy.as_slice()).into_object()
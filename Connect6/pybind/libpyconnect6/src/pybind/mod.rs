@@ -4,6 +4,8 @@
 //!
 pub use self::pybind_impl::*;
 pub use self::pyeval::*;
+pub use self::replay_buffer::*;
 
 mod pybind_impl;
 mod pyeval;
+mod replay_buffer;
@@ -2,9 +2,11 @@
 //!
 //! `rust-cpython` based rust bindings.
 //! It provides some utilities related to implement AlphaZero.
-use connect6::{agent, Board, BOARD_SIZE};
+use connect6::{agent, game::GameStatus, game::Player, policy::SearchInfo, Board, GenericBoard};
 use cpython::*;
 
+use std::time::Duration;
+
 #[cfg(test)]
 mod tests;
 
@@ -27,12 +29,11 @@ where
 
 /// Convert board to PyList
 pub fn pylist_from_board(py: Python, board: &Board) -> PyObject {
-    let mut ordered: Vec<PyObject> = Vec::with_capacity(BOARD_SIZE * BOARD_SIZE);
-    for i in 0..BOARD_SIZE {
-        for j in 0..BOARD_SIZE {
-            ordered.push((board[i][j] as i32).to_py_object(py).into_object());
-        }
-    }
+    let ordered = board
+        .as_slice()
+        .iter()
+        .map(|x| (*x as i32).to_py_object(py).into_object())
+        .collect::<Vec<PyObject>>();
     PyList::new(py, ordered.as_slice()).into_object()
 }
 
@@ -63,13 +64,87 @@ pub fn pylist_from_multiple(py: Python, boards: &Vec<Board>) -> PyObject {
     PyList::new(py, lists.as_slice()).into_object()
 }
 
+/// Raise python `ValueError` with given message
+fn value_error(py: Python, msg: &str) -> PyErr {
+    PyErr::new::<exc::ValueError, _>(py, msg)
+}
+
+/// Convert flattened python sequence, list(int, board_size ** 2), to board
+pub fn board_from_pylist(py: Python, obj: PyObject) -> PyResult<Board> {
+    let cells =
+        pyiter_to_vec::<i32>(py, obj).ok_or_else(|| value_error(py, "board is not iterable"))?;
+    let size = (cells.len() as f64).sqrt() as usize;
+    let cells = cells.into_iter().map(Player::from).collect();
+    Board::from_vec(size, cells).ok_or_else(|| value_error(py, "board is not a square"))
+}
+
+/// Convert python tuple of `PathWrapper` to connect6::agent::Path
+///
+/// `search` and `elapsed` could be omitted, as `(turn, board, pos)`.
+pub fn path_from_py(py: Python, obj: PyObject) -> PyResult<agent::Path> {
+    let seq = obj.cast_into::<PySequence>(py)?;
+    let len = seq.len(py)?;
+    if len < 3 {
+        return Err(value_error(
+            py,
+            "path must be (turn, board, pos, search, elapsed)",
+        ));
+    }
+
+    let turn = Player::from(seq.get_item(py, 0)?.extract::<i32>(py)?);
+    let board = board_from_pylist(py, seq.get_item(py, 1)?)?;
+    let pos = seq.get_item(py, 2)?.extract::<(usize, usize)>(py)?;
+    if pos.0 >= board.size() || pos.1 >= board.size() {
+        return Err(value_error(py, "position out of the board"));
+    }
+
+    let search = if len > 3 {
+        seq.get_item(py, 3)?
+            .extract::<Option<(PyObject, f32, f32)>>(py)?
+    } else {
+        None
+    };
+    let search = match search {
+        Some((visits, value, q_value)) => {
+            let visits = pyiter_to_vec::<f32>(py, visits)
+                .and_then(|x| GenericBoard::from_vec(board.size(), x))
+                .ok_or_else(|| value_error(py, "visits must be list(float, board_size ** 2)"))?;
+            Some(SearchInfo {
+                visits,
+                value,
+                q_value,
+            })
+        }
+        None => None,
+    };
+
+    let elapsed = if len > 4 {
+        seq.get_item(py, 4)?.extract::<f64>(py)?.max(0.)
+    } else {
+        0.
+    };
+    let elapsed = Duration::new(elapsed as u64, (elapsed.fract() * 1e9) as u32);
+
+    Ok(agent::Path {
+        turn,
+        board,
+        pos,
+        search,
+        elapsed,
+    })
+}
+
 /// connect6::agent::Path wrapper for Python object conversion
 pub struct PathWrapper<'a>(pub &'a agent::Path);
 
 impl<'a> ToPyObject for PathWrapper<'a> {
     type ObjectType = PyTuple;
 
-    /// Return `PyTuple, (turn: int, board: list(int, board_size ** 2), pos: (int, int))`
+    /// Return `PyTuple, (turn: int, board: list(int, board_size ** 2), pos: (int, int), search, elapsed: float)`
+    ///
+    /// `search` is `None` if the policy doesn't search,
+    /// or `(visits: list(float, board_size ** 2), value: float, q_value: float)`.
+    /// `elapsed` is the time spent on the selection in seconds.
     fn to_py_object(&self, py: Python) -> PyTuple {
         let turn = (self.0.turn as i32).to_py_object(py).into_object();
         let board = pylist_from_board(py, &self.0.board);
@@ -79,20 +154,90 @@ impl<'a> ToPyObject for PathWrapper<'a> {
         let col = (col as i32).to_py_object(py).into_object();
         let pos_tuple = PyTuple::new(py, &[row, col]).into_object();
 
-        let tuple = PyTuple::new(py, &[turn, board, pos_tuple]);
+        let search = match self.0.search {
+            Some(ref info) => {
+                let visits = info
+                    .visits
+                    .as_slice()
+                    .iter()
+                    .map(|x| x.to_py_object(py).into_object())
+                    .collect::<Vec<_>>();
+                let visits = PyList::new(py, visits.as_slice()).into_object();
+                let value = info.value.to_py_object(py).into_object();
+                let q_value = info.q_value.to_py_object(py).into_object();
+                PyTuple::new(py, &[visits, value, q_value]).into_object()
+            }
+            None => py.None(),
+        };
+        let elapsed = self.0.elapsed;
+        let elapsed = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9;
+        let elapsed = elapsed.to_py_object(py).into_object();
+
+        let tuple = PyTuple::new(py, &[turn, board, pos_tuple, search, elapsed]);
         tuple
     }
 }
 
+/// Convert GameStatus to python int, { 0: Ongoing, 1: Win, 2: Draw }
+pub fn status_to_int(status: &GameStatus) -> i32 {
+    match status {
+        &GameStatus::Ongoing => 0,
+        &GameStatus::Win(_) => 1,
+        &GameStatus::Draw => 2,
+    }
+}
+
+/// Convert python int to GameStatus, inverse of `status_to_int`
+pub fn status_from_int(status: i32, winner: Player) -> Option<GameStatus> {
+    match status {
+        0 => Some(GameStatus::Ongoing),
+        1 => Some(GameStatus::Win(winner)),
+        2 => Some(GameStatus::Draw),
+        _ => None,
+    }
+}
+
+/// Convert python tuple of `RunResultWrapper`, (winner, path, status), to connect6::agent::PlayResult
+///
+/// Reason of the game end is recovered from the status, without adjudication.
+pub fn result_from_py(py: Python, obj: PyObject) -> PyResult<agent::PlayResult> {
+    let seq = obj.cast_into::<PySequence>(py)?;
+    if seq.len(py)? != 3 {
+        return Err(value_error(
+            py,
+            "play result must be (winner, path, status)",
+        ));
+    }
+
+    let winner = Player::from(seq.get_item(py, 0)?.extract::<i32>(py)?);
+    let path = seq
+        .get_item(py, 1)?
+        .iter(py)?
+        .map(|x| x.and_then(|x| path_from_py(py, x)))
+        .collect::<PyResult<Vec<_>>>()?;
+    let status = seq.get_item(py, 2)?.extract::<i32>(py)?;
+    let status =
+        status_from_int(status, winner).ok_or_else(|| value_error(py, "invalid status"))?;
+
+    Ok(agent::PlayResult {
+        winner,
+        status,
+        reason: agent::EndReason::from_status(status),
+        would_resign: Player::None,
+        path,
+    })
+}
+
 /// connect6::agent::PlayResult wrapper for Python object conversion
 pub struct RunResultWrapper<'a>(pub &'a agent::PlayResult);
 
 impl<'a> ToPyObject for RunResultWrapper<'a> {
     type ObjectType = PyTuple;
 
-    /// Return `PyTuple, (winner: int, path: list(Path as PyTuple))`
+    /// Return `PyTuple, (winner: int, path: list(Path as PyTuple), status: int)`
     fn to_py_object(&self, py: Python) -> PyTuple {
         let win = (self.0.winner as i32).to_py_object(py).into_object();
+        let status = status_to_int(&self.0.status).to_py_object(py).into_object();
         let path = self
             .0
             .path
@@ -100,7 +245,7 @@ impl<'a> ToPyObject for RunResultWrapper<'a> {
             .map(|x| PathWrapper(x).to_py_object(py).into_object())
             .collect::<Vec<_>>();
         let list = PyList::new(py, path.as_slice()).into_object();
-        let tuple = PyTuple::new(py, &[win, list]);
+        let tuple = PyTuple::new(py, &[win, list, status]);
         tuple
     }
 }
@@ -1,5 +1,7 @@
 use super::*;
-use connect6::{game::Player, BOARD_CAPACITY};
+use connect6::agent::Agent;
+use connect6::policy::{AlphaZero, HyperParameter, RandomEvaluator};
+use connect6::{game::Player, BOARD_CAPACITY, BOARD_SIZE};
 
 #[test]
 fn test_pyiter_to_vec() {
@@ -27,7 +29,7 @@ fn test_pylist_from_board() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let mut board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut board = Board::new(BOARD_SIZE);
     board[0][0] = Player::White;
     board[1][0] = Player::Black;
 
@@ -36,7 +38,7 @@ fn test_pylist_from_board() {
     assert!(seq.is_some());
 
     let seq = seq.unwrap();
-    let mut recovered = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+    let mut recovered = Board::new(BOARD_SIZE);
     for i in 0..BOARD_SIZE {
         for j in 0..BOARD_SIZE {
             let player = match seq[i * BOARD_SIZE + j] {
@@ -60,8 +62,8 @@ fn test_pylist_from_multiple() {
     let py = gil.python();
 
     let board = vec![
-        [[Player::None; BOARD_SIZE]; BOARD_SIZE],
-        [[Player::Black; BOARD_SIZE]; BOARD_SIZE],
+        Board::new(BOARD_SIZE),
+        Board::filled(BOARD_SIZE, Player::Black),
     ];
     let list = pylist_from_multiple(py, &board);
 
@@ -84,3 +86,31 @@ fn test_pylist_from_multiple() {
     assert_eq!(vec[0], vec![Player::None as i32; BOARD_CAPACITY]);
     assert_eq!(vec[1], vec![Player::Black as i32; BOARD_CAPACITY]);
 }
+
+#[test]
+fn test_result_from_py() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let param = HyperParameter::light_weight();
+    let mut policy = AlphaZero::with_param(Box::new(RandomEvaluator::new()), param);
+    let result = Agent::new(&mut policy).with_board_size(5).play().unwrap();
+
+    let obj = RunResultWrapper(&result).to_py_object(py).into_object();
+    let recovered = result_from_py(py, obj);
+    assert!(recovered.is_ok());
+
+    let recovered = recovered.unwrap();
+    assert_eq!(recovered.winner, result.winner);
+    assert_eq!(recovered.status, result.status);
+    assert_eq!(recovered.path.len(), result.path.len());
+    for (recovered, path) in recovered.path.iter().zip(result.path.iter()) {
+        assert_eq!(recovered.turn, path.turn);
+        assert_eq!(recovered.board, path.board);
+        assert_eq!(recovered.pos, path.pos);
+        assert_eq!(recovered.search, path.search);
+    }
+
+    let invalid = PyTuple::new(py, &[py.None()]).into_object();
+    assert!(result_from_py(py, invalid).is_err());
+}
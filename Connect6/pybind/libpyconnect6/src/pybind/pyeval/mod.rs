@@ -19,7 +19,7 @@
 //!
 use pybind::{pyiter_to_vec, pylist_from_multiple};
 
-use connect6::{game, policy, Board, BOARD_SIZE};
+use connect6::{game, policy, Board, GenericBoard};
 use cpython::{ObjectProtocol, PyObject, PySequence, PyTuple, Python, ToPyObject};
 
 #[cfg(test)]
@@ -34,9 +34,9 @@ mod tests;
 /// # extern crate cpython;
 /// # extern crate connect6;
 /// # #[macro_use] extern crate pyconnect6;
-/// # use connect6::{game::Player, policy::Evaluator, BOARD_SIZE};
+/// # use connect6::{policy::Evaluator, game::Player, Board, BOARD_SIZE};
 /// let pyeval = py_policy!();
-/// let board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
+/// let board = Board::new(BOARD_SIZE);
 /// assert!(pyeval.eval(Player::Black, &vec![board]).is_some());
 /// ```
 pub struct PyEval {
@@ -60,12 +60,12 @@ impl policy::Evaluator for PyEval {
     /// # Errors
     /// - if `value` is not a sequence type object consists of floats.
     /// - if `policy` is not a 2D sequence type object consists of floats.
-    /// - if `policy` is not shaped `[boards.len(), BOARD_SIZE ** 2]`
+    /// - if `policy` is not shaped `[boards.len(), board_size ** 2]`
     fn eval(
         &self,
         turn: game::Player,
         board: &Vec<Board>,
-    ) -> Option<(Vec<f32>, Vec<[[f32; BOARD_SIZE]; BOARD_SIZE]>)> {
+    ) -> Option<(Vec<f32>, Vec<GenericBoard<f32>>)> {
         // acquire python gil
         let gil = Python::acquire_gil();
         let py = gil.python();
@@ -95,15 +95,10 @@ impl policy::Evaluator for PyEval {
             .filter_map(|x| x.ok()) // pyiter returns iterator of Result
             .filter_map(|x| pyiter_to_vec::<f32>(py, x));
 
+        let board_size = board.first().map_or(0, |x| x.size());
         let mut policy_vec = Vec::with_capacity(board.len());
         for policy in policy_iter {
-            let mut temporal = [[0.; BOARD_SIZE]; BOARD_SIZE];
-            for i in 0..BOARD_SIZE {
-                for j in 0..BOARD_SIZE {
-                    temporal[i][j] = policy[i * BOARD_SIZE + j];
-                }
-            }
-            policy_vec.push(temporal);
+            policy_vec.push(GenericBoard::from_vec(board_size, policy)?);
         }
 
         Some((value_vec, policy_vec))
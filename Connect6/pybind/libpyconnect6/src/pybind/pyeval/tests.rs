@@ -2,13 +2,14 @@ use super::*;
 
 use connect6::game::Player;
 use connect6::policy::Evaluator;
+use connect6::BOARD_SIZE;
 
 #[test]
 fn test_eval() {
     let pyeval = py_policy!();
 
-    let board = [[Player::None; BOARD_SIZE]; BOARD_SIZE];
-    let result = pyeval.eval(Player::Black, &vec![board, board, board]);
+    let board = Board::new(BOARD_SIZE);
+    let result = pyeval.eval(Player::Black, &vec![board.clone(), board.clone(), board]);
     assert!(result.is_some());
 
     let (value_vec, policy_vec) = result.unwrap();
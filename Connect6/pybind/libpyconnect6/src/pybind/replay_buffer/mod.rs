@@ -0,0 +1,117 @@
+//! Python class of connect6::replay::ReplayBuffer.
+//!
+//! Self-play results of `pyconnect6.self_play` are pushed as they are,
+//! and mini-batches are sampled as python lists for feeding the network.
+use super::{pylist_from_board, result_from_py};
+use connect6::replay;
+use cpython::*;
+
+use std::cell::RefCell;
+
+/// Raise python `IOError` with given io error
+fn io_error(py: Python, err: std::io::Error) -> PyErr {
+    PyErr::new::<exc::IOError, _>(py, err.to_string())
+}
+
+/// Convert samples to `PyTuple, (values, turns, boards, policies, positions)`
+fn samples_to_py(py: Python, samples: &[replay::Sample]) -> PyTuple {
+    let values = samples
+        .iter()
+        .map(|x| x.value.to_py_object(py).into_object())
+        .collect::<Vec<_>>();
+    let turns = samples
+        .iter()
+        .map(|x| (x.turn as i32).to_py_object(py).into_object())
+        .collect::<Vec<_>>();
+    let boards = samples
+        .iter()
+        .map(|x| pylist_from_board(py, &x.board))
+        .collect::<Vec<_>>();
+    let policies = samples
+        .iter()
+        .map(|x| {
+            let probs = x
+                .policy
+                .as_slice()
+                .iter()
+                .map(|p| p.to_py_object(py).into_object())
+                .collect::<Vec<_>>();
+            PyList::new(py, probs.as_slice()).into_object()
+        })
+        .collect::<Vec<_>>();
+    let positions = samples
+        .iter()
+        .map(|x| {
+            let row = (x.pos.0 as i32).to_py_object(py).into_object();
+            let col = (x.pos.1 as i32).to_py_object(py).into_object();
+            PyTuple::new(py, &[row, col]).into_object()
+        })
+        .collect::<Vec<_>>();
+
+    let lists = [values, turns, boards, policies, positions]
+        .iter()
+        .map(|x| PyList::new(py, x.as_slice()).into_object())
+        .collect::<Vec<_>>();
+    PyTuple::new(py, lists.as_slice())
+}
+
+py_class!(pub class PyReplayBuffer |py| {
+    data buffer: RefCell<replay::ReplayBuffer>;
+
+    // Construct a new buffer keeping at most `capacity` positions of the latest `max_age` games.
+    // `max_age` could be None for no limit of the games.
+    def __new__(_cls, capacity: usize, max_age: Option<u64>) -> PyResult<PyReplayBuffer> {
+        let mut buffer = replay::ReplayBuffer::new(capacity);
+        if let Some(max_age) = max_age {
+            buffer = buffer.with_max_age(max_age);
+        }
+        PyReplayBuffer::create_instance(py, RefCell::new(buffer))
+    }
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(self.buffer(py).borrow().len())
+    }
+
+    // Push the play result, (winner, path, status), returned by `pyconnect6.self_play`.
+    def push_game(&self, result: PyObject) -> PyResult<PyObject> {
+        let result = result_from_py(py, result)?;
+        self.buffer(py).borrow_mut().push(&result);
+        Ok(py.None())
+    }
+
+    // Number of the games pushed since the construction.
+    def num_games(&self) -> PyResult<u64> {
+        Ok(self.buffer(py).borrow().num_games())
+    }
+
+    // Sample `num_sample` positions without replacement, with random 8-way symmetry if `augment`.
+    // Return tuple(values, turns, boards, policies, positions).
+    def sample(&self, num_sample: usize, augment: bool) -> PyResult<PyTuple> {
+        let samples = self.buffer(py).borrow_mut().sample(num_sample, augment);
+        Ok(samples_to_py(py, &samples))
+    }
+
+    // Seed the random number generator of the sampling.
+    def seed(&self, seed: u64) -> PyResult<PyObject> {
+        self.buffer(py).borrow_mut().seed(seed);
+        Ok(py.None())
+    }
+
+    def clear(&self) -> PyResult<PyObject> {
+        self.buffer(py).borrow_mut().clear();
+        Ok(py.None())
+    }
+
+    // Save the snapshot of the buffer to the file.
+    def snapshot(&self, path: String) -> PyResult<PyObject> {
+        self.buffer(py).borrow().snapshot(&path).map_err(|e| io_error(py, e))?;
+        Ok(py.None())
+    }
+
+    // Restore the buffer from the snapshot file.
+    @staticmethod
+    def restore(path: String) -> PyResult<PyReplayBuffer> {
+        let buffer = replay::ReplayBuffer::restore(&path).map_err(|e| io_error(py, e))?;
+        PyReplayBuffer::create_instance(py, RefCell::new(buffer))
+    }
+});
@@ -2,8 +2,18 @@ import json
 from . import pyconnect6
 
 
-def board_size():
-    return 15
+class GameStatus:
+    """status code of game, returned as the last element of play result"""
+    ONGOING = 0  # policy gave up before the game end
+    WIN = 1
+    DRAW = 2
+
+
+def board_size(param=None):
+    """length of one side of the board, default 15 or param['board_size'] if given"""
+    if param is None:
+        return 15
+    return param['board_size']
 
 
 def self_play(policy, param=None):
@@ -24,9 +34,10 @@ def self_play(policy, param=None):
 
         param: hyperparameter for playing combined mcts, reference `pyconnect6.default_param()`.
 
-    Return tuple(winner, play_result):
-        winner: int, winner of game { -1: Black, 0: Draw, 1: White }
+    Return tuple(winner, play_result, status):
+        winner: int, winner of game { -1: Black, 0: None, 1: White }
         player_result: list, in-game data produced by self-play, each cell consists of (turn, board, choice)
+        status: int, status of game { 0: Ongoing, 1: Win, 2: Draw }, reference `pyconnect6.GameStatus`
     """
     if param is None:
         param = default_param()
@@ -41,7 +52,107 @@ def play_with(policy, param=None):
     """
     if param is None:
         param = default_param()
-    return pyconnect6.play_with(policy, *param_to_tuple(param)[:-2])
+    num_simulation, epsilon, dirichlet_alpha, c_puct, _, _, size = param_to_tuple(param)
+    return pyconnect6.play_with(policy, num_simulation, epsilon, dirichlet_alpha, c_puct, size)
+
+
+def _as_results(results):
+    """wrap the single play result, returned by `self_play` with a single game thread"""
+    if len(results) == 3 and isinstance(results[0], int):
+        return [results]
+    return results
+
+
+def export_npz(results, path, param=None, compress=True):
+    """export play results to npz file, loaded by `numpy.load(path)`
+
+    Args:
+        results: play result or list of play results returned by `pyconnect6.self_play`.
+        path: str, path of the npz file.
+        param: hyperparameter for the board size, reference `pyconnect6.board_size(param)`.
+        compress: bool, deflate the arrays, same as `numpy.savez_compressed`.
+
+    Return number of the exported positions, npz contains
+        features: float32, (len, 3, board_size, board_size), stones of the player on turn,
+            stones of the opponent and the plane filled with 1 if the player on turn is black
+        policies: float32, (len, board_size ** 2), visit distribution of mcts
+        values: float32, (len,), { 1: win, 0: draw, -1: loss } from the perspective of the turn
+    """
+    return pyconnect6.export_npz(_as_results(results), path, board_size(param), compress)
+
+
+def export_shard(results, path, param=None, chunk_size=1024, compress=True):
+    """export play results to chunked binary shard with the index
+
+    Format is described on the docs of `connect6::export::shard`.
+    Return number of the exported positions.
+    """
+    return pyconnect6.export_shard(_as_results(results), path, board_size(param), chunk_size, compress)
+
+
+class ReplayBuffer:
+    """python wrapper for pyconnect6::ReplayBuffer, buffer of self-play positions for training
+
+    Args:
+        capacity: int, maximum number of the positions, old positions are evicted first.
+        max_age: int, keep only the positions of the latest `max_age` games, None for no limit.
+    """
+    def __init__(self, capacity, max_age=None, buffer=None):
+        if buffer is None:
+            buffer = pyconnect6.ReplayBuffer(capacity, max_age)
+        self.buffer = buffer
+
+    def __len__(self):
+        return len(self.buffer)
+
+    def push(self, play_result):
+        """push the positions of play result, (winner, path, status), returned by `pyconnect6.self_play`
+
+        value target of each position is { 1: win, 0: draw, -1: loss } from the perspective of the turn,
+        and policy target is the visit distribution of mcts.
+        """
+        self.buffer.push_game(play_result)
+
+    def extend(self, play_results):
+        """push multiple play results, returned by `pyconnect6.self_play` with multiple game threads"""
+        for play_result in play_results:
+            self.push(play_result)
+
+    def num_games(self):
+        """number of the games pushed since the construction"""
+        return self.buffer.num_games()
+
+    def sample(self, num_sample, augment=True):
+        """sample positions without replacement, at most the number of the positions
+
+        Args:
+            num_sample: int, number of the positions.
+            augment: bool, transform each position into one of the 8 symmetries at random.
+
+        Return tuple(values, turns, boards, policies, positions):
+            values: list(float), value target from the perspective of the turn
+            turns: list(int), { -1: Black, 1: White }
+            boards: list(list(int, board_size ** 2))
+            policies: list(list(float, board_size ** 2))
+            positions: list((int, int)), selected position
+        """
+        return self.buffer.sample(num_sample, augment)
+
+    def seed(self, seed):
+        """seed the random number generator of the sampling"""
+        self.buffer.seed(seed)
+
+    def clear(self):
+        self.buffer.clear()
+
+    def snapshot(self, path):
+        """save the buffer to the file"""
+        self.buffer.snapshot(path)
+
+    @staticmethod
+    def restore(path):
+        """restore the buffer from the file saved by `snapshot`"""
+        return ReplayBuffer(0, buffer=pyconnect6.ReplayBuffer.restore(path))
 
 
 def default_param():
@@ -53,6 +164,7 @@ def default_param():
         'c_puct': 1,                # parameter for puct (metamorphism of upper confidence tree algorithm)
         'debug': False,             # if debug, debug info from pyconnect6 will be printed
         'num_game_thread': 1,       # number of thread run game asynchronously
+        'board_size': 15,           # length of one side of the board
     }
 
 
@@ -62,7 +174,8 @@ def param_to_tuple(param):
            param['dirichlet_alpha'],\
            param['c_puct'],\
            param['debug'],\
-           param['num_game_thread']
+           param['num_game_thread'],\
+           param['board_size']
 
 
 def dump_param(path, param=None):
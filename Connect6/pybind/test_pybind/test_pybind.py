@@ -3,13 +3,10 @@ import numpy as np
 
 
 class RandomPolicy:
-    def __init__(self):
-        self.board_size = pyconnect6.board_size()
-
     def __call__(self, turn, board):
         size = len(board)
         value = np.random.rand(size)
-        rand_policy = np.random.rand(size, self.board_size * self.board_size)
+        rand_policy = np.random.rand(size, len(board[0]))
         return value, rand_policy
 
 
@@ -24,6 +21,19 @@ def test_result_length():
     assert len(result) == 2
 
 
+def test_board_size():
+    policy = RandomPolicy()
+
+    param = pyconnect6.default_param()
+    param['num_simulation'] = 2
+    param['board_size'] = 9
+
+    winner, path, status = pyconnect6.self_play(policy, param)
+    assert (status == pyconnect6.GameStatus.WIN) == (winner != 0)
+    for _, board, *_ in path:
+        assert len(board) == 9 * 9
+
+
 def test_echo_pyeval():
     def gen_player(): return np.random.randint(3) - 1
     turn = gen_player()
@@ -39,7 +49,7 @@ def test_echo_pyeval():
         return value, board
 
     test_echo_pyeval = pyconnect6.pyconnect6.test_echo_pyeval
-    value, policy = test_echo_pyeval(double_policy, turn, boards)
+    value, policy = test_echo_pyeval(double_policy, turn, boards, board_size)
 
     idx = 0
     for i in range(boards_len):
@@ -47,3 +57,54 @@ def test_echo_pyeval():
         for p in policy[i]:
             assert p == 2 * boards[idx]
             idx += 1
+
+
+def test_replay_buffer(tmpdir):
+    policy = RandomPolicy()
+
+    param = pyconnect6.default_param()
+    param['num_simulation'] = 2
+    param['board_size'] = 7
+
+    buffer = pyconnect6.ReplayBuffer(1000)
+    result = pyconnect6.self_play(policy, param)
+    buffer.push(result)
+
+    _, path, _ = result
+    assert len(buffer) == len(path)
+    assert buffer.num_games() == 1
+
+    values, turns, boards, policies, positions = buffer.sample(8, True)
+    assert len(values) == 8
+    for board, prob, (row, col) in zip(boards, policies, positions):
+        assert len(board) == 7 * 7
+        assert len(prob) == 7 * 7
+        assert board[row * 7 + col] == 0
+
+    path = str(tmpdir.join('replay.bin'))
+    buffer.snapshot(path)
+    restored = pyconnect6.ReplayBuffer.restore(path)
+    assert len(restored) == len(buffer)
+
+
+def test_export(tmpdir):
+    policy = RandomPolicy()
+
+    param = pyconnect6.default_param()
+    param['num_simulation'] = 2
+    param['num_game_thread'] = 2
+    param['board_size'] = 7
+
+    results = pyconnect6.self_play(policy, param)
+    num_positions = sum(len(path) for _, path, _ in results)
+
+    path = str(tmpdir.join('export.npz'))
+    assert pyconnect6.export_npz(results, path, param) == num_positions
+
+    data = np.load(path)
+    assert data['features'].shape == (num_positions, 3, 7, 7)
+    assert data['policies'].shape == (num_positions, 49)
+    assert data['values'].shape == (num_positions,)
+
+    path = str(tmpdir.join('export.bin'))
+    assert pyconnect6.export_shard(results, path, param, 16) == num_positions
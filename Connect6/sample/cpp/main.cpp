@@ -3,8 +3,10 @@
 #include <iostream>
 #include <random>
 
-void callback(int player, float* values, float* policies, int len_) {
+void callback(int player, float* values, float* policies, int len_, int board_size_) {
     size_t len = len_;
+    size_t board_size = board_size_;
+    size_t board_capacity = board_size * board_size;
     
     std::random_device rd;
     std::default_random_engine gen(rd());
@@ -15,13 +17,10 @@ void callback(int player, float* values, float* policies, int len_) {
         values[i] = dist(gen);
     }
 
-    using Connect6::BOARD_SIZE;
-    using Connect6::BOARD_CAPACITY;
-
     for (size_t i = 0; i < len; ++i) {
-        for (size_t j = 0; j < BOARD_SIZE; ++j) {
-            for (size_t k = 0; k < BOARD_SIZE; ++k) {
-                policies[i * BOARD_CAPACITY + j * BOARD_SIZE + k] = dist(gen);
+        for (size_t j = 0; j < board_size; ++j) {
+            for (size_t k = 0; k < board_size; ++k) {
+                policies[i * board_capacity + j * board_size + k] = dist(gen);
             }
         }
     }
@@ -34,6 +33,7 @@ int main() {
         .Debug(true);
     auto result = Connect6::self_play(callback, param);
     std::cout << "Winner : " << to_string(result[0].GetWinner()) << std::endl;
+    std::cout << "Status : " << to_string(result[0].GetStatus()) << std::endl;
 
     return 0;
 }
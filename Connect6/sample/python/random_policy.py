@@ -33,5 +33,5 @@ param['debug'] = True
 
 # pass policy to pyconnect6.self_play,
 # and connect6::self_play method will be use given policy to make choice
-winner, path = pyconnect6.self_play(policy, param)
-print('winner {}, len {}'.format(winner, len(path)))
+winner, path, status = pyconnect6.self_play(policy, param)
+print('winner {}, status {}, len {}'.format(winner, status, len(path)))
@@ -5,7 +5,7 @@ use connect6::{agent, policy};
 
 fn main() {
     let param = policy::HyperParameter::light_weight();
-    let eval = Box::new(policy::RandomEvaluator {});
+    let eval = Box::new(policy::RandomEvaluator::new());
     let mut policy = policy::AlphaZero::with_param(eval, param);
 
     let result = agent::Agent::debug(&mut policy).play();